@@ -11,7 +11,7 @@ pub struct AudioContext {
     // pub note_handler: NoteHandlerRef,
     pub note_channel_receiver: Receiver<NoteEvent>,
     pub sample_rate: f64,
-    pub spectral_mask_output: Option<triple_buffer::Output<SpectralMask>>,
+    pub spectral_mask_output: Option<triple_buffer::Output<StereoMask>>,
     pub reso_bank_data_output: Option<triple_buffer::Output<ResoBankData>>,
     pub voice_event_sender: Sender<VoiceEvent>,
     pub voice_event_receiver: Option<Receiver<VoiceEvent>>,
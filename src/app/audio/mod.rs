@@ -5,7 +5,7 @@ use crate::dsp::*;
 use crate::gui::spectrum::*;
 
 use nannou_audio::Buffer;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use thread_pool::ThreadPool;
@@ -1,6 +1,7 @@
 //! Audio state constructors.
 
 use super::*;
+use crate::dsp::synthesis::NoiseOsc;
 use crate::dsp::*;
 use atomic_float::AtomicF64;
 use std::sync::atomic::AtomicUsize;
@@ -18,36 +19,31 @@ pub fn build_audio_model(
     let spectral_mask = context.spectral_mask_output.take();
     let reso_bank_data = context.reso_bank_data_output.take();
     let sample_rate = context.sample_rate;
-    let upsampled_rate = DEFAULT_OVERSAMPLING_FACTOR as f64 * sample_rate;
 
     AudioModelBuilder::new(context)
-        .processors(audio_processors(sample_rate, sample_rate, ui_params))
+        .processors(audio_processors(sample_rate, ui_params))
         .generation(audio_generation(sample_rate))
-        .data(audio_data(sample_rate, sample_rate, ui_params))
+        .data(audio_data(sample_rate, ui_params))
         .buffers(audio_buffers(spectral_mask, reso_bank_data))
         .params(ui_params)
         .build()
 }
 
 #[allow(clippy::too_many_lines)]
-fn audio_processors(
-    sample_rate: f64,
-    upsampled_rate: f64,
-    ui_params: &UIParams,
-) -> AudioProcessors {
+fn audio_processors(sample_rate: f64, ui_params: &UIParams) -> AudioProcessors {
     let st_bq = || {
         Box::new([
-            BiquadFilter::new(upsampled_rate),
-            BiquadFilter::new(upsampled_rate),
+            BiquadFilter::new(sample_rate),
+            BiquadFilter::new(sample_rate),
         ])
     };
 
-    let mut comb = IirCombFilter::with_interpolation(true, upsampled_rate);
+    let mut comb = IirCombFilter::with_interpolation(true, sample_rate);
     // comb.
     comb.set_freq(3.0);
     comb.set_gain_db(-3.0);
 
-    // let mut comb_peak = BiquadFilter::new(upsampled_rate);
+    // let mut comb_peak = BiquadFilter::new(sample_rate);
     // comb_peak.set_params(&BiquadParams {
     //     freq: 726.0,
     //     gain: 4.0,
@@ -55,7 +51,7 @@ fn audio_processors(
     //     filter_type: FilterType::Peak,
     // });
     //
-    // let mut comb_lp = FirstOrderFilter::new(upsampled_rate);
+    // let mut comb_lp = FirstOrderFilter::new(sample_rate);
     // comb_lp.set_type(FilterType::Lowpass);
     // comb_lp.set_freq(3000.0);
 
@@ -74,26 +70,21 @@ fn audio_processors(
         SpectralFilter::new(NUM_CHANNELS, MAX_SPECTRAL_BLOCK_SIZE);
     spectral_filter.set_block_size(ui_params.mask_resolution.lr().value());
 
-    let mut filter_low = st_bq();
-    let mut filter_peak = st_bq();
-    let mut filter_high = st_bq();
-
-    for ch in 0..2 {
-        filter_low[ch].set_type(FilterType::Highpass);
-        filter_low[ch].set_freq(ui_params.low_filter_cutoff.current_value());
-        filter_low[ch].set_q(ui_params.low_filter_q.current_value());
-
-        filter_peak[ch].set_type(FilterType::Peak);
-        filter_peak[ch].set_freq(ui_params.peak_filter_cutoff.current_value());
-        filter_peak[ch].set_gain(ui_params.peak_filter_gain_db.current_value());
-
-        filter_high[ch].set_type(FilterType::Highshelf);
-        filter_high[ch].set_freq(ui_params.high_filter_cutoff.current_value());
-        filter_high[ch].set_gain(ui_params.high_filter_gain_db.current_value());
+    let mut eq = ParametricEQ::new(MAX_EQ_BANDS, sample_rate);
+
+    for (band, params) in eq.bands_mut().zip(&ui_params.eq_params.bands) {
+        band.set_enabled(params.enabled.lr());
+        band.set_type(params.filter_type.lr());
+        band.set_slope(params.slope.lr());
+        band.set_freq(params.cutoff.current_value());
+        band.set_gain_db(params.gain_db.current_value());
+        band.set_q(params.q.current_value());
+        band.set_threshold_db(params.threshold_db.lr());
+        band.set_dynamic(params.dynamic.lr());
     }
 
     let mut resonator_bank =
-        ResonatorBank::new(upsampled_rate, MAX_NUM_RESONATORS);
+        ResonatorBank::new(sample_rate, MAX_NUM_RESONATORS);
     resonator_bank
         .set_num_resonators(ui_params.reso_bank_resonator_count.lr() as usize);
     resonator_bank.set_scale(ui_params.reso_bank_scale.lr());
@@ -103,25 +94,36 @@ fn audio_processors(
     resonator_bank.set_freq_shift(ui_params.reso_bank_shift.current_value());
     resonator_bank.quantize_to_scale(ui_params.reso_bank_quantize.lr());
     resonator_bank.set_panning_scale(ui_params.reso_bank_pan.current_value());
+    resonator_bank.set_decay_time(ui_params.reso_bank_decay.current_value());
+    resonator_bank.set_damping(ui_params.reso_bank_damping.current_value());
+    resonator_bank
+        .set_decay_random(ui_params.reso_bank_decay_random.current_value());
+    resonator_bank.set_pitch_layout(ui_params.reso_bank_pitch_layout.lr());
+    resonator_bank
+        .set_layout_blend(ui_params.reso_bank_layout_blend.current_value());
     resonator_bank.randomize();
 
     let mut resonator_bank = DryWet::new(resonator_bank);
     resonator_bank.set_mix_equal_power(ui_params.reso_bank_mix.current_value());
 
-    let mut resonator = TwoPoleResonator::new(upsampled_rate);
+    let mut resonator = TwoPoleResonator::new(sample_rate);
     resonator.set_resonance(0.99);
     resonator.set_cutoff(440.0);
 
-    let mut delay = Delay::new(2.0, upsampled_rate).with_delay_time(0.332);
+    let mut delay = Delay::new(2.0, sample_rate).with_delay_time(0.332);
     delay.set_feedback_amount(0.7);
 
     let mut delay = DryWet::new(delay);
     delay.set_mix_equal_power(0.1);
 
-    let mut st_delay = StereoDelay::new(1.0, upsampled_rate)
+    let mut st_delay = StereoDelay::new(1.0, sample_rate)
         .with_delay_time(ui_params.delay_time_ms.lr() * 0.001)
         .with_ping_pong(ui_params.use_ping_pong.lr());
     st_delay.set_feedback_amount(ui_params.delay_feedback.current_value());
+    st_delay.set_low_cut(ui_params.delay_low_cut.current_value());
+    st_delay.set_high_cut(ui_params.delay_high_cut.current_value());
+    st_delay.set_saturation(ui_params.delay_saturation.current_value());
+    st_delay.set_wow_flutter(ui_params.delay_wow_flutter.current_value());
 
     let mut stereo_delay = DryWet::new(st_delay);
     stereo_delay.set_mix_equal_power(ui_params.delay_mix.current_value());
@@ -144,18 +146,78 @@ fn audio_processors(
         });
     }
 
-    let mut compressor = Compressor::new(upsampled_rate);
+    let mut compressor = Compressor::new(sample_rate);
     compressor.set_threshold_level_db(ui_params.comp_thresh.current_value());
     compressor.set_ratio(ui_params.comp_ratio.current_value());
     compressor.set_attack_time_ms(ui_params.comp_attack_ms.current_value());
     compressor.set_release_time_ms(ui_params.comp_release_ms.current_value());
-    compressor.set_knee_width(5.0);
-    compressor.use_rms(false);
+    compressor.set_knee_width(ui_params.comp_knee_db.current_value());
+    compressor.use_rms(ui_params.comp_use_rms.lr());
+    compressor.set_stereo_link(ui_params.comp_stereo_link.current_value());
+    compressor.set_makeup_gain_db(ui_params.comp_makeup_db.current_value());
+    compressor.use_auto_makeup(ui_params.comp_auto_makeup.lr());
+    compressor.set_lookahead_ms(ui_params.comp_lookahead_ms.lr());
+    compressor.set_sidechain_hpf(ui_params.comp_sidechain_hpf.current_value());
+
+    let dist_bands = &ui_params.dist_bands;
+    let comp_bands = &ui_params.comp_bands;
+    let mut dist_crossover = Crossover::new(NUM_BANDS, sample_rate);
+    let mut comp_crossover = Crossover::new(NUM_BANDS, sample_rate);
+
+    for i in 0..NUM_BANDS - 1 {
+        let dist_freq = dist_bands.crossover_freqs[i].current_value();
+        dist_crossover.set_crossover_freq(i, dist_freq);
+        let comp_freq = comp_bands.crossover_freqs[i].current_value();
+        comp_crossover.set_crossover_freq(i, comp_freq);
+    }
+
+    let oversampler =
+        Oversampler::new(MAX_BUFFER_SIZE, MAX_OVERSAMPLING_FACTOR, 3);
+
+    let mut limiter = Limiter::new(sample_rate);
+    limiter.set_ceiling_db(ui_params.limiter_ceiling_db.current_value());
+    limiter.set_release_time_ms(ui_params.limiter_release_ms.current_value());
+
+    let mod_rate = ui_params.mod_rate.current_value();
+    let mod_depth = ui_params.mod_depth.current_value();
+    let mod_feedback = ui_params.mod_feedback.current_value();
+    let mod_mix = ui_params.mod_mix.current_value();
+
+    let mut chorus = DryWet::new(Chorus::new(sample_rate));
+    chorus.set_mod_rate(mod_rate);
+    chorus.set_mod_depth(mod_depth);
+    chorus.set_mix_equal_power(mod_mix);
+
+    let mut flanger = DryWet::new(Flanger::new(sample_rate));
+    flanger.set_mod_rate(mod_rate);
+    flanger.set_mod_depth(mod_depth);
+    flanger.set_feedback(mod_feedback);
+    flanger.set_mix_equal_power(mod_mix);
+
+    let mut phaser = DryWet::new(Phaser::new(sample_rate));
+    phaser.set_mod_rate(mod_rate);
+    phaser.set_mod_depth(mod_depth);
+    phaser.set_feedback(mod_feedback);
+    phaser.set_mix_equal_power(mod_mix);
+
+    let mut decimator = Decimator::new(sample_rate);
+    decimator.set_target_rate(ui_params.crush_rate.current_value());
+    decimator.set_bit_depth(ui_params.crush_bits.current_value());
+    decimator.set_jitter(ui_params.crush_jitter.current_value());
+    decimator.set_anti_alias(ui_params.crush_anti_alias.lr());
+    decimator.set_dither(ui_params.crush_dither.lr());
+
+    let mut reverb = DryWet::new(Reverb::new(sample_rate));
+    reverb.set_size(ui_params.reverb_size.current_value());
+    reverb.set_decay(ui_params.reverb_decay.current_value());
+    reverb.set_damping(ui_params.reverb_damping.current_value());
+    reverb.set_pre_delay(ui_params.reverb_pre_delay.current_value());
+    reverb.set_modulation(ui_params.reverb_modulation.current_value());
+    reverb.set_mix_equal_power(ui_params.reverb_mix.current_value());
 
     AudioProcessors {
-        filter_low,
-        filter_peak,
-        filter_high,
+        eq,
+        linear_phase_eq: LinearPhaseEQ::new(sample_rate),
 
         filter_hs_ts: filter_hs_2,
         filter_pk_ts: st_bq(),
@@ -163,10 +225,10 @@ fn audio_processors(
         filter_comb: Box::new([comb.clone(), comb]),
 
         pre_fx_dc_filter: Box::new(std::array::from_fn(|_| {
-            DCFilter::new(upsampled_rate, 2)
+            DCFilter::new(sample_rate, 2)
         })),
         post_fx_dc_filter: Box::new(std::array::from_fn(|_| {
-            DCFilter::new(upsampled_rate, 2)
+            DCFilter::new(sample_rate, 2)
         })),
 
         delay: Box::new([delay.clone(), delay]),
@@ -177,14 +239,28 @@ fn audio_processors(
 
         spectral_filter,
         waveshaper: Box::new(waveshaper),
+        decimator: Box::new(decimator),
 
+        band_compressors: Box::new(std::array::from_fn(|_| {
+            compressor.clone()
+        })),
         compressor: Box::new(compressor),
-
-        oversamplers: vec![
-            Oversampler::new(
-                MAX_BUFFER_SIZE, MAX_OVERSAMPLING_FACTOR, 3
-            );
-            NUM_CHANNELS
+        comp_key_crossover: Box::new(comp_crossover.clone()),
+        comp_crossover: Box::new(comp_crossover),
+        dist_crossover: Box::new(dist_crossover),
+        limiter: Box::new(limiter),
+        safety_mute: Box::new(SafetyMute::new(sample_rate)),
+
+        chorus: Box::new(chorus),
+        flanger: Box::new(flanger),
+        phaser: Box::new(phaser),
+
+        reverb: Box::new(reverb),
+
+        oversamplers: vec![oversampler.clone(); NUM_CHANNELS],
+        band_oversamplers: vec![
+            [oversampler.clone(), oversampler];
+            NUM_BANDS
         ],
     }
 }
@@ -193,30 +269,33 @@ fn audio_generation(sample_rate: f64) -> AudioGeneration {
     let mut amp_envelope = AdsrEnvelope::new(sample_rate);
     amp_envelope.set_parameters(15.0, 300.0, 1.0, 20.0);
 
-    AudioGeneration { amp_envelope, generator: Generator::Noise }
+    AudioGeneration {
+        amp_envelope,
+        generator: Generator::Noise(NoiseOsc::new(stream_seed("noise"))),
+    }
 }
 
-fn audio_data(
-    sample_rate: f64,
-    upsampled_rate: f64,
-    ui_params: &UIParams,
-) -> AudioData {
+fn audio_data(sample_rate: f64, ui_params: &UIParams) -> AudioData {
     AudioData {
         voice_gain: Smoother::new(1.0, 0.01, sample_rate),
         // master_gain: Arc::new(SmootherAtomic::new(
-        //     1.0, DEFAULT_GAIN, upsampled_rate,
+        //     1.0, DEFAULT_GAIN, sample_rate,
         // )),
         sample_rate: Arc::new(AtomicF64::new(sample_rate)),
-        upsampled_rate: Arc::new(AtomicF64::new(upsampled_rate)),
-        latency_samples: 0,
-        oversampling_factor: Arc::new(AtomicUsize::new(
-            DEFAULT_OVERSAMPLING_FACTOR,
-        )),
+        upsampled_rate: Arc::new(AtomicF64::new(sample_rate)),
+        latency_samples: Arc::new(AtomicU32::new(0)),
+        oversampling_factor: Arc::clone(&ui_params.dist_oversampling),
+        current_oversampling_factor: 0,
         is_processing: false,
         idle_timer_samples: 0,
         average_load: vec![0.0; DSP_LOAD_AVERAGING_SAMPLES],
         average_pos: 0,
         distortion_algorithm: ui_params.dist_type.lr(),
+        modulation_type: ui_params.mod_type.lr(),
+        mod_rate: ui_params.mod_rate.current_value(),
+        dist_is_multiband: ui_params.dist_bands.enabled.lr(),
+        comp_is_multiband: ui_params.comp_bands.enabled.lr(),
+        eq_is_linear_phase: ui_params.eq_params.linear_phase.lr(),
         sample_timer: 0,
         callback_time_elapsed: Arc::new(Mutex::new(std::time::Instant::now())),
 
@@ -228,13 +307,14 @@ fn audio_data(
 
         delay_time_ms: 250.0,
 
-        high_filter_is_shelf: ui_params.high_filter_is_shelf.lr(),
-        low_filter_is_shelf: ui_params.low_filter_is_shelf.lr(),
+        transport: Transport::new(sample_rate),
+        comp_gain_reduction: Arc::new(AtomicF64::new(0.0)),
+        safety_muted: Arc::new(AtomicBool::new(false)),
     }
 }
 
 fn audio_buffers(
-    spectral_mask: Option<Output<SpectralMask>>,
+    spectral_mask: Option<Output<StereoMask>>,
     reso_bank_data: Option<Output<ResoBankData>>,
 ) -> AudioBuffers {
     AudioBuffers {
@@ -246,6 +326,11 @@ fn audio_buffers(
         oversampling_buffer: OversamplingBuffer::new(
             NUM_CHANNELS, MAX_BUFFER_SIZE,
         ),
+        dist_curve_buffer: vec![0.0; MAX_BUFFER_SIZE],
+        comp_key_buffer: vec![0.0; MAX_BUFFER_SIZE * NUM_CHANNELS],
+        band_buffers: (0..NUM_BANDS)
+            .map(|_| OversamplingBuffer::new(NUM_CHANNELS, MAX_BUFFER_SIZE))
+            .collect(),
         spectral_mask,
         reso_bank_data,
     }
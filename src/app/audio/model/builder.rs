@@ -16,6 +16,10 @@ pub struct AudioPackage {
     pub spectrum_outputs: (SpectrumOutput, SpectrumOutput),
     pub callback_timer_ref: Arc<Mutex<std::time::Instant>>,
     pub sample_rate_ref: Arc<AtomicF64>,
+    pub transport_position_ref: Arc<AtomicF64>,
+    pub comp_gain_reduction_ref: Arc<AtomicF64>,
+    pub safety_muted_ref: Arc<AtomicBool>,
+    pub latency_ref: Arc<AtomicU32>,
     pub message_channels: AudioMessageSenders,
 }
 
@@ -120,6 +124,12 @@ impl AudioModelBuilder {
                 &self.model.data.callback_time_elapsed,
             ),
             sample_rate_ref: Arc::clone(&self.model.data.sample_rate),
+            transport_position_ref: self.model.data.transport.position_ref(),
+            comp_gain_reduction_ref: Arc::clone(
+                &self.model.data.comp_gain_reduction,
+            ),
+            safety_muted_ref: Arc::clone(&self.model.data.safety_muted),
+            latency_ref: Arc::clone(&self.model.data.latency_samples),
             message_channels: self.message_channels(),
             model: self.model,
         }
@@ -199,6 +209,11 @@ impl AudioModelBuilder {
     }
 
     pub fn attach_ui_params(&mut self, ui_params: &UIParams) {
+        // transport
+        self.model.params.bpm = Arc::clone(&ui_params.bpm);
+        self.model.params.transport_playing =
+            Arc::clone(&ui_params.transport_playing);
+
         // mask
         self.model.params.mask_resolution =
             Arc::clone(&ui_params.mask_resolution);
@@ -220,6 +235,16 @@ impl AudioModelBuilder {
         self.model.params.reso_bank_pan = Arc::clone(&ui_params.reso_bank_pan);
         self.model.params.reso_bank_quantize =
             Arc::clone(&ui_params.reso_bank_quantize);
+        self.model.params.reso_bank_pitch_layout =
+            Arc::clone(&ui_params.reso_bank_pitch_layout);
+        self.model.params.reso_bank_layout_blend =
+            Arc::clone(&ui_params.reso_bank_layout_blend);
+        self.model.params.reso_bank_decay =
+            Arc::clone(&ui_params.reso_bank_decay);
+        self.model.params.reso_bank_damping =
+            Arc::clone(&ui_params.reso_bank_damping);
+        self.model.params.reso_bank_decay_random =
+            Arc::clone(&ui_params.reso_bank_decay_random);
         self.model.params.reso_bank_resonator_count =
             Arc::clone(&ui_params.reso_bank_resonator_count);
         self.model.params.reso_bank_mix = Arc::clone(&ui_params.reso_bank_mix);
@@ -228,41 +253,60 @@ impl AudioModelBuilder {
             .voice_handler
             .attach_generator_osc(Arc::clone(&ui_params.exciter_osc));
 
-        // low filter
-        self.model.params.low_filter_cutoff =
-            Arc::clone(&ui_params.low_filter_cutoff);
-        self.model.params.low_filter_q = Arc::clone(&ui_params.low_filter_q);
-        self.model.params.low_filter_gain_db =
-            Arc::clone(&ui_params.low_filter_gain_db);
-        self.model.params.low_filter_is_shelf =
-            Arc::clone(&ui_params.low_filter_is_shelf);
-
-        // peak filter
-        self.model.params.peak_filter_cutoff =
-            Arc::clone(&ui_params.peak_filter_cutoff);
-        self.model.params.peak_filter_q = Arc::clone(&ui_params.peak_filter_q);
-        self.model.params.peak_filter_gain_db =
-            Arc::clone(&ui_params.peak_filter_gain_db);
-
-        // high filter
-        self.model.params.high_filter_cutoff =
-            Arc::clone(&ui_params.high_filter_cutoff);
-        self.model.params.high_filter_q = Arc::clone(&ui_params.high_filter_q);
-        self.model.params.high_filter_gain_db =
-            Arc::clone(&ui_params.high_filter_gain_db);
-        self.model.params.high_filter_is_shelf =
-            Arc::clone(&ui_params.high_filter_is_shelf);
+        // eq
+        self.model.params.eq_params = ui_params.eq_params.clone();
 
         // delay
         self.model.params.delay_time_ms = Arc::clone(&ui_params.delay_time_ms);
         self.model.params.delay_feedback =
             Arc::clone(&ui_params.delay_feedback);
         self.model.params.delay_mix = Arc::clone(&ui_params.delay_mix);
+        self.model.params.delay_sync = Arc::clone(&ui_params.delay_sync);
+        self.model.params.delay_division =
+            Arc::clone(&ui_params.delay_division);
         self.model.params.use_ping_pong = Arc::clone(&ui_params.use_ping_pong);
+        self.model.params.delay_link = Arc::clone(&ui_params.delay_link);
+        self.model.params.delay_time_r_ms =
+            Arc::clone(&ui_params.delay_time_r_ms);
+        self.model.params.delay_division_r =
+            Arc::clone(&ui_params.delay_division_r);
+        self.model.params.delay_low_cut = Arc::clone(&ui_params.delay_low_cut);
+        self.model.params.delay_high_cut =
+            Arc::clone(&ui_params.delay_high_cut);
+        self.model.params.delay_saturation =
+            Arc::clone(&ui_params.delay_saturation);
+        self.model.params.delay_wow_flutter =
+            Arc::clone(&ui_params.delay_wow_flutter);
 
         // distortion
         self.model.params.dist_amount = Arc::clone(&ui_params.dist_amount);
         self.model.params.dist_type = Arc::clone(&ui_params.dist_type);
+        self.model.params.crush_rate = Arc::clone(&ui_params.crush_rate);
+        self.model.params.crush_bits = Arc::clone(&ui_params.crush_bits);
+        self.model.params.crush_jitter = Arc::clone(&ui_params.crush_jitter);
+        self.model.params.crush_anti_alias =
+            Arc::clone(&ui_params.crush_anti_alias);
+        self.model.params.crush_dither = Arc::clone(&ui_params.crush_dither);
+
+        // modulation
+        self.model.params.mod_type = Arc::clone(&ui_params.mod_type);
+        self.model.params.mod_rate = Arc::clone(&ui_params.mod_rate);
+        self.model.params.mod_sync = Arc::clone(&ui_params.mod_sync);
+        self.model.params.mod_division = Arc::clone(&ui_params.mod_division);
+        self.model.params.mod_depth = Arc::clone(&ui_params.mod_depth);
+        self.model.params.mod_feedback = Arc::clone(&ui_params.mod_feedback);
+        self.model.params.mod_mix = Arc::clone(&ui_params.mod_mix);
+
+        // reverb
+        self.model.params.reverb_size = Arc::clone(&ui_params.reverb_size);
+        self.model.params.reverb_decay = Arc::clone(&ui_params.reverb_decay);
+        self.model.params.reverb_damping =
+            Arc::clone(&ui_params.reverb_damping);
+        self.model.params.reverb_pre_delay =
+            Arc::clone(&ui_params.reverb_pre_delay);
+        self.model.params.reverb_modulation =
+            Arc::clone(&ui_params.reverb_modulation);
+        self.model.params.reverb_mix = Arc::clone(&ui_params.reverb_mix);
 
         // compression
         self.model.params.comp_thresh = Arc::clone(&ui_params.comp_thresh);
@@ -271,6 +315,30 @@ impl AudioModelBuilder {
             Arc::clone(&ui_params.comp_attack_ms);
         self.model.params.comp_release_ms =
             Arc::clone(&ui_params.comp_release_ms);
+        self.model.params.comp_knee_db = Arc::clone(&ui_params.comp_knee_db);
+        self.model.params.comp_use_rms = Arc::clone(&ui_params.comp_use_rms);
+        self.model.params.comp_stereo_link =
+            Arc::clone(&ui_params.comp_stereo_link);
+        self.model.params.comp_makeup_db =
+            Arc::clone(&ui_params.comp_makeup_db);
+        self.model.params.comp_auto_makeup =
+            Arc::clone(&ui_params.comp_auto_makeup);
+        self.model.params.comp_lookahead_ms =
+            Arc::clone(&ui_params.comp_lookahead_ms);
+        self.model.params.comp_sidechain_hpf =
+            Arc::clone(&ui_params.comp_sidechain_hpf);
+        self.model.params.comp_exciter_key =
+            Arc::clone(&ui_params.comp_exciter_key);
+
+        // multiband
+        self.model.params.dist_bands = ui_params.dist_bands.clone();
+        self.model.params.comp_bands = ui_params.comp_bands.clone();
+
+        // limiter
+        self.model.params.limiter_ceiling_db =
+            Arc::clone(&ui_params.limiter_ceiling_db);
+        self.model.params.limiter_release_ms =
+            Arc::clone(&ui_params.limiter_release_ms);
 
         // master gain
         self.model.params.master_gain = Arc::clone(&ui_params.master_gain);
@@ -9,9 +9,8 @@ use super::*;
 #[derive(Default)]
 pub struct AudioProcessors {
     // FILTERS
-    pub filter_low: Box<[BiquadFilter; NUM_CHANNELS]>,
-    pub filter_peak: Box<[BiquadFilter; NUM_CHANNELS]>,
-    pub filter_high: Box<[BiquadFilter; NUM_CHANNELS]>,
+    pub eq: ParametricEQ,
+    pub linear_phase_eq: LinearPhaseEQ,
 
     pub filter_pk_ts: Box<[BiquadFilter; NUM_CHANNELS]>,
     pub filter_hs_ts: Box<[BiquadFilter; NUM_CHANNELS]>,
@@ -30,11 +29,65 @@ pub struct AudioProcessors {
 
     // FX
     pub waveshaper: Box<[Waveshaper; NUM_CHANNELS]>,
-    // TODO: compression/limiting, delay, diopser, reverb
+    pub decimator: Box<Decimator>,
+    // TODO: compression/limiting, delay, diopser
     pub compressor: Box<Compressor>,
+    pub band_compressors: Box<[Compressor; NUM_BANDS]>,
+    pub comp_crossover: Box<Crossover>,
+    /// Splits the compressor's key signal alongside its input.
+    pub comp_key_crossover: Box<Crossover>,
+    pub dist_crossover: Box<Crossover>,
+    pub limiter: Box<Limiter>,
+    pub safety_mute: Box<SafetyMute>,
+
+    // MODULATION
+    pub chorus: Box<DryWet<Chorus>>,
+    pub flanger: Box<DryWet<Flanger>>,
+    pub phaser: Box<DryWet<Phaser>>,
+
+    // REVERB
+    pub reverb: Box<DryWet<Reverb>>,
 
     // OVERSAMPLING
     pub oversamplers: Vec<Oversampler>,
+    /// The oversamplers of each band of the multiband waveshaper.
+    pub band_oversamplers: Vec<[Oversampler; NUM_CHANNELS]>,
+}
+
+impl AudioProcessors {
+    /// Clears the state of every effect before the safety mute, which may
+    /// hold non-finite samples after it trips.
+    pub fn reset_effects(&mut self) {
+        self.pre_fx_dc_filter.iter_mut().for_each(DCFilter::reset);
+        self.post_fx_dc_filter.iter_mut().for_each(DCFilter::reset);
+        self.resonator_bank.reset();
+
+        self.stereo_delay.reset();
+        self.eq.reset();
+        self.linear_phase_eq.clear();
+        self.filter_hs_ts.iter_mut().for_each(BiquadFilter::reset);
+        self.filter_pk_ts.iter_mut().for_each(BiquadFilter::reset);
+
+        self.oversamplers.iter_mut().for_each(Oversampler::reset);
+        self.band_oversamplers
+            .iter_mut()
+            .flatten()
+            .for_each(Oversampler::reset);
+        self.dist_crossover.reset();
+        self.decimator.reset();
+
+        self.chorus.reset();
+        self.flanger.reset();
+        self.phaser.reset();
+        self.reverb.reset();
+
+        self.compressor.reset();
+        self.band_compressors.iter_mut().for_each(Compressor::reset);
+        self.comp_crossover.reset();
+        self.comp_key_crossover.reset();
+
+        self.spectral_filter.clear();
+    }
 }
 
 /// Audio generation types.
@@ -51,17 +104,30 @@ pub struct AudioData {
     pub sample_rate: Arc<AtomicF64>,
     pub upsampled_rate: Arc<AtomicF64>,
 
-    pub latency_samples: u32,
+    /// The total latency of the audio model in samples, which is shared with
+    /// the UI.
+    pub latency_samples: Arc<AtomicU32>,
 
+    /// The selected oversampling factor of the waveshaper (as an exponent).
     pub oversampling_factor: Arc<AtomicUsize>,
+    /// The oversampling factor the oversamplers are currently using.
+    pub current_oversampling_factor: usize,
 
     pub is_processing: bool,
     pub idle_timer_samples: u64,
 
-    pub low_filter_is_shelf: bool,
-    pub high_filter_is_shelf: bool,
-
     pub distortion_algorithm: DistortionType,
+    pub modulation_type: ModulationType,
+    /// The rate of the modulation effect in hertz, which is either the
+    /// smoothed rate or that of the synced note division.
+    pub mod_rate: f64,
+
+    /// Whether the waveshaper is currently processing each band separately.
+    pub dist_is_multiband: bool,
+    /// Whether the compressor is currently processing each band separately.
+    pub comp_is_multiband: bool,
+    /// Whether the EQ is currently processed with linear phase.
+    pub eq_is_linear_phase: bool,
 
     pub spectral_filter_size: usize,
     pub spectral_mask_post_fx: bool,
@@ -73,6 +139,16 @@ pub struct AudioData {
 
     pub delay_time_ms: f64,
 
+    /// The transport clock, which is advanced each audio callback.
+    pub transport: Transport,
+
+    /// The compressor's peak gain reduction over the last buffer in
+    /// decibels, which is read by the UI.
+    pub comp_gain_reduction: Arc<AtomicF64>,
+    /// Whether the output has been muted by the safety mute, which is read by
+    /// the UI.
+    pub safety_muted: Arc<AtomicBool>,
+
     pub sample_timer: u32,
 
     pub callback_time_elapsed: Arc<Mutex<Instant>>,
@@ -89,13 +165,11 @@ impl Default for AudioData {
             latency_samples: Default::default(),
 
             oversampling_factor: Arc::default(),
+            current_oversampling_factor: 0,
 
             is_processing: Default::default(),
             idle_timer_samples: Default::default(),
 
-            low_filter_is_shelf: false,
-            high_filter_is_shelf: false,
-
             spectral_mask_post_fx: false,
             spectral_filter_size: 1024,
             average_load: Vec::default(),
@@ -106,7 +180,17 @@ impl Default for AudioData {
 
             delay_time_ms: 250.0,
 
+            transport: Transport::new(unsafe { SAMPLE_RATE }),
+            comp_gain_reduction: Arc::default(),
+            safety_muted: Arc::default(),
+
             distortion_algorithm: DistortionType::default(),
+            modulation_type: ModulationType::default(),
+            mod_rate: 0.0,
+
+            dist_is_multiband: false,
+            comp_is_multiband: false,
+            eq_is_linear_phase: false,
 
             sample_timer: 0,
 
@@ -121,8 +205,16 @@ pub struct AudioBuffers {
     pub master_gain_buffer: Vec<f64>,
 
     pub oversampling_buffer: OversamplingBuffer,
+    /// The distortion curve of each sample in the block, used by the
+    /// oversampled waveshaper.
+    pub dist_curve_buffer: Vec<f64>,
+    /// The interleaved dry exciter signal of each frame in the block, which
+    /// may be used to key the compressor.
+    pub comp_key_buffer: Vec<f64>,
+    /// The bands of the multiband waveshaper's input.
+    pub band_buffers: Vec<OversamplingBuffer>,
 
-    pub spectral_mask: Option<triple_buffer::Output<SpectralMask>>,
+    pub spectral_mask: Option<triple_buffer::Output<StereoMask>>,
 
     pub reso_bank_data: Option<triple_buffer::Output<ResoBankData>>,
 }
@@ -161,3 +253,92 @@ pub struct AudioMessageSenders {
     pub resonator_bank_reset_pan: CCSender<()>,
     pub spectral_mask_post_fx: CCSender<()>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+    const BLOCK_SIZE: usize = 512;
+
+    /// Passes a block of `input` through the effects which hold state, and
+    /// then the safety mute.
+    fn process_block(
+        processors: &mut AudioProcessors,
+        input: f64,
+    ) -> Vec<(f64, f64)> {
+        let AudioProcessors {
+            resonator_bank,
+            stereo_delay,
+            eq,
+            decimator,
+            phaser,
+            reverb,
+            compressor,
+            spectral_filter,
+            safety_mute,
+            ..
+        } = processors;
+
+        let mut block_l = [0.0; BLOCK_SIZE];
+        let mut block_r = [0.0; BLOCK_SIZE];
+
+        for (out_l, out_r) in block_l.iter_mut().zip(&mut block_r) {
+            let (l, r) = resonator_bank.process_stereo(input, input);
+            let (l, r) = stereo_delay.process_stereo(l, r);
+            let (l, r) = eq.process_stereo(l, r);
+            let (l, r) = decimator.process_stereo(l, r);
+            let (l, r) = phaser.process_stereo(l, r);
+            let (l, r) = reverb.process_stereo(l, r);
+            (*out_l, *out_r) = compressor.process_stereo(l, r);
+        }
+
+        spectral_filter
+            .process_block(&mut [&mut block_l[..], &mut block_r[..]][..]);
+
+        block_l
+            .iter()
+            .zip(&block_r)
+            .map(|(&l, &r)| safety_mute.process_stereo(l, r))
+            .collect()
+    }
+
+    #[test]
+    fn effects_recover_from_nan_after_safety_mute() {
+        let mut stereo_delay = StereoDelay::new(1.0, SAMPLE_RATE)
+            .with_delay_time(0.25);
+        stereo_delay.set_feedback_amount(0.75);
+
+        let mut spectral_filter = SpectralFilter::new(NUM_CHANNELS, 1024);
+        spectral_filter.set_mix(0.5);
+
+        let mut processors = AudioProcessors {
+            resonator_bank: DryWet::new(ResonatorBank::new(SAMPLE_RATE, 8)),
+            stereo_delay: Box::new(DryWet::new(stereo_delay)),
+            eq: ParametricEQ::new(4, SAMPLE_RATE),
+            decimator: Box::new(Decimator::new(SAMPLE_RATE)),
+            phaser: Box::new(DryWet::new(Phaser::new(SAMPLE_RATE))),
+            reverb: Box::new(DryWet::new(Reverb::new(SAMPLE_RATE))),
+            compressor: Box::new(Compressor::new(SAMPLE_RATE)),
+            spectral_filter,
+            safety_mute: Box::new(SafetyMute::new(SAMPLE_RATE)),
+            ..AudioProcessors::default()
+        };
+
+        process_block(&mut processors, f64::NAN);
+        assert!(processors.safety_mute.take_tripped());
+        assert!(!processors.safety_mute.take_tripped());
+        processors.reset_effects();
+
+        // the mute is released once the effects have produced a second of
+        // finite output
+        let num_blocks = (SAMPLE_RATE * 2.0) as usize / BLOCK_SIZE;
+        let output: Vec<(f64, f64)> = (0..num_blocks)
+            .flat_map(|_| process_block(&mut processors, 0.01))
+            .collect();
+
+        assert!(output.iter().all(|(l, r)| l.is_finite() && r.is_finite()));
+        assert!(!processors.safety_mute.is_muted());
+        assert!(!processors.safety_mute.take_tripped());
+    }
+}
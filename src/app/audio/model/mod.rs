@@ -18,6 +18,9 @@ pub use params::AudioParams;
 /// is skipped to reduce total load when idle.
 const DSP_IDLE_HOLD_TIME_SECS: f64 = 0.8;
 
+/// The maximum time of the stereo delay in milliseconds.
+const MAX_DELAY_TIME_MS: f64 = 1000.0;
+
 /// The program's audio state.
 pub struct AudioModel {
     /// Fields related to audio generation (envelopes, oscillators, ...).
@@ -140,6 +143,39 @@ impl AudioModel {
         self.data.upsampled_rate.lr()
     }
 
+    /// Returns the total latency of the audio model in samples, as of the
+    /// last call to [`update_latency()`](Self::update_latency).
+    pub fn get_latency_samples(&self) -> u32 {
+        self.data.latency_samples.lr()
+    }
+
+    /// Updates the reported latency, which is the sum of the latencies of the
+    /// oversampled waveshaper (unless the decimator replaces it), the
+    /// compressor's lookahead, the output limiter and, if it is used, the
+    /// linear-phase EQ.
+    pub fn update_latency(&mut self) {
+        let oversampling =
+            if self.data.distortion_algorithm == DistortionType::Crush {
+                0
+            }
+            else {
+                let factor = self.data.current_oversampling_factor;
+                self.processors.oversamplers[0].latency(factor)
+            };
+
+        let lookahead = self.processors.compressor.latency_samples() as u32;
+        let limiter = self.processors.limiter.latency_samples() as u32;
+
+        let eq = if self.data.eq_is_linear_phase {
+            self.processors.linear_phase_eq.latency_samples()
+        }
+        else {
+            0
+        };
+
+        self.data.latency_samples.sr(oversampling + lookahead + limiter + eq);
+    }
+
     /// Returns the next available note event, if it exists.
     pub fn next_note_event(&self) -> Option<NoteEvent> {
         self.message_channels
@@ -224,6 +260,30 @@ impl AudioModel {
                 .resonator_bank
                 .set_panning_scale(self.params.reso_bank_pan.next());
         }
+        self.processors
+            .resonator_bank
+            .set_pitch_layout(self.params.reso_bank_pitch_layout.lr());
+
+        if self.params.reso_bank_layout_blend.is_active() {
+            self.processors
+                .resonator_bank
+                .set_layout_blend(self.params.reso_bank_layout_blend.next());
+        }
+        if self.params.reso_bank_decay.is_active() {
+            self.processors
+                .resonator_bank
+                .set_decay_time(self.params.reso_bank_decay.next());
+        }
+        if self.params.reso_bank_damping.is_active() {
+            self.processors
+                .resonator_bank
+                .set_damping(self.params.reso_bank_damping.next());
+        }
+        if self.params.reso_bank_decay_random.is_active() {
+            self.processors
+                .resonator_bank
+                .set_decay_random(self.params.reso_bank_decay_random.next());
+        }
         if self.params.reso_bank_mix.is_active() {
             self.processors
                 .resonator_bank
@@ -239,21 +299,545 @@ impl AudioModel {
         );
     }
 
+    /// Updates the transport from its parameters and advances it by
+    /// `num_samples`.
+    pub fn update_transport(&mut self, num_samples: usize) {
+        let transport = &mut self.data.transport;
+
+        transport.set_sample_rate(self.data.sample_rate.lr());
+        transport.set_bpm(self.params.bpm.lr());
+        transport.set_playing(self.params.transport_playing.lr());
+        transport.advance(num_samples);
+    }
+
+    /// Processes the waveshaper over `buffer` at the selected oversampling
+    /// factor. The curve of each sample is read from the distortion curve
+    /// buffer, which is filled by the post-processing loop.
+    ///
+    /// The decimator runs at the base sample rate, so replaces the waveshaper
+    /// entirely when it is selected.
+    pub fn process_waveshaper(&mut self, buffer: &mut Buffer<f64>) {
+        let factor = self.data.oversampling_factor.lr();
+
+        if factor != self.data.current_oversampling_factor {
+            self.set_oversampling_factor(factor);
+        }
+
+        let is_multiband = self.params.dist_bands.enabled.lr();
+
+        if is_multiband != self.data.dist_is_multiband {
+            self.data.dist_is_multiband = is_multiband;
+            self.reset_waveshaper();
+        }
+
+        if self.data.distortion_algorithm == DistortionType::Crush {
+            self.process_decimator(buffer);
+        }
+        else if is_multiband {
+            self.process_multiband_waveshaper(buffer, factor);
+        }
+        else {
+            let AudioBuffers { oversampling_buffer, dist_curve_buffer, .. } =
+                &mut self.buffers;
+            let AudioProcessors { oversamplers, waveshaper, .. } =
+                &mut self.processors;
+
+            let num_samples = buffer.len_frames();
+            oversampling_buffer.copy_from_buffer(buffer);
+
+            for (ch, (oversampler, ws)) in
+                oversamplers.iter_mut().zip(waveshaper.iter_mut()).enumerate()
+            {
+                let block =
+                    &mut oversampling_buffer.channel_mut(ch)[..num_samples];
+
+                oversampler.process(block, factor, |upsampled| {
+                    for (i, sample) in upsampled.iter_mut().enumerate() {
+                        ws.set_curve(dist_curve_buffer[i >> factor]);
+                        *sample = ws.process(*sample);
+                    }
+                });
+            }
+
+            oversampling_buffer.copy_to_buffer(buffer);
+        }
+    }
+
+    /// Splits `buffer` into bands and processes the waveshaper over each
+    /// band separately. Each band has its own oversamplers, so bypassed bands
+    /// are still resampled to stay aligned with the others.
+    fn process_multiband_waveshaper(
+        &mut self,
+        buffer: &mut Buffer<f64>,
+        factor: usize,
+    ) {
+        let AudioBuffers { band_buffers, dist_curve_buffer, .. } =
+            &mut self.buffers;
+        let AudioProcessors {
+            dist_crossover, band_oversamplers, waveshaper, ..
+        } = &mut self.processors;
+        let params = &self.params.dist_bands;
+
+        let num_samples = buffer.len_frames();
+        let mut bands = [(0.0, 0.0); NUM_BANDS];
+
+        for (i, fr) in buffer.frames_mut().enumerate() {
+            let freqs = params.next_crossover_freqs();
+
+            for (idx, freq) in freqs.into_iter().enumerate() {
+                if let Some(freq) = freq {
+                    dist_crossover.set_crossover_freq(idx, freq);
+                }
+            }
+
+            dist_crossover.split(fr[0], fr[1], &mut bands);
+
+            for (band_buffer, (l, r)) in band_buffers.iter_mut().zip(bands) {
+                band_buffer.channel_mut(0)[i] = l;
+                band_buffer.channel_mut(1)[i] = r;
+            }
+        }
+
+        for (b, (band_buffer, oversamplers)) in
+            band_buffers.iter_mut().zip(band_oversamplers).enumerate()
+        {
+            let is_bypassed = params.bypass[b].lr();
+
+            for (ch, (oversampler, ws)) in
+                oversamplers.iter_mut().zip(waveshaper.iter_mut()).enumerate()
+            {
+                let block = &mut band_buffer.channel_mut(ch)[..num_samples];
+
+                oversampler.process(block, factor, |upsampled| {
+                    if is_bypassed {
+                        return;
+                    }
+
+                    for (i, sample) in upsampled.iter_mut().enumerate() {
+                        ws.set_curve(dist_curve_buffer[i >> factor]);
+                        *sample = ws.process(*sample);
+                    }
+                });
+            }
+        }
+
+        for (i, fr) in buffer.frames_mut().enumerate() {
+            let gains = params.next_gains();
+            fr[0] = 0.0;
+            fr[1] = 0.0;
+
+            for (band_buffer, gain) in band_buffers.iter().zip(gains) {
+                fr[0] += band_buffer.channel(0)[i] * gain;
+                fr[1] += band_buffer.channel(1)[i] * gain;
+            }
+        }
+    }
+
+    /// Clears the state of the waveshaper's oversamplers and crossover.
+    fn reset_waveshaper(&mut self) {
+        let AudioProcessors {
+            oversamplers, band_oversamplers, dist_crossover, ..
+        } = &mut self.processors;
+
+        oversamplers.iter_mut().for_each(Oversampler::reset);
+        band_oversamplers
+            .iter_mut()
+            .flatten()
+            .for_each(Oversampler::reset);
+        dist_crossover.reset();
+    }
+
+    /// Processes the decimator over `buffer`, which is mixed with the input
+    /// by the distortion amount.
+    fn process_decimator(&mut self, buffer: &mut Buffer<f64>) {
+        let decimator = &mut self.processors.decimator;
+        let AudioParams {
+            crush_rate,
+            crush_bits,
+            crush_jitter,
+            crush_anti_alias,
+            crush_dither,
+            ..
+        } = &self.params;
+
+        decimator.set_anti_alias(crush_anti_alias.lr());
+        decimator.set_dither(crush_dither.lr());
+
+        for (fr, &amount) in
+            buffer.frames_mut().zip(&self.buffers.dist_curve_buffer)
+        {
+            if crush_rate.is_active() {
+                decimator.set_target_rate(crush_rate.next());
+            }
+            if crush_bits.is_active() {
+                decimator.set_bit_depth(crush_bits.next());
+            }
+            if crush_jitter.is_active() {
+                decimator.set_jitter(crush_jitter.next());
+            }
+
+            let (l, r) = decimator.process_stereo(fr[0], fr[1]);
+            fr[0] = lerp(fr[0], l, amount);
+            fr[1] = lerp(fr[1], r, amount);
+        }
+    }
+
+    /// Changes the oversampling factor of the waveshaper.
+    fn set_oversampling_factor(&mut self, factor: usize) {
+        self.reset_waveshaper();
+
+        let sample_rate = self.data.sample_rate.lr();
+
+        self.data.current_oversampling_factor = factor;
+        self.data.upsampled_rate.sr(sample_rate * (1 << factor) as f64);
+    }
+
+    /// Updates the internal state of the selected modulation effect.
+    pub fn update_modulation(&mut self) {
+        let mod_type = self.params.mod_type.lr();
+
+        if mod_type != self.data.modulation_type {
+            self.data.modulation_type = mod_type;
+            self.reset_modulation();
+        }
+
+        let rate = self.next_mod_rate();
+
+        let AudioProcessors { chorus, flanger, phaser, .. } =
+            &mut self.processors;
+        let AudioParams { mod_depth, mod_feedback, mod_mix, .. } =
+            &self.params;
+
+        match mod_type {
+            ModulationType::None => {}
+            ModulationType::Chorus => {
+                if let Some(rate) = rate {
+                    chorus.set_mod_rate(rate);
+                }
+                if mod_depth.is_active() {
+                    chorus.set_mod_depth(mod_depth.next());
+                }
+                if mod_mix.is_active() {
+                    chorus.set_mix_equal_power(mod_mix.next());
+                }
+            }
+            ModulationType::Flanger => {
+                if let Some(rate) = rate {
+                    flanger.set_mod_rate(rate);
+                }
+                if mod_depth.is_active() {
+                    flanger.set_mod_depth(mod_depth.next());
+                }
+                if mod_feedback.is_active() {
+                    flanger.set_feedback(mod_feedback.next());
+                }
+                if mod_mix.is_active() {
+                    flanger.set_mix_equal_power(mod_mix.next());
+                }
+            }
+            ModulationType::Phaser => {
+                if let Some(rate) = rate {
+                    phaser.set_mod_rate(rate);
+                }
+                if mod_depth.is_active() {
+                    phaser.set_mod_depth(mod_depth.next());
+                }
+                if mod_feedback.is_active() {
+                    phaser.set_feedback(mod_feedback.next());
+                }
+                if mod_mix.is_active() {
+                    phaser.set_mix_equal_power(mod_mix.next());
+                }
+            }
+        }
+    }
+
+    /// Clears the selected modulation effect, and sets its parameters to
+    /// their current values, as it may have been unused for some time.
+    fn reset_modulation(&mut self) {
+        let AudioProcessors { chorus, flanger, phaser, .. } =
+            &mut self.processors;
+
+        let rate = self.data.mod_rate;
+        let depth = self.params.mod_depth.current_value();
+        let feedback = self.params.mod_feedback.current_value();
+        let mix = self.params.mod_mix.current_value();
+
+        match self.data.modulation_type {
+            ModulationType::None => {}
+            ModulationType::Chorus => {
+                chorus.reset();
+                chorus.set_mod_rate(rate);
+                chorus.set_mod_depth(depth);
+                chorus.set_mix_equal_power(mix);
+            }
+            ModulationType::Flanger => {
+                flanger.reset();
+                flanger.set_mod_rate(rate);
+                flanger.set_mod_depth(depth);
+                flanger.set_feedback(feedback);
+                flanger.set_mix_equal_power(mix);
+            }
+            ModulationType::Phaser => {
+                phaser.reset();
+                phaser.set_mod_rate(rate);
+                phaser.set_mod_depth(depth);
+                phaser.set_feedback(feedback);
+                phaser.set_mix_equal_power(mix);
+            }
+        }
+    }
+
+    /// Returns the new rate of the modulation effect if it has changed,
+    /// which is that of the note division if the rate is synced to the
+    /// transport.
+    fn next_mod_rate(&mut self) -> Option<f64> {
+        let rate = if self.params.mod_sync.lr() {
+            let division = self.params.mod_division.lr();
+            division.hz(self.data.transport.bpm())
+        }
+        else if self.params.mod_rate.is_active() {
+            self.params.mod_rate.next()
+        }
+        else {
+            self.params.mod_rate.current_value()
+        };
+
+        if rate == self.data.mod_rate {
+            return None;
+        }
+
+        self.data.mod_rate = rate;
+        Some(rate)
+    }
+
+    /// Processes the selected modulation effect.
+    pub fn process_modulation(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        match self.data.modulation_type {
+            ModulationType::None => (in_l, in_r),
+            ModulationType::Chorus => {
+                self.processors.chorus.process_stereo(in_l, in_r)
+            }
+            ModulationType::Flanger => {
+                self.processors.flanger.process_stereo(in_l, in_r)
+            }
+            ModulationType::Phaser => {
+                self.processors.phaser.process_stereo(in_l, in_r)
+            }
+        }
+    }
+
+    /// Updates the internal state of the reverb.
+    pub fn update_reverb(&mut self) {
+        let reverb = &mut self.processors.reverb;
+        let AudioParams {
+            reverb_size: size,
+            reverb_decay: decay,
+            reverb_damping: damping,
+            reverb_pre_delay: pre_delay,
+            reverb_modulation: modulation,
+            reverb_mix: mix,
+            ..
+        } = &self.params;
+
+        if size.is_active() {
+            reverb.set_size(size.next());
+        }
+        if decay.is_active() {
+            reverb.set_decay(decay.next());
+        }
+        if damping.is_active() {
+            reverb.set_damping(damping.next());
+        }
+        if pre_delay.is_active() {
+            reverb.set_pre_delay(pre_delay.next());
+        }
+        if modulation.is_active() {
+            reverb.set_modulation(modulation.next());
+        }
+        if mix.is_active() {
+            reverb.set_mix_equal_power(mix.next());
+        }
+    }
+
+    /// Updates the internal state of the compressor and the per-band
+    /// compressors.
+    pub fn update_compressor(&mut self) {
+        let AudioParams {
+            comp_ratio,
+            comp_thresh,
+            comp_attack_ms,
+            comp_release_ms,
+            comp_knee_db,
+            comp_stereo_link,
+            comp_makeup_db,
+            comp_sidechain_hpf,
+            ..
+        } = &self.params;
+
+        // each smoother is only advanced once, as its value is shared by all
+        // of the compressors
+        let next = |param: &SmootherAtomic<f64>| {
+            param.is_active().then(|| param.next())
+        };
+        let ratio = next(comp_ratio);
+        let thresh = next(comp_thresh);
+        let attack_ms = next(comp_attack_ms);
+        let release_ms = next(comp_release_ms);
+        let knee_db = next(comp_knee_db);
+        let stereo_link = next(comp_stereo_link);
+        let makeup_db = next(comp_makeup_db);
+        let sidechain_hpf = next(comp_sidechain_hpf);
+
+        let use_rms = self.params.comp_use_rms.lr();
+        let use_auto_makeup = self.params.comp_auto_makeup.lr();
+        let lookahead_ms = self.params.comp_lookahead_ms.lr();
+
+        let AudioProcessors { compressor, band_compressors, .. } =
+            &mut self.processors;
+
+        let compressors = std::iter::once(&mut **compressor)
+            .chain(band_compressors.iter_mut());
+
+        for compressor in compressors {
+            if let Some(ratio) = ratio {
+                compressor.set_ratio(ratio);
+            }
+            if let Some(thresh) = thresh {
+                compressor.set_threshold_level_db(thresh);
+            }
+            if let Some(attack_ms) = attack_ms {
+                compressor.set_attack_time_ms(attack_ms);
+            }
+            if let Some(release_ms) = release_ms {
+                compressor.set_release_time_ms(release_ms);
+            }
+            if let Some(knee_db) = knee_db {
+                compressor.set_knee_width(knee_db);
+            }
+            if let Some(stereo_link) = stereo_link {
+                compressor.set_stereo_link(stereo_link);
+            }
+            if let Some(makeup_db) = makeup_db {
+                compressor.set_makeup_gain_db(makeup_db);
+            }
+            if let Some(sidechain_hpf) = sidechain_hpf {
+                compressor.set_sidechain_hpf(sidechain_hpf);
+            }
+
+            compressor.use_rms(use_rms);
+            compressor.use_auto_makeup(use_auto_makeup);
+            compressor.set_lookahead_ms(lookahead_ms);
+        }
+
+        let AudioProcessors { comp_crossover, comp_key_crossover, .. } =
+            &mut self.processors;
+        let freqs = self.params.comp_bands.next_crossover_freqs();
+
+        for (i, freq) in freqs.into_iter().enumerate() {
+            if let Some(freq) = freq {
+                comp_crossover.set_crossover_freq(i, freq);
+                comp_key_crossover.set_crossover_freq(i, freq);
+            }
+        }
+    }
+
+    /// Processes the compressor, which is keyed by `key` if it is provided.
+    /// If the compressor is multiband, the input and key are split into
+    /// bands which are compressed separately.
+    pub fn process_compressor(
+        &mut self,
+        in_l: f64,
+        in_r: f64,
+        key: Option<(f64, f64)>,
+    ) -> (f64, f64) {
+        let is_multiband = self.params.comp_bands.enabled.lr();
+        let AudioProcessors {
+            compressor,
+            band_compressors,
+            comp_crossover,
+            comp_key_crossover,
+            ..
+        } = &mut self.processors;
+
+        if is_multiband != self.data.comp_is_multiband {
+            self.data.comp_is_multiband = is_multiband;
+            comp_crossover.reset();
+            comp_key_crossover.reset();
+        }
+
+        if !is_multiband {
+            let (key_l, key_r) = key.unwrap_or((in_l, in_r));
+            return compressor.process_stereo_with_key(in_l, in_r, key_l, key_r);
+        }
+
+        let mut bands = [(0.0, 0.0); NUM_BANDS];
+        comp_crossover.split(in_l, in_r, &mut bands);
+
+        // each band is keyed by the same band of the key
+        let mut key_bands = bands;
+        if let Some((key_l, key_r)) = key {
+            comp_key_crossover.split(key_l, key_r, &mut key_bands);
+        }
+
+        let params = &self.params.comp_bands;
+        let gains = params.next_gains();
+        let (mut out_l, mut out_r) = (0.0, 0.0);
+
+        for (b, compressor) in band_compressors.iter_mut().enumerate() {
+            let (l, r) = bands[b];
+            let (l, r) = if params.bypass[b].lr() {
+                compressor.process_bypassed(l, r)
+            }
+            else {
+                let (key_l, key_r) = key_bands[b];
+                compressor.process_stereo_with_key(l, r, key_l, key_r)
+            };
+
+            out_l += l * gains[b];
+            out_r += r * gains[b];
+        }
+
+        (out_l, out_r)
+    }
+
+    /// Returns the gain reduction of the compressor in decibels. If the
+    /// compressor is multiband, the largest reduction of any band is used.
+    pub fn compressor_gain_reduction_db(&self) -> f64 {
+        if self.data.comp_is_multiband {
+            self.processors
+                .band_compressors
+                .iter()
+                .fold(0.0, |max, comp| max.max(comp.gain_reduction_db()))
+        }
+        else {
+            self.processors.compressor.gain_reduction_db()
+        }
+    }
+
+    /// Updates the internal state of the output limiter.
+    pub fn update_limiter(&mut self) {
+        let limiter = &mut self.processors.limiter;
+
+        if self.params.limiter_ceiling_db.is_active() {
+            limiter.set_ceiling_db(self.params.limiter_ceiling_db.next());
+        }
+        if self.params.limiter_release_ms.is_active() {
+            limiter.set_release_time_ms(self.params.limiter_release_ms.next());
+        }
+    }
+
     /// Updates the internal state of the post-processors.
     #[allow(clippy::too_many_lines)]
     pub fn update_post_processors(&mut self) {
-        let AudioProcessors {
-            filter_low,  // arr
-            filter_peak, // arr
-            filter_high, // arr
+        self.update_eq();
 
+        let AudioProcessors {
             stereo_delay,
 
             spectral_filter,
 
             waveshaper, // arr
-
-            compressor,
             ..
         } = &mut self.processors;
 
@@ -265,25 +849,49 @@ impl AudioModel {
             stereo_delay.set_feedback_amount(self.params.delay_feedback.next());
         }
 
-        let delay_time = self.params.delay_time_ms.lr();
+        // synced delay times are limited to the delay's maximum time
+        let bpm = self.data.transport.bpm();
+        let sync = self.params.delay_sync.lr();
+        let time_of = |division: NoteDivision, time_ms: f64| {
+            if sync {
+                (division.duration_secs(bpm) * 1000.0).min(MAX_DELAY_TIME_MS)
+            }
+            else {
+                time_ms
+            }
+        };
 
-        stereo_delay.ping_pong(self.params.use_ping_pong.lr());
-        stereo_delay.set_delay_time(delay_time * 0.001);
+        let delay_time = time_of(
+            self.params.delay_division.lr(),
+            self.params.delay_time_ms.lr(),
+        );
+        // the right channel follows the left channel while linked
+        let delay_time_r = if self.params.delay_link.lr() {
+            delay_time
+        }
+        else {
+            time_of(
+                self.params.delay_division_r.lr(),
+                self.params.delay_time_r_ms.lr(),
+            )
+        };
 
-        // compressor
-        if self.params.comp_ratio.is_active() {
-            compressor.set_ratio(self.params.comp_ratio.next());
+        if self.params.delay_low_cut.is_active() {
+            stereo_delay.set_low_cut(self.params.delay_low_cut.next());
         }
-        if self.params.comp_thresh.is_active() {
-            compressor.set_threshold_level_db(self.params.comp_thresh.next());
+        if self.params.delay_high_cut.is_active() {
+            stereo_delay.set_high_cut(self.params.delay_high_cut.next());
         }
-        if self.params.comp_attack_ms.is_active() {
-            compressor.set_attack_time_ms(self.params.comp_attack_ms.next());
+        if self.params.delay_saturation.is_active() {
+            stereo_delay.set_saturation(self.params.delay_saturation.next());
         }
-        if self.params.comp_release_ms.is_active() {
-            compressor.set_release_time_ms(self.params.comp_release_ms.next());
+        if self.params.delay_wow_flutter.is_active() {
+            stereo_delay.set_wow_flutter(self.params.delay_wow_flutter.next());
         }
 
+        stereo_delay.ping_pong(self.params.use_ping_pong.lr());
+        stereo_delay.set_delay_times(delay_time * 0.001, delay_time_r * 0.001);
+
         // waveshaper
         let param_dist_algo = self.params.dist_type.lr();
         let curr_dist_algo = self.data.distortion_algorithm;
@@ -296,11 +904,6 @@ impl AudioModel {
             true
         };
 
-        if self.params.dist_amount.is_active() {
-            waveshaper[0].set_curve(self.params.dist_amount.next());
-            waveshaper[1].set_curve(self.params.dist_amount.current_value());
-        }
-
         for ch in 0..2 {
             if update_ws_algo {
                 match self.data.distortion_algorithm {
@@ -345,125 +948,95 @@ impl AudioModel {
                             },
                         );
                     }
+                    // the decimator replaces the waveshaper
                     DistortionType::Crush => {
-                        waveshaper[ch].set_curve_range(0.0..=1.0);
-                        waveshaper[ch].set_xfer_function(|input, mut cv| {
-                            let round = |val: f64| val.floor();
-
-                            cv = scale(cv, 80.0, 2.0);
-                            round(cv * input) / cv
-                        });
+                        waveshaper[ch].set_asymmetric(false);
+                        waveshaper[ch].set_xfer_function(|input, _| input);
                     }
                 }
             }
         }
 
-        let low_fil_shelf = self.params.low_filter_is_shelf.lr();
+        // the oversamplers aren't used by the decimator, so are cleared of
+        // the audio they held before it was selected
+        if update_ws_algo && curr_dist_algo == DistortionType::Crush {
+            self.reset_waveshaper();
+        }
+    }
+
+    /// Updates the bands of the parametric EQ.
+    fn update_eq(&mut self) {
+        let bands = self.processors.eq.bands_mut();
 
-        if self.data.low_filter_is_shelf != low_fil_shelf {
-            self.data.low_filter_is_shelf = low_fil_shelf;
+        for (band, params) in bands.zip(&self.params.eq_params.bands) {
+            let enabled = params.enabled.lr();
 
-            filter_low[0].set_type(if low_fil_shelf {
-                FilterType::Lowshelf
+            // bands may be moved whilst disabled, so catch up when enabled
+            if enabled && !band.is_enabled() {
+                band.set_freq(params.cutoff.current_value());
+                band.set_gain_db(params.gain_db.current_value());
+                band.set_q(params.q.current_value());
             }
-            else {
-                FilterType::Highpass
-            });
 
-            filter_low[1].set_type(if low_fil_shelf {
-                FilterType::Lowshelf
+            band.set_enabled(enabled);
+            band.set_type(params.filter_type.lr());
+            band.set_slope(params.slope.lr());
+            band.set_dynamic(params.dynamic.lr());
+            band.set_threshold_db(params.threshold_db.lr());
+
+            if params.cutoff.is_active() {
+                band.set_freq(params.cutoff.next());
+            }
+            if params.gain_db.is_active() {
+                band.set_gain_db(params.gain_db.next());
+            }
+            if params.q.is_active() {
+                band.set_q(params.q.next());
             }
-            else {
-                FilterType::Highpass
-            });
         }
+    }
 
-        let high_fil_shelf = self.params.high_filter_is_shelf.lr();
+    /// Switches the EQ between its minimum-phase and linear-phase modes,
+    /// clearing the state of the mode being switched to.
+    pub fn update_eq_mode(&mut self) {
+        let is_linear_phase = self.params.eq_params.linear_phase.lr();
 
-        if self.data.high_filter_is_shelf != high_fil_shelf {
-            self.data.high_filter_is_shelf = high_fil_shelf;
+        if is_linear_phase != self.data.eq_is_linear_phase {
+            self.data.eq_is_linear_phase = is_linear_phase;
 
-            filter_high[0].set_type(if high_fil_shelf {
-                FilterType::Highshelf
+            if is_linear_phase {
+                self.processors.linear_phase_eq.clear();
             }
             else {
-                FilterType::Lowpass
-            });
-
-            filter_high[1].set_type(if high_fil_shelf {
-                FilterType::Highshelf
+                self.processors.eq.reset();
             }
-            else {
-                FilterType::Lowpass
-            });
-        }
-
-        // low filter params
-        if self.params.low_filter_cutoff.is_active() {
-            filter_low[0].set_freq(self.params.low_filter_cutoff.next());
-            filter_low[1]
-                .set_freq(self.params.low_filter_cutoff.current_value());
-        }
-        if self.params.low_filter_gain_db.is_active() {
-            filter_low[0].set_gain(self.params.low_filter_gain_db.next());
-            filter_low[1]
-                .set_gain(self.params.low_filter_gain_db.current_value());
-        }
-        if self.params.low_filter_q.is_active() {
-            let q = if low_fil_shelf {
-                BUTTERWORTH_Q
-            }
-            else {
-                self.params.low_filter_q.next()
-            };
-            filter_low[0].set_q(q);
-            filter_low[1].set_q(q);
-        }
-
-        if self.params.peak_filter_cutoff.is_active() {
-            filter_peak[0].set_freq(self.params.peak_filter_cutoff.next());
-            filter_peak[1]
-                .set_freq(self.params.peak_filter_cutoff.current_value());
-        }
-        if self.params.peak_filter_q.is_active() {
-            filter_peak[0].set_q(self.params.peak_filter_q.next());
-            filter_peak[1].set_q(self.params.peak_filter_q.current_value());
-        }
-        if self.params.peak_filter_gain_db.is_active() {
-            filter_peak[0].set_gain(self.params.peak_filter_gain_db.next());
-            filter_peak[1]
-                .set_gain(self.params.peak_filter_gain_db.current_value());
         }
+    }
 
-        if self.params.high_filter_cutoff.is_active() {
-            filter_high[0].set_freq(self.params.high_filter_cutoff.next());
-            filter_high[1]
-                .set_freq(self.params.high_filter_cutoff.current_value());
+    /// Processes the EQ. In linear-phase mode, this only follows the
+    /// envelopes of dynamic bands, as the EQ is processed over the whole
+    /// block by [`process_linear_phase_eq()`](Self::process_linear_phase_eq).
+    pub fn process_eq(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        if self.data.eq_is_linear_phase {
+            self.processors.eq.update_envelopes(in_l, in_r);
+            (in_l, in_r)
         }
-        if self.params.high_filter_gain_db.is_active() {
-            filter_high[0].set_gain(self.params.high_filter_gain_db.next());
-            filter_high[1]
-                .set_gain(self.params.high_filter_gain_db.current_value());
+        else {
+            self.processors.eq.process_stereo(in_l, in_r)
         }
-        if self.params.high_filter_q.is_active() {
-            let q = if high_fil_shelf {
-                BUTTERWORTH_Q
-            }
-            else {
-                self.params.high_filter_q.next()
-            };
+    }
 
-            filter_high[0].set_q(q);
-            filter_high[1].set_q(q);
+    /// Processes the linear-phase EQ over `buffer`, if it is enabled.
+    pub fn process_linear_phase_eq(&mut self, buffer: &mut Buffer<f64>) {
+        if self.data.eq_is_linear_phase {
+            let AudioProcessors { eq, linear_phase_eq, .. } =
+                &mut self.processors;
+            linear_phase_eq.process_block(buffer, eq);
         }
     }
 
     /// Processes all filters.
     pub fn process_filters(&mut self, mut sample: f64, ch_idx: usize) -> f64 {
-        sample = self.processors.filter_low[ch_idx].process(sample);
-        sample = self.processors.filter_peak[ch_idx].process(sample);
-        sample = self.processors.filter_high[ch_idx].process(sample);
-
         // tone shaping filters
         sample = self.processors.filter_hs_ts[ch_idx].process(sample);
         sample = self.processors.filter_pk_ts[ch_idx].process(sample);
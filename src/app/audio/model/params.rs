@@ -11,6 +11,11 @@ use std::sync::{
 /// All audio-related parameters linked to the UI.
 #[derive(Default)]
 pub struct AudioParams {
+    /// The tempo of the transport in beats per minute.
+    pub bpm: Arc<AtomicF64>,
+    /// Whether the transport is playing.
+    pub transport_playing: Arc<AtomicBool>,
+
     ///  The block size of the spectral filter.
     pub mask_resolution: Arc<Atomic<SpectralFilterSize>>,
     /// Whether the spectral filter is post-FX or not.
@@ -32,6 +37,16 @@ pub struct AudioParams {
     pub reso_bank_pan: Arc<SmootherAtomic<f64>>,
     /// Whether the resonators should quantise their pitch to a scale.
     pub reso_bank_quantize: Arc<AtomicBool>,
+    /// The pitch layout which the resonator pitches are blended with.
+    pub reso_bank_pitch_layout: Arc<Atomic<PitchLayout>>,
+    /// How much each resonator's pitch is blended towards the pitch layout.
+    pub reso_bank_layout_blend: Arc<SmootherAtomic<f64>>,
+    /// The time for each resonator to decay by 60 dB in seconds.
+    pub reso_bank_decay: Arc<SmootherAtomic<f64>>,
+    /// How much faster higher resonators decay than lower ones.
+    pub reso_bank_damping: Arc<SmootherAtomic<f64>>,
+    /// How much each resonator's decay time is randomly varied.
+    pub reso_bank_decay_random: Arc<SmootherAtomic<f64>>,
     /// The number of active resonators in the bank.
     pub reso_bank_resonator_count: Arc<AtomicU32>,
     /// The dry/wet mix of the resonator bank.
@@ -39,31 +54,8 @@ pub struct AudioParams {
     /// The exciter oscillator.
     pub exciter_osc: Arc<Atomic<ExciterOscillator>>,
 
-    /// The cutoff of the filter in Hz.
-    pub low_filter_cutoff: Arc<SmootherAtomic<f64>>,
-    /// The Q value of the cut filter.
-    pub low_filter_q: Arc<SmootherAtomic<f64>>,
-    /// The gain value of the shelf filter
-    pub low_filter_gain_db: Arc<SmootherAtomic<f64>>,
-    /// Whether the low filter is a shelf filter or not.
-    pub low_filter_is_shelf: Arc<AtomicBool>,
-
-    // PEAK
-    /// The cutoff of the filter in Hz.
-    pub peak_filter_cutoff: Arc<SmootherAtomic<f64>>,
-    /// The Q value of the peak filter.
-    pub peak_filter_q: Arc<SmootherAtomic<f64>>,
-    /// The gain value of the peak filter.
-    pub peak_filter_gain_db: Arc<SmootherAtomic<f64>>,
-
-    /// The cutoff of the high-pass filter in Hz.
-    pub high_filter_cutoff: Arc<SmootherAtomic<f64>>,
-    /// The Q value of the high-pass filter.
-    pub high_filter_q: Arc<SmootherAtomic<f64>>,
-    /// The gain value of the shelf filter.
-    pub high_filter_gain_db: Arc<SmootherAtomic<f64>>,
-    /// Whether the high filter is a shelf filter or not.
-    pub high_filter_is_shelf: Arc<AtomicBool>,
+    /// The bands of the parametric EQ.
+    pub eq_params: EQParams,
 
     /// The time between delay taps in milliseconds.
     pub delay_time_ms: Arc<AtomicF64>,
@@ -73,12 +65,69 @@ pub struct AudioParams {
     pub delay_mix: Arc<SmootherAtomic<f64>>,
     /// Whether to use ping-pong delay or not.
     pub use_ping_pong: Arc<AtomicBool>,
+    /// Whether the delay time is synced to the transport.
+    pub delay_sync: Arc<AtomicBool>,
+    /// The note division of the delay time when synced to the transport.
+    pub delay_division: Arc<Atomic<NoteDivision>>,
+    /// Whether the right delay channel uses the left channel's time.
+    pub delay_link: Arc<AtomicBool>,
+    /// The time between right channel delay taps in milliseconds.
+    pub delay_time_r_ms: Arc<AtomicF64>,
+    /// The note division of the right channel when synced.
+    pub delay_division_r: Arc<Atomic<NoteDivision>>,
+    /// The delay feedback highpass cutoff.
+    pub delay_low_cut: Arc<SmootherAtomic<f64>>,
+    /// The delay feedback lowpass cutoff.
+    pub delay_high_cut: Arc<SmootherAtomic<f64>>,
+    /// The delay feedback saturation.
+    pub delay_saturation: Arc<SmootherAtomic<f64>>,
+    /// The depth of the delay wow and flutter.
+    pub delay_wow_flutter: Arc<SmootherAtomic<f64>>,
 
     /// The "amount" of distortion, i.e. drive.
     pub dist_amount: Arc<SmootherAtomic<f64>>,
     /// The distortion algorithm.
     pub dist_type: Arc<Atomic<DistortionType>>,
 
+    /// The bitcrusher sample rate in hertz.
+    pub crush_rate: Arc<SmootherAtomic<f64>>,
+    /// The bitcrusher bit depth.
+    pub crush_bits: Arc<SmootherAtomic<f64>>,
+    /// The bitcrusher sampling jitter.
+    pub crush_jitter: Arc<SmootherAtomic<f64>>,
+    /// Whether the bitcrusher's input is lowpass filtered.
+    pub crush_anti_alias: Arc<AtomicBool>,
+    /// The bitcrusher dither.
+    pub crush_dither: Arc<Atomic<DitherType>>,
+
+    /// The modulation effect.
+    pub mod_type: Arc<Atomic<ModulationType>>,
+    /// The modulation rate in hertz.
+    pub mod_rate: Arc<SmootherAtomic<f64>>,
+    /// Whether the modulation rate is synced to the transport.
+    pub mod_sync: Arc<AtomicBool>,
+    /// The note division of the modulation rate when synced to the transport.
+    pub mod_division: Arc<Atomic<NoteDivision>>,
+    /// The modulation depth.
+    pub mod_depth: Arc<SmootherAtomic<f64>>,
+    /// The flanger and phaser feedback.
+    pub mod_feedback: Arc<SmootherAtomic<f64>>,
+    /// The dry/wet mix of the modulation.
+    pub mod_mix: Arc<SmootherAtomic<f64>>,
+
+    /// The reverb size.
+    pub reverb_size: Arc<SmootherAtomic<f64>>,
+    /// The reverb decay time in seconds.
+    pub reverb_decay: Arc<SmootherAtomic<f64>>,
+    /// The reverb high-frequency damping.
+    pub reverb_damping: Arc<SmootherAtomic<f64>>,
+    /// The reverb pre-delay in milliseconds.
+    pub reverb_pre_delay: Arc<SmootherAtomic<f64>>,
+    /// The depth of the reverb modulation.
+    pub reverb_modulation: Arc<SmootherAtomic<f64>>,
+    /// The dry/wet mix of the reverb.
+    pub reverb_mix: Arc<SmootherAtomic<f64>>,
+
     /// Compressor threshold.
     pub comp_thresh: Arc<SmootherAtomic<f64>>,
     /// Compressor ratio.
@@ -87,6 +136,32 @@ pub struct AudioParams {
     pub comp_attack_ms: Arc<SmootherAtomic<f64>>,
     /// Compressor release time in milliseconds.
     pub comp_release_ms: Arc<SmootherAtomic<f64>>,
+    /// Compressor knee width in decibels.
+    pub comp_knee_db: Arc<SmootherAtomic<f64>>,
+    /// Whether the compressor detects RMS levels.
+    pub comp_use_rms: Arc<AtomicBool>,
+    /// Compressor stereo link amount.
+    pub comp_stereo_link: Arc<SmootherAtomic<f64>>,
+    /// Compressor manual makeup gain in decibels.
+    pub comp_makeup_db: Arc<SmootherAtomic<f64>>,
+    /// Whether the compressor's makeup gain is automatic.
+    pub comp_auto_makeup: Arc<AtomicBool>,
+    /// Compressor lookahead time in milliseconds.
+    pub comp_lookahead_ms: Arc<AtomicF64>,
+    /// Compressor sidechain highpass cutoff.
+    pub comp_sidechain_hpf: Arc<SmootherAtomic<f64>>,
+    /// Whether the compressor is keyed by the dry exciter signal.
+    pub comp_exciter_key: Arc<AtomicBool>,
+
+    /// The waveshaper's multiband settings.
+    pub dist_bands: MultibandParams,
+    /// The compressor's multiband settings.
+    pub comp_bands: MultibandParams,
+
+    /// Limiter ceiling in decibels true peak.
+    pub limiter_ceiling_db: Arc<SmootherAtomic<f64>>,
+    /// Limiter release time in milliseconds.
+    pub limiter_release_ms: Arc<SmootherAtomic<f64>>,
 
     pub pre_fx_gain: Arc<SmootherAtomic<f64>>,
     /// The device's master gain level.
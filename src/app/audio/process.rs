@@ -23,6 +23,9 @@ pub fn process(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
     let audio_is_idle = audio.is_idle();
     let buffer_len = buffer.len_frames();
 
+    // the transport runs regardless of whether any audio is being processed
+    audio.update_transport(buffer_len);
+
     // best not to block at all here - if the VoiceHandler lock can't be
     // obtained, then the note events won't be processed for this buffer.
     // let mut note_handler_guard = context.note_handler.try_lock().ok();
@@ -63,9 +66,11 @@ pub fn process(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
                                 audio.data.sample_rate.lr(),
                                 Some(audio.generation.amp_envelope.clone()),
                             );
+                            audio.processors.resonator_bank.note_on(note);
                         }
                         NoteEvent::NoteOff { note, .. } => {
                             voice_handler.start_release_for_voice(None, note);
+                            audio.processors.resonator_bank.note_off(note);
                         }
                     }
 
@@ -125,12 +130,13 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
     // update spectral mask
     if let Some(mask) = &mut audio.buffers.spectral_mask {
         if mask.update() {
-            audio.processors.spectral_filter.set_mask(mask.read());
+            audio.processors.spectral_filter.set_stereo_mask(mask.read());
         }
     }
 
     // set spectral filter
     audio.update_spectral_filter();
+    audio.update_eq_mode();
 
     let should_process_reso_bank =
         audio.params.reso_bank_mix.current_value() > f64::EPSILON;
@@ -142,11 +148,17 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
         for ch in 0..NUM_CHANNELS {
             fr[ch] =
                 audio.processors.pre_fx_dc_filter[ch].process_mono(fr[ch], ch);
-            if should_process_reso_bank {
-                fr[ch] =
-                    audio.processors.resonator_bank.process_mono(fr[ch], ch);
+            // the dry exciter signal, which may key the compressor. the
+            // resonator bank is linear, so its input is scaled instead
+            let exciter = fr[ch] * 64.0;
+            audio.buffers.comp_key_buffer[i * NUM_CHANNELS + ch] = exciter;
+
+            fr[ch] = if should_process_reso_bank {
+                audio.processors.resonator_bank.process_mono(exciter, ch)
             }
-            fr[ch] *= 64.0;
+            else {
+                exciter
+            };
         }
     }
 
@@ -163,6 +175,7 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
 
     for (i, fr) in buffer.frames_mut().enumerate() {
         audio.update_post_processors();
+        audio.buffers.dist_curve_buffer[i] = audio.params.dist_amount.next();
 
         // because ping-pong delay requires cross-feeding channels, it has to
         // be out of the other two loops in the middle here.
@@ -170,24 +183,50 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
             audio.processors.stereo_delay.process_stereo(fr[0], fr[1]);
 
         // process filters
+        (fr[0], fr[1]) = audio.process_eq(fr[0], fr[1]);
+
         for ch in 0..NUM_CHANNELS {
-            let mut sample = fr[ch];
+            fr[ch] = audio.process_filters(fr[ch], ch);
+        }
+    }
 
-            sample = audio.process_filters(sample, ch);
+    // the linear-phase eq is processed over the whole block
+    audio.process_linear_phase_eq(buffer);
 
-            sample = audio.processors.waveshaper[ch].process(sample);
+    // the waveshaper is oversampled, so it is processed over the whole block
+    audio.process_waveshaper(buffer);
 
-            sample =
-                audio.processors.post_fx_dc_filter[ch].process_mono(sample, ch);
+    let use_exciter_key = audio.params.comp_exciter_key.lr();
+    let mut gain_reduction_db: f64 = 0.0;
 
-            fr[ch] = sample;
+    for (i, fr) in buffer.frames_mut().enumerate() {
+        audio.update_modulation();
+        audio.update_reverb();
+        audio.update_compressor();
+
+        for ch in 0..NUM_CHANNELS {
+            fr[ch] =
+                audio.processors.post_fx_dc_filter[ch].process_mono(fr[ch], ch);
         }
 
-        // process compressor
+        (fr[0], fr[1]) = audio.process_modulation(fr[0], fr[1]);
         (fr[0], fr[1]) =
-            audio.processors.compressor.process_stereo(fr[0], fr[1]);
+            audio.processors.reverb.process_stereo(fr[0], fr[1]);
+
+        // process compressor
+        let key = use_exciter_key.then(|| {
+            let key = &audio.buffers.comp_key_buffer[i * NUM_CHANNELS..];
+            (key[0], key[1])
+        });
+
+        (fr[0], fr[1]) = audio.process_compressor(fr[0], fr[1], key);
+
+        gain_reduction_db =
+            gain_reduction_db.max(audio.compressor_gain_reduction_db());
     }
 
+    audio.data.comp_gain_reduction.sr(gain_reduction_db);
+
     // process the spectral filter
     audio
         .processors
@@ -198,45 +237,6 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
     // process the post-fx spectrum analyser
     audio.compute_post_spectrum(buffer);
 
-    // UNUSED OVERSAMPLING LOOP 
-
-    // // copy the audio buffer into the oversampling buffer so the channel layout
-    // // is compatible with the oversamplers.
-    // audio.oversampling_buffer.copy_from_buffer(buffer);
-    //
-    // // compute gain information
-    // audio.master_gain.next_block_exact(&mut audio.gain_data);
-    //
-    // // process the oversampling
-    // for ((ch, block), oversampler) in audio
-    //     .oversampling_buffer
-    //     .iter_mut()
-    //     .enumerate()
-    //     .zip(audio.oversamplers.iter_mut())
-    // {
-    //     oversampler.process(
-    //         &mut block[..BUFFER_SIZE],
-    //         audio
-    //             .oversampling_factor
-    //             .load(std::sync::atomic::Ordering::Relaxed),
-    //         |upsampled| {
-    //             // a single channel iterating through each upsampled sample
-    //             for (smp_idx, sample) in upsampled.iter_mut().enumerate() {
-    //                 *sample = audio.pre_dc_filter[ch].process_mono(*sample);
-    //                 *sample = audio.waveshaper[ch].process(*sample);
-    //                 *sample = audio.post_dc_filter[ch].process_mono(*sample);
-    //                 // *sample = audio.filter_lp[ch].process(*sample);
-    //
-    //                 *sample *= audio.gain_data[smp_idx];
-    //             }
-    //         },
-    //     );
-    // }
-    //
-    // // copy the oversampling buffer content back to the main audio buffer with the
-    // // correct channel layout.
-    // audio.oversampling_buffer.copy_to_buffer(buffer);
-
     // final loop
     let mut is_processing = false;
     for (i, output) in buffer.frames_mut().enumerate() {
@@ -246,6 +246,14 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
         output[0] *= gain;
         output[1] *= gain;
 
+        // the safety mute runs first so that the limiter's state isn't
+        // corrupted by non-finite samples
+        audio.update_limiter();
+        (output[0], output[1]) =
+            audio.processors.safety_mute.process_stereo(output[0], output[1]);
+        (output[0], output[1]) =
+            audio.processors.limiter.process_stereo(output[0], output[1]);
+
         // used to decide whether to skip DSP processing in the next block or not
         if (output[0].abs() > SIGNAL_EPSILON
             || output[1].abs() > SIGNAL_EPSILON)
@@ -258,4 +266,12 @@ fn process_fx(audio: &mut AudioModel, buffer: &mut Buffer<f64>) {
     }
 
     audio.data.is_processing = is_processing;
+    audio.data.safety_muted.sr(audio.processors.safety_mute.is_muted());
+
+    // non-finite samples would otherwise remain in the effects' state
+    if audio.processors.safety_mute.take_tripped() {
+        audio.processors.reset_effects();
+    }
+
+    audio.update_latency();
 }
@@ -87,11 +87,17 @@ impl Voice {
             ExciterOscillator::Square => {
                 Generator::Square(SquareOsc::new(freq, sample_rate))
             }
-            ExciterOscillator::Noise => Generator::Noise,
+            ExciterOscillator::Noise => noise_generator(self.id),
         }
     }
 }
 
+/// Returns a noise generator for the voice with `id`. Each voice has its own
+/// noise, which is reproducible from the session seed.
+fn noise_generator(id: u64) -> Generator {
+    Generator::Noise(NoiseOsc::new(stream_seed("noise").wrapping_add(id)))
+}
+
 /// A struct to handle all voices, i.e. the spawning and termination of voices.
 #[derive(Debug)]
 pub struct VoiceHandler {
@@ -200,7 +206,7 @@ impl VoiceHandler {
             sample_rate: Arc::clone(&self.sample_rate),
             generator_type: Arc::clone(gen),
             curr_generator: ExciterOscillator::Noise,
-            generator: { Generator::Noise },
+            generator: noise_generator(next_voice_id),
         };
 
         new_voice.update_generator();
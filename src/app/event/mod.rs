@@ -12,6 +12,11 @@ pub fn event(app: &App, model: &mut Model, win_event: WindowEvent) {
         MouseWheel(MouseScrollDelta::PixelDelta(pos), _) => {
             model.input_data.scroll_delta = vec2(pos.x as f32, pos.y as f32);
         }
+        DroppedFile(path) => {
+            if let Ok(guard) = model.image_mask.read() {
+                guard.generator().load(path);
+            }
+        }
         Focused => model.input_data.is_win_focussed = true,
         Unfocused => model.input_data.is_win_focussed = false,
         _ => {}
@@ -26,21 +26,28 @@ pub fn key_pressed(_app: &App, model: &mut Model, key: Key) {
                 drop(sml);
             }
             GenerativeAlgo::Voronoi => {
-                let (m_tl, m_br) = (
-                    model.mask_rect.top_left(),
-                    model.mask_rect.bottom_right(),
-                );
-
                 if let Ok(mut guard) = model.voronoi_vectors.write() {
-                    guard.override_points().iter_mut().for_each(|p| {
-                        p.vel.x = random_range(-1.0, 1.0);
-                        p.vel.y = random_range(-1.0, 1.0);
-
-                        p.pos.x = random_range(m_tl.x, m_br.x);
-                        p.pos.y = random_range(m_br.y, m_tl.y);
-                    });
+                    guard.scatter_points(1.0);
                 }
             }
+            GenerativeAlgo::ReactionDiffusion => {
+                let rd = model.reaction_diffusion.read().unwrap();
+
+                rd.generator().randomize();
+                drop(rd);
+            }
+            GenerativeAlgo::Image => {
+                let img = model.image_mask.read().unwrap();
+
+                img.generator().restart();
+                drop(img);
+            }
+            GenerativeAlgo::SpectralFeedback => {
+                let fb = model.spectral_feedback.read().unwrap();
+
+                fb.generator().clear();
+                drop(fb);
+            }
         },
         Key::Tab => {
             model.reso_bank_push_sender_key.send(()).unwrap();
@@ -23,8 +23,67 @@ use update::update;
 
 /// Runs the app via Nannou.
 pub fn run_app() {
+    init_session_seed();
+
     nannou::app(model::Model::build)
         .loop_mode(RefreshSync)
         .update(update)
         .run();
 }
+
+/// Sets the session seed from the `--seed <integer>` command-line argument, or
+/// to a random seed if it is not given. The seed is printed so that the
+/// session can be reproduced.
+fn init_session_seed() {
+    let seed = seed_arg(std::env::args().skip(1))
+        .unwrap_or_else(nannou::rand::random);
+
+    set_session_seed(seed);
+    println!("session seed: {seed}");
+}
+
+fn seed_arg(mut args: impl Iterator<Item = String>) -> Option<u64> {
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--seed") {
+            Some("") => args.next(),
+            Some(value) if value.starts_with('=') => Some(value[1..].into()),
+            _ => continue,
+        };
+
+        let seed = value.and_then(|v| v.parse().ok());
+        if seed.is_none() {
+            eprintln!("invalid session seed: expected `--seed <integer>`");
+        }
+
+        return seed;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_from(args: &[&str]) -> Option<u64> {
+        seed_arg(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn seed_argument_is_parsed() {
+        assert_eq!(seed_from(&["--seed", "42"]), Some(42));
+        assert_eq!(seed_from(&["--seed=42"]), Some(42));
+        assert_eq!(seed_from(&["--other", "--seed", "7", "x"]), Some(7));
+        assert_eq!(seed_from(&["--seed=1", "--seed=2"]), Some(1));
+    }
+
+    #[test]
+    fn missing_or_invalid_seed_is_none() {
+        assert_eq!(seed_from(&[]), None);
+        assert_eq!(seed_from(&["42"]), None);
+        assert_eq!(seed_from(&["--seed"]), None);
+        assert_eq!(seed_from(&["--seed", "abc"]), None);
+        assert_eq!(seed_from(&["--seed=-1"]), None);
+        assert_eq!(seed_from(&["--seeds=3"]), None);
+    }
+}
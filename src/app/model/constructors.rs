@@ -4,6 +4,7 @@ use super::audio_constructor::build_audio_model;
 use super::*;
 use crate::app::audio::audio_constructor::MAX_NUM_RESONATORS;
 use crate::dsp::ResoBankData;
+use crate::generative::voronoi;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::mpsc;
 
@@ -26,13 +27,17 @@ pub fn build_window(app: &App, width: u32, height: u32) -> Id {
 pub struct AudioSystem {
     pub(super) stream: Stream<AudioModel>,
     pub(super) sample_rate_ref: Arc<AtomicF64>,
+    pub(super) transport_position_ref: Arc<AtomicF64>,
+    pub(super) comp_gain_reduction_ref: Arc<AtomicF64>,
+    pub(super) safety_muted_ref: Arc<AtomicBool>,
+    pub(super) latency_ref: Arc<AtomicU32>,
     pub(super) senders: AudioMessageSenders,
     pub(super) callback_timer_ref: CallbackTimerRef,
     pub(super) note_handler: NoteHandlerRef,
     pub(super) pre_spectrum: SpectrumOutput,
     pub(super) post_spectrum: SpectrumOutput,
     pub(super) voice_event_sender: mpsc::Sender<VoiceEvent>,
-    pub(super) spectral_mask: triple_buffer::Input<SpectralMask>,
+    pub(super) spectral_mask: triple_buffer::Input<StereoMask>,
     pub(super) reso_bank_data: triple_buffer::Input<ResoBankData>,
 }
 
@@ -43,7 +48,7 @@ pub fn build_audio_system(params: &UIParams) -> AudioSystem {
     // setup audio structs
     let note_handler = Arc::new(Mutex::new(NoteHandler::new()));
     let (spectral_mask, spectral_mask_output) =
-        triple_buffer::TripleBuffer::new(&SpectralMask::new(
+        triple_buffer::TripleBuffer::new(&StereoMask::new(
             MAX_SPECTRAL_BLOCK_SIZE,
         ))
         .split();
@@ -75,6 +80,10 @@ pub fn build_audio_system(params: &UIParams) -> AudioSystem {
         spectrum_outputs: (pre_spectrum, post_spectrum),
         callback_timer_ref,
         sample_rate_ref,
+        transport_position_ref,
+        comp_gain_reduction_ref,
+        safety_muted_ref,
+        latency_ref,
         message_channels: senders,
     } = build_audio_model(audio_context, params);
 
@@ -93,6 +102,10 @@ pub fn build_audio_system(params: &UIParams) -> AudioSystem {
     AudioSystem {
         stream,
         sample_rate_ref,
+        transport_position_ref,
+        comp_gain_reduction_ref,
+        safety_muted_ref,
+        latency_ref,
         senders,
         callback_timer_ref,
         note_handler,
@@ -109,11 +122,15 @@ pub struct GuiElements {
     pub(super) bank_rect: Rect,
     pub(super) spectrum_rect: Rect,
 
-    pub(super) contours: ContoursGPU,
-    pub(super) smooth_life: SmoothLifeGPU,
-    pub(super) voronoi_mask: VoronoiGPU,
+    pub(super) contours: ContoursMask,
+    pub(super) smooth_life: SmoothLifeMask,
+    pub(super) voronoi_mask: VoronoiMask,
+    pub(super) reaction_diffusion: ReactionDiffusionMask,
+    pub(super) image_mask: ImageMask,
+    pub(super) spectral_feedback: SpectralFeedbackMask,
     pub(super) voronoi_vectors: VectorField,
 
+    pub(super) voronoi_reso_bank: VoronoiMask,
     pub(super) vectors_reso_bank: VectorField,
 
     pub(super) pre_spectrum_analyzer: SpectrumAnalyzer,
@@ -153,28 +170,91 @@ pub fn build_gui_elements(
         SpectrumAnalyzer::new(post_spectrum, spectrum_rect);
     post_spectrum_analyzer.set_mesh_color(post_spectrum_mesh_color);
 
+    // fall back to the CPU mask generators if there is no suitable GPU
+    let use_gpu = gpu_compute_available(app);
+    let window = app.main_window();
+    let device = window.device();
+
+    let contour_speed = params.contour_speed.lr() as f32;
+    let contour_count = params.contour_count.lr();
+    let contour_thickness = params.contour_thickness.lr() as f32;
+    let contour_noise = ContourNoiseParams {
+        noise: params.contour_noise.lr(),
+        octaves: params.contour_octaves.lr(),
+        lacunarity: params.contour_lacunarity.lr() as f32,
+        persistence: params.contour_persistence.lr() as f32,
+        warp: params.contour_warp.lr() as f32,
+    };
+
     GuiElements {
         bank_rect,
         mask_rect,
         spectrum_rect,
 
-        // contours: Contours::new(app.main_window().device(), mask_rect)
-        //     .with_num_threads(16)
-        //     .expect("failed to allocate threads to contour generator")
-        //     .with_feathering(false)
-        //     .with_z_increment(params.contour_speed.lr())
-        //     .with_num_contours(params.contour_count.lr())
-        //     .with_contour_range(0.0..=params.contour_thickness.lr()),
-        contours: ContoursGPU::new(app, mask_rect)
-            .with_z_increment(params.contour_speed.lr() as f32)
-            .with_num_contours(params.contour_count.lr())
-            .with_contour_range(0.0..=(params.contour_thickness.lr() as f32)),
-        smooth_life: SmoothLifeGPU::new(app, mask_rect),
-        voronoi_mask: VoronoiGPU::new(app, mask_rect),
-        voronoi_vectors: VectorField::new(MAX_NUM_RESONATORS, mask_rect)
+        contours: MaskBackend::select(
+            use_gpu,
+            || {
+                ContoursGPU::new(app, mask_rect)
+                    .with_z_increment(contour_speed)
+                    .with_num_contours(contour_count)
+                    .with_contour_range(0.0..=contour_thickness)
+                    .with_noise_params(contour_noise)
+            },
+            device,
+            mask_rect,
+            || {
+                ContoursCPU::new()
+                    .with_z_increment(contour_speed)
+                    .with_num_contours(contour_count)
+                    .with_contour_range(0.0..=contour_thickness)
+                    .with_noise_params(contour_noise)
+            },
+        ),
+        smooth_life: MaskBackend::select(
+            use_gpu,
+            || {
+                SmoothLifeGPU::new(app, mask_rect)
+                    .with_state(Arc::clone(&params.smoothlife_state))
+            },
+            device,
+            mask_rect,
+            || {
+                SmoothLifeCPU::new(mask_rect)
+                    .with_state(Arc::clone(&params.smoothlife_state))
+            },
+        ),
+        voronoi_mask: MaskBackend::select(
+            use_gpu,
+            || VoronoiGPU::new(app, mask_rect),
+            device,
+            mask_rect,
+            || VoronoiCPU::new(mask_rect),
+        ),
+        reaction_diffusion: CpuMask::new(
+            device,
+            mask_rect,
+            ReactionDiffusion::new()
+                .with_preset(params.reaction_diffusion_preset.lr()),
+        ),
+        image_mask: CpuMask::new(device, mask_rect, ImageSequence::new()),
+        spectral_feedback: CpuMask::new(
+            device,
+            mask_rect,
+            SpectralFeedback::new(),
+        ),
+        voronoi_vectors: VectorField::new(voronoi::MAX_NUM_POINTS, mask_rect)
+            .with_rng_stream("voronoi_vectors")
             .with_point_radius(5.0),
 
+        voronoi_reso_bank: MaskBackend::select(
+            use_gpu,
+            || VoronoiGPU::new(app, bank_rect),
+            device,
+            bank_rect,
+            || VoronoiCPU::new(bank_rect),
+        ),
         vectors_reso_bank: VectorField::new(MAX_NUM_RESONATORS, bank_rect)
+            .with_rng_stream("reso_bank_vectors")
             .with_point_radius(5.0)
             .with_point_color(Rgba::new(0.9, 0.4, 0.0, 0.6)),
 
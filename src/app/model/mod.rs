@@ -4,14 +4,16 @@ use super::audio::audio_constructor;
 use super::audio::*;
 use super::view::view;
 use super::*;
+use crate::app::musical::transport::division_phase;
 use crate::app::params::*;
 use crate::dsp::{
     BiquadFilter, BiquadParams, Filter, FilterType, ResoBankData,
-    ResonatorBankParams, SpectralMask, BUTTERWORTH_Q,
+    ResonatorBankParams, SpectralMask, StereoMask, BUTTERWORTH_Q,
 };
 use crate::generative::*;
 use crate::gui::rdp::rdp_in_place;
 use crate::gui::{spectrum::*, EQDisplay};
+use crate::gui::ui::fx_page_area;
 use crate::gui::{EQFilterParams, UIComponents};
 use crate::prelude::interp::linear_unclamped;
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -21,7 +23,7 @@ use std::f64::consts::SQRT_2;
 use std::{
     cell::RefCell,
     collections::HashMap,
-    sync::{mpsc, Arc, Mutex, RwLock},
+    sync::{atomic::{AtomicBool, AtomicU32}, mpsc, Arc, Mutex, RwLock},
     time::Instant,
 };
 
@@ -30,6 +32,10 @@ use constructors::*;
 
 type CallbackTimerRef = Arc<Mutex<Instant>>;
 
+/// The maximum distance of each stereo scan line from the main scan line, as a
+/// proportion of the mask's width.
+const MASK_STEREO_MAX_OFFSET: f64 = 0.25;
+
 /// The app's model, i.e. its state.
 #[allow(clippy::struct_excessive_bools)]
 pub struct Model {
@@ -43,8 +49,8 @@ pub struct Model {
     /// Channels to send messages directly to the audio thread.
     pub audio_senders: Arc<AudioMessageSenders>,
 
-    /// Input to the spectral mask channel, sent to the audio thread.
-    pub spectral_mask: triple_buffer::Input<SpectralMask>,
+    /// Input to the stereo spectral mask channel, sent to the audio thread.
+    pub spectral_mask: triple_buffer::Input<StereoMask>,
 
     /// Channel to send voice events (such as killing all voices).
     pub voice_event_sender: mpsc::Sender<VoiceEvent>,
@@ -53,6 +59,22 @@ pub struct Model {
     /// was last called.
     pub audio_callback_timer: CallbackTimerRef,
 
+    /// The transport's position in beats, which is updated by the audio
+    /// thread.
+    pub transport_position: Arc<AtomicF64>,
+
+    /// The compressor's gain reduction in decibels, which is updated by the
+    /// audio thread.
+    pub comp_gain_reduction: Arc<AtomicF64>,
+
+    /// Whether the output has been muted by the safety mute, which is updated
+    /// by the audio thread.
+    pub safety_muted: Arc<AtomicBool>,
+
+    /// The total latency of the audio model in samples, which is updated by
+    /// the audio thread.
+    pub latency_samples: Arc<AtomicU32>,
+
     /// A reference to the sample rate value.
     pub sample_rate_ref: Arc<AtomicF64>,
 
@@ -96,24 +118,34 @@ pub struct Model {
     pub spectrum_rect: Rect,
 
     /// A Perlin noise contour generator.
-    pub contours: Arc<RwLock<ContoursGPU>>,
+    pub contours: Arc<RwLock<ContoursMask>>,
     /// A SmoothLife simulation.
-    pub smooth_life: Arc<RwLock<SmoothLifeGPU>>,
+    pub smooth_life: Arc<RwLock<SmoothLifeMask>>,
     /// A Voronoi noise generator used for the spectral mask.
-    pub voronoi_mask: Arc<RwLock<VoronoiGPU>>,
+    pub voronoi_mask: Arc<RwLock<VoronoiMask>>,
     /// A vector field used to manage points for the Voronoi mask.
     pub voronoi_vectors: Arc<RwLock<VectorField>>,
+    /// A Gray-Scott reaction-diffusion simulation.
+    pub reaction_diffusion: Arc<RwLock<ReactionDiffusionMask>>,
+    /// A still image or sequence of frames, which may be loaded by dropping a
+    /// file or folder onto the window.
+    pub image_mask: Arc<RwLock<ImageMask>>,
+    /// A scrolling spectrogram of the pre-FX signal.
+    pub spectral_feedback: Arc<RwLock<SpectralFeedbackMask>>,
 
     /// A simple vector field for the resonator bank points.
     pub vectors_reso_bank: VectorField,
     /// A channel to receive a message when the number of resonators has changed.
     resonator_count_receiver: Receiver<()>,
     /// The voronoi generator for the resonator bank vector field.
-    pub voronoi_reso_bank: VoronoiGPU,
+    pub voronoi_reso_bank: VoronoiMask,
     /// The line which shows which column is being used as a spectral mask.
     pub mask_scan_line_pos: f64,
     /// The amount to increment the position of the mask scan line each frame.
     pub mask_scan_line_increment: f64,
+    /// The direction of the mask scan line, either `1.0` or `-1.0`. Only used
+    /// when the scan line motion is set to ping-pong.
+    pub mask_scan_line_direction: f64,
 
     /// The EQ display — filter nodes and the frequency response line.
     pub eq_display: EQDisplay,
@@ -141,6 +173,10 @@ impl Model {
         let AudioSystem {
             stream: audio_stream,
             sample_rate_ref,
+            transport_position_ref,
+            comp_gain_reduction_ref,
+            safety_muted_ref,
+            latency_ref,
             senders: audio_senders,
             callback_timer_ref: audio_callback_timer,
             note_handler,
@@ -163,16 +199,17 @@ impl Model {
             contours,
             smooth_life,
             voronoi_mask,
+            reaction_diffusion,
+            image_mask,
+            spectral_feedback,
             mut voronoi_vectors,
+            voronoi_reso_bank,
             pre_spectrum_analyzer,
             post_spectrum_analyzer,
             vectors_reso_bank,
         } = build_gui_elements(app, pre_spectrum, post_spectrum, &params);
 
-        voronoi_vectors.override_points().iter_mut().for_each(|p| {
-            p.vel.x = random_range(-1.0, 1.0);
-            p.vel.y = random_range(-1.0, 1.0);
-        });
+        voronoi_vectors.kick_points(1.0);
 
         let audio_senders = Arc::new(audio_senders);
         let audio_senders_cl = Arc::clone(&audio_senders);
@@ -181,6 +218,12 @@ impl Model {
         let smooth_life = Arc::new(RwLock::new(smooth_life));
         let ctr_1 = Arc::clone(&contours);
         let sml = Arc::clone(&smooth_life);
+        let reaction_diffusion = Arc::new(RwLock::new(reaction_diffusion));
+        let rd = Arc::clone(&reaction_diffusion);
+        let image_mask = Arc::new(RwLock::new(image_mask));
+        let img = Arc::clone(&image_mask);
+        let spectral_feedback = Arc::new(RwLock::new(spectral_feedback));
+        let fb = Arc::clone(&spectral_feedback);
         let gen_algo = Arc::clone(&params.mask_algorithm);
 
         let (reso_bank_reset_sender, reso_bank_reset_receiver) = unbounded();
@@ -192,8 +235,6 @@ impl Model {
         let voronoi_vectors = Arc::new(RwLock::new(voronoi_vectors));
         let vv = Arc::clone(&voronoi_vectors);
 
-        let (m_tl, m_br) = (mask_rect.top_left(), mask_rect.bottom_right());
-
         let mut ui_components = UIComponents::new(&params)
             .attach_reso_bank_randomize_callback(move |_| {
                 reso_bank_reset_sender.send(());
@@ -214,19 +255,30 @@ impl Model {
                 }
                 GenerativeAlgo::Voronoi => {
                     if let Ok(mut guard) = vv.write() {
-                        guard.override_points().iter_mut().for_each(|p| {
-                            p.vel.x = random_range(-1.0, 1.0);
-                            p.vel.y = random_range(-1.0, 1.0);
-
-                            p.pos.x = random_range(m_tl.x, m_br.x);
-                            p.pos.y = random_range(m_br.y, m_tl.y);
-                        });
+                        guard.scatter_points(1.0);
+                    }
+                }
+                GenerativeAlgo::ReactionDiffusion => {
+                    if let Ok(guard) = rd.read() {
+                        guard.generator().randomize();
+                    }
+                }
+                GenerativeAlgo::Image => {
+                    if let Ok(guard) = img.read() {
+                        guard.generator().restart();
+                    }
+                }
+                GenerativeAlgo::SpectralFeedback => {
+                    if let Ok(guard) = fb.read() {
+                        guard.generator().clear();
                     }
                 }
             })
             .setup_mask_callbacks(
                 Arc::clone(&contours),
-                Arc::clone(&smooth_life),
+                Arc::clone(&reaction_diffusion),
+                Arc::clone(&image_mask),
+                Arc::clone(&spectral_feedback),
                 &params,
             );
 
@@ -240,32 +292,6 @@ impl Model {
                 resontor_count_sender.send(()).unwrap();
             });
 
-        let mut low_filter = BiquadFilter::new(sample_rate_ref.lr());
-        low_filter.set_params(&BiquadParams {
-            freq: params.low_filter_cutoff.current_value(),
-            gain: params.low_filter_gain_db.current_value(),
-            q: params.low_filter_q.current_value(),
-            filter_type: if params.low_filter_is_shelf.lr() {
-                FilterType::Lowshelf
-            }
-            else {
-                FilterType::Highpass
-            },
-        });
-
-        let mut high_filter = BiquadFilter::new(sample_rate_ref.lr());
-        high_filter.set_params(&BiquadParams {
-            freq: params.high_filter_cutoff.current_value(),
-            gain: params.high_filter_gain_db.current_value(),
-            q: params.high_filter_q.current_value(),
-            filter_type: if params.high_filter_is_shelf.lr() {
-                FilterType::Highshelf
-            }
-            else {
-                FilterType::Lowpass
-            },
-        });
-
         Self {
             window,
 
@@ -290,6 +316,11 @@ impl Model {
 
             audio_callback_timer,
 
+            transport_position: transport_position_ref,
+            comp_gain_reduction: comp_gain_reduction_ref,
+            safety_muted: safety_muted_ref,
+            latency_samples: latency_ref,
+
             pre_spectrum_analyzer,
             post_spectrum_analyzer,
 
@@ -316,8 +347,11 @@ impl Model {
 
             voronoi_mask: Arc::new(RwLock::new(voronoi_mask)),
             voronoi_vectors,
+            reaction_diffusion,
+            image_mask,
+            spectral_feedback,
 
-            voronoi_reso_bank: VoronoiGPU::new(app, bank_rect),
+            voronoi_reso_bank,
             vectors_reso_bank,
             resonator_count_receiver,
 
@@ -325,6 +359,7 @@ impl Model {
 
             mask_scan_line_pos: 0.0,
             mask_scan_line_increment: 0.1,
+            mask_scan_line_direction: 1.0,
 
             input_data: InputData {
                 is_win_focussed: true, // required for the window to be initialized on Windows
@@ -353,19 +388,85 @@ impl Model {
         })
     }
 
-    /// Increments the position of the mask scan line.
+    /// Increments the position of the mask scan line. In ping-pong mode, the
+    /// scan line changes direction at either edge rather than wrapping around.
+    ///
+    /// If the scan line is synced, its position is instead locked to the
+    /// transport, where one pass across the mask lasts one note division.
     pub fn increment_mask_scan_line(&mut self) {
-        let increment = self.ui_params.mask_scan_line_speed.lr();
-        self.mask_scan_line_pos += increment * self.input_data.delta_time;
+        if self.ui_params.mask_scan_line_sync.lr() {
+            let division = self.ui_params.mask_scan_line_division.lr();
+            let position = self.transport_position.lr();
+
+            self.mask_scan_line_pos =
+                match self.ui_params.mask_scan_line_motion.lr() {
+                    ScanLineMotion::Loop => {
+                        division_phase(position, division)
+                    }
+                    ScanLineMotion::PingPong => {
+                        let phase = (position / division.beats())
+                            .rem_euclid(2.0);
+                        1.0 - (phase - 1.0).abs()
+                    }
+                };
+
+            return;
+        }
+
+        let increment = self.ui_params.mask_scan_line_speed.lr()
+            * self.input_data.delta_time;
 
-        if self.mask_scan_line_pos > 1.0 {
-            self.mask_scan_line_pos -= 1.0;
+        match self.ui_params.mask_scan_line_motion.lr() {
+            ScanLineMotion::Loop => {
+                self.mask_scan_line_pos =
+                    (self.mask_scan_line_pos + increment).rem_euclid(1.0);
+            }
+            ScanLineMotion::PingPong => {
+                self.mask_scan_line_pos +=
+                    increment * self.mask_scan_line_direction;
+
+                if self.mask_scan_line_pos > 1.0 {
+                    self.mask_scan_line_pos = 2.0 - self.mask_scan_line_pos;
+                    self.mask_scan_line_direction *= -1.0;
+                }
+                else if self.mask_scan_line_pos < 0.0 {
+                    self.mask_scan_line_pos = -self.mask_scan_line_pos;
+                    self.mask_scan_line_direction *= -1.0;
+                }
+
+                self.mask_scan_line_pos =
+                    self.mask_scan_line_pos.clamp(0.0, 1.0);
+            }
         }
-        else if self.mask_scan_line_pos < 0.0 {
-            self.mask_scan_line_pos += 1.0;
+    }
+
+    /// Returns the current spectral mask readout settings.
+    pub fn mask_readout(&self) -> MaskReadout {
+        MaskReadout {
+            mode: self.ui_params.mask_readout_mode.lr(),
+            num_lines: self.ui_params.mask_scan_line_count.lr() as usize,
+            spread: self.ui_params.mask_scan_line_spread.lr(),
+            blend: self.ui_params.mask_scan_line_blend.lr(),
         }
     }
 
+    /// Returns the normalized `(left, right)` positions of the stereo mask scan
+    /// lines, which are offset either side of the main scan line based on the
+    /// stereo width parameter. Both positions wrap around the mask.
+    ///
+    /// If the stereo width is `0.0`, both positions are equal to the main scan
+    /// line's position.
+    pub fn stereo_mask_scan_line_pos(&self) -> (f64, f64) {
+        let offset =
+            self.ui_params.mask_stereo_width.lr() * MASK_STEREO_MAX_OFFSET;
+        let wrap = |pos: f64| pos.rem_euclid(1.0);
+
+        (
+            wrap(self.mask_scan_line_pos - offset),
+            wrap(self.mask_scan_line_pos + offset),
+        )
+    }
+
     /// # Panics
     ///
     /// This will panic if the `SmoothLife` generator cannot be locked.
@@ -373,24 +474,35 @@ impl Model {
         self.mask_rect
     }
 
-    /// Draws the spectral mask scan line.
+    /// Draws the spectral mask scan lines, where the opacity of each line
+    /// follows its blend weight. If the stereo width is non-zero, the left and
+    /// right scan lines are also drawn.
     pub fn draw_mask_scan_line(&self, draw: &Draw) {
         let rect = self.mask_rect();
-        let y_top = rect.top() - 1.0;
-        let y_bot = rect.bottom() + 1.0;
-
-        let x = map(
-            self.mask_scan_line_pos,
-            0.0,
-            1.0,
-            rect.left() as f64,
-            rect.right() as f64,
-        ) as f32;
-
-        draw.line()
-            .points(pt2(x, y_bot), pt2(x, y_top))
-            .weight(3.0)
-            .color(Rgba::new(0.9, 0.4, 0.0, 0.5));
+        let readout = self.mask_readout();
+
+        let draw_path = |pos: f64, weight: f32, alpha: f32| {
+            draw.polyline()
+                .weight(weight)
+                .points(readout.path_points(pos, &rect))
+                .color(Rgba::new(0.9, 0.4, 0.0, alpha));
+        };
+
+        let (lines, num_lines) = readout.lines(self.mask_scan_line_pos);
+
+        for &(pos, line_weight) in &lines[..num_lines] {
+            draw_path(pos, 3.0, 0.15 + 0.35 * line_weight as f32);
+        }
+
+        let (left, right) = self.stereo_mask_scan_line_pos();
+
+        if epsilon_eq(left, right) {
+            return;
+        }
+
+        for pos in [left, right] {
+            draw_path(pos, 1.5, 0.35);
+        }
     }
 
     /// Updates the model's input data.
@@ -420,6 +532,12 @@ impl Model {
 
         self.vectors_reso_bank
             .set_friction(self.ui_params.reso_bank_field_friction.lr());
+        self.vectors_reso_bank.set_forces(FieldForces {
+            mouse: self.ui_params.reso_bank_field_mouse.lr() as f32,
+            flocking: self.ui_params.reso_bank_field_flocking.lr() as f32,
+            collision: self.ui_params.reso_bank_field_collision.lr() as f32,
+            gravity: self.ui_params.reso_bank_field_gravity.lr() as f32,
+        });
 
         if self.reso_bank_reset_receiver.try_recv().is_ok() {
             self.vectors_reso_bank.randomize_points();
@@ -496,14 +614,22 @@ impl Model {
                 || self.mask_clicked
             {
                 if !self.mouse_clicked_outside_of_mask {
-                    let x_pos = self.input_data.mouse_pos.x as f64;
-                    let l = self.mask_rect.left() as f64;
-                    let r = self.mask_rect.right() as f64;
-
-                    self.mask_scan_line_pos = normalize(x_pos, l, r).fract();
-                    if self.mask_scan_line_pos.is_sign_negative() {
-                        self.mask_scan_line_pos = 1.0 + self.mask_scan_line_pos;
-                    }
+                    let rect = &self.mask_rect;
+                    let mouse_pos = self.input_data.mouse_pos;
+
+                    let x = normalize(
+                        mouse_pos.x as f64,
+                        rect.left() as f64,
+                        rect.right() as f64,
+                    );
+                    let y = normalize(
+                        mouse_pos.y as f64,
+                        rect.top() as f64,
+                        rect.bottom() as f64,
+                    );
+
+                    self.mask_scan_line_pos =
+                        self.mask_readout().position_from_point(x, y);
                 }
                 self.mask_clicked = true;
             }
@@ -519,68 +645,41 @@ impl Model {
 
     /// Updates the EQ GUI.
     pub fn update_eq(&mut self, app: &App) {
-        if self.ui_components.exciter_osc.is_open() {
+        if self.ui_components.exciter_osc.is_open()
+            || self.ui_components.eq_band.is_menu_open()
+        {
             self.eq_display.clicked_outside_of_spectrum = true;
             return;
         }
 
         self.eq_display.update(app, &self.input_data);
 
-        // if the eq is being updated, update the ui components
-        if self.input_data.is_left_clicked
-            && (self.spectrum_rect.contains(self.input_data.mouse_pos)
-                || self.eq_display.spectrum_is_clicked)
-            && !self.eq_display.clicked_outside_of_spectrum
+        let selected = self.eq_display.selected_band();
+        let editor = &mut self.ui_components.eq_band;
+
+        // if the eq is being updated or another band was selected, update
+        // the band editor
+        if editor.shown_band() != selected
+            || self.eq_display.band_was_removed()
+            || (self.input_data.is_left_clicked
+                && (self.spectrum_rect.contains(self.input_data.mouse_pos)
+                    || self.eq_display.spectrum_is_clicked)
+                && !self.eq_display.clicked_outside_of_spectrum)
         {
-            let EQFilterParams { cutoff, gain, q } =
-                self.eq_display.low_filter_params;
-
-            self.ui_components
-                .low_filter_cutoff
-                .set_value(freq_to_note(cutoff));
-            self.ui_components.low_filter_gain.set_value(gain);
-            self.ui_components.low_filter_q.set_value(q.recip());
-
-            let EQFilterParams { cutoff, gain, q } =
-                self.eq_display.peak_filter_params;
-            self.ui_components
-                .peak_filter_cutoff
-                .set_value(freq_to_note(cutoff));
-            self.ui_components.peak_filter_gain.set_value(gain);
-            self.ui_components.peak_filter_q.set_value(q.recip());
-
-            let EQFilterParams { cutoff, gain, q } =
-                self.eq_display.high_filter_params;
-            self.ui_components
-                .high_filter_cutoff
-                .set_value(freq_to_note(cutoff));
-            self.ui_components.high_filter_gain.set_value(gain);
-            self.ui_components.high_filter_q.set_value(q.recip());
+            editor.show_band(
+                selected,
+                &self.ui_params.eq_params,
+                &self.eq_display.band_params[selected],
+            );
         }
         // otherwise if a ui parameter is being changed, set the eq params
         else if self.input_data.is_left_clicked
             && self.eq_display.clicked_outside_of_spectrum
         {
-            self.eq_display.low_filter_params.cutoff =
-                note_to_freq(self.ui_components.low_filter_cutoff.value());
-            self.eq_display.low_filter_params.gain =
-                self.ui_components.low_filter_gain.value();
-            self.eq_display.low_filter_params.q =
-                self.ui_components.low_filter_q.value().recip();
-
-            self.eq_display.peak_filter_params.cutoff =
-                note_to_freq(self.ui_components.peak_filter_cutoff.value());
-            self.eq_display.peak_filter_params.gain =
-                self.ui_components.peak_filter_gain.value();
-            self.eq_display.peak_filter_params.q =
-                self.ui_components.peak_filter_q.value().recip();
-
-            self.eq_display.high_filter_params.cutoff =
-                note_to_freq(self.ui_components.high_filter_cutoff.value());
-            self.eq_display.high_filter_params.gain =
-                self.ui_components.high_filter_gain.value();
-            self.eq_display.high_filter_params.q =
-                self.ui_components.high_filter_q.value().recip();
+            let filter = &mut self.eq_display.band_params[selected];
+            filter.cutoff = note_to_freq(editor.cutoff.value());
+            filter.gain = editor.gain.value();
+            filter.q = editor.q.value();
         }
     }
 
@@ -627,6 +726,9 @@ impl Model {
         let UIComponents {
             mask_algorithm,
             mask_resolution,
+            mask_readout_mode,
+            mask_scan_line_blend,
+            mask_scan_line_motion,
             contour_count,
             contour_speed,
             contour_thickness,
@@ -636,6 +738,10 @@ impl Model {
             spectrogram_label,
             dist_type,
             delay_feedback,
+            crush_dither,
+            mod_type,
+            fx_page,
+            eq_band,
             ..
         } = &self.ui_components;
 
@@ -649,6 +755,21 @@ impl Model {
             draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
         }
 
+        if mask_readout_mode.needs_redraw() {
+            let rect = mask_readout_mode.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
+        }
+
+        if mask_scan_line_blend.needs_redraw() {
+            let rect = mask_scan_line_blend.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
+        }
+
+        if mask_scan_line_motion.needs_redraw() {
+            let rect = mask_scan_line_motion.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
+        }
+
         if reso_bank_scale.needs_redraw() {
             let rect = reso_bank_scale.rect();
             draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
@@ -675,46 +796,27 @@ impl Model {
                 .wh(pt2(5.0, 20.0))
                 .color(BLACK);
         }
-    }
-
-    /// Redraws the filter text sliders when filter types are changed.
-    pub fn redraw_filter_sliders(&self, draw: &Draw) {
-        let hf_changed = self.ui_components.high_filter_type.was_just_changed();
-        let lf_changed = self.ui_components.low_filter_type.was_just_changed();
 
-        if hf_changed {
-            let is_shelf = self.ui_components.high_filter_type.enabled();
-            let rect = self.ui_components.high_filter_gain.rect();
-            let h = rect.h();
-            draw.rect()
-                .xy(pt2(rect.x(), rect.y() + h * 0.5))
-                .wh(pt2(rect.w(), rect.h() * 2.0))
-                .color(BLACK);
+        if crush_dither.needs_redraw() {
+            let rect = crush_dither.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
+        }
 
-            if is_shelf {
-                self.ui_components.high_filter_gain.redraw_label(draw);
-            }
-            else {
-                self.ui_components.high_filter_q.redraw_label(draw);
-            }
+        if mod_type.needs_redraw() {
+            let rect = mod_type.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
         }
 
-        if lf_changed {
-            let is_shelf = self.ui_components.low_filter_type.enabled();
-            let rect = self.ui_components.low_filter_gain.rect();
-            let h = rect.h();
-            draw.rect()
-                .xy(pt2(rect.x(), rect.y() + h * 0.5))
-                .wh(pt2(rect.w(), rect.h() * 2.0))
-                .color(BLACK);
+        // the whole page is cleared when it is changed
+        if fx_page.needs_redraw() {
+            let area = fx_page_area();
+            draw.rect().xy(area.xy()).wh(area.wh()).color(BLACK);
 
-            if is_shelf {
-                self.ui_components.low_filter_gain.redraw_label(draw);
-            }
-            else {
-                self.ui_components.low_filter_q.redraw_label(draw);
-            }
+            let rect = fx_page.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
         }
+
+        eq_band.redraw_under_menus(draw);
     }
 }
 
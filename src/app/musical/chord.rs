@@ -1,12 +1,15 @@
 //! Musical chord representations. Unused in this project.
 
 use crate::prelude::*;
+use crate::util::SessionRng;
+use rand::Rng;
 
 const MAX: usize = NUM_VOICES as usize;
 
 /// A simple struct for generating the notes of a common chords.
 pub struct ChordGen {
     voices: Vec<f64>,
+    rng: SessionRng,
 }
 
 impl ChordGen {
@@ -129,7 +132,7 @@ impl ChordGen {
     pub fn invert_random(&mut self) -> &[f64] {
         let len = self.voices.len();
         // we start at 1 to avoid the root note - wouldn't be an inversion otherwise!
-        self.voices[random_range(1, len)] -= 12.0;
+        self.voices[self.rng.gen_range(1..len)] -= 12.0;
 
         &self.voices
     }
@@ -140,10 +143,10 @@ impl ChordGen {
     /// The root note is always retained.
     pub fn spread_voicing(&mut self) -> &[f64] {
         let len = self.voices.len();
-        let num_iters = random_range(1, len);
+        let num_iters = self.rng.gen_range(1..len);
 
         for _ in 0..num_iters {
-            self.voices[random_range(1, len)] += 12.0;
+            self.voices[self.rng.gen_range(1..len)] += 12.0;
         }
 
         &self.voices
@@ -157,7 +160,8 @@ impl ChordGen {
         max_variation = max_variation.clamp(0.0, 0.5);
 
         for voice in &mut self.voices {
-            *voice += scale(random_f64(), -max_variation, max_variation);
+            let offset = self.rng.next_f64();
+            *voice += scale(offset, -max_variation, max_variation);
         }
 
         &self.voices
@@ -185,6 +189,7 @@ impl Default for ChordGen {
     fn default() -> Self {
         Self {
             voices: vec![0.0; MAX],
+            rng: SessionRng::new("chords"),
         }
     }
 }
@@ -0,0 +1,102 @@
+//! Musical note divisions, used to lock time-based parameters to the
+//! transport's tempo.
+
+use bytemuck::NoUninit;
+use std::fmt::Display;
+
+/// Common note divisions, where `Whole` is one bar of 4/4.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoteDivision {
+    FourBars,
+    TwoBars,
+    Whole,
+    HalfDotted,
+    Half,
+    QuarterDotted,
+    #[default]
+    Quarter,
+    EighthDotted,
+    QuarterTriplet,
+    Eighth,
+    EighthTriplet,
+    Sixteenth,
+}
+
+impl Display for NoteDivision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FourBars => write!(f, "4/1"),
+            Self::TwoBars => write!(f, "2/1"),
+            Self::Whole => write!(f, "1/1"),
+            Self::HalfDotted => write!(f, "1/2 D"),
+            Self::Half => write!(f, "1/2"),
+            Self::QuarterDotted => write!(f, "1/4 D"),
+            Self::Quarter => write!(f, "1/4"),
+            Self::EighthDotted => write!(f, "1/8 D"),
+            Self::QuarterTriplet => write!(f, "1/4 T"),
+            Self::Eighth => write!(f, "1/8"),
+            Self::EighthTriplet => write!(f, "1/8 T"),
+            Self::Sixteenth => write!(f, "1/16"),
+        }
+    }
+}
+
+unsafe impl NoUninit for NoteDivision {}
+
+impl NoteDivision {
+    /// All divisions, from longest to shortest.
+    pub const ALL: [Self; 12] = [
+        Self::FourBars,
+        Self::TwoBars,
+        Self::Whole,
+        Self::HalfDotted,
+        Self::Half,
+        Self::QuarterDotted,
+        Self::Quarter,
+        Self::EighthDotted,
+        Self::QuarterTriplet,
+        Self::Eighth,
+        Self::EighthTriplet,
+        Self::Sixteenth,
+    ];
+
+    /// Returns the length of the division in beats (quarter notes).
+    pub fn beats(&self) -> f64 {
+        match self {
+            Self::FourBars => 16.0,
+            Self::TwoBars => 8.0,
+            Self::Whole => 4.0,
+            Self::HalfDotted => 3.0,
+            Self::Half => 2.0,
+            Self::QuarterDotted => 1.5,
+            Self::Quarter => 1.0,
+            Self::EighthDotted => 0.75,
+            Self::QuarterTriplet => 2.0 / 3.0,
+            Self::Eighth => 0.5,
+            Self::EighthTriplet => 1.0 / 3.0,
+            Self::Sixteenth => 0.25,
+        }
+    }
+
+    /// Returns the length of the division in seconds at the given tempo.
+    pub fn duration_secs(&self, bpm: f64) -> f64 {
+        self.beats() * 60.0 / bpm
+    }
+
+    /// Returns the rate of the division in Hz at the given tempo, i.e. how
+    /// many times it repeats per second. Useful for tempo-synced LFOs.
+    pub fn hz(&self, bpm: f64) -> f64 {
+        bpm / (60.0 * self.beats())
+    }
+
+    /// Returns the division at `idx` in [`ALL`](Self::ALL), or `None` if `idx`
+    /// is out of bounds.
+    pub fn from_idx(idx: usize) -> Option<Self> {
+        Self::ALL.get(idx).copied()
+    }
+
+    /// Returns the index of the division in [`ALL`](Self::ALL).
+    pub fn idx(&self) -> usize {
+        Self::ALL.iter().position(|div| div == self).unwrap_or_default()
+    }
+}
@@ -1,9 +1,84 @@
 //! Musical types and logic.
 
 pub mod chord;
+pub mod division;
 pub mod note;
+pub mod pitch_layout;
 pub mod rhythm16;
 pub mod scale;
+pub mod transport;
 
+pub use division::NoteDivision;
 pub use note::*;
+pub use pitch_layout::PitchLayout;
 pub use scale::Scale;
+pub use transport::Transport;
+
+#[cfg(test)]
+mod tests {
+    use super::transport::{bar_beat_sixteenth, division_phase};
+    use crate::prelude::*;
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn transport_advances_in_beats() {
+        let mut transport = Transport::new(SAMPLE_RATE);
+        transport.set_bpm(120.0);
+
+        // a stopped transport doesn't move
+        transport.advance(SAMPLE_RATE as usize);
+        assert_eq!(transport.position(), 0.0);
+
+        // half a second is one beat at 120 bpm
+        transport.set_playing(true);
+        transport.advance(SAMPLE_RATE as usize / 2);
+        assert!(approx_eq(transport.position(), 1.0));
+        assert!(approx_eq(transport.position_ref().lr(), 1.0));
+
+        transport.advance(SAMPLE_RATE as usize * 2);
+        assert!(approx_eq(transport.position(), 5.0));
+        assert_eq!(bar_beat_sixteenth(transport.position()), (2, 2, 1));
+
+        // stopping returns to the start
+        transport.set_playing(false);
+        assert_eq!(transport.position(), 0.0);
+        assert_eq!(transport.position_ref().lr(), 0.0);
+        assert_eq!(bar_beat_sixteenth(transport.position()), (1, 1, 1));
+    }
+
+    #[test]
+    fn division_lengths_and_rates() {
+        assert!(approx_eq(NoteDivision::Whole.beats(), 4.0));
+        assert!(approx_eq(NoteDivision::EighthDotted.beats(), 0.75));
+        assert!(approx_eq(NoteDivision::QuarterTriplet.beats() * 3.0, 2.0));
+
+        assert!(approx_eq(NoteDivision::Quarter.duration_secs(120.0), 0.5));
+        assert!(approx_eq(NoteDivision::Quarter.hz(120.0), 2.0));
+        assert!(approx_eq(NoteDivision::Sixteenth.hz(60.0), 4.0));
+        assert!(approx_eq(NoteDivision::TwoBars.hz(120.0), 0.25));
+
+        for div in NoteDivision::ALL {
+            assert!(approx_eq(div.hz(97.0) * div.duration_secs(97.0), 1.0));
+        }
+
+        assert!(approx_eq(division_phase(1.5, NoteDivision::Half), 0.75));
+    }
+
+    #[test]
+    fn divisions_are_ordered_longest_to_shortest() {
+        for pair in NoteDivision::ALL.windows(2) {
+            assert!(pair[0].beats() > pair[1].beats());
+        }
+
+        for (i, div) in NoteDivision::ALL.iter().enumerate() {
+            assert_eq!(div.idx(), i);
+            assert_eq!(NoteDivision::from_idx(i), Some(*div));
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Pitch layouts, which describe the frequency ratios of a set of resonances.
+
+use bytemuck::NoUninit;
+use std::fmt::Display;
+
+/// Layouts of resonant pitches built upon a fundamental note, either from the
+/// harmonic series, from the modes of common physical resonators, or from the
+/// currently held notes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PitchLayout {
+    #[default]
+    Harmonic,
+    Bar,
+    Membrane,
+    Bell,
+    Plate,
+    Keyboard,
+}
+
+impl Display for PitchLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Harmonic => write!(f, "Harmonic"),
+            Self::Bar => write!(f, "Bar"),
+            Self::Membrane => write!(f, "Membrane"),
+            Self::Bell => write!(f, "Bell"),
+            Self::Plate => write!(f, "Plate"),
+            Self::Keyboard => write!(f, "Keys"),
+        }
+    }
+}
+
+unsafe impl NoUninit for PitchLayout {}
+
+impl PitchLayout {
+    /// All layouts, in menu order.
+    pub const ALL: [Self; 6] = [
+        Self::Harmonic,
+        Self::Bar,
+        Self::Membrane,
+        Self::Bell,
+        Self::Plate,
+        Self::Keyboard,
+    ];
+
+    /// Returns the frequency ratio of the mode at `idx` relative to the
+    /// fundamental.
+    ///
+    /// Modes beyond the end of a modal table repeat the table an octave
+    /// higher. `Keyboard` has no fixed ratios, so it uses the harmonic series.
+    pub fn ratio(&self, idx: usize) -> f64 {
+        let table = match self {
+            Self::Harmonic | Self::Keyboard => return (idx + 1) as f64,
+            Self::Bar => &ModalRatios::BAR,
+            Self::Membrane => &ModalRatios::MEMBRANE,
+            Self::Bell => &ModalRatios::BELL,
+            Self::Plate => &ModalRatios::PLATE,
+        };
+
+        let len = table.len();
+        table[idx % len] * ((idx / len) as f64).exp2()
+    }
+
+    /// Returns the interval of the mode at `idx` above the fundamental in
+    /// semitones.
+    pub fn interval(&self, idx: usize) -> f64 {
+        12.0 * self.ratio(idx).log2()
+    }
+
+    /// Returns the layout at `idx` in [`ALL`](Self::ALL), or `None` if `idx` is
+    /// out of bounds.
+    pub fn from_idx(idx: usize) -> Option<Self> {
+        Self::ALL.get(idx).copied()
+    }
+
+    /// Returns the index of the layout in [`ALL`](Self::ALL).
+    pub fn idx(&self) -> usize {
+        Self::ALL.iter().position(|layout| layout == self).unwrap_or_default()
+    }
+}
+
+struct ModalRatios;
+
+impl ModalRatios {
+    /// A free-free bar, such as a xylophone or glockenspiel bar.
+    pub const BAR: [f64; 8] =
+        [1.0, 2.756, 5.404, 8.933, 13.345, 18.638, 24.815, 31.871];
+    /// An ideal circular membrane, such as a drum head.
+    pub const MEMBRANE: [f64; 8] =
+        [1.0, 1.594, 2.136, 2.296, 2.653, 2.918, 3.156, 3.501];
+    /// A church bell, from the hum note up to the upper octave nominal.
+    pub const BELL: [f64; 8] = [0.5, 1.0, 1.183, 1.506, 2.0, 2.514, 2.662, 4.0];
+    /// A simply-supported square plate.
+    pub const PLATE: [f64; 8] = [1.0, 2.5, 4.0, 5.0, 6.5, 8.5, 9.0, 10.0];
+}
@@ -1,4 +1,5 @@
-//! Bitwise rhythm representations. Unused in this device.
+//! Bitwise rhythm representations, which may be queried against the
+//! transport's position (see `transport::is_beat()`).
 
 pub trait BitwiseRhythm16 {
     fn is_beat(&self, idx: usize) -> bool;
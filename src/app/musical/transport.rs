@@ -0,0 +1,118 @@
+//! The musical transport clock.
+
+use super::division::NoteDivision;
+use super::rhythm16::BitwiseRhythm16;
+use crate::prelude::*;
+use std::sync::Arc;
+
+/// A musical transport (tempo, play/stop state, and position), which is owned
+/// and advanced by the audio thread.
+///
+/// The position is shared through an atomic reference so that it may be read
+/// from elsewhere, such as the UI. See [`position_ref()`](Self::position_ref).
+#[derive(Debug)]
+pub struct Transport {
+    bpm: f64,
+    is_playing: bool,
+    sample_rate: f64,
+
+    /// The position of the transport in beats.
+    position: f64,
+    position_ref: Arc<AtomicF64>,
+}
+
+impl Transport {
+    /// Returns a new, stopped `Transport` at `DEFAULT_BPM`.
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            bpm: DEFAULT_BPM,
+            is_playing: false,
+            sample_rate,
+            position: 0.0,
+            position_ref: Arc::new(AtomicF64::new(0.0)),
+        }
+    }
+
+    /// Returns a reference to the transport's position in beats, which is
+    /// updated each time the transport is advanced.
+    pub fn position_ref(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.position_ref)
+    }
+
+    /// Sets the tempo of the transport.
+    pub fn set_bpm(&mut self, bpm: f64) {
+        debug_assert!(bpm > 0.0);
+        self.bpm = bpm;
+    }
+
+    /// Sets the sample rate of the transport.
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Starts or stops the transport. Stopping the transport returns it to the
+    /// start of the first bar.
+    pub fn set_playing(&mut self, is_playing: bool) {
+        if self.is_playing && !is_playing {
+            self.position = 0.0;
+            self.position_ref.sr(0.0);
+        }
+
+        self.is_playing = is_playing;
+    }
+
+    /// Advances the transport by `num_samples`, if it is playing.
+    pub fn advance(&mut self, num_samples: usize) {
+        if !self.is_playing {
+            return;
+        }
+
+        self.position +=
+            num_samples as f64 * self.bpm / (60.0 * self.sample_rate);
+        self.position_ref.sr(self.position);
+    }
+
+    /// The tempo of the transport.
+    pub fn bpm(&self) -> f64 {
+        self.bpm
+    }
+
+    /// Whether the transport is playing.
+    pub fn is_playing(&self) -> bool {
+        self.is_playing
+    }
+
+    /// The position of the transport in beats.
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+}
+
+/// Returns the `(bar, beat, sixteenth)` at `position` (in beats) in 4/4, where
+/// each value starts from `1` — i.e. the start of the transport is `(1, 1, 1)`.
+pub fn bar_beat_sixteenth(position: f64) -> (u32, u32, u32) {
+    let sixteenths = sixteenth_idx(position);
+
+    (
+        (sixteenths / 16) as u32 + 1,
+        (sixteenths / 4 % 4) as u32 + 1,
+        (sixteenths % 4) as u32 + 1,
+    )
+}
+
+/// Returns the number of whole sixteenth notes at `position` (in beats).
+pub fn sixteenth_idx(position: f64) -> usize {
+    (position.max(0.0) * 4.0) as usize
+}
+
+/// Returns the normalized phase (`[0.0, 1.0)`) of `division` at `position` (in
+/// beats), which restarts at the beginning of each division.
+pub fn division_phase(position: f64, division: NoteDivision) -> f64 {
+    (position / division.beats()).rem_euclid(1.0)
+}
+
+/// Returns whether the sixteenth note at `position` (in beats) is a beat in
+/// `rhythm` — see [`Rhythm16th`](super::rhythm16::Rhythm16th).
+pub fn is_beat<R: BitwiseRhythm16>(position: f64, rhythm: &R) -> bool {
+    rhythm.is_beat(sixteenth_idx(position))
+}
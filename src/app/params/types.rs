@@ -1,6 +1,7 @@
 //! All the custom UI parameter types.
 
 use super::*;
+use crate::dsp::FilterType;
 use bytemuck::NoUninit;
 use std::fmt::{Display, Formatter, Result};
 
@@ -14,6 +15,12 @@ pub enum GenerativeAlgo {
     SmoothLife,
     /// A Voronoi noise generator.
     Voronoi,
+    /// A Gray-Scott reaction-diffusion simulation.
+    ReactionDiffusion,
+    /// A still image or sequence of frames.
+    Image,
+    /// A scrolling spectrogram of the input signal.
+    SpectralFeedback,
 }
 
 impl Display for GenerativeAlgo {
@@ -22,6 +29,9 @@ impl Display for GenerativeAlgo {
             Self::Contours => write!(f, "Contours"),
             Self::SmoothLife => write!(f, "Smooth Life"),
             Self::Voronoi => write!(f, "Voronoi"),
+            Self::ReactionDiffusion => write!(f, "Gray-Scott"),
+            Self::Image => write!(f, "Image"),
+            Self::SpectralFeedback => write!(f, "Feedback"),
         }
     }
 }
@@ -30,12 +40,182 @@ unsafe impl NoUninit for GenerativeAlgo {}
 
 // *** //
 
-#[derive(Clone, Copy, Debug, Default)]
+/// The path used to read the spectral mask from the generative algorithm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MaskReadoutMode {
+    #[default]
+    /// A vertical column, where frequency increases from bottom to top.
+    Column,
+    /// A horizontal row, where frequency increases from left to right.
+    Row,
+    /// A line from the centre to the edge, which sweeps around the centre.
+    Radial,
+    /// A circle around the centre, whose radius follows the scan line.
+    Circular,
+}
+
+impl Display for MaskReadoutMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Column => write!(f, "Column"),
+            Self::Row => write!(f, "Row"),
+            Self::Radial => write!(f, "Radial"),
+            Self::Circular => write!(f, "Circle"),
+        }
+    }
+}
+
+unsafe impl NoUninit for MaskReadoutMode {}
+
+// *** //
+
+/// How multiple scan lines are combined into one spectral mask.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScanLineBlend {
+    #[default]
+    /// All scan lines are weighted equally.
+    Average,
+    /// The scan lines are cross-faded in turn as the scan line moves.
+    Crossfade,
+}
+
+impl Display for ScanLineBlend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Average => write!(f, "Average"),
+            Self::Crossfade => write!(f, "X-fade"),
+        }
+    }
+}
+
+unsafe impl NoUninit for ScanLineBlend {}
+
+// *** //
+
+/// How the spectral mask scan line moves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScanLineMotion {
+    #[default]
+    /// The scan line wraps around when it reaches either edge.
+    Loop,
+    /// The scan line changes direction when it reaches either edge.
+    PingPong,
+}
+
+impl Display for ScanLineMotion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Loop => write!(f, "Loop"),
+            Self::PingPong => write!(f, "Ping-pong"),
+        }
+    }
+}
+
+unsafe impl NoUninit for ScanLineMotion {}
+
+// *** //
+
+/// The noise function used by the contours algorithm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContourNoise {
+    /// Gradient noise on a square grid.
+    #[default]
+    Perlin,
+    /// Gradient noise on a triangular grid, with fewer directional
+    /// artifacts.
+    Simplex,
+    /// The distance to the nearest of a set of moving points.
+    Worley,
+    /// Fractal Brownian motion: several octaves of Perlin noise.
+    Fbm,
+    /// Ridged multifractal noise, which forms sharp creases.
+    Ridged,
+}
+
+impl Display for ContourNoise {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Perlin => write!(f, "Perlin"),
+            Self::Simplex => write!(f, "Simplex"),
+            Self::Worley => write!(f, "Worley"),
+            Self::Fbm => write!(f, "fBm"),
+            Self::Ridged => write!(f, "Ridged"),
+        }
+    }
+}
+
+unsafe impl NoUninit for ContourNoise {}
+
+// *** //
+
+/// The distance metric used by the Voronoi algorithm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VoronoiMetric {
+    /// Straight-line distance, which gives convex polygonal cells.
+    #[default]
+    Euclidean,
+    /// The sum of the horizontal and vertical distances.
+    Manhattan,
+    /// The larger of the horizontal and vertical distances, which gives
+    /// boxy cells.
+    Chebyshev,
+    /// A generalization of the above, controlled by an exponent.
+    Minkowski,
+}
+
+impl Display for VoronoiMetric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Euclidean => write!(f, "Euclidean"),
+            Self::Manhattan => write!(f, "Manhattan"),
+            Self::Chebyshev => write!(f, "Chebyshev"),
+            Self::Minkowski => write!(f, "Minkowski"),
+        }
+    }
+}
+
+unsafe impl NoUninit for VoronoiMetric {}
+
+// *** //
+
+/// How the Voronoi algorithm shades its cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VoronoiMode {
+    /// Bright borders between cells, with isolines inside each cell.
+    #[default]
+    Borders,
+    /// The difference between the distances to the nearest and
+    /// second-nearest points, which glows around the borders.
+    F2MinusF1,
+    /// Each cell is filled with its own random value.
+    CellFill,
+}
+
+impl Display for VoronoiMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Borders => write!(f, "Borders"),
+            Self::F2MinusF1 => write!(f, "F2 - F1"),
+            Self::CellFill => write!(f, "Cells"),
+        }
+    }
+}
+
+unsafe impl NoUninit for VoronoiMode {}
+
+// *** //
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum SmoothLifePreset {
     #[default]
     Jitter,
     Slime,
     Corrupt,
+    Gliders,
+    Drift,
+    Fluid,
+    /// The state last saved by the user.
+    User,
 }
 
 impl Display for SmoothLifePreset {
@@ -44,6 +224,10 @@ impl Display for SmoothLifePreset {
             Self::Jitter => write!(f, "Jitter"),
             Self::Slime => write!(f, "Slime"),
             Self::Corrupt => write!(f, "Corrupt"),
+            Self::Gliders => write!(f, "Gliders"),
+            Self::Drift => write!(f, "Drift"),
+            Self::Fluid => write!(f, "Fluid"),
+            Self::User => write!(f, "User"),
         }
     }
 }
@@ -52,6 +236,30 @@ unsafe impl NoUninit for SmoothLifePreset {}
 
 // *** //
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReactionDiffusionPreset {
+    #[default]
+    Coral,
+    Mitosis,
+    Maze,
+    Spots,
+}
+
+impl Display for ReactionDiffusionPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Coral => write!(f, "Coral"),
+            Self::Mitosis => write!(f, "Mitosis"),
+            Self::Maze => write!(f, "Maze"),
+            Self::Spots => write!(f, "Spots"),
+        }
+    }
+}
+
+unsafe impl NoUninit for ReactionDiffusionPreset {}
+
+// *** //
+
 #[derive(Clone, Copy, Debug, Default)]
 pub enum SpectrogramView {
     #[default]
@@ -90,7 +298,9 @@ pub enum DistortionType {
     Hard,
     /// A wrapping clipping algorithm. TODO
     Wrap,
-    /// Downsampling distortion. TODO
+    /// Sample-rate and bit-depth reduction.
+    ///
+    /// ([`Decimator`](crate::dsp::distortion::decimation::Decimator))
     Crush,
 }
 
@@ -110,6 +320,159 @@ unsafe impl NoUninit for DistortionType {}
 
 // *** //
 
+/// The dither applied by the bitcrusher before quantisation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DitherType {
+    #[default]
+    /// No dither.
+    None,
+    /// Uniform noise of one quantisation step.
+    Rectangular,
+    /// Triangular noise of two quantisation steps, which decorrelates the
+    /// quantisation error from the signal.
+    Triangular,
+}
+
+impl Display for DitherType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Rectangular => write!(f, "RPDF"),
+            Self::Triangular => write!(f, "TPDF"),
+        }
+    }
+}
+
+unsafe impl NoUninit for DitherType {}
+
+// *** //
+
+/// The modulation effect in the FX chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ModulationType {
+    #[default]
+    /// No modulation.
+    None,
+    /// A multi-tap stereo chorus.
+    Chorus,
+    /// A short modulated delay with feedback.
+    Flanger,
+    /// Cascaded allpass filters with a swept frequency.
+    Phaser,
+}
+
+impl Display for ModulationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Chorus => write!(f, "Chorus"),
+            Self::Flanger => write!(f, "Flanger"),
+            Self::Phaser => write!(f, "Phaser"),
+        }
+    }
+}
+
+unsafe impl NoUninit for ModulationType {}
+
+// *** //
+
+/// The page of effect controls shown in the UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FxPage {
+    #[default]
+    /// Distortion, delay and compression.
+    Main,
+    /// The tone and modulation of the stereo delay.
+    Delay,
+    /// The bitcrusher of the `Crush` distortion.
+    Crush,
+    /// Modulation effects.
+    Modulation,
+    /// Reverb.
+    Reverb,
+    /// The sidechain, lookahead and makeup gain of the compressor.
+    Compressor,
+    /// The bands of the multiband waveshaper.
+    DistBands,
+    /// The bands of the multiband compressor.
+    CompBands,
+}
+
+impl Display for FxPage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Main => write!(f, "Main"),
+            Self::Delay => write!(f, "Delay"),
+            Self::Crush => write!(f, "Crush"),
+            Self::Modulation => write!(f, "Mod"),
+            Self::Reverb => write!(f, "Reverb"),
+            Self::Compressor => write!(f, "Comp"),
+            Self::DistBands => write!(f, "MB Dist"),
+            Self::CompBands => write!(f, "MB Comp"),
+        }
+    }
+}
+
+unsafe impl NoUninit for FxPage {}
+
+// *** //
+
+impl Display for FilterType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Peak => write!(f, "Peak"),
+            Self::Lowpass => write!(f, "Low Pass"),
+            Self::Highpass => write!(f, "High Pass"),
+            Self::Lowshelf => write!(f, "Low Shelf"),
+            Self::Highshelf => write!(f, "High Shelf"),
+            Self::Bandpass => write!(f, "Band Pass"),
+            Self::Notch => write!(f, "Notch"),
+            Self::Allpass => write!(f, "All Pass"),
+        }
+    }
+}
+
+unsafe impl NoUninit for FilterType {}
+
+// *** //
+
+/// The slope of a band of the parametric EQ.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FilterSlope {
+    #[default]
+    /// 12 dB/octave, from a single biquad filter.
+    Db12,
+    /// 24 dB/octave, from two cascaded biquad filters.
+    Db24,
+    /// 48 dB/octave, from four cascaded biquad filters.
+    Db48,
+}
+
+impl FilterSlope {
+    /// Returns the number of cascaded biquad filters used for the slope.
+    pub const fn num_stages(self) -> usize {
+        match self {
+            Self::Db12 => 1,
+            Self::Db24 => 2,
+            Self::Db48 => 4,
+        }
+    }
+}
+
+impl Display for FilterSlope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Db12 => write!(f, "12 dB"),
+            Self::Db24 => write!(f, "24 dB"),
+            Self::Db48 => write!(f, "48 dB"),
+        }
+    }
+}
+
+unsafe impl NoUninit for FilterSlope {}
+
+// *** //
+
 #[derive(Clone, Copy, Debug, Default)]
 pub enum SmoothLifeSize {
     S16,
@@ -3,7 +3,8 @@
 use super::*;
 use crate::app::audio::audio_constructor::DEFAULT_SPECTRAL_BLOCK_SIZE;
 use crate::app::musical::*;
-use crate::dsp::BUTTERWORTH_Q;
+use crate::dsp::{FilterType, BUTTERWORTH_Q};
+use crate::generative::smooth_life::{SLState, SLStateAtomic};
 use crate::prelude::*;
 use atomic::Atomic;
 
@@ -20,6 +21,12 @@ use std::sync::{
 #[derive(Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct UIParams {
+    // ### TRANSPORT ###
+    /// The tempo of the transport in beats per minute.
+    pub bpm: Arc<AtomicF64>,
+    /// Whether the transport is playing.
+    pub transport_playing: Arc<AtomicBool>,
+
     // ### SPECTRAL FILTER ###
     /// The algorithm to use for the spectral filter.
     pub mask_algorithm: Arc<Atomic<GenerativeAlgo>>,
@@ -29,6 +36,23 @@ pub struct UIParams {
     pub mask_is_post_fx: Arc<AtomicBool>,
 
     pub mask_mix: Arc<AtomicF64>,
+    /// The stereo width of the spectral filter, i.e. how far apart the left and
+    /// right scan lines are.
+    pub mask_stereo_width: Arc<AtomicF64>,
+    /// The path used to read the spectral mask from the generative algorithm.
+    pub mask_readout_mode: Arc<Atomic<MaskReadoutMode>>,
+    /// The number of scan lines used to read the spectral mask.
+    pub mask_scan_line_count: Arc<AtomicU32>,
+    /// The distance between the first and last scan lines.
+    pub mask_scan_line_spread: Arc<AtomicF64>,
+    /// How multiple scan lines are combined.
+    pub mask_scan_line_blend: Arc<Atomic<ScanLineBlend>>,
+    /// How the scan line moves across the mask.
+    pub mask_scan_line_motion: Arc<Atomic<ScanLineMotion>>,
+    /// Whether the scan line is locked to the transport.
+    pub mask_scan_line_sync: Arc<AtomicBool>,
+    /// The note division of one scan line pass when synced to the transport.
+    pub mask_scan_line_division: Arc<Atomic<NoteDivision>>,
     /// The block size of the spectral filter.
     pub mask_resolution: Arc<Atomic<SpectralFilterSize>>,
 
@@ -39,14 +63,57 @@ pub struct UIParams {
     pub contour_thickness: Arc<AtomicF64>,
     /// The speed of the contour animation.
     pub contour_speed: Arc<AtomicF64>,
+    /// The noise function used by the contour generator.
+    pub contour_noise: Arc<Atomic<ContourNoise>>,
+    /// The number of octaves used by fractal contour noise.
+    pub contour_octaves: Arc<AtomicU32>,
+    /// The frequency multiplier between octaves of fractal contour noise.
+    pub contour_lacunarity: Arc<AtomicF64>,
+    /// The amplitude multiplier between octaves of fractal contour noise.
+    pub contour_persistence: Arc<AtomicF64>,
+    /// The amount of domain warping applied to the contour noise.
+    pub contour_warp: Arc<AtomicF64>,
 
     // SMOOTHLIFE ALGORITHM
     /// The resolution of the smooth life simulation.
     pub smoothlife_resolution: Arc<Atomic<SmoothLifeSize>>,
-    /// The speed of the smoothlife simulation.
-    pub smoothlife_speed: Arc<AtomicF64>,
     /// The state preset of the smoothlife simulation.
     pub smoothlife_preset: Arc<Atomic<SmoothLifePreset>>,
+    /// The parameters of the smoothlife simulation, which are shared with
+    /// the generator.
+    pub smoothlife_state: Arc<SLStateAtomic>,
+
+    // REACTION-DIFFUSION ALGORITHM
+    /// The state preset of the reaction-diffusion simulation.
+    pub reaction_diffusion_preset: Arc<Atomic<ReactionDiffusionPreset>>,
+    /// The feed rate of the reaction-diffusion simulation.
+    pub reaction_diffusion_feed: Arc<AtomicF64>,
+    /// The kill rate of the reaction-diffusion simulation.
+    pub reaction_diffusion_kill: Arc<AtomicF64>,
+    /// The diffusion rate of chemical `u` in the reaction-diffusion
+    /// simulation.
+    pub reaction_diffusion_diffusion_u: Arc<AtomicF64>,
+    /// The diffusion rate of chemical `v` in the reaction-diffusion
+    /// simulation.
+    pub reaction_diffusion_diffusion_v: Arc<AtomicF64>,
+
+    // IMAGE ALGORITHM
+    /// The brightness offset of the image mask.
+    pub image_brightness: Arc<AtomicF64>,
+    /// The contrast of the image mask.
+    pub image_contrast: Arc<AtomicF64>,
+    /// The gamma of the image mask.
+    pub image_gamma: Arc<AtomicF64>,
+    /// Whether the image mask is inverted.
+    pub image_invert: Arc<AtomicBool>,
+
+    // SPECTRAL FEEDBACK ALGORITHM
+    /// The time taken for the spectral feedback history to fade, in seconds.
+    pub spectral_feedback_decay: Arc<AtomicF64>,
+    /// The level below which the spectral feedback is black, in decibels.
+    pub spectral_feedback_threshold: Arc<AtomicF64>,
+    /// Whether the spectral feedback is inverted.
+    pub spectral_feedback_invert: Arc<AtomicBool>,
 
     // ### SPECTROGRAMS ###
     /// The resolution of both spectrograms.
@@ -62,6 +129,12 @@ pub struct UIParams {
     pub voronoi_cell_speed: Arc<AtomicF64>,
     /// The weight of the Voronoi borders and isolines.
     pub voronoi_border_weight: Arc<AtomicF64>,
+    /// The distance metric of the Voronoi algorithm.
+    pub voronoi_metric: Arc<Atomic<VoronoiMetric>>,
+    /// The exponent of the Minkowski distance metric.
+    pub voronoi_exponent: Arc<AtomicF64>,
+    /// How the Voronoi cells are shaded.
+    pub voronoi_mode: Arc<Atomic<VoronoiMode>>,
 
     // ### RESONATOR BANK ###
     /// The musical scale of the resonator bank.
@@ -78,12 +151,30 @@ pub struct UIParams {
     pub reso_bank_pan: Arc<SmootherAtomic<f64>>,
     /// Whether the resonators should quantise their pitch to a scale.
     pub reso_bank_quantize: Arc<AtomicBool>,
+    /// The pitch layout which the resonator pitches are blended with.
+    pub reso_bank_pitch_layout: Arc<Atomic<PitchLayout>>,
+    /// How much each resonator's pitch is blended towards the pitch layout.
+    pub reso_bank_layout_blend: Arc<SmootherAtomic<f64>>,
+    /// The time for each resonator to decay by 60 dB in seconds.
+    pub reso_bank_decay: Arc<SmootherAtomic<f64>>,
+    /// How much faster higher resonators decay than lower ones.
+    pub reso_bank_damping: Arc<SmootherAtomic<f64>>,
+    /// How much each resonator's decay time is randomly varied.
+    pub reso_bank_decay_random: Arc<SmootherAtomic<f64>>,
 
     /// The number of active resonators in the bank.
     pub reso_bank_resonator_count: Arc<AtomicU32>,
     /// The friction applied to each point in the vector field.
 
     pub reso_bank_field_friction: Arc<AtomicF64>,
+    /// The attraction of each point toward the mouse.
+    pub reso_bank_field_mouse: Arc<AtomicF64>,
+    /// The strength of flocking between points.
+    pub reso_bank_field_flocking: Arc<AtomicF64>,
+    /// The elasticity of collisions between points.
+    pub reso_bank_field_collision: Arc<AtomicF64>,
+    /// The attraction of each point toward the centre of the field.
+    pub reso_bank_field_gravity: Arc<AtomicF64>,
 
     /// The dry/wet mix of the resonator bank.
     pub reso_bank_mix: Arc<SmootherAtomic<f64>>,
@@ -91,34 +182,12 @@ pub struct UIParams {
     pub exciter_osc: Arc<Atomic<ExciterOscillator>>,
 
     // ### POST EFFECTS ###
+    /// The page of effect controls shown in the UI.
+    pub fx_page: Arc<Atomic<FxPage>>,
 
-    // LOW-PASS
-    /// The cutoff of the filter in Hz.
-    pub low_filter_cutoff: Arc<SmootherAtomic<f64>>,
-    /// The Q value of the cut filter.
-    pub low_filter_q: Arc<SmootherAtomic<f64>>,
-    /// The gain value of the shelf filter.
-    pub low_filter_gain_db: Arc<SmootherAtomic<f64>>,
-    /// Whether the low filter is a shelf filter or not.
-    pub low_filter_is_shelf: Arc<AtomicBool>,
-
-    // PEAK
-    /// The cutoff of the filter in Hz.
-    pub peak_filter_cutoff: Arc<SmootherAtomic<f64>>,
-    /// The Q value of the peak filter.
-    pub peak_filter_q: Arc<SmootherAtomic<f64>>,
-    /// The gain value of the peak filter.
-    pub peak_filter_gain_db: Arc<SmootherAtomic<f64>>,
-
-    // HIGH-PASS
-    /// The cutoff of the high-pass filter in Hz.
-    pub high_filter_cutoff: Arc<SmootherAtomic<f64>>,
-    /// The Q value of the high-pass filter.
-    pub high_filter_q: Arc<SmootherAtomic<f64>>,
-    /// The gain value of the shelf filter.
-    pub high_filter_gain_db: Arc<SmootherAtomic<f64>>,
-    /// Whether the high filter is a shelf filter or not.
-    pub high_filter_is_shelf: Arc<AtomicBool>,
+    // EQ
+    /// The bands of the parametric EQ.
+    pub eq_params: EQParams,
 
     // STEREO DELAY
     /// The time between delay taps in milliseconds.
@@ -129,12 +198,77 @@ pub struct UIParams {
     pub delay_mix: Arc<SmootherAtomic<f64>>,
     /// Whether to use ping-pong delay or not.
     pub use_ping_pong: Arc<AtomicBool>,
+    /// Whether the delay time is synced to the transport.
+    pub delay_sync: Arc<AtomicBool>,
+    /// The note division of the delay time when synced to the transport.
+    pub delay_division: Arc<Atomic<NoteDivision>>,
+    /// Whether the right channel uses the same time as the left channel.
+    pub delay_link: Arc<AtomicBool>,
+    /// The time between right channel delay taps in milliseconds.
+    pub delay_time_r_ms: Arc<AtomicF64>,
+    /// The note division of the right channel when synced to the transport.
+    pub delay_division_r: Arc<Atomic<NoteDivision>>,
+    /// The cutoff of the highpass filter in the delay feedback path.
+    pub delay_low_cut: Arc<SmootherAtomic<f64>>,
+    /// The cutoff of the lowpass filter in the delay feedback path.
+    pub delay_high_cut: Arc<SmootherAtomic<f64>>,
+    /// The amount of saturation in the delay feedback path.
+    pub delay_saturation: Arc<SmootherAtomic<f64>>,
+    /// The depth of the delay's wow and flutter modulation.
+    pub delay_wow_flutter: Arc<SmootherAtomic<f64>>,
 
     // DISTORTION
     /// The "amount" of distortion.
     pub dist_amount: Arc<SmootherAtomic<f64>>,
     /// The distortion algorithm.
     pub dist_type: Arc<Atomic<DistortionType>>,
+    /// The oversampling factor of the distortion, as a power of two (i.e.
+    /// `2` is 4x oversampling).
+    pub dist_oversampling: Arc<AtomicUsize>,
+
+    // CRUSH
+    /// The sample rate of the bitcrusher in hertz.
+    pub crush_rate: Arc<SmootherAtomic<f64>>,
+    /// The bit depth of the bitcrusher.
+    pub crush_bits: Arc<SmootherAtomic<f64>>,
+    /// The random variation of the bitcrusher's sampling period, between
+    /// `0.0` and `1.0`.
+    pub crush_jitter: Arc<SmootherAtomic<f64>>,
+    /// Whether the bitcrusher's input is lowpass filtered.
+    pub crush_anti_alias: Arc<AtomicBool>,
+    /// The dither applied by the bitcrusher.
+    pub crush_dither: Arc<Atomic<DitherType>>,
+
+    // MODULATION
+    /// The modulation effect.
+    pub mod_type: Arc<Atomic<ModulationType>>,
+    /// The modulation rate in hertz.
+    pub mod_rate: Arc<SmootherAtomic<f64>>,
+    /// Whether the modulation rate is synced to the transport.
+    pub mod_sync: Arc<AtomicBool>,
+    /// The note division of the modulation rate when synced to the transport.
+    pub mod_division: Arc<Atomic<NoteDivision>>,
+    /// The modulation depth, between `0.0` and `1.0`.
+    pub mod_depth: Arc<SmootherAtomic<f64>>,
+    /// The flanger and phaser feedback, between `-1.0` and `1.0`.
+    pub mod_feedback: Arc<SmootherAtomic<f64>>,
+    /// The dry/wet mix of the modulation.
+    pub mod_mix: Arc<SmootherAtomic<f64>>,
+
+    // REVERB
+    /// The size of the reverb, between `0.0` and `1.0`.
+    pub reverb_size: Arc<SmootherAtomic<f64>>,
+    /// The reverb decay time in seconds.
+    pub reverb_decay: Arc<SmootherAtomic<f64>>,
+    /// The reverb high-frequency damping, between `0.0` and `1.0`.
+    pub reverb_damping: Arc<SmootherAtomic<f64>>,
+    /// The reverb pre-delay in milliseconds.
+    pub reverb_pre_delay: Arc<SmootherAtomic<f64>>,
+    /// The depth of the reverb's delay line modulation, between `0.0` and
+    /// `1.0`.
+    pub reverb_modulation: Arc<SmootherAtomic<f64>>,
+    /// The dry/wet mix of the reverb.
+    pub reverb_mix: Arc<SmootherAtomic<f64>>,
 
     // COMPRESSION
     /// Compression threshold in decibels.
@@ -145,6 +279,36 @@ pub struct UIParams {
     pub comp_attack_ms: Arc<SmootherAtomic<f64>>,
     /// Compression release time in milliseconds.
     pub comp_release_ms: Arc<SmootherAtomic<f64>>,
+    /// Compression knee width in decibels.
+    pub comp_knee_db: Arc<SmootherAtomic<f64>>,
+    /// Whether the compressor detects RMS levels rather than peak levels.
+    pub comp_use_rms: Arc<AtomicBool>,
+    /// How much the compressor's channels are linked, between `0.0` and
+    /// `1.0`.
+    pub comp_stereo_link: Arc<SmootherAtomic<f64>>,
+    /// Manual compression makeup gain in decibels.
+    pub comp_makeup_db: Arc<SmootherAtomic<f64>>,
+    /// Whether the compression makeup gain is automatic.
+    pub comp_auto_makeup: Arc<AtomicBool>,
+    /// Compression lookahead time in milliseconds.
+    pub comp_lookahead_ms: Arc<AtomicF64>,
+    /// The cutoff of the compressor's sidechain highpass filter.
+    pub comp_sidechain_hpf: Arc<SmootherAtomic<f64>>,
+    /// Whether the compressor is keyed by the dry exciter signal (i.e. before
+    /// the resonator bank) rather than its input.
+    pub comp_exciter_key: Arc<AtomicBool>,
+
+    // MULTIBAND
+    /// The multiband settings of the waveshaper.
+    pub dist_bands: MultibandParams,
+    /// The multiband settings of the compressor.
+    pub comp_bands: MultibandParams,
+
+    // LIMITER
+    /// The output limiter's ceiling in decibels true peak.
+    pub limiter_ceiling_db: Arc<SmootherAtomic<f64>>,
+    /// The output limiter's release time in milliseconds.
+    pub limiter_release_ms: Arc<SmootherAtomic<f64>>,
 
     pub pre_fx_gain: Arc<SmootherAtomic<f64>>,
     /// Master gain level in decibels.
@@ -155,10 +319,29 @@ impl Default for UIParams {
     /// Default parameter values.
     fn default() -> Self {
         Self {
+            bpm: Arc::new(AtomicF64::new(DEFAULT_BPM)),
+            transport_playing: Arc::new(AtomicBool::new(true)),
+
             mask_algorithm: Arc::new(Atomic::new(GenerativeAlgo::default())),
             mask_scan_line_speed: Arc::new(AtomicF64::new(0.1)),
             mask_is_post_fx: Arc::new(AtomicBool::new(false)),
             mask_mix: Arc::new(AtomicF64::new(1.0)),
+            mask_stereo_width: Arc::new(AtomicF64::new(0.0)),
+            mask_readout_mode: Arc::new(Atomic::new(
+                MaskReadoutMode::default(),
+            )),
+            mask_scan_line_count: Arc::new(AtomicU32::new(1)),
+            mask_scan_line_spread: Arc::new(AtomicF64::new(0.2)),
+            mask_scan_line_blend: Arc::new(Atomic::new(
+                ScanLineBlend::default(),
+            )),
+            mask_scan_line_motion: Arc::new(Atomic::new(
+                ScanLineMotion::default(),
+            )),
+            mask_scan_line_sync: Arc::new(AtomicBool::new(false)),
+            mask_scan_line_division: Arc::new(Atomic::new(
+                NoteDivision::Whole,
+            )),
             mask_resolution: Arc::new(Atomic::new(
                 SpectralFilterSize::default(),
             )),
@@ -166,18 +349,46 @@ impl Default for UIParams {
             contour_count: Arc::new(AtomicU32::new(8)),
             contour_thickness: Arc::new(AtomicF64::new(0.6)),
             contour_speed: Arc::new(AtomicF64::new(0.2)),
+            contour_noise: Arc::new(Atomic::new(ContourNoise::default())),
+            contour_octaves: Arc::new(AtomicU32::new(4)),
+            contour_lacunarity: Arc::new(AtomicF64::new(2.0)),
+            contour_persistence: Arc::new(AtomicF64::new(0.5)),
+            contour_warp: Arc::new(AtomicF64::new(0.0)),
 
             smoothlife_resolution: Arc::new(Atomic::new(
                 SmoothLifeSize::default(),
             )),
-            smoothlife_speed: Arc::new(AtomicF64::new(0.2)),
             smoothlife_preset: Arc::new(Atomic::new(
                 SmoothLifePreset::default(),
             )),
+            smoothlife_state: Arc::new(SLStateAtomic::new(
+                SLState::from_preset(SmoothLifePreset::default())
+                    .unwrap_or_default(),
+            )),
+
+            reaction_diffusion_preset: Arc::new(Atomic::new(
+                ReactionDiffusionPreset::default(),
+            )),
+            reaction_diffusion_feed: Arc::new(AtomicF64::new(0.0545)),
+            reaction_diffusion_kill: Arc::new(AtomicF64::new(0.062)),
+            reaction_diffusion_diffusion_u: Arc::new(AtomicF64::new(1.0)),
+            reaction_diffusion_diffusion_v: Arc::new(AtomicF64::new(0.5)),
+
+            image_brightness: Arc::new(AtomicF64::new(0.0)),
+            image_contrast: Arc::new(AtomicF64::new(1.0)),
+            image_gamma: Arc::new(AtomicF64::new(1.0)),
+            image_invert: Arc::new(AtomicBool::new(false)),
+
+            spectral_feedback_decay: Arc::new(AtomicF64::new(2.0)),
+            spectral_feedback_threshold: Arc::new(AtomicF64::new(-60.0)),
+            spectral_feedback_invert: Arc::new(AtomicBool::new(false)),
 
             voronoi_cell_count: Arc::new(AtomicU32::new(10)),
             voronoi_cell_speed: Arc::new(AtomicF64::new(0.3)),
             voronoi_border_weight: Arc::new(AtomicF64::new(0.65)),
+            voronoi_metric: Arc::new(Atomic::new(VoronoiMetric::default())),
+            voronoi_exponent: Arc::new(AtomicF64::new(3.0)),
+            voronoi_mode: Arc::new(Atomic::new(VoronoiMode::default())),
 
             spectrogram_resolution: Arc::new(Atomic::new(
                 SpectrogramSize::default(),
@@ -192,43 +403,218 @@ impl Default for UIParams {
             reso_bank_inharm: smoother(0.3),
             reso_bank_pan: smoother(1.0),
             reso_bank_quantize: Arc::new(AtomicBool::new(true)),
+            reso_bank_pitch_layout: Arc::new(Atomic::new(
+                PitchLayout::default(),
+            )),
+            reso_bank_layout_blend: smoother(0.0),
+            reso_bank_decay: smoother(1.5),
+            reso_bank_damping: smoother(0.0),
+            reso_bank_decay_random: smoother(0.0),
 
             reso_bank_resonator_count: Arc::new(AtomicU32::new(8)),
             reso_bank_field_friction: Arc::new(AtomicF64::new(0.5)),
+            reso_bank_field_mouse: Arc::new(AtomicF64::new(0.0)),
+            reso_bank_field_flocking: Arc::new(AtomicF64::new(0.0)),
+            reso_bank_field_collision: Arc::new(AtomicF64::new(0.0)),
+            reso_bank_field_gravity: Arc::new(AtomicF64::new(0.0)),
 
             reso_bank_mix: smoother(1.0),
             exciter_osc: Arc::new(Atomic::new(ExciterOscillator::default())),
 
-            low_filter_cutoff: smoother(500.0),
-            low_filter_q: smoother(BUTTERWORTH_Q),
-            low_filter_gain_db: smoother(0.0),
-            low_filter_is_shelf: Arc::new(AtomicBool::new(false)),
-
-            peak_filter_cutoff: smoother(1000.0),
-            peak_filter_q: smoother(BUTTERWORTH_Q),
-            peak_filter_gain_db: smoother(0.0),
+            fx_page: Arc::new(Atomic::new(FxPage::default())),
 
-            high_filter_cutoff: smoother(2000.0),
-            high_filter_q: smoother(BUTTERWORTH_Q),
-            high_filter_gain_db: smoother(0.0),
-            high_filter_is_shelf: Arc::new(AtomicBool::new(true)),
+            eq_params: EQParams::default(),
 
             delay_time_ms: Arc::new(AtomicF64::new(250.0)),
             delay_feedback: smoother(0.75),
             delay_mix: smoother(0.0),
             use_ping_pong: Arc::new(AtomicBool::new(true)),
+            delay_sync: Arc::new(AtomicBool::new(false)),
+            delay_division: Arc::new(Atomic::new(NoteDivision::EighthDotted)),
+            delay_link: Arc::new(AtomicBool::new(true)),
+            delay_time_r_ms: Arc::new(AtomicF64::new(250.0)),
+            delay_division_r: Arc::new(Atomic::new(NoteDivision::EighthDotted)),
+            delay_low_cut: smoother(20.0),
+            delay_high_cut: smoother(20000.0),
+            delay_saturation: smoother(0.0),
+            delay_wow_flutter: smoother(0.0),
 
             dist_amount: smoother(0.0),
             dist_type: Arc::new(Atomic::new(DistortionType::default())),
+            dist_oversampling: Arc::new(AtomicUsize::new(
+                DEFAULT_OVERSAMPLING_FACTOR,
+            )),
+
+            crush_rate: smoother(8000.0),
+            crush_bits: smoother(8.0),
+            crush_jitter: smoother(0.0),
+            crush_anti_alias: Arc::new(AtomicBool::new(true)),
+            crush_dither: Arc::new(Atomic::new(DitherType::default())),
+
+            mod_type: Arc::new(Atomic::new(ModulationType::default())),
+            mod_rate: smoother(0.5),
+            mod_sync: Arc::new(AtomicBool::new(false)),
+            mod_division: Arc::new(Atomic::new(NoteDivision::Whole)),
+            mod_depth: smoother(0.5),
+            mod_feedback: smoother(0.0),
+            mod_mix: smoother(0.5),
+
+            reverb_size: smoother(0.5),
+            reverb_decay: smoother(2.0),
+            reverb_damping: smoother(0.5),
+            reverb_pre_delay: smoother(20.0),
+            reverb_modulation: smoother(0.3),
+            reverb_mix: smoother(0.0),
 
             comp_thresh: smoother(-12.0),
             comp_ratio: smoother(10.0),
             comp_attack_ms: smoother(80.0),
             comp_release_ms: smoother(200.0),
+            comp_knee_db: smoother(5.0),
+            comp_use_rms: Arc::new(AtomicBool::new(false)),
+            comp_stereo_link: smoother(0.0),
+            comp_makeup_db: smoother(0.0),
+            comp_auto_makeup: Arc::new(AtomicBool::new(false)),
+            comp_lookahead_ms: Arc::new(AtomicF64::new(0.0)),
+            comp_sidechain_hpf: smoother(20.0),
+            comp_exciter_key: Arc::new(AtomicBool::new(false)),
+
+            dist_bands: MultibandParams::default(),
+            comp_bands: MultibandParams::default(),
+
+            limiter_ceiling_db: smoother(-0.3),
+            limiter_release_ms: smoother(50.0),
 
             pre_fx_gain: smoother(0.0),
             master_gain: smoother(1.0),
-            // eq_params: EQParams::default(),
+        }
+    }
+}
+
+/// The settings of a multiband processor, which splits its input into
+/// [`NUM_BANDS`] bands and processes each separately.
+#[derive(Debug, Clone)]
+pub struct MultibandParams {
+    /// Whether the processor runs per band.
+    pub enabled: Arc<AtomicBool>,
+    /// The frequencies between each band in hertz, from lowest to highest.
+    pub crossover_freqs: [Arc<SmootherAtomic<f64>>; NUM_BANDS - 1],
+    /// The output gain of each band in decibels.
+    pub gain_db: [Arc<SmootherAtomic<f64>>; NUM_BANDS],
+    /// Whether each band skips the processor.
+    pub bypass: [Arc<AtomicBool>; NUM_BANDS],
+    /// Whether each band is soloed. If any band is soloed, the others are
+    /// muted.
+    pub solo: [Arc<AtomicBool>; NUM_BANDS],
+}
+
+impl MultibandParams {
+    /// Returns whether any band is soloed.
+    pub fn any_solo(&self) -> bool {
+        self.solo.iter().any(|solo| solo.lr())
+    }
+
+    /// Returns the gain of each band for the next sample, which is `0.0` for
+    /// bands muted by another band's solo.
+    pub fn next_gains(&self) -> [f64; NUM_BANDS] {
+        let any_solo = self.any_solo();
+
+        std::array::from_fn(|b| {
+            let gain = db_to_level(self.gain_db[b].next());
+
+            if any_solo && !self.solo[b].lr() { 0.0 } else { gain }
+        })
+    }
+
+    /// Returns the next value of each crossover frequency, or `None` if it
+    /// isn't changing.
+    pub fn next_crossover_freqs(&self) -> [Option<f64>; NUM_BANDS - 1] {
+        std::array::from_fn(|i| {
+            let freq = &self.crossover_freqs[i];
+            freq.is_active().then(|| freq.next())
+        })
+    }
+}
+
+impl Default for MultibandParams {
+    fn default() -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            crossover_freqs: [smoother(200.0), smoother(2000.0)],
+            gain_db: std::array::from_fn(|_| smoother(0.0)),
+            bypass: std::array::from_fn(|_| Arc::new(AtomicBool::new(false))),
+            solo: std::array::from_fn(|_| Arc::new(AtomicBool::new(false))),
+        }
+    }
+}
+
+/// The settings of a single band of the parametric EQ.
+#[derive(Debug, Clone)]
+pub struct EQBandParams {
+    /// Whether the band is active.
+    pub enabled: Arc<AtomicBool>,
+    /// The filter type of the band.
+    pub filter_type: Arc<Atomic<FilterType>>,
+    /// The slope of the band.
+    pub slope: Arc<Atomic<FilterSlope>>,
+    /// The cutoff or centre frequency of the band in hertz.
+    pub cutoff: Arc<SmootherAtomic<f64>>,
+    /// The gain of the band in decibels, used by peak and shelving bands.
+    pub gain_db: Arc<SmootherAtomic<f64>>,
+    /// The Q of the band.
+    pub q: Arc<SmootherAtomic<f64>>,
+    /// Whether the gain of the band follows its envelope.
+    pub dynamic: Arc<AtomicBool>,
+    /// The level above which a dynamic band applies its gain in decibels.
+    pub threshold_db: Arc<AtomicF64>,
+}
+
+impl EQBandParams {
+    fn new(enabled: bool, filter_type: FilterType, cutoff: f64) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            filter_type: Arc::new(Atomic::new(filter_type)),
+            slope: Arc::new(Atomic::new(FilterSlope::default())),
+            cutoff: smoother(cutoff),
+            gain_db: smoother(0.0),
+            q: smoother(BUTTERWORTH_Q),
+            dynamic: Arc::new(AtomicBool::new(false)),
+            threshold_db: Arc::new(AtomicF64::new(-24.0)),
+        }
+    }
+}
+
+/// The settings of the parametric EQ, which has up to [`MAX_EQ_BANDS`]
+/// bands.
+#[derive(Debug, Clone)]
+pub struct EQParams {
+    /// The settings of each band. Disabled bands are unused.
+    pub bands: [EQBandParams; MAX_EQ_BANDS],
+    /// The index of the band shown in the band editor. Only used by the UI.
+    pub selected_band: Arc<AtomicUsize>,
+    /// Whether the EQ is processed with linear phase.
+    pub linear_phase: Arc<AtomicBool>,
+}
+
+impl EQParams {
+    /// Returns the index of the first disabled band, if there is one.
+    pub fn first_free_band(&self) -> Option<usize> {
+        self.bands.iter().position(|band| !band.enabled.lr())
+    }
+}
+
+impl Default for EQParams {
+    /// A low cut, a peak and a high shelf.
+    fn default() -> Self {
+        Self {
+            bands: std::array::from_fn(|i| match i {
+                0 => EQBandParams::new(true, FilterType::Highpass, 500.0),
+                1 => EQBandParams::new(true, FilterType::Peak, 1000.0),
+                2 => EQBandParams::new(true, FilterType::Highshelf, 2000.0),
+                _ => EQBandParams::new(false, FilterType::Peak, 1000.0),
+            }),
+            selected_band: Arc::new(AtomicUsize::new(1)),
+            linear_phase: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -1,6 +1,8 @@
 //! The update callback, for mutating state each frame. Not for drawing.
 
 use super::*;
+use crate::dsp::StereoMask;
+use crate::generative::MaskReadout;
 use std::sync::{Arc, Mutex, RwLock};
 
 /// The app's update callback for updating state.
@@ -31,11 +33,23 @@ pub fn update(app: &App, model: &mut Model, update: Update) {
 
     // ui components
     model.ui_components.update(app, &model.input_data);
+    model
+        .ui_components
+        .set_transport_position(model.transport_position.lr());
+    model
+        .ui_components
+        .set_comp_gain_reduction(model.comp_gain_reduction.lr());
+    model.ui_components.set_safety_muted(model.safety_muted.lr());
+    model.ui_components.set_latency(
+        model.latency_samples.lr(),
+        model.sample_rate_ref.lr(),
+    );
 
     let mask_mix = model.ui_params.mask_mix.lr();
 
     if mask_mix > 0.0 {
-        let pos = model.mask_scan_line_pos;
+        let pos = model.stereo_mask_scan_line_pos();
+        let readout = model.mask_readout();
         let mask_len = model.ui_params.mask_resolution.lr().value();
 
         match model.ui_params.mask_algorithm.lr() {
@@ -43,7 +57,9 @@ pub fn update(app: &App, model: &mut Model, update: Update) {
                 let mut ctr = model.contours.write().unwrap();
 
                 ctr.update(app, &model.input_data);
-                ctr.column_to_mask(
+                readout_to_stereo_mask(
+                    &readout,
+                    &*ctr,
                     model.spectral_mask.input_buffer(),
                     mask_len,
                     pos,
@@ -53,7 +69,9 @@ pub fn update(app: &App, model: &mut Model, update: Update) {
                 let mut sml = model.smooth_life.write().unwrap();
 
                 sml.update(app, &model.input_data);
-                sml.column_to_mask(
+                readout_to_stereo_mask(
+                    &readout,
+                    &*sml,
                     model.spectral_mask.input_buffer(),
                     mask_len,
                     pos,
@@ -67,9 +85,56 @@ pub fn update(app: &App, model: &mut Model, update: Update) {
 
                 vrn.copy_from_vectors(&vv);
                 vrn.set_weight(model.ui_params.voronoi_border_weight.lr());
+                vrn.set_metric(
+                    model.ui_params.voronoi_metric.lr(),
+                    model.ui_params.voronoi_exponent.lr(),
+                );
+                vrn.set_mode(model.ui_params.voronoi_mode.lr());
 
                 vrn.update(app, &model.input_data);
-                vrn.column_to_mask(
+                readout_to_stereo_mask(
+                    &readout,
+                    &*vrn,
+                    model.spectral_mask.input_buffer(),
+                    mask_len,
+                    pos,
+                );
+            }
+            GenerativeAlgo::ReactionDiffusion => {
+                let mut rd = model.reaction_diffusion.write().unwrap();
+
+                rd.update(app, &model.input_data);
+                readout_to_stereo_mask(
+                    &readout,
+                    &*rd,
+                    model.spectral_mask.input_buffer(),
+                    mask_len,
+                    pos,
+                );
+            }
+            GenerativeAlgo::Image => {
+                let mut img = model.image_mask.write().unwrap();
+
+                img.update(app, &model.input_data);
+                readout_to_stereo_mask(
+                    &readout,
+                    &*img,
+                    model.spectral_mask.input_buffer(),
+                    mask_len,
+                    pos,
+                );
+            }
+            GenerativeAlgo::SpectralFeedback => {
+                let mut fb = model.spectral_feedback.write().unwrap();
+
+                fb.generator_mut().set_spectrum(
+                    model.pre_spectrum_analyzer.spectrum_db(),
+                    unsafe { SAMPLE_RATE },
+                );
+                fb.update(app, &model.input_data);
+                readout_to_stereo_mask(
+                    &readout,
+                    &*fb,
                     model.spectral_mask.input_buffer(),
                     mask_len,
                     pos,
@@ -87,3 +152,23 @@ pub fn update(app: &App, model: &mut Model, update: Update) {
     // model.update_filter_line();
     // model.update_filter_nodes(app);
 }
+
+/// Reads `generator` with `readout` at the normalized `(left, right)` scan line
+/// positions into the left and right channels of `mask`. If both positions are
+/// equal, the mask is only read once and copied to the right channel.
+fn readout_to_stereo_mask<M: MaskSource>(
+    readout: &MaskReadout,
+    generator: &M,
+    mask: &mut StereoMask,
+    mask_len: usize,
+    (left, right): (f64, f64),
+) {
+    readout.write_mask(generator, &mut mask.left, mask_len, left);
+
+    if epsilon_eq(left, right) {
+        mask.copy_left_to_right(mask_len);
+    }
+    else {
+        readout.write_mask(generator, &mut mask.right, mask_len, right);
+    }
+}
@@ -22,7 +22,6 @@ pub fn view(app: &App, model: &Model, frame: Frame) {
     let bank_rect = &model.bank_rect;
 
     model.redraw_under_menus(draw, is_first_frame);
-    model.redraw_filter_sliders(draw);
 
     let spectrum_rect = model.spectrum_rect;
     draw.rect()
@@ -67,6 +66,17 @@ pub fn view(app: &App, model: &Model, frame: Frame) {
             GenerativeAlgo::Voronoi => {
                 model.voronoi_mask.read().unwrap().draw(app, draw, &frame);
             }
+            GenerativeAlgo::ReactionDiffusion => {
+                let rd = model.reaction_diffusion.read().unwrap();
+                rd.draw(app, draw, &frame);
+            }
+            GenerativeAlgo::Image => {
+                model.image_mask.read().unwrap().draw(app, draw, &frame);
+            }
+            GenerativeAlgo::SpectralFeedback => {
+                let fb = model.spectral_feedback.read().unwrap();
+                fb.draw(app, draw, &frame);
+            }
         }
     }
     if mask_mix < 1.0 {
@@ -9,3 +9,120 @@ pub mod ring_buffer;
 pub use delay::Delay;
 pub use stereo_delay::StereoDelay;
 pub use ring_buffer::RingBuffer;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    /// Processes silence until the smoothed delay times have settled.
+    fn settle(delay: &mut StereoDelay) {
+        for _ in 0..(SAMPLE_RATE * 0.5) as usize {
+            delay.process_stereo(0.0, 0.0);
+        }
+    }
+
+    /// Returns the index of the loudest sample of each channel of the
+    /// stereo delay's impulse response.
+    fn peak_positions(delay: &mut StereoDelay, len: usize) -> (usize, usize) {
+        settle(delay);
+        let (mut peak_l, mut peak_r) = ((0, 0.0), (0, 0.0));
+
+        for i in 0..len {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            let (out_l, out_r) = delay.process_stereo(x, x);
+
+            if out_l.abs() > peak_l.1 {
+                peak_l = (i, out_l.abs());
+            }
+            if out_r.abs() > peak_r.1 {
+                peak_r = (i, out_r.abs());
+            }
+        }
+
+        (peak_l.0, peak_r.0)
+    }
+
+    #[test]
+    fn stereo_delay_independent_times() {
+        let mut delay = StereoDelay::new(1.0, SAMPLE_RATE);
+        delay.set_delay_times(0.1, 0.2);
+
+        let (peak_l, peak_r) = peak_positions(&mut delay, 22050);
+
+        assert_eq!(peak_l, 4410);
+        assert_eq!(peak_r, 8820);
+    }
+
+    #[test]
+    fn stereo_delay_feedback_is_filtered() {
+        let repeat_energy = |high_cut| {
+            let mut delay = StereoDelay::new(1.0, SAMPLE_RATE);
+            delay.set_delay_time(0.01);
+            delay.set_feedback_amount(0.9);
+            delay.set_high_cut(high_cut);
+
+            settle(&mut delay);
+
+            (0..4410)
+                .map(|i| {
+                    let x = if i == 0 { 1.0 } else { 0.0 };
+                    delay.process_stereo(x, x).0
+                })
+                .map(|x| x * x)
+                .sum::<f64>()
+        };
+
+        assert!(repeat_energy(1000.0) < repeat_energy(20000.0) * 0.5);
+    }
+
+    /// Returns the loudest output of the left channel in the first and last
+    /// seconds of the delay's response to an impulse of `amp`, over `secs`.
+    fn first_and_last_peaks(
+        delay: &mut StereoDelay,
+        amp: f64,
+        secs: f64,
+    ) -> (f64, f64) {
+        settle(delay);
+        let len = (secs * SAMPLE_RATE) as usize;
+        let second = SAMPLE_RATE as usize;
+
+        let output: Vec<f64> = (0..len)
+            .map(|i| {
+                let x = if i == 0 { amp } else { 0.0 };
+                delay.process_stereo(x, x).0.abs()
+            })
+            .collect();
+
+        let peak = |xs: &[f64]| xs.iter().copied().fold(0.0, f64::max);
+        (peak(&output[..second]), peak(&output[len - second..]))
+    }
+
+    /// A delay with the default parameters of the app.
+    fn default_delay() -> StereoDelay {
+        let mut delay = StereoDelay::new(1.0, SAMPLE_RATE);
+        delay.set_delay_time(0.25);
+        delay.set_feedback_amount(0.75);
+
+        delay
+    }
+
+    #[test]
+    fn stereo_delay_feedback_decays() {
+        let mut delay = default_delay();
+        let (first, last) = first_and_last_peaks(&mut delay, 1.0, 10.0);
+
+        assert!(last < first * 0.01, "{first} -> {last}");
+    }
+
+    #[test]
+    fn stereo_delay_saturation_does_not_self_oscillate() {
+        let mut delay = default_delay();
+        delay.set_saturation(0.3);
+        delay.set_high_cut(8000.0);
+        let (first, last) = first_and_last_peaks(&mut delay, 0.01, 10.0);
+
+        assert!(last < first * 0.01, "{first} -> {last}");
+    }
+}
@@ -84,8 +84,28 @@ impl RingBuffer {
 
     /// Reads the delayed element from the `RingBuffer`.
     pub fn read(&mut self) -> f64 {
+        self.read_modulated(0.0)
+    }
+
+    /// Reads the element `offset_samples` behind the delayed element, which
+    /// allows the smoothed delay time to be modulated.
+    ///
+    /// The buffer must be large enough to hold the delay time plus the
+    /// offset.
+    pub fn read_modulated(&mut self, offset_samples: f64) -> f64 {
+        let delay_samples = self.sample_rate * self.delay_secs.next();
+        self.read_at(delay_samples + offset_samples)
+    }
+
+    /// Reads the element `delay_samples` behind the write position, ignoring
+    /// the buffer's delay time. This is intended for modulated delays, which
+    /// change their delay time every sample and so must not be smoothed.
+    ///
+    /// `delay_samples` should be at least `2.0` when using cubic
+    /// interpolation.
+    pub fn read_at(&self, delay_samples: f64) -> f64 {
         use InterpType as IT;
-        let (read_pos, interp) = self.get_read_pos_and_interp();
+        let (read_pos, interp) = self.get_read_pos_and_interp(delay_samples);
         // r1 is the same as read_pos
         let (r0, r1, r2, r3) =
             if matches!(self.interpolation_type, IT::NoInterp) {
@@ -204,8 +224,7 @@ impl RingBuffer {
         size / self.sample_rate
     }
 
-    fn get_read_pos_and_interp(&mut self) -> (usize, f64) {
-        let delay_samples = self.sample_rate * self.delay_secs.next();
+    fn get_read_pos_and_interp(&self, delay_samples: f64) -> (usize, f64) {
         // the exact delay sample, i.e. read position
         let samples_exact = delay_samples.floor();
         // the interpolation between this sample and the next
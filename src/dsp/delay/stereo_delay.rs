@@ -1,32 +1,85 @@
 //! A simple stereo delay.
 
 use super::*;
+use crate::dsp::synthesis::Phasor;
+use std::f64::consts::TAU;
+
+/// The rate of the wow modulation in hertz.
+const WOW_RATE: f64 = 0.6;
+/// The furthest the read position is modulated by wow, in milliseconds.
+const MAX_WOW_MS: f64 = 3.0;
+/// The rate of the flutter modulation in hertz.
+const FLUTTER_RATE: f64 = 7.0;
+/// The furthest the read position is modulated by flutter, in milliseconds.
+const MAX_FLUTTER_MS: f64 = 0.3;
+/// The LFO phase offset of the right channel.
+const STEREO_PHASE_OFFSET: f64 = 0.25;
+/// The drive of the feedback saturation at full saturation.
+const MAX_SATURATION_DRIVE: f64 = 10.0;
 
 /// A stereo delay processor, capable of ping-pong effects via channel
 /// cross-feeding.
-#[derive(Clone, Debug, Default)]
+///
+/// The signal written to the delay lines passes through a highpass filter, a
+/// lowpass filter and a saturator, so each repeat is progressively filtered
+/// and driven. The read position may be modulated to emulate tape wow and
+/// flutter.
+#[derive(Clone, Debug)]
 pub struct StereoDelay {
     buffer_l: RingBuffer,
     buffer_r: RingBuffer,
     feedback_amount: f64,
     use_ping_pong: bool,
+
+    /// The highpass (low cut) and lowpass (high cut) filters of each channel.
+    tone_filters: [[BiquadFilter; 2]; 2],
+    saturation: f64,
+
+    wow_lfo: Phasor,
+    flutter_lfo: Phasor,
+    wow_flutter: f64,
 }
 
 impl StereoDelay {
     /// Creates a new stereo delay with `max_delay_time_secs` maximum time allocated.
     pub fn new(max_delay_time_secs: f64, sample_rate: f64) -> Self {
+        // extra room is allocated for the wow and flutter modulation
+        let max_mod_secs = (MAX_WOW_MS + MAX_FLUTTER_MS) * 0.001;
         let buffer = RingBuffer::new(
-            (max_delay_time_secs * sample_rate) as usize,
+            ((max_delay_time_secs + max_mod_secs) * sample_rate) as usize + 4,
             sample_rate,
         )
         .with_smoothing(delay::DEFAULT_DELAY_SMOOTHING, 0.1)
         .with_interpolation(InterpType::DefaultCubic);
-        Self {
+
+        // the biquad filter takes the reciprocal of Q
+        let mut low_cut = BiquadFilter::new(sample_rate);
+        low_cut.set_type(FilterType::Highpass);
+        low_cut.set_q(BUTTERWORTH_Q.recip());
+        let mut high_cut = BiquadFilter::new(sample_rate);
+        high_cut.set_type(FilterType::Lowpass);
+        high_cut.set_q(BUTTERWORTH_Q.recip());
+
+        let mut delay = Self {
             buffer_l: buffer.clone(),
             buffer_r: buffer,
             feedback_amount: 0.0,
             use_ping_pong: false,
-        }
+
+            tone_filters: [
+                [low_cut.clone(), high_cut.clone()],
+                [low_cut, high_cut],
+            ],
+            saturation: 0.0,
+
+            wow_lfo: Phasor::new(WOW_RATE, sample_rate),
+            flutter_lfo: Phasor::new(FLUTTER_RATE, sample_rate),
+            wow_flutter: 0.0,
+        };
+
+        delay.set_low_cut(20.0);
+        delay.set_high_cut(20000.0);
+        delay
     }
 
     /// Sets the initial delay time of the stereo delay.
@@ -52,6 +105,12 @@ impl StereoDelay {
         self.buffer_r.set_delay_time(delay_secs);
     }
 
+    /// Sets the delay times of the left and right channels in seconds.
+    pub fn set_delay_times(&mut self, delay_l_secs: f64, delay_r_secs: f64) {
+        self.buffer_l.set_delay_time(delay_l_secs);
+        self.buffer_r.set_delay_time(delay_r_secs);
+    }
+
     /// Sets the delay time of the delay in samples.
     pub fn set_delay_time_samples(&mut self, delay_samples: f64) {
         self.set_delay_time(delay_samples / self.get_sample_rate());
@@ -62,9 +121,40 @@ impl StereoDelay {
         self.feedback_amount = feedback.clamp(0.0, 1.0);
     }
 
+    /// Sets the cutoff of the highpass filter in the feedback path, in hertz.
+    pub fn set_low_cut(&mut self, freq_hz: f64) {
+        let freq = freq_hz.clamp(1.0, self.get_sample_rate() * 0.45);
+
+        for filters in &mut self.tone_filters {
+            filters[0].set_freq(freq);
+        }
+    }
+
+    /// Sets the cutoff of the lowpass filter in the feedback path, in hertz.
+    pub fn set_high_cut(&mut self, freq_hz: f64) {
+        let freq = freq_hz.clamp(1.0, self.get_sample_rate() * 0.45);
+
+        for filters in &mut self.tone_filters {
+            filters[1].set_freq(freq);
+        }
+    }
+
+    /// Sets the amount of saturation in the feedback path, between `0.0` and
+    /// `1.0`.
+    pub fn set_saturation(&mut self, saturation: f64) {
+        self.saturation = saturation.clamp(0.0, 1.0);
+    }
+
+    /// Sets the depth of the wow and flutter modulation, between `0.0` and
+    /// `1.0`.
+    pub fn set_wow_flutter(&mut self, amount: f64) {
+        self.wow_flutter = amount.clamp(0.0, 1.0);
+    }
+
     /// Returns the maximum delay time of the stereo delay.
     pub fn max_delay_time_secs(&self) -> f64 {
-        self.buffer_l.max_delay_secs()
+        let max_mod_secs = (MAX_WOW_MS + MAX_FLUTTER_MS) * 0.001;
+        (self.buffer_l.max_delay_secs() - max_mod_secs).max(0.0)
     }
 
     /// Sets whether the stereo delay uses ping-pong or not.
@@ -80,27 +170,95 @@ impl StereoDelay {
             .set_smoothing(delay::DEFAULT_DELAY_SMOOTHING, smoothing_time_secs);
     }
 
+    /// Clears the delay lines and the state of the feedback filters.
+    pub fn reset(&mut self) {
+        self.buffer_l.clear();
+        self.buffer_r.clear();
+        self.tone_filters
+            .iter_mut()
+            .flatten()
+            .for_each(BiquadFilter::reset);
+    }
+
     /// Resets the sample rate of the stereo delay.
     pub fn set_sample_rate(&mut self, sample_rate: f64) {
         self.buffer_l.set_sample_rate(sample_rate);
         self.buffer_r.set_sample_rate(sample_rate);
     }
+
+    /// Returns the read position offsets of the left and right channels in
+    /// samples, due to wow and flutter.
+    fn mod_offsets(&mut self) -> (f64, f64) {
+        if self.wow_flutter <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let ms_to_samples = 0.001 * self.get_sample_rate();
+        let wow_depth = self.wow_flutter * MAX_WOW_MS * ms_to_samples;
+        let flutter_depth = self.wow_flutter * MAX_FLUTTER_MS * ms_to_samples;
+
+        // the offsets are always positive, so the LFOs are unipolar
+        let offset = |phase: f64| 0.5f64.mul_add(-(TAU * phase).cos(), 0.5);
+        let offset_at = |phase_offset: f64| {
+            let wow = offset(self.wow_lfo.phase + phase_offset);
+            let flutter = offset(self.flutter_lfo.phase + phase_offset);
+            wow.mul_add(wow_depth, flutter * flutter_depth)
+        };
+
+        let offsets = (offset_at(0.0), offset_at(STEREO_PHASE_OFFSET));
+
+        self.wow_lfo.increment_phase();
+        self.flutter_lfo.increment_phase();
+
+        offsets
+    }
+
+    /// Filters and saturates a signal before it is written to the delay line
+    /// of channel `ch`.
+    fn process_feedback(&mut self, sample: f64, ch: usize) -> f64 {
+        let filtered = self.tone_filters[ch]
+            .iter_mut()
+            .fold(sample, |x, filter| filter.process(x));
+
+        if self.saturation <= 0.0 {
+            return filtered;
+        }
+
+        // the saturator has unity gain for small signals, so it can only
+        // reduce the gain of the feedback loop
+        let drive = (MAX_SATURATION_DRIVE - 1.0).mul_add(self.saturation, 1.0);
+        let saturated = (filtered * drive).tanh() / drive;
+
+        lerp(filtered, saturated, self.saturation)
+    }
+}
+
+impl Default for StereoDelay {
+    fn default() -> Self {
+        Self::new(1.0, unsafe { SAMPLE_RATE })
+    }
 }
 
 impl Effect for StereoDelay {
     fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
-        let out_l = self.buffer_l.read();
-        let out_r = self.buffer_r.read();
-        if self.use_ping_pong {
-            self.buffer_l.push(in_l + out_r * self.feedback_amount);
-            self.buffer_r.push(out_l * self.feedback_amount);
+        let (offset_l, offset_r) = self.mod_offsets();
+        let out_l = self.buffer_l.read_modulated(offset_l);
+        let out_r = self.buffer_r.read_modulated(offset_r);
+
+        let (write_l, write_r) = if self.use_ping_pong {
+            (in_l + out_r * self.feedback_amount, out_l * self.feedback_amount)
         }
         else {
-            self.buffer_l
-                .push(out_l.mul_add(self.feedback_amount, in_l));
-            self.buffer_r
-                .push(out_r.mul_add(self.feedback_amount, in_r));
-        }
+            (
+                out_l.mul_add(self.feedback_amount, in_l),
+                out_r.mul_add(self.feedback_amount, in_r),
+            )
+        };
+
+        let write_l = self.process_feedback(write_l, 0);
+        let write_r = self.process_feedback(write_r, 1);
+        self.buffer_l.push(write_l);
+        self.buffer_r.push(write_r);
 
         (out_l, out_r)
     }
@@ -1,5 +1,172 @@
 //! Module for signal decimation, AKA downsampling.
 
+use super::*;
+use crate::app::DitherType;
+
+/// The number of cascaded biquads in the anti-aliasing filter.
+const NUM_AA_STAGES: usize = 2;
+/// The cutoff of the anti-aliasing filter, relative to the target rate.
+const AA_CUTOFF: f64 = 0.45;
+
+/// A bitcrusher: a sample-rate reducer (sample-and-hold) combined with a
+/// bit-depth reducer.
+///
+/// The input may optionally be lowpass filtered before it is held, which
+/// tames the aliasing of the sample-and-hold, and the sampling period may be
+/// randomly varied ("jitter"). Quantisation may be dithered.
+#[derive(Clone, Debug)]
 pub struct Decimator {
-    // TODO
+    /// The phase of the sample-and-hold, which takes a new sample each time
+    /// it passes `1.0`.
+    phase: f64,
+    held: [f64; 2],
+
+    aa_filters: [[BiquadFilter; NUM_AA_STAGES]; 2],
+    use_anti_alias: bool,
+
+    target_rate: f64,
+    bit_depth: f64,
+    jitter: f64,
+    dither: DitherType,
+
+    rng: SessionRng,
+    sample_rate: f64,
+}
+
+impl Decimator {
+    /// The lowest target sample rate in hertz.
+    pub const MIN_RATE: f64 = 100.0;
+    /// The highest target sample rate in hertz.
+    pub const MAX_RATE: f64 = 48000.0;
+    /// The lowest bit depth.
+    pub const MIN_BIT_DEPTH: f64 = 1.0;
+    /// The highest bit depth.
+    pub const MAX_BIT_DEPTH: f64 = 24.0;
+
+    pub fn new(sample_rate: f64) -> Self {
+        let mut aa_filter = BiquadFilter::new(sample_rate);
+        aa_filter.set_type(FilterType::Lowpass);
+        // the biquad filter takes the reciprocal of Q
+        aa_filter.set_q(BUTTERWORTH_Q.recip());
+
+        let mut decimator = Self {
+            phase: 1.0,
+            held: [0.0; 2],
+
+            aa_filters: std::array::from_fn(|_| {
+                std::array::from_fn(|_| aa_filter.clone())
+            }),
+            use_anti_alias: true,
+
+            target_rate: sample_rate,
+            bit_depth: Self::MAX_BIT_DEPTH,
+            jitter: 0.0,
+            dither: DitherType::None,
+
+            rng: SessionRng::new("decimator"),
+            sample_rate,
+        };
+
+        decimator.set_target_rate(8000.0);
+        decimator
+    }
+
+    /// Sets the rate at which the input is sampled, in hertz. This is
+    /// limited to the sample rate.
+    pub fn set_target_rate(&mut self, rate_hz: f64) {
+        let max_rate = Self::MAX_RATE.min(self.sample_rate);
+        self.target_rate = rate_hz.clamp(Self::MIN_RATE, max_rate);
+
+        let cutoff = self.target_rate * AA_CUTOFF;
+
+        for filter in self.aa_filters.iter_mut().flatten() {
+            filter.set_freq(cutoff);
+        }
+    }
+
+    /// Sets the bit depth of the output. Fractional bit depths are
+    /// supported, which smoothly change the number of quantisation levels.
+    pub fn set_bit_depth(&mut self, bits: f64) {
+        self.bit_depth = bits.clamp(Self::MIN_BIT_DEPTH, Self::MAX_BIT_DEPTH);
+    }
+
+    /// Sets how much the sampling period randomly varies, between `0.0` and
+    /// `1.0`. At `1.0`, each period varies by up to half of its length.
+    pub fn set_jitter(&mut self, jitter: f64) {
+        self.jitter = jitter.clamp(0.0, 1.0);
+    }
+
+    /// Sets whether the input is lowpass filtered before it is sampled.
+    pub fn set_anti_alias(&mut self, use_anti_alias: bool) {
+        self.use_anti_alias = use_anti_alias;
+    }
+
+    /// Sets the dither applied before quantisation.
+    pub fn set_dither(&mut self, dither: DitherType) {
+        self.dither = dither;
+    }
+
+    /// Clears the held samples and the state of the anti-aliasing filters,
+    /// so that the next input is sampled immediately.
+    pub fn reset(&mut self) {
+        self.phase = 1.0;
+        self.held = [0.0; 2];
+
+        self.aa_filters
+            .iter_mut()
+            .flatten()
+            .for_each(BiquadFilter::reset);
+    }
+
+    /// Quantises `sample` to the current bit depth.
+    fn quantize(&mut self, sample: f64) -> f64 {
+        let levels = (self.bit_depth - 1.0).exp2();
+
+        // the dither noise is measured in quantisation steps
+        let noise = match self.dither {
+            DitherType::None => 0.0,
+            DitherType::Rectangular => self.rng.next_f64() - 0.5,
+            DitherType::Triangular => {
+                self.rng.next_f64() - self.rng.next_f64()
+            }
+        };
+
+        sample.mul_add(levels, noise).round() / levels
+    }
+}
+
+impl Default for Decimator {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+impl Effect for Decimator {
+    fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let mut input = [in_l, in_r];
+
+        if self.use_anti_alias {
+            for (sample, filters) in input.iter_mut().zip(&mut self.aa_filters)
+            {
+                *sample = filters
+                    .iter_mut()
+                    .fold(*sample, |x, filter| filter.process(x));
+            }
+        }
+
+        if self.phase >= 1.0 {
+            let variation = self.jitter * (self.rng.next_f64() - 0.5);
+            self.phase = (self.phase - 1.0 - variation).max(0.0);
+
+            self.held = [self.quantize(input[0]), self.quantize(input[1])];
+        }
+
+        self.phase += self.target_rate / self.sample_rate;
+
+        (self.held[0], self.held[1])
+    }
+
+    fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
 }
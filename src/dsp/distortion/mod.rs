@@ -5,4 +5,78 @@ use super::*;
 pub mod decimation;
 pub mod waveshaper;
 
+pub use decimation::Decimator;
 pub use waveshaper::Waveshaper;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::DitherType;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    fn ramp(len: usize) -> Vec<f64> {
+        (0..len).map(|i| i as f64 / len as f64).collect()
+    }
+
+    fn process(decimator: &mut Decimator, input: &[f64]) -> Vec<f64> {
+        input.iter().map(|&x| decimator.process_stereo(x, x).0).collect()
+    }
+
+    #[test]
+    fn decimator_holds_samples() {
+        let mut decimator = Decimator::new(SAMPLE_RATE);
+        decimator.set_anti_alias(false);
+        decimator.set_target_rate(SAMPLE_RATE / 4.0);
+
+        let output = process(&mut decimator, &ramp(64));
+
+        for block in output.chunks(4) {
+            assert!(block.iter().all(|&x| x == block[0]));
+        }
+        assert!(output[0] < output[4]);
+    }
+
+    #[test]
+    fn decimator_reduces_bit_depth() {
+        let mut decimator = Decimator::new(SAMPLE_RATE);
+        decimator.set_anti_alias(false);
+        decimator.set_target_rate(SAMPLE_RATE);
+        decimator.set_bit_depth(3.0);
+
+        // 3 bits quantises to quarters
+        for x in process(&mut decimator, &ramp(256)) {
+            assert_eq!((x * 4.0).fract(), 0.0);
+        }
+    }
+
+    #[test]
+    fn dither_decorrelates_quantisation() {
+        let mut decimator = Decimator::new(SAMPLE_RATE);
+        decimator.set_anti_alias(false);
+        decimator.set_target_rate(SAMPLE_RATE);
+        decimator.set_bit_depth(2.0);
+
+        // without dither, a constant input always quantises to the same level
+        let input = vec![0.2; 4096];
+        let output = process(&mut decimator, &input);
+        assert!(output.iter().all(|&x| x == 0.0));
+
+        // with dither, the average of the output tracks the input
+        decimator.set_dither(DitherType::Triangular);
+        let output = process(&mut decimator, &input);
+        let mean = output.iter().sum::<f64>() / output.len() as f64;
+
+        assert!((mean - 0.2).abs() < 0.05, "{mean}");
+    }
+
+    #[test]
+    fn decimator_reset_clears_nan() {
+        let mut decimator = Decimator::new(SAMPLE_RATE);
+        process(&mut decimator, &[f64::NAN]);
+        decimator.reset();
+
+        let output = process(&mut decimator, &ramp(256));
+        assert!(output.iter().all(|x| x.is_finite()));
+    }
+}
@@ -7,6 +7,12 @@ const DEFAULT_RELEASE_TIME_MS: f64 = 100.0;
 
 /// A simple dynamics compressor. Supports a variable knee width,
 /// attack and release times, and ratio.
+///
+/// The compressor may be keyed by an external sidechain signal via
+/// [`process_stereo_with_key()`](Self::process_stereo_with_key), and its
+/// detector is highpass filtered and may be linked between channels. The
+/// input may be delayed ("lookahead") so that the gain reduction is applied
+/// before transients rather than just after them.
 #[derive(Clone, Debug)]
 pub struct Compressor {
     sample_rate: f64,
@@ -17,10 +23,37 @@ pub struct Compressor {
     ratio: f64,
 
     envelope_filter: BallisticsFilter,
+    sidechain_filters: [BiquadFilter; NUM_CHANNELS],
+    stereo_link: f64,
+
+    makeup_gain_db: f64,
+    use_auto_makeup: bool,
+
+    lookahead_buffers: [RingBuffer; NUM_CHANNELS],
+    lookahead_samples: f64,
+
+    /// The gain reduction of the last processed sample in decibels.
+    gain_reduction_db: f64,
 }
 
 impl Compressor {
+    /// The longest lookahead time in milliseconds.
+    pub const MAX_LOOKAHEAD_MS: f64 = 10.0;
+    /// The lowest cutoff of the sidechain highpass filter in hertz.
+    pub const MIN_SIDECHAIN_HPF: f64 = 20.0;
+
     pub fn new(sample_rate: f64) -> Self {
+        let mut sidechain_filter = BiquadFilter::new(sample_rate);
+        sidechain_filter.set_type(FilterType::Highpass);
+        // the biquad filter takes the reciprocal of Q
+        sidechain_filter.set_q(BUTTERWORTH_Q.recip());
+        sidechain_filter.set_freq(Self::MIN_SIDECHAIN_HPF);
+
+        let lookahead_size =
+            (Self::MAX_LOOKAHEAD_MS * 0.001 * sample_rate).ceil() as usize + 2;
+        let lookahead_buffer = RingBuffer::new(lookahead_size, sample_rate)
+            .with_interpolation(InterpType::NoInterp);
+
         Self {
             sample_rate,
 
@@ -31,6 +64,16 @@ impl Compressor {
             ratio: 1.0,
 
             envelope_filter: BallisticsFilter::new(NUM_CHANNELS, sample_rate),
+            sidechain_filters: [sidechain_filter.clone(), sidechain_filter],
+            stereo_link: 0.0,
+
+            makeup_gain_db: 0.0,
+            use_auto_makeup: false,
+
+            lookahead_buffers: [lookahead_buffer.clone(), lookahead_buffer],
+            lookahead_samples: 0.0,
+
+            gain_reduction_db: 0.0,
         }
     }
 
@@ -90,6 +133,133 @@ impl Compressor {
         }
     }
 
+    /// Sets how much the detectors of each channel are linked, between `0.0`
+    /// (independent) and `1.0` (fully linked). Linked channels receive the
+    /// same gain reduction, which preserves the stereo image.
+    pub fn set_stereo_link(&mut self, link: f64) {
+        self.stereo_link = link.clamp(0.0, 1.0);
+    }
+
+    /// Sets the manual makeup gain in decibels. This is ignored if automatic
+    /// makeup gain is used.
+    pub fn set_makeup_gain_db(&mut self, gain_db: f64) {
+        self.makeup_gain_db = gain_db;
+    }
+
+    /// Sets whether the makeup gain is automatically derived from the
+    /// threshold and ratio.
+    pub fn use_auto_makeup(&mut self, use_auto_makeup: bool) {
+        self.use_auto_makeup = use_auto_makeup;
+    }
+
+    /// Sets the lookahead time in milliseconds, which delays the input
+    /// relative to the detector. Clamped to
+    /// [`MAX_LOOKAHEAD_MS`](Self::MAX_LOOKAHEAD_MS).
+    pub fn set_lookahead_ms(&mut self, time_ms: f64) {
+        let time_ms = time_ms.clamp(0.0, Self::MAX_LOOKAHEAD_MS);
+        self.lookahead_samples = (time_ms * 0.001 * self.sample_rate).round();
+    }
+
+    /// Returns the latency of the lookahead in samples.
+    pub fn latency_samples(&self) -> usize {
+        self.lookahead_samples as usize
+    }
+
+    /// Sets the cutoff of the sidechain highpass filter in hertz, which stops
+    /// low frequencies from dominating the detector.
+    pub fn set_sidechain_hpf(&mut self, freq_hz: f64) {
+        let freq =
+            freq_hz.clamp(Self::MIN_SIDECHAIN_HPF, self.sample_rate * 0.45);
+
+        for filter in &mut self.sidechain_filters {
+            filter.set_freq(freq);
+        }
+    }
+
+    /// Clears the state of the detector and the lookahead buffers.
+    pub fn reset(&mut self) {
+        self.envelope_filter.reset(0.0);
+        self.sidechain_filters.iter_mut().for_each(BiquadFilter::reset);
+        self.lookahead_buffers.iter_mut().for_each(RingBuffer::clear);
+        self.gain_reduction_db = 0.0;
+    }
+
+    /// Returns the gain reduction of the last processed sample in decibels,
+    /// as a positive value. The louder channel is used.
+    pub fn gain_reduction_db(&self) -> f64 {
+        self.gain_reduction_db
+    }
+
+    /// Returns the makeup gain in decibels. The automatic makeup gain
+    /// restores half of the gain reduction of a full-scale signal.
+    pub fn makeup_gain_db(&self) -> f64 {
+        if self.use_auto_makeup {
+            self.gain_function(0.0) * -0.5
+        }
+        else {
+            self.makeup_gain_db
+        }
+    }
+
+    /// Processes a stereo input, using `key_l` and `key_r` as the sidechain
+    /// signal rather than the input.
+    pub fn process_stereo_with_key(
+        &mut self,
+        in_l: f64,
+        in_r: f64,
+        key_l: f64,
+        key_r: f64,
+    ) -> (f64, f64) {
+        let key_l = self.sidechain_filters[0].process(key_l);
+        let key_r = self.sidechain_filters[1].process(key_r);
+
+        let (env_l, env_r) = self.envelope_filter.process_stereo(key_l, key_r);
+
+        let env_max = env_l.max(env_r);
+        let env_l = lerp(env_l, env_max, self.stereo_link);
+        let env_r = lerp(env_r, env_max, self.stereo_link);
+
+        let reduction_l = self.gain_function(level_to_db(env_l));
+        let reduction_r = self.gain_function(level_to_db(env_r));
+        self.gain_reduction_db = -reduction_l.min(reduction_r);
+
+        let makeup_db = self.makeup_gain_db();
+        let gain_l = db_to_level(reduction_l + makeup_db);
+        let gain_r = db_to_level(reduction_r + makeup_db);
+
+        let (in_l, in_r) = self.lookahead(in_l, in_r);
+
+        (gain_l * in_l, gain_r * in_r)
+    }
+
+    /// Delays the input by the lookahead time without compressing it, so that
+    /// a bypassed compressor stays aligned with any others running in
+    /// parallel.
+    pub fn process_bypassed(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        self.gain_reduction_db = 0.0;
+        self.lookahead(in_l, in_r)
+    }
+
+    /// Delays the input by the lookahead time.
+    fn lookahead(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let mut input = [in_l, in_r];
+
+        let buffers = &mut self.lookahead_buffers;
+
+        for (sample, buffer) in input.iter_mut().zip(buffers) {
+            // the buffers are always written to so that they hold the recent
+            // input when the lookahead time changes
+            let delayed = buffer.read_at(self.lookahead_samples.max(1.0));
+            buffer.push(*sample);
+
+            if self.lookahead_samples >= 1.0 {
+                *sample = delayed;
+            }
+        }
+
+        (input[0], input[1])
+    }
+
     /// Standard compression gain function with a rounded knee and, otherwise,
     /// a linear profile. This represents the *amount of gain to apply* for a
     /// given envelope level, not a scale.
@@ -123,12 +293,7 @@ impl Compressor {
 
 impl Effect for Compressor {
     fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
-        let (env_l, env_r) = self.envelope_filter.process_stereo(in_l, in_r);
-
-        let gain_l = db_to_level(self.gain_function(level_to_db(env_l)));
-        let gain_r = db_to_level(self.gain_function(level_to_db(env_r)));
-
-        (gain_l * in_l, gain_r * in_r)
+        self.process_stereo_with_key(in_l, in_r, in_l, in_r)
     }
 
     fn get_sample_rate(&self) -> f64 {
@@ -0,0 +1,230 @@
+//! Module for true-peak brickwall limiting.
+
+use super::*;
+use std::f64::consts::PI;
+
+/// The lookahead time of the limiter in milliseconds.
+const LOOKAHEAD_MS: f64 = 1.5;
+/// The number of taps of each interpolation filter of the peak detector.
+const NUM_TAPS: usize = 8;
+/// The oversampling factor of the peak detector.
+const NUM_PHASES: usize = 4;
+/// The delay of the peak detector in samples.
+const DETECTOR_DELAY: usize = NUM_TAPS / 2 - 1;
+
+/// Estimates the true (inter-sample) peak level of a signal by 4x
+/// oversampling it with windowed-sinc interpolation filters, as in ITU-R
+/// BS.1770.
+#[derive(Clone, Debug)]
+struct TruePeakDetector {
+    /// The most recent input samples of each channel, oldest first.
+    history: [[f64; NUM_TAPS]; NUM_CHANNELS],
+    /// The interpolation filter of each intermediate phase.
+    filters: [[f64; NUM_TAPS]; NUM_PHASES - 1],
+}
+
+impl TruePeakDetector {
+    fn new() -> Self {
+        let sinc = |x: f64| {
+            if x.abs() < 1e-12 { 1.0 } else { (PI * x).sin() / (PI * x) }
+        };
+        let half_width = NUM_TAPS as f64 / 2.0;
+        let hann = |x: f64| 0.5f64.mul_add((PI * x / half_width).cos(), 0.5);
+
+        let filters = std::array::from_fn(|phase| {
+            // the position between the two central taps
+            let pos = DETECTOR_DELAY as f64
+                + (phase + 1) as f64 / NUM_PHASES as f64;
+
+            let mut filter: [f64; NUM_TAPS] = std::array::from_fn(|i| {
+                let x = pos - i as f64;
+                sinc(x) * hann(x)
+            });
+
+            // normalise to unity gain at DC
+            let sum: f64 = filter.iter().sum();
+            filter.iter_mut().for_each(|c| *c /= sum);
+            filter
+        });
+
+        Self { history: [[0.0; NUM_TAPS]; NUM_CHANNELS], filters }
+    }
+
+    /// Returns the peak level of both channels between the samples
+    /// [`DETECTOR_DELAY`] and `DETECTOR_DELAY + 1` samples ago.
+    fn process(&mut self, in_l: f64, in_r: f64) -> f64 {
+        let mut peak: f64 = 0.0;
+
+        for (history, sample) in self.history.iter_mut().zip([in_l, in_r]) {
+            history.copy_within(1.., 0);
+            history[NUM_TAPS - 1] = sample;
+
+            peak = peak.max(history[DETECTOR_DELAY + 1].abs());
+
+            for filter in &self.filters {
+                let interp: f64 =
+                    filter.iter().zip(history.iter()).map(|(c, x)| c * x).sum();
+                peak = peak.max(interp.abs());
+            }
+        }
+
+        peak
+    }
+
+    fn reset(&mut self) {
+        self.history = [[0.0; NUM_TAPS]; NUM_CHANNELS];
+    }
+}
+
+/// A stereo true-peak brickwall limiter.
+///
+/// The input is delayed by a short lookahead so that the gain reduction is
+/// fully applied by the time a peak reaches the output. Gain reduction is
+/// always linked between channels, and the output is hard clipped to the
+/// ceiling as a last resort.
+#[derive(Clone, Debug)]
+pub struct Limiter {
+    detector: TruePeakDetector,
+    delay_buffers: [RingBuffer; NUM_CHANNELS],
+
+    /// The required gain of recent samples, used to hold the minimum gain
+    /// over the lookahead window.
+    hold_history: Vec<f64>,
+    hold_pos: usize,
+    /// The held gains which are averaged to smooth the attack.
+    attack_history: Vec<f64>,
+    attack_pos: usize,
+    attack_sum: f64,
+    lookahead_samples: usize,
+
+    gain: f64,
+    ceiling: f64,
+    release_coeff: f64,
+
+    sample_rate: f64,
+}
+
+impl Limiter {
+    /// The shortest release time in milliseconds.
+    pub const MIN_RELEASE_MS: f64 = 1.0;
+    /// The longest release time in milliseconds.
+    pub const MAX_RELEASE_MS: f64 = 1000.0;
+
+    pub fn new(sample_rate: f64) -> Self {
+        let lookahead_samples =
+            ((LOOKAHEAD_MS * 0.001 * sample_rate).round() as usize).max(1);
+
+        let delay = lookahead_samples + DETECTOR_DELAY;
+        let delay_buffer = RingBuffer::new(delay + 2, sample_rate)
+            .with_interpolation(InterpType::NoInterp);
+
+        let mut limiter = Self {
+            detector: TruePeakDetector::new(),
+            delay_buffers: [delay_buffer.clone(), delay_buffer],
+
+            // the hold window also covers the neighbours of each peak
+            hold_history: vec![1.0; lookahead_samples + 2],
+            hold_pos: 0,
+            attack_history: vec![1.0; lookahead_samples],
+            attack_pos: 0,
+            attack_sum: lookahead_samples as f64,
+            lookahead_samples,
+
+            gain: 1.0,
+            ceiling: 1.0,
+            release_coeff: 0.0,
+
+            sample_rate,
+        };
+
+        limiter.set_release_time_ms(50.0);
+        limiter
+    }
+
+    /// Sets the ceiling of the limiter in decibels true peak. This is limited
+    /// to `0.0`.
+    pub fn set_ceiling_db(&mut self, ceiling_db: f64) {
+        self.ceiling = db_to_level(ceiling_db.min(0.0));
+    }
+
+    /// Sets the time for the gain reduction to recover in milliseconds.
+    pub fn set_release_time_ms(&mut self, time_ms: f64) {
+        let time_ms = time_ms.clamp(Self::MIN_RELEASE_MS, Self::MAX_RELEASE_MS);
+        let time_samples = time_ms * 0.001 * self.sample_rate;
+        self.release_coeff = (-1.0 / time_samples).exp();
+    }
+
+    /// Returns the latency of the limiter in samples.
+    pub fn latency_samples(&self) -> usize {
+        self.lookahead_samples + DETECTOR_DELAY
+    }
+
+    /// Clears the state of the limiter.
+    pub fn reset(&mut self) {
+        self.detector.reset();
+        self.delay_buffers.iter_mut().for_each(RingBuffer::clear);
+
+        self.hold_history.iter_mut().for_each(|g| *g = 1.0);
+        self.attack_history.iter_mut().for_each(|g| *g = 1.0);
+        self.attack_sum = self.lookahead_samples as f64;
+        self.gain = 1.0;
+    }
+
+    /// Computes the gain to apply to the delayed signal from the true peak
+    /// level of the input.
+    fn compute_gain(&mut self, peak: f64) -> f64 {
+        let required =
+            if peak > self.ceiling { self.ceiling / peak } else { 1.0 };
+
+        self.hold_history[self.hold_pos] = required;
+        self.hold_pos = (self.hold_pos + 1) % self.hold_history.len();
+        let held = self.hold_history.iter().fold(1.0f64, |a, &b| a.min(b));
+
+        // a moving average of the held gain ramps the gain down over the
+        // lookahead window, so it reaches the held gain as the peak arrives
+        self.attack_sum += held - self.attack_history[self.attack_pos];
+        self.attack_history[self.attack_pos] = held;
+        self.attack_pos = (self.attack_pos + 1) % self.attack_history.len();
+
+        let attack_len = self.attack_history.len() as f64;
+        let target = (self.attack_sum / attack_len).min(1.0);
+
+        self.gain = if target < self.gain {
+            target
+        }
+        else {
+            target + (self.gain - target) * self.release_coeff
+        };
+
+        self.gain
+    }
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+impl Effect for Limiter {
+    fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let peak = self.detector.process(in_l, in_r);
+        let gain = self.compute_gain(peak);
+
+        let delay = self.latency_samples() as f64;
+        let mut output = [in_l, in_r];
+
+        for (sample, buffer) in output.iter_mut().zip(&mut self.delay_buffers) {
+            let delayed = buffer.read_at(delay);
+            buffer.push(*sample);
+
+            *sample = (delayed * gain).clamp(-self.ceiling, self.ceiling);
+        }
+
+        (output[0], output[1])
+    }
+
+    fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+}
@@ -7,5 +7,150 @@ use super::*;
 
 pub mod adsr;
 pub mod compressor;
+pub mod limiter;
+pub mod safety;
 
 pub use compressor::Compressor;
+pub use limiter::Limiter;
+pub use safety::SafetyMute;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    fn compressor() -> Compressor {
+        let mut comp = Compressor::new(SAMPLE_RATE);
+        comp.set_threshold_level_db(-20.0);
+        comp.set_ratio(4.0);
+        comp.set_attack_time_ms(1.0);
+        comp.set_release_time_ms(50.0);
+        comp
+    }
+
+    /// Keys the compressor with a 1 kHz sine in the left channel and silence
+    /// in the right, and returns the final gain of each channel.
+    fn left_keyed_gains(comp: &mut Compressor) -> (f64, f64) {
+        let mut out = (0.0, 0.0);
+
+        for i in 0..4410 {
+            let x = (TAU * 1000.0 * i as f64 / SAMPLE_RATE).sin() * 0.5;
+            out = comp.process_stereo_with_key(1.0, 1.0, x, 0.0);
+        }
+
+        out
+    }
+
+    #[test]
+    fn compressor_stereo_link() {
+        let mut unlinked = compressor();
+        let (gain_l, gain_r) = left_keyed_gains(&mut unlinked);
+        assert!(gain_l < 0.5);
+        assert!((gain_r - 1.0).abs() < 1e-9);
+
+        let mut linked = compressor();
+        linked.set_stereo_link(1.0);
+        let (gain_l, gain_r) = left_keyed_gains(&mut linked);
+        assert!(gain_l < 0.5);
+        assert!((gain_l - gain_r).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compressor_auto_makeup() {
+        let mut comp = compressor();
+        comp.use_auto_makeup(true);
+
+        // -20 dB threshold at 4:1 reduces 0 dB by 15 dB
+        assert!((comp.makeup_gain_db() - 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compressor_lookahead_delays_input() {
+        let mut comp = Compressor::new(SAMPLE_RATE);
+        comp.set_lookahead_ms(1.0);
+
+        let output: Vec<f64> = (0..100)
+            .map(|i| {
+                let x = if i == 0 { 1.0 } else { 0.0 };
+                comp.process_stereo_with_key(x, x, 0.0, 0.0).0
+            })
+            .collect();
+
+        let peak = output
+            .iter()
+            .position(|&x| (x - 1.0).abs() < 1e-9)
+            .unwrap();
+        assert_eq!(peak, 44);
+    }
+
+    #[test]
+    fn limiter_catches_inter_sample_peaks() {
+        let mut limiter = Limiter::new(SAMPLE_RATE);
+
+        // a quarter-rate sine sampled 45 degrees off its peaks, so the true
+        // peak of 2.0 lies between samples of about 1.41
+        let output: Vec<f64> = (0..4410)
+            .map(|i| {
+                let x = 2.0 * (TAU * (i as f64 / 4.0 + 0.125)).sin();
+                limiter.process_stereo(x, x).0
+            })
+            .collect();
+
+        let peak = output.iter().fold(0.0f64, |a, x| a.max(x.abs()));
+        assert!(peak < 0.72);
+        assert!(peak > 0.6);
+    }
+
+    #[test]
+    fn limiter_reduces_gain_before_bursts() {
+        let mut limiter = Limiter::new(SAMPLE_RATE);
+        limiter.set_ceiling_db(-6.0);
+        let ceiling = db_to_level(-6.0);
+        let mut num_at_ceiling = 0;
+
+        for i in 0..44100 {
+            // a sine with sudden bursts
+            let burst = if i % 4410 < 100 { 20.0 } else { 1.0 };
+            let x = burst * (TAU * 440.0 * i as f64 / SAMPLE_RATE).sin();
+            let (out_l, out_r) = limiter.process_stereo(x, -x);
+
+            assert!(out_l.abs() <= ceiling && out_r.abs() <= ceiling);
+
+            if out_l.abs() > ceiling * 0.999 {
+                num_at_ceiling += 1;
+            }
+        }
+
+        // the gain is reduced before each burst arrives, so only the crests
+        // of the bursts reach the ceiling rather than being clipped
+        assert!(num_at_ceiling > 0);
+        assert!(num_at_ceiling < 40);
+    }
+
+    #[test]
+    fn safety_mute_trips_and_recovers() {
+        let mut safety = SafetyMute::new(SAMPLE_RATE);
+
+        assert_eq!(safety.process_stereo(0.5, 0.5), (0.5, 0.5));
+        assert_eq!(safety.process_stereo(f64::NAN, 0.5), (0.0, 0.0));
+        assert!(safety.is_muted());
+
+        // still muted within the hold time
+        for _ in 0..22050 {
+            assert_eq!(safety.process_stereo(0.5, 0.5), (0.0, 0.0));
+        }
+
+        // then fades back in
+        let output: Vec<f64> =
+            (0..44100).map(|_| safety.process_stereo(0.5, 0.5).0).collect();
+        assert!(!safety.is_muted());
+        assert!((output[44099] - 0.5).abs() < 1e-12);
+
+        // sustained overs also mute the output
+        for _ in 0..22050 {
+            safety.process_stereo(100.0, 100.0);
+        }
+        assert!(safety.is_muted());
+    }
+}
@@ -0,0 +1,133 @@
+//! Module for the output safety mute.
+
+use super::*;
+
+/// The level above which the input is considered to be "over", in decibels.
+const OVER_LEVEL_DB: f64 = 24.0;
+/// How long the input may be over before the output is muted, in seconds.
+const MAX_OVER_SECS: f64 = 0.25;
+/// How long the input must be safe before the output is unmuted, in seconds.
+const HOLD_SECS: f64 = 1.0;
+/// The fade-in time after the output is unmuted, in seconds.
+const FADE_IN_SECS: f64 = 0.5;
+/// The release time of the level detector in milliseconds.
+const DETECTOR_RELEASE_MS: f64 = 20.0;
+
+/// An emergency mute, which silences its output as soon as its input is
+/// `NaN` or infinite, or has been far over full scale for a sustained period
+/// (e.g. from runaway feedback).
+///
+/// Once muted, the output stays silent until the input has been safe for a
+/// second, after which it fades back in. Any processors feeding the mute
+/// should be cleared when it trips (see
+/// [`take_tripped()`](Self::take_tripped)), as non-finite samples in their
+/// state would otherwise keep it muted.
+#[derive(Clone, Debug)]
+pub struct SafetyMute {
+    envelope: f64,
+    release_coeff: f64,
+    over_level: f64,
+
+    over_samples: u32,
+    max_over_samples: u32,
+
+    is_muted: bool,
+    /// Whether the mute has tripped since it was last checked.
+    has_tripped: bool,
+    safe_samples: u32,
+    hold_samples: u32,
+
+    gain: f64,
+    fade_increment: f64,
+
+    sample_rate: f64,
+}
+
+impl SafetyMute {
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            envelope: 0.0,
+            release_coeff: (-1.0 / (DETECTOR_RELEASE_MS * 0.001 * sample_rate))
+                .exp(),
+            over_level: db_to_level(OVER_LEVEL_DB),
+
+            over_samples: 0,
+            max_over_samples: (MAX_OVER_SECS * sample_rate) as u32,
+
+            is_muted: false,
+            has_tripped: false,
+            safe_samples: 0,
+            hold_samples: (HOLD_SECS * sample_rate) as u32,
+
+            gain: 1.0,
+            fade_increment: (FADE_IN_SECS * sample_rate).recip(),
+
+            sample_rate,
+        }
+    }
+
+    /// Returns whether the output is currently muted.
+    pub fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+
+    /// Returns whether the mute has tripped since this was last called, so
+    /// that the state of any processors feeding it can be cleared.
+    pub fn take_tripped(&mut self) -> bool {
+        std::mem::take(&mut self.has_tripped)
+    }
+
+    /// Mutes the output immediately.
+    fn trip(&mut self) {
+        self.is_muted = true;
+        self.has_tripped = true;
+        self.envelope = 0.0;
+        self.over_samples = 0;
+        self.safe_samples = 0;
+        self.gain = 0.0;
+    }
+}
+
+impl Default for SafetyMute {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+impl Effect for SafetyMute {
+    fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        if !(in_l.is_finite() && in_r.is_finite()) {
+            self.trip();
+            return (0.0, 0.0);
+        }
+
+        let level = in_l.abs().max(in_r.abs());
+        self.envelope = level.max(self.envelope * self.release_coeff);
+        let is_over = self.envelope > self.over_level;
+
+        if self.is_muted {
+            self.safe_samples = if is_over { 0 } else { self.safe_samples + 1 };
+
+            if self.safe_samples < self.hold_samples {
+                return (0.0, 0.0);
+            }
+
+            self.is_muted = false;
+        }
+
+        self.over_samples = if is_over { self.over_samples + 1 } else { 0 };
+
+        if self.over_samples > self.max_over_samples {
+            self.trip();
+            return (0.0, 0.0);
+        }
+
+        self.gain = (self.gain + self.fade_increment).min(1.0);
+
+        (in_l * self.gain, in_r * self.gain)
+    }
+
+    fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+}
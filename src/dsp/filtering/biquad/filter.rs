@@ -101,23 +101,9 @@ impl Filter for BiquadFilter {
     /// is computed — not the filter coefficients. In other words, this method
     /// will compute much faster if there is no parameter change between calls.
     fn process(&mut self, sample: f64) -> f64 {
+        self.update_coefs();
         let Coefs { a0, a1, a2, b1, b2, b0 } = self.coefs;
 
-        if self.needs_recompute {
-            match self.params.filter_type {
-                FT::Peak => self.set_peak_coefs(),
-                FT::Lowpass => self.set_lowpass_coefs(),
-                FT::Highpass => self.set_highpass_coefs(),
-                FT::Lowshelf => self.set_lowshelf_coefs(),
-                FT::Highshelf => self.set_highshelf_coefs(),
-                FT::Bandpass => self.set_bandpass_coefs(),
-                FT::Notch => self.set_notch_coefs(),
-                FT::Allpass => self.set_allpass_coefs(),
-            };
-
-            self.needs_recompute = false;
-        }
-
         let bottom_sum = self.delayed_in.1 * b2 + self.delayed_out.1 * -a2;
         let middle_sum = self.delayed_in.0 * b1 + self.delayed_out.0 * -a1;
         let output = bottom_sum + middle_sum + (sample * b0);
@@ -166,6 +152,12 @@ impl BiquadFilter {
         self.needs_recompute = false;
     }
 
+    /// Clears the filter's delayed samples, without altering its parameters.
+    pub fn reset(&mut self) {
+        self.delayed_in = (0.0, 0.0);
+        self.delayed_out = (0.0, 0.0);
+    }
+
     /// Resets the sample rate of the filter.
     ///
     /// # Safety
@@ -187,6 +179,28 @@ impl BiquadFilter {
         self.needs_recompute = true;
     }
 
+    /// Recomputes the filter's coefficients if any of its parameters have
+    /// changed. This is called by `process()`, but may be used to keep
+    /// `response_at()` up to date for a filter which isn't processing audio.
+    pub fn update_coefs(&mut self) {
+        if !self.needs_recompute {
+            return;
+        }
+
+        match self.params.filter_type {
+            FT::Peak => self.set_peak_coefs(),
+            FT::Lowpass => self.set_lowpass_coefs(),
+            FT::Highpass => self.set_highpass_coefs(),
+            FT::Lowshelf => self.set_lowshelf_coefs(),
+            FT::Highshelf => self.set_highshelf_coefs(),
+            FT::Bandpass => self.set_bandpass_coefs(),
+            FT::Notch => self.set_notch_coefs(),
+            FT::Allpass => self.set_allpass_coefs(),
+        };
+
+        self.needs_recompute = false;
+    }
+
     /// Sets the parameters of the filter all at once.
     ///
     /// # Panics
@@ -419,51 +433,51 @@ impl BiquadFilter {
         *a2 = ((amp + 1.0) - (amp - 1.0) * cos_phi - ra_2a) / *a0;
     }
 
-    /// Sets the filter coefficients for a bandpass filter.
+    /// Sets the filter coefficients for a bandpass filter, with a peak gain
+    /// of 0 dB.
     fn set_bandpass_coefs(&mut self) {
-        self.bandpass_notch_b_coefs();
-        let Coefs { a0, a1, a2, b1, b2, .. } = &mut self.coefs;
+        let alpha = self.bandpass_notch_allpass_a_coefs();
+        let Coefs { a0, b0, b1, b2, .. } = &mut self.coefs;
 
-        *a0 = (1.0 - *b2) / 2.0;
-        *a1 = 0.0;
-        *a2 = -(*a0);
+        *b0 = alpha / *a0;
+        *b1 = 0.0;
+        *b2 = -*b0;
     }
 
     /// Sets the filter coefficients for a notch filter.
     fn set_notch_coefs(&mut self) {
-        self.bandpass_notch_b_coefs();
-        let Coefs { a0, a1, a2, b1, b2, .. } = &mut self.coefs;
+        self.bandpass_notch_allpass_a_coefs();
+        let Coefs { a0, a1, b0, b1, b2, .. } = &mut self.coefs;
 
-        *a0 = (1.0 + *b2) / 2.0;
-        *a1 = *b1;
-        *a2 = *a0;
+        *b0 = a0.recip();
+        *b1 = *a1;
+        *b2 = *b0;
     }
 
-    /// Sets common coefficients for notch and bandpass designs.
-    fn bandpass_notch_b_coefs(&mut self) {
-        let Coefs { a0, a1, a2, b1, b2, .. } = &mut self.coefs;
-        let BiquadParams { freq, q, .. } = self.params;
-        let sr = &self.sample_rate;
-
-        let phi = (TAU * freq) / sr;
+    /// Sets the filter coefficients for a allpass filter.
+    fn set_allpass_coefs(&mut self) {
+        self.bandpass_notch_allpass_a_coefs();
+        let Coefs { a0, a1, a2, b0, b1, b2 } = &mut self.coefs;
 
-        *b2 = (PI / 4.0) - (phi / (2.0 * q)).tan();
-        *b1 = -(1.0 + *b2) * phi.cos();
+        *b0 = *a2;
+        *b1 = *a1;
+        *b2 = 1.0;
     }
 
-    /// Sets the filter coefficients for a allpass filter.
-    fn set_allpass_coefs(&mut self) {
-        let Coefs { a0, a1, a2, b1, b2, .. } = &mut self.coefs;
-        let BiquadParams { freq, q, .. } = self.params;
-        let sr = &self.sample_rate;
+    /// Sets the common output side coefficients for bandpass, notch and
+    /// allpass designs, and returns the value of "alpha".
+    ///
+    /// `a0` is left unnormalised for the input side coefficients to use.
+    fn bandpass_notch_allpass_a_coefs(&mut self) -> f64 {
+        let phi = self.get_phi();
+        let alpha = self.get_alpha(phi);
+        let Coefs { a0, a1, a2, .. } = &mut self.coefs;
 
-        let phi = (TAU * freq) / sr;
+        *a0 = 1.0 + alpha;
+        *a1 = (-2.0 * phi.cos()) / *a0;
+        *a2 = (1.0 - alpha) / *a0;
 
-        *b2 = (phi / 2.0).mul_add(-q, PI / 4.0);
-        *b1 = -(1.0 + *b2) * phi.cos();
-        *a0 = *b2;
-        *a1 = *b1;
-        *a2 = 1.0;
+        alpha
     }
 
     /// Convenience method for obtaining the value of "phi".
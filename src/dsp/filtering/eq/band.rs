@@ -0,0 +1,344 @@
+//! A single band of the parametric EQ.
+
+use super::*;
+
+/// The maximum number of cascaded biquad filters in a band.
+pub const MAX_STAGES: usize = 4;
+
+/// The attack time of the envelope of dynamic bands in milliseconds.
+const DYNAMIC_ATTACK_MS: f64 = 5.0;
+/// The release time of the envelope of dynamic bands in milliseconds.
+const DYNAMIC_RELEASE_MS: f64 = 80.0;
+/// How far the envelope of a dynamic band must exceed its threshold, in
+/// decibels, for the band to apply its full gain.
+pub const DYNAMIC_RANGE_DB: f64 = 12.0;
+
+/// A stereo band of a [`ParametricEQ`](super::ParametricEQ), which cascades
+/// up to [`MAX_STAGES`] biquad filters per channel to steepen its slope.
+///
+/// Lowpass and highpass bands are designed as Butterworth cascades, where
+/// the band's Q scales the resonance of the final stage. Peak and shelving
+/// bands divide their gain between each stage, and the other filter types
+/// cascade identical stages.
+///
+/// Peak and shelving bands may also be dynamic, in which case their gain
+/// follows the envelope of the signal around the band's frequency once it
+/// exceeds a threshold. The envelope is only followed by
+/// [`process_stereo()`](Self::process_stereo) and
+/// [`update_envelope()`](Self::update_envelope).
+///
+/// Parameter changes are applied to the filters at most once per sample,
+/// when the band is next processed (see
+/// [`update_coefs()`](Self::update_coefs)).
+#[derive(Debug, Clone)]
+pub struct EQBand {
+    stages: [[BiquadFilter; MAX_STAGES]; NUM_CHANNELS],
+    /// Whether the stages need to be recomputed.
+    needs_update: bool,
+
+    enabled: bool,
+    filter_type: FilterType,
+    slope: FilterSlope,
+    freq: f64,
+    gain_db: f64,
+    q: f64,
+
+    dynamic: bool,
+    threshold_db: f64,
+    /// Bandpass filters which isolate the signal around the band's frequency.
+    detectors: [BiquadFilter; NUM_CHANNELS],
+    envelope: BallisticsFilter,
+    /// The gain applied by the band, which differs from `gain_db` for
+    /// dynamic bands.
+    applied_gain_db: f64,
+
+    sample_rate: f64,
+}
+
+impl EQBand {
+    /// Returns a new, enabled 12 dB/octave peak band at 1 kHz.
+    pub fn new(sample_rate: f64) -> Self {
+        let filter = BiquadFilter::new(sample_rate);
+
+        let mut detector = BiquadFilter::new(sample_rate);
+        detector.set_type(FilterType::Bandpass);
+
+        let mut envelope = BallisticsFilter::new(NUM_CHANNELS, sample_rate);
+        envelope.set_attack_time_ms(DYNAMIC_ATTACK_MS);
+        envelope.set_release_time_ms(DYNAMIC_RELEASE_MS);
+
+        let mut band = Self {
+            stages: std::array::from_fn(|_| {
+                std::array::from_fn(|_| filter.clone())
+            }),
+            needs_update: false,
+
+            enabled: true,
+            filter_type: FilterType::Peak,
+            slope: FilterSlope::Db12,
+            freq: 1000.0,
+            gain_db: 0.0,
+            q: BUTTERWORTH_Q,
+
+            dynamic: false,
+            threshold_db: 0.0,
+            detectors: [detector.clone(), detector],
+            envelope,
+            applied_gain_db: 0.0,
+
+            sample_rate,
+        };
+
+        band.update_stages();
+        band.update_detectors();
+        band
+    }
+
+    /// Processes a single sample of channel `ch_idx`. Disabled bands return
+    /// the sample unaltered.
+    pub fn process(&mut self, mut sample: f64, ch_idx: usize) -> f64 {
+        if !self.enabled {
+            return sample;
+        }
+
+        self.update_coefs();
+        let num_stages = self.slope.num_stages();
+
+        for stage in &mut self.stages[ch_idx][..num_stages] {
+            sample = stage.process(sample);
+        }
+
+        sample
+    }
+
+    /// Processes a pair of stereo samples, and follows their envelope if the
+    /// band is dynamic.
+    pub fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        self.update_envelope(in_l, in_r);
+
+        (self.process(in_l, 0), self.process(in_r, 1))
+    }
+
+    /// Follows the envelope of a pair of stereo samples without processing
+    /// them, and updates the gain of the band if it is dynamic.
+    pub fn update_envelope(&mut self, in_l: f64, in_r: f64) {
+        if self.is_dynamic() {
+            let det_l = self.detectors[0].process(in_l);
+            let det_r = self.detectors[1].process(in_r);
+            let (env_l, env_r) = self.envelope.process_stereo(det_l, det_r);
+
+            let over_db = level_to_db(env_l.max(env_r)) - self.threshold_db;
+            let amount = (over_db / DYNAMIC_RANGE_DB).clamp(0.0, 1.0);
+            let gain_db = self.gain_db * amount;
+
+            // avoid recomputing the coefficients for inaudible changes
+            if (gain_db - self.applied_gain_db).abs() > 0.01 {
+                self.applied_gain_db = gain_db;
+                self.needs_update = true;
+            }
+        }
+
+        self.update_coefs();
+    }
+
+    /// Recomputes the band's filters if any of its parameters have changed.
+    /// This is called by [`process()`](Self::process) and
+    /// [`update_envelope()`](Self::update_envelope), but may be used to keep
+    /// [`response_at()`](Self::response_at) up to date for a band which
+    /// isn't processing audio.
+    pub fn update_coefs(&mut self) {
+        if self.needs_update {
+            self.update_stages();
+            self.needs_update = false;
+        }
+    }
+
+    /// Returns the magnitude response of the band at `freq_hz` in decibels.
+    /// Disabled bands have a flat response.
+    pub fn response_at(&self, freq_hz: f64) -> f64 {
+        if !self.enabled {
+            return 0.0;
+        }
+
+        let num_stages = self.slope.num_stages();
+        let phi = TAU * freq_hz / self.sample_rate;
+
+        self.stages[0][..num_stages]
+            .iter()
+            .map(|stage| stage.response_at(phi))
+            .sum()
+    }
+
+    /// Sets whether the band is enabled. The band's state is cleared when it
+    /// is re-enabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled && !self.enabled {
+            self.reset();
+        }
+
+        self.enabled = enabled;
+    }
+
+    /// Sets the filter type of the band.
+    pub fn set_type(&mut self, filter_type: FilterType) {
+        if self.filter_type != filter_type {
+            self.filter_type = filter_type;
+            self.needs_update = true;
+        }
+    }
+
+    /// Sets the slope of the band. The band's state is cleared if the slope
+    /// changes.
+    pub fn set_slope(&mut self, slope: FilterSlope) {
+        if self.slope != slope {
+            self.slope = slope;
+            self.needs_update = true;
+            self.reset();
+        }
+    }
+
+    /// Sets the frequency of the band in hertz.
+    pub fn set_freq(&mut self, freq_hz: f64) {
+        self.freq = freq_hz.clamp(10.0, self.sample_rate * 0.49);
+        self.needs_update = true;
+        self.update_detectors();
+    }
+
+    /// Sets the gain of the band in decibels. Only used by peak and shelving
+    /// bands. For dynamic bands, this is the gain applied once the envelope
+    /// is [`DYNAMIC_RANGE_DB`] above the threshold.
+    pub fn set_gain_db(&mut self, gain_db: f64) {
+        self.gain_db = gain_db;
+
+        if !self.dynamic {
+            self.applied_gain_db = gain_db;
+            self.needs_update = true;
+        }
+    }
+
+    /// Sets the Q of the band.
+    pub fn set_q(&mut self, q: f64) {
+        self.q = q.max(0.01);
+        self.needs_update = true;
+        self.update_detectors();
+    }
+
+    /// Sets whether the gain of the band follows its envelope. The envelope
+    /// is cleared when the band becomes dynamic.
+    pub fn set_dynamic(&mut self, dynamic: bool) {
+        if self.dynamic == dynamic {
+            return;
+        }
+
+        self.dynamic = dynamic;
+        self.envelope.reset(0.0);
+        self.detectors.iter_mut().for_each(BiquadFilter::reset);
+
+        self.applied_gain_db = if dynamic { 0.0 } else { self.gain_db };
+        self.needs_update = true;
+    }
+
+    /// Sets the level above which a dynamic band starts to apply its gain,
+    /// in decibels.
+    pub fn set_threshold_db(&mut self, threshold_db: f64) {
+        self.threshold_db = threshold_db;
+    }
+
+    /// Returns whether the band is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the filter type of the band.
+    pub fn filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    /// Returns the frequency of the band in hertz.
+    pub fn freq(&self) -> f64 {
+        self.freq
+    }
+
+    /// Returns the gain of the band in decibels.
+    pub fn gain_db(&self) -> f64 {
+        self.gain_db
+    }
+
+    /// Returns the gain currently applied by the band in decibels, which
+    /// follows the envelope of dynamic bands.
+    pub fn applied_gain_db(&self) -> f64 {
+        self.applied_gain_db
+    }
+
+    /// Returns whether the gain of the band follows its envelope. Only
+    /// enabled peak and shelving bands may be dynamic.
+    pub fn is_dynamic(&self) -> bool {
+        self.enabled && self.dynamic && self.filter_type.uses_gain()
+    }
+
+    /// Clears the state of every filter in the band.
+    pub fn reset(&mut self) {
+        for stage in self.stages.iter_mut().flatten() {
+            stage.reset();
+        }
+
+        self.detectors.iter_mut().for_each(BiquadFilter::reset);
+        self.envelope.reset(0.0);
+    }
+
+    /// Recomputes the parameters of each active stage.
+    fn update_stages(&mut self) {
+        let num_stages = self.slope.num_stages();
+
+        for stage in 0..num_stages {
+            let params = BiquadParams {
+                freq: self.freq,
+                gain: self.applied_gain_db / num_stages as f64,
+                // the biquad filter takes the reciprocal of Q
+                q: self.stage_q(stage, num_stages).recip(),
+                filter_type: self.filter_type,
+            };
+
+            for filters in &mut self.stages {
+                filters[stage].set_params(&params);
+                filters[stage].update_coefs();
+            }
+        }
+    }
+
+    /// Tunes the envelope detectors to the band's frequency and Q.
+    fn update_detectors(&mut self) {
+        for detector in &mut self.detectors {
+            detector.set_freq(self.freq);
+            // the biquad filter takes the reciprocal of Q
+            detector.set_q(self.q.recip());
+        }
+    }
+
+    /// Returns the Q of `stage` in a cascade of `num_stages` filters.
+    fn stage_q(&self, stage: usize, num_stages: usize) -> f64 {
+        use FilterType as FT;
+
+        if !matches!(self.filter_type, FT::Lowpass | FT::Highpass) {
+            return self.q;
+        }
+
+        // the Butterworth pole pairs, from least to most resonant
+        let order = (num_stages * 2) as f64;
+        let theta = (2 * stage + 1) as f64 * PI / (2.0 * order);
+        let q = 0.5 / theta.cos();
+
+        if stage == num_stages - 1 {
+            q * self.q / BUTTERWORTH_Q
+        }
+        else {
+            q
+        }
+    }
+}
+
+impl Default for EQBand {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
@@ -0,0 +1,168 @@
+//! Linear-phase processing for the parametric EQ.
+
+use super::*;
+use crate::dsp::spectral::stft::stft_trait::StftInputMut;
+use crate::dsp::StftHelper;
+use crate::util::window::hann;
+use realfft::{
+    num_complex::Complex, ComplexToReal, RealFftPlanner, RealToComplex,
+};
+use std::sync::Arc;
+
+/// The size of the FFT used for convolution.
+const FFT_SIZE: usize = 1 << 12;
+/// The length of the FIR kernel. The first tap is always zero, so the
+/// kernel is symmetric around its centre.
+const KERNEL_LEN: usize = 1 << 10;
+/// The number of new samples convolved per block.
+const BLOCK_SIZE: usize = FFT_SIZE - KERNEL_LEN;
+
+/// A linear-phase counterpart to the [`ParametricEQ`], which applies the
+/// magnitude response of the EQ as a symmetric FIR filter.
+///
+/// The filter is applied via overlap-add convolution, and its kernel is
+/// redesigned from the response of the EQ once per block, so dynamic bands
+/// are only updated at that rate.
+pub struct LinearPhaseEQ {
+    /// stft processor
+    stft: StftHelper,
+
+    /// forward fft plan
+    fft: Arc<dyn RealToComplex<f64>>,
+    /// inverse fft plan
+    ifft: Arc<dyn ComplexToReal<f64>>,
+
+    /// The frequency response of the FIR kernel.
+    kernel_spectrum: Vec<Complex<f64>>,
+    /// The FIR kernel, which is also used as scratch space when it is
+    /// designed.
+    kernel: Vec<f64>,
+    /// The window applied to the kernel.
+    kernel_window: Vec<f64>,
+
+    /// frequency domain buffer
+    complex_buffer: Vec<Complex<f64>>,
+
+    sample_rate: f64,
+}
+
+impl LinearPhaseEQ {
+    pub fn new(sample_rate: f64) -> Self {
+        let num_bins = FFT_SIZE / 2 + 1;
+
+        Self {
+            stft: StftHelper::new(NUM_CHANNELS, BLOCK_SIZE, KERNEL_LEN),
+
+            fft: RealFftPlanner::new().plan_fft_forward(FFT_SIZE),
+            ifft: RealFftPlanner::new().plan_fft_inverse(FFT_SIZE),
+
+            kernel_spectrum: vec![Complex::default(); num_bins],
+            kernel: vec![0.0; FFT_SIZE],
+            kernel_window: hann(KERNEL_LEN),
+
+            complex_buffer: vec![Complex::default(); num_bins],
+
+            sample_rate,
+        }
+    }
+
+    /// Processes a block of audio through the magnitude response of `eq`.
+    /// This introduces [`latency_samples()`](Self::latency_samples) of
+    /// latency.
+    #[allow(clippy::missing_panics_doc)] // this function will not panic.
+    pub fn process_block<B>(&mut self, buffer: &mut B, eq: &ParametricEQ)
+    where
+        B: StftInputMut + ?Sized,
+    {
+        let Self {
+            stft,
+            fft,
+            ifft,
+            kernel_spectrum,
+            kernel,
+            kernel_window,
+            complex_buffer,
+            sample_rate,
+        } = self;
+
+        stft.process_overlap_add(buffer, 1, |ch_idx, audio_block| {
+            // both channels share the same kernel
+            if ch_idx == 0 {
+                design_kernel(
+                    eq,
+                    *sample_rate,
+                    (fft.as_ref(), ifft.as_ref()),
+                    kernel,
+                    kernel_window,
+                    complex_buffer,
+                    kernel_spectrum,
+                );
+            }
+
+            // to freq domain
+            fft.process(audio_block, complex_buffer).unwrap();
+
+            // apply the kernel
+            for (bin, &k) in complex_buffer.iter_mut().zip(&*kernel_spectrum) {
+                *bin *= k;
+            }
+
+            // back to time domain
+            ifft.process(complex_buffer, audio_block).unwrap();
+        });
+    }
+
+    /// The latency of the processor in samples, which includes the delay of
+    /// the FIR kernel.
+    pub fn latency_samples(&self) -> u32 {
+        self.stft.latency_samples() + (KERNEL_LEN / 2) as u32
+    }
+
+    /// Clears the processor's internal buffers.
+    pub fn clear(&mut self) {
+        self.stft.clear();
+        self.complex_buffer.fill(Complex::default());
+    }
+}
+
+impl Default for LinearPhaseEQ {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+/// Designs a symmetric FIR kernel from the magnitude response of `eq` via
+/// frequency sampling, and stores its spectrum in `kernel_spectrum`.
+fn design_kernel(
+    eq: &ParametricEQ,
+    sample_rate: f64,
+    (fft, ifft): (&dyn RealToComplex<f64>, &dyn ComplexToReal<f64>),
+    kernel: &mut [f64],
+    kernel_window: &[f64],
+    complex_buffer: &mut [Complex<f64>],
+    kernel_spectrum: &mut [Complex<f64>],
+) {
+    let bin_width = sample_rate / FFT_SIZE as f64;
+
+    // sample the magnitude response with zero phase. NaN responses (from
+    // the log of tiny negative values) are flushed to zero by max()
+    for (k, bin) in complex_buffer.iter_mut().enumerate() {
+        let level = db_to_level(eq.response_at(k as f64 * bin_width));
+        *bin = Complex::new(level.max(0.0), 0.0);
+    }
+
+    ifft.process(complex_buffer, kernel).unwrap();
+
+    // the impulse is centred around the first sample, so it is rotated to
+    // the centre of the kernel and windowed. the scaling normalises both
+    // this inverse FFT and the one used for convolution
+    kernel.rotate_right(KERNEL_LEN / 2);
+    let scale = ((FFT_SIZE * FFT_SIZE) as f64).recip();
+
+    for (x, &w) in kernel.iter_mut().zip(kernel_window) {
+        *x *= w * scale;
+    }
+    kernel[KERNEL_LEN..].fill(0.0);
+
+    fft.process(kernel, kernel_spectrum).unwrap();
+}
@@ -0,0 +1,200 @@
+//! Parametric EQ built from cascaded biquad filters.
+
+use super::biquad::{BiquadFilter, BiquadParams};
+use super::simple::ballistics::BallisticsFilter;
+use crate::dsp::Effect;
+use super::*;
+use crate::app::FilterSlope;
+use crate::prelude::*;
+
+pub mod band;
+pub mod linear_phase;
+pub mod parametric;
+
+pub use band::EQBand;
+pub use linear_phase::LinearPhaseEQ;
+pub use parametric::ParametricEQ;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    /// Returns the RMS level of a sine at `freq_hz` after passing through
+    /// `eq`, measured after it has settled.
+    fn sine_level(eq: &mut ParametricEQ, freq_hz: f64) -> f64 {
+        let mut sum = 0.0;
+
+        for i in 0..44100 {
+            let x = (TAU * freq_hz * i as f64 / SAMPLE_RATE).sin();
+            let y = eq.process(x, 0);
+
+            if i >= 22050 {
+                sum += y * y;
+            }
+        }
+
+        (sum / 22050.0).sqrt()
+    }
+
+    fn single_band(
+        filter_type: FilterType,
+        slope: FilterSlope,
+    ) -> ParametricEQ {
+        let mut eq = ParametricEQ::new(1, SAMPLE_RATE);
+        let band = eq.band_mut(0);
+        band.set_type(filter_type);
+        band.set_slope(slope);
+        band.set_freq(1000.0);
+        eq.update_coefs();
+
+        eq
+    }
+
+    #[test]
+    fn cut_slopes_are_butterworth() {
+        for (slope, order) in [
+            (FilterSlope::Db12, 2),
+            (FilterSlope::Db24, 4),
+            (FilterSlope::Db48, 8),
+        ] {
+            let eq = single_band(FilterType::Highpass, slope);
+            let cutoff = eq.response_at(1000.0);
+            let octave_below = eq.response_at(500.0);
+            let expected = -10.0 * (1.0 + 2.0f64.powi(2 * order)).log10();
+
+            assert!((cutoff + 3.01).abs() < 0.1, "{slope}: {cutoff}");
+            assert!(
+                (octave_below - expected).abs() < 0.5,
+                "{slope}: {octave_below}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn gain_is_shared_between_stages() {
+        for slope in [FilterSlope::Db12, FilterSlope::Db24, FilterSlope::Db48] {
+            let mut eq = single_band(FilterType::Peak, slope);
+            eq.band_mut(0).set_gain_db(12.0);
+            eq.update_coefs();
+            let peak = eq.response_at(1000.0);
+
+            assert!((peak - 12.0).abs() < 1e-6, "{slope}: {peak}");
+        }
+    }
+
+    #[test]
+    fn notch_and_allpass() {
+        let mut notch = single_band(FilterType::Notch, FilterSlope::Db12);
+        let mut allpass = single_band(FilterType::Allpass, FilterSlope::Db48);
+
+        assert!(sine_level(&mut notch, 1000.0) < 1e-3);
+        assert!((sine_level(&mut notch, 100.0) - FRAC_1_SQRT_2).abs() < 0.01);
+
+        for freq in [100.0, 1000.0, 10000.0] {
+            let level = sine_level(&mut allpass, freq);
+            assert!((level - FRAC_1_SQRT_2).abs() < 1e-3, "{freq} Hz: {level}");
+        }
+    }
+
+    #[test]
+    fn dynamic_bands_follow_envelope() {
+        let mut eq = single_band(FilterType::Peak, FilterSlope::Db12);
+        let band = eq.band_mut(0);
+        band.set_gain_db(-12.0);
+        band.set_threshold_db(-30.0);
+        band.set_dynamic(true);
+
+        let mut run = |amp: f64| {
+            for i in 0..22050 {
+                let x = amp * (TAU * 1000.0 * i as f64 / SAMPLE_RATE).sin();
+                eq.process_stereo(x, x);
+            }
+
+            eq.band(0).applied_gain_db()
+        };
+
+        // below the threshold, the band is flat
+        assert!(run(db_to_level(-50.0)).abs() < 0.1);
+        // well above it, the full gain is applied
+        assert!((run(1.0) + 12.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn linear_phase_is_symmetric() {
+        let mut eq = single_band(FilterType::Peak, FilterSlope::Db24);
+        eq.band_mut(0).set_gain_db(9.0);
+        eq.update_coefs();
+
+        let mut lp_eq = LinearPhaseEQ::new(SAMPLE_RATE);
+        let latency = lp_eq.latency_samples() as usize;
+
+        let len = latency * 2 + 1;
+        let mut l = vec![0.0; len];
+        let mut r = vec![0.0; len];
+        l[0] = 1.0;
+        r[0] = 1.0;
+
+        let mut buffer: [&mut [f64]; 2] = [&mut l, &mut r];
+        lp_eq.process_block(&mut buffer[..], &eq);
+
+        // the impulse response is centred on the latency
+        let peak = l
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map(|(i, _)| i)
+            .unwrap();
+        assert_eq!(peak, latency);
+
+        for i in 1..512 {
+            let (before, after) = (l[latency - i], l[latency + i]);
+            assert!((before - after).abs() < 1e-9, "{i}: {before}, {after}");
+        }
+
+        // and it has the magnitude response of the eq
+        let ir = &l[latency - 512..latency + 512];
+
+        for freq in [100.0, 1000.0, 5000.0] {
+            let (mut re, mut im) = (0.0, 0.0);
+
+            for (n, &x) in ir.iter().enumerate() {
+                let phase = TAU * freq * n as f64 / SAMPLE_RATE;
+                re += x * phase.cos();
+                im -= x * phase.sin();
+            }
+
+            let level_db = level_to_db(re.hypot(im));
+            let expected = eq.response_at(freq);
+            assert!(
+                (level_db - expected).abs() < 0.25,
+                "{freq} Hz: {level_db}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn parameter_changes_are_deferred() {
+        let mut eq = single_band(FilterType::Peak, FilterSlope::Db12);
+        let band = eq.band_mut(0);
+        band.set_gain_db(6.0);
+        band.set_freq(2000.0);
+        band.set_q(2.0);
+
+        // the filters are only recomputed once the band is processed
+        assert!(eq.response_at(2000.0).abs() < 1e-12);
+        eq.process_stereo(0.0, 0.0);
+        assert!((eq.response_at(2000.0) - 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn disabled_bands_are_flat() {
+        let mut eq = single_band(FilterType::Lowpass, FilterSlope::Db48);
+        eq.band_mut(0).set_enabled(false);
+
+        assert!(eq.response_at(10000.0).abs() < 1e-12);
+        assert!((sine_level(&mut eq, 10000.0) - FRAC_1_SQRT_2).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,97 @@
+//! Multi-band parametric EQ.
+
+use super::*;
+
+/// A stereo parametric EQ with a fixed number of [`EQBand`]s, which are
+/// processed in series. Bands may be enabled and disabled freely, so the
+/// EQ never needs to allocate whilst processing.
+#[derive(Debug, Clone)]
+pub struct ParametricEQ {
+    bands: Vec<EQBand>,
+}
+
+impl ParametricEQ {
+    /// Returns a new EQ with `num_bands` default bands.
+    pub fn new(num_bands: usize, sample_rate: f64) -> Self {
+        Self { bands: vec![EQBand::new(sample_rate); num_bands] }
+    }
+
+    /// Returns the number of bands of the EQ, including disabled bands.
+    pub fn num_bands(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Returns a reference to the band at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of bands.
+    pub fn band(&self, index: usize) -> &EQBand {
+        &self.bands[index]
+    }
+
+    /// Returns a mutable reference to the band at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of bands.
+    pub fn band_mut(&mut self, index: usize) -> &mut EQBand {
+        &mut self.bands[index]
+    }
+
+    /// Returns an iterator over mutable references to each band.
+    pub fn bands_mut(&mut self) -> std::slice::IterMut<'_, EQBand> {
+        self.bands.iter_mut()
+    }
+
+    /// Processes a single sample of channel `ch_idx` through every enabled
+    /// band.
+    pub fn process(&mut self, sample: f64, ch_idx: usize) -> f64 {
+        self.bands
+            .iter_mut()
+            .fold(sample, |sample, band| band.process(sample, ch_idx))
+    }
+
+    /// Processes a pair of stereo samples through every enabled band. The
+    /// gain of dynamic bands follows the envelope of their input.
+    pub fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        self.bands
+            .iter_mut()
+            .fold((in_l, in_r), |(l, r), band| band.process_stereo(l, r))
+    }
+
+    /// Updates the gain of each dynamic band from a pair of stereo samples,
+    /// without processing them. Unlike
+    /// [`process_stereo()`](Self::process_stereo), every band follows the
+    /// envelope of the same input.
+    pub fn update_envelopes(&mut self, in_l: f64, in_r: f64) {
+        for band in &mut self.bands {
+            band.update_envelope(in_l, in_r);
+        }
+    }
+
+    /// Recomputes the filters of any bands whose parameters have changed, for
+    /// an EQ which isn't processing audio.
+    pub fn update_coefs(&mut self) {
+        self.bands.iter_mut().for_each(EQBand::update_coefs);
+    }
+
+    /// Returns the combined magnitude response of every enabled band at
+    /// `freq_hz` in decibels.
+    pub fn response_at(&self, freq_hz: f64) -> f64 {
+        self.bands.iter().map(|band| band.response_at(freq_hz)).sum()
+    }
+
+    /// Clears the state of every band.
+    pub fn reset(&mut self) {
+        for band in &mut self.bands {
+            band.reset();
+        }
+    }
+}
+
+impl Default for ParametricEQ {
+    fn default() -> Self {
+        Self::new(MAX_EQ_BANDS, unsafe { SAMPLE_RATE })
+    }
+}
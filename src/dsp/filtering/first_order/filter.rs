@@ -1,6 +1,6 @@
 use super::*;
 
-use std::f64::consts::TAU;
+use std::f64::consts::{PI, TAU};
 
 #[derive(Debug, Clone)]
 struct Coefs {
@@ -29,6 +29,8 @@ impl Default for Coefs {
 pub struct FirstOrderFilter {
     coefs: Coefs,
     z1: f64,
+    /// Whether the coefficients need to be recomputed.
+    needs_recompute: bool,
 
     freq: f64,
     filter_type: FilterType,
@@ -47,20 +49,12 @@ impl crate::dsp::Effect for FirstOrderFilter {
 
 impl Filter for FirstOrderFilter {
     fn process(&mut self, sample: f64) -> f64 {
-        let Coefs { a0, a1, b1 } = self.coefs;
-
-        match self.filter_type {
-            FilterType::Lowpass => self.set_lowpass_coefs(),
-            FilterType::Highpass => self.set_highpass_coefs(),
-            _ => {
-                self.identity();
-                dbg!(
-                    &self.filter_type,
-                    "only low/highpass filters are implemented for first order filters"
-                );
-            }
+        if self.needs_recompute {
+            self.update_coefs();
         }
 
+        let Coefs { a0, a1, b1 } = self.coefs;
+
         let output = a0.mul_add(sample, self.z1);
         self.z1 = a1.mul_add(sample, -b1 * output);
 
@@ -78,6 +72,7 @@ impl FirstOrderFilter {
 
     pub fn reset_sample_rate(&mut self, new_sample_rate: f64) {
         self.sample_rate = new_sample_rate;
+        self.needs_recompute = true;
     }
 
     pub fn identity(&mut self) {
@@ -86,11 +81,29 @@ impl FirstOrderFilter {
 
     pub fn set_freq(&mut self, freq: f64) {
         self.freq = freq;
+        self.needs_recompute = true;
         self.debug_assertions();
     }
 
     pub fn set_type(&mut self, filter_type: FilterType) {
         self.filter_type = filter_type;
+        self.needs_recompute = true;
+    }
+
+    /// Returns the coefficient of a first-order allpass filter at `freq` Hz.
+    pub fn allpass_coef(freq: f64, sample_rate: f64) -> f64 {
+        let t = (PI * freq / sample_rate).tan();
+        (t - 1.0) / (t + 1.0)
+    }
+
+    /// Makes the filter an allpass filter with the coefficient `coef`, as
+    /// returned by [`allpass_coef()`](Self::allpass_coef). This allows a
+    /// cascade of allpass filters at the same frequency to share a single
+    /// coefficient.
+    pub fn set_allpass_coef(&mut self, coef: f64) {
+        self.filter_type = FilterType::Allpass;
+        self.coefs = Coefs { a0: coef, a1: 1.0, b1: coef };
+        self.needs_recompute = false;
     }
 
     pub fn reset(&mut self) {
@@ -101,6 +114,24 @@ impl FirstOrderFilter {
         self.sample_rate
     }
 
+    /// Recomputes the coefficients for the filter type and frequency.
+    fn update_coefs(&mut self) {
+        match self.filter_type {
+            FilterType::Lowpass => self.set_lowpass_coefs(),
+            FilterType::Highpass => self.set_highpass_coefs(),
+            FilterType::Allpass => self.set_allpass_coefs(),
+            _ => {
+                self.identity();
+                dbg!(
+                    &self.filter_type,
+                    "only low/high/allpass filters are implemented for first order filters"
+                );
+            }
+        }
+
+        self.needs_recompute = false;
+    }
+
     fn set_lowpass_coefs(&mut self) {
         self.set_common_coefs();
         let Coefs { a0, a1, b1 } = &mut self.coefs;
@@ -117,6 +148,10 @@ impl FirstOrderFilter {
         *a1 = -(*a0);
     }
 
+    fn set_allpass_coefs(&mut self) {
+        self.set_allpass_coef(Self::allpass_coef(self.freq, self.sample_rate));
+    }
+
     fn set_common_coefs(&mut self) {
         let freq = self.freq;
         let sr = self.sample_rate;
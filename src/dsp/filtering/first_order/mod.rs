@@ -1,4 +1,4 @@
-//! First-order lowpass and highpass filters.
+//! First-order lowpass, highpass and allpass filters.
 
 pub mod filter;
 use super::*;
@@ -9,6 +9,7 @@ pub use filter::FirstOrderFilter;
 mod tests {
     use super::*;
     use nannou::rand;
+    use std::f64::consts::TAU;
 
     #[test]
     #[should_panic]
@@ -60,4 +61,28 @@ mod tests {
 
         assert!(low_passed != high_passed);
     }
+
+    #[test]
+    fn allpass_preserves_magnitude() {
+        let sample_rate = 44100.0;
+        let mut filter = FirstOrderFilter::new(sample_rate);
+        filter.set_freq(1000.0);
+        filter.set_type(FilterType::Allpass);
+
+        for freq in [100.0, 1000.0, 10000.0] {
+            let input: Vec<f64> = (0..8192)
+                .map(|i| (TAU * freq * i as f64 / sample_rate).sin())
+                .collect();
+            let output: Vec<f64> =
+                input.iter().map(|x| filter.process(*x)).collect();
+
+            let rms = |x: &[f64]| {
+                (x.iter().map(|s| s * s).sum::<f64>() / x.len() as f64).sqrt()
+            };
+
+            let error = rms(&input[4096..]) - rms(&output[4096..]);
+            assert!(error.abs() < 1e-2, "{freq} Hz: {error}");
+            assert_ne!(input, output);
+        }
+    }
 }
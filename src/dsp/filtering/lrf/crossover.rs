@@ -0,0 +1,124 @@
+//! Multiband crossovers.
+
+use super::*;
+
+/// A stereo crossover which splits a signal into any number of bands with
+/// fourth-order Linkwitz-Riley filters.
+///
+/// The signal is split at each crossover frequency in turn, from lowest to
+/// highest. Each band is then passed through allpass filters at the
+/// crossover frequencies above it, so that every band shares the same phase
+/// response and the sum of all bands has a flat magnitude response.
+#[derive(Clone, Debug)]
+pub struct Crossover {
+    /// The filter which splits the signal at each crossover frequency.
+    splitters: Vec<LinkwitzRileyFilter>,
+    /// The phase compensation filters, paired with the index of the band
+    /// they process and the index of the crossover frequency they match.
+    allpasses: Vec<(usize, usize, LinkwitzRileyFilter)>,
+
+    sample_rate: f64,
+}
+
+impl Crossover {
+    /// The lowest crossover frequency in hertz.
+    pub const MIN_FREQ: f64 = 20.0;
+
+    /// Returns a new crossover with `num_bands` bands. The crossover
+    /// frequencies are spread logarithmically between 100 Hz and 5 kHz by
+    /// default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_bands` is less than `2`.
+    pub fn new(num_bands: usize, sample_rate: f64) -> Self {
+        assert!(num_bands >= 2, "a crossover requires at least two bands");
+        let num_splits = num_bands - 1;
+
+        let mut allpass = LinkwitzRileyFilter::new(sample_rate);
+        allpass.set_type(FilterType::Allpass);
+
+        let allpasses = (0..num_splits)
+            .flat_map(|band| {
+                (band + 1..num_splits).map(move |split| (band, split))
+            })
+            .map(|(band, split)| (band, split, allpass.clone()))
+            .collect();
+
+        let mut crossover = Self {
+            splitters: vec![LinkwitzRileyFilter::new(sample_rate); num_splits],
+            allpasses,
+            sample_rate,
+        };
+
+        for split in 0..num_splits {
+            let pos = (split + 1) as f64 / num_bands as f64;
+            crossover.set_crossover_freq(split, 100.0 * 50.0f64.powf(pos));
+        }
+
+        crossover
+    }
+
+    /// Returns the number of bands of the crossover.
+    pub fn num_bands(&self) -> usize {
+        self.splitters.len() + 1
+    }
+
+    /// Sets the frequency of the crossover at `index` in hertz, where `0` is
+    /// the crossover between the lowest two bands. Crossover frequencies
+    /// should be in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of crossovers.
+    pub fn set_crossover_freq(&mut self, index: usize, freq_hz: f64) {
+        let freq = freq_hz.clamp(Self::MIN_FREQ, self.sample_rate * 0.45);
+        self.splitters[index].set_cutoff_freq(freq);
+
+        for (_, split, filter) in &mut self.allpasses {
+            if *split == index {
+                filter.set_cutoff_freq(freq);
+            }
+        }
+    }
+
+    /// Splits a stereo input into `bands`, from lowest to highest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bands` doesn't match the number of bands of
+    /// the crossover.
+    pub fn split(&mut self, in_l: f64, in_r: f64, bands: &mut [(f64, f64)]) {
+        assert_eq!(bands.len(), self.num_bands());
+        let mut rest = (in_l, in_r);
+
+        for (band, splitter) in bands.iter_mut().zip(&mut self.splitters) {
+            let (low, high) = splitter.process_high_low(rest.0, rest.1);
+            *band = low;
+            rest = high;
+        }
+
+        bands[self.splitters.len()] = rest;
+
+        for (band, _, filter) in &mut self.allpasses {
+            let (l, r) = bands[*band];
+            bands[*band] = filter.process_stereo(l, r);
+        }
+    }
+
+    /// Clears the state of the crossover.
+    pub fn reset(&mut self) {
+        for filter in &mut self.splitters {
+            filter.reset(0.0);
+        }
+        for (_, _, filter) in &mut self.allpasses {
+            filter.reset(0.0);
+        }
+    }
+}
+
+impl Default for Crossover {
+    fn default() -> Self {
+        Self::new(NUM_BANDS, unsafe { SAMPLE_RATE })
+    }
+}
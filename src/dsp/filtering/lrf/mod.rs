@@ -6,13 +6,74 @@ use super::*;
 use crate::dsp::Effect;
 use crate::prelude::*;
 
+pub mod crossover;
 pub mod filter;
 
+pub use crossover::Crossover;
 pub use filter::LinkwitzRileyFilter;
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    //
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    /// Returns the RMS level of each band of `crossover` for a sine at
+    /// `freq_hz`, measured after it has settled.
+    fn band_levels(crossover: &mut Crossover, freq_hz: f64) -> Vec<f64> {
+        let num_bands = crossover.num_bands();
+        let mut bands = vec![(0.0, 0.0); num_bands];
+        let mut sums = vec![0.0; num_bands + 1];
+
+        for i in 0..44100 {
+            let x = (TAU * freq_hz * i as f64 / SAMPLE_RATE).sin();
+            crossover.split(x, x, &mut bands);
+
+            if i >= 22050 {
+                let total: f64 = bands.iter().map(|band| band.0).sum();
+                sums[num_bands] += total * total;
+
+                for (sum, band) in sums.iter_mut().zip(&bands) {
+                    *sum += band.0 * band.0;
+                }
+            }
+        }
+
+        sums.iter().map(|sum| (sum / 22050.0).sqrt()).collect()
+    }
+
+    #[test]
+    fn crossover_sums_flat() {
+        let mut crossover = Crossover::new(4, SAMPLE_RATE);
+        crossover.set_crossover_freq(0, 150.0);
+        crossover.set_crossover_freq(1, 800.0);
+        crossover.set_crossover_freq(2, 4000.0);
+
+        for freq in [50.0, 150.0, 400.0, 800.0, 2000.0, 4000.0, 10000.0] {
+            let levels = band_levels(&mut crossover, freq);
+            let total = levels[4] * SQRT_2;
+
+            assert!((total - 1.0).abs() < 1e-3, "{freq} Hz: {total}");
+        }
+    }
+
+    #[test]
+    fn crossover_separates_bands() {
+        let mut crossover = Crossover::new(3, SAMPLE_RATE);
+        crossover.set_crossover_freq(0, 200.0);
+        crossover.set_crossover_freq(1, 2000.0);
+
+        for (freq, expected_band) in [(30.0, 0), (630.0, 1), (12000.0, 2)] {
+            let levels = band_levels(&mut crossover, freq);
+
+            for (band, &level) in levels[..3].iter().enumerate() {
+                if band == expected_band {
+                    assert!(level > 0.6, "{freq} Hz, band {band}: {level}");
+                }
+                else {
+                    assert!(level < 0.1, "{freq} Hz, band {band}: {level}");
+                }
+            }
+        }
+    }
 }
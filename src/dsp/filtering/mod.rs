@@ -6,6 +6,7 @@ pub const BUTTERWORTH_Q: f64 = std::f64::consts::FRAC_1_SQRT_2;
 
 pub mod biquad;
 pub mod comb;
+pub mod eq;
 pub mod filter_design;
 pub mod first_order;
 pub mod lrf;
@@ -23,9 +24,9 @@ dyn_clone::clone_trait_object!(Filter);
 
 /// An enum which covers the available filter types.
 ///
-/// Currently, peak, lowpass, highpass, bandpass, notch, and allpass biquad
-/// filters are implemented.
-#[derive(Debug, Clone, Copy, Default)]
+/// Currently, peak, lowpass, highpass, shelving, bandpass, notch, and allpass
+/// biquad filters are implemented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum FilterType {
     #[default]
     Peak,
@@ -38,5 +39,13 @@ pub enum FilterType {
     Allpass,
 }
 
+impl FilterType {
+    /// Returns whether the filter type boosts or cuts by a gain, rather than
+    /// by its Q.
+    pub const fn uses_gain(self) -> bool {
+        matches!(self, Self::Peak | Self::Lowshelf | Self::Highshelf)
+    }
+}
+
 // TODO SIMD optimisations, vroom
 // Add more common methods to this trait.
@@ -3,6 +3,7 @@
 use super::*;
 use crate::dsp::*;
 use crate::prelude::*;
+use rand::Rng;
 use two_pole_resonator::TwoPoleResonator;
 
 type Resonator = AudioUtility<StereoWrapper<TwoPoleResonator>>;
@@ -24,6 +25,21 @@ pub struct ResonatorBankParams {
     pub freq_shift: f64,
     /// The amount each pitch can skew towards its original value.
     pub inharm: f64,
+
+    /// The time for each resonator to decay by 60 dB in seconds (T60), before
+    /// damping and randomisation are applied.
+    pub decay_secs: f64,
+    /// How much faster higher resonators decay than lower ones.
+    pub damping: f64,
+    /// How much each resonator's decay time is randomly varied.
+    pub decay_random: f64,
+
+    /// The pitch layout which the field-derived pitches are blended with.
+    pub pitch_layout: PitchLayout,
+    /// How much each resonator's pitch is blended towards the pitch layout.
+    pub layout_blend: f64,
+    /// The fundamental note of the pitch layout, i.e. the last played note.
+    pub layout_note: f64,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -44,8 +60,16 @@ pub struct ResonatorBank {
     original_pitches: Vec<f64>,
     active_pitches: Vec<Smoother<f64>>,
     panning: Vec<Smoother<f64>>,
+    /// Random offsets (`-1.0` to `1.0`) applied to each resonator's decay time.
+    decay_offsets: Vec<f64>,
+    /// The currently (or most recently) held notes, used by the `Keyboard`
+    /// pitch layout.
+    held_notes: Vec<f64>,
+    num_pressed: usize,
     params: ResonatorBankParams,
     num_active: usize,
+    decays_need_update: bool,
+    rng: SessionRng,
 }
 
 impl ResonatorBank {
@@ -54,6 +78,21 @@ impl ResonatorBank {
     pub const NOTE_MAX: f64 = 128.0;
     const INHARM_SCALE: f64 = 0.015;
 
+    pub const DECAY_MIN_SECS: f64 = 0.05;
+    pub const DECAY_MAX_SECS: f64 = 20.0;
+    /// The frequency at which damping has no effect on the decay time.
+    const DAMPING_REF_FREQ: f64 = 1000.0;
+    /// The maximum number of octaves by which the decay time is randomly varied.
+    const DECAY_RANDOM_OCTAVES: f64 = 2.0;
+
+    /// The resonance the output gain is calibrated for. Shorter decay times
+    /// have their gain compensated relative to this.
+    const REF_RESONANCE: f64 = 0.9999;
+    const REF_GAIN_DB: f64 = -75.0;
+
+    /// The maximum number of held notes tracked by the `Keyboard` layout.
+    const MAX_HELD_NOTES: usize = 16;
+
     pub fn new(sample_rate: f64, max_num_resonators: usize) -> Self {
         assert!(max_num_resonators > 0);
 
@@ -71,7 +110,12 @@ impl ResonatorBank {
             original_pitches: vec![0.0; max_num_resonators],
             active_pitches: vec![pitch_smoother; max_num_resonators],
             panning: vec![pan_smoother; max_num_resonators],
+            decay_offsets: vec![0.0; max_num_resonators],
+            held_notes: Vec::with_capacity(Self::MAX_HELD_NOTES),
+            num_pressed: 0,
             num_active: max_num_resonators,
+            decays_need_update: true,
+            rng: SessionRng::new("resonator_bank"),
             params: ResonatorBankParams {
                 panning_scale: 1.0,
                 freq_shift: 0.0,
@@ -80,13 +124,19 @@ impl ResonatorBank {
                 quantize_to_scale: false,
                 scale: Scale::default(),
                 inharm: 0.0,
+                decay_secs: 1.5,
+                damping: 0.0,
+                decay_random: 0.0,
+                pitch_layout: PitchLayout::default(),
+                layout_blend: 0.0,
+                layout_note: 48.0,
             },
         };
 
         s.resonators.iter_mut().for_each(|res| {
-            res.l.set_resonance(0.9999);
-            res.r.set_resonance(0.9999);
-            res.set_gain_db(-75.0);
+            res.l.set_resonance(Self::REF_RESONANCE);
+            res.r.set_resonance(Self::REF_RESONANCE);
+            res.set_gain_db(Self::REF_GAIN_DB);
         });
 
         s
@@ -94,6 +144,7 @@ impl ResonatorBank {
 
     pub fn set_params(&mut self, params: ResonatorBankParams) {
         self.params = params;
+        self.decays_need_update = true;
         self.set_active_pitches();
     }
 
@@ -119,11 +170,26 @@ impl ResonatorBank {
         self.resonators.capacity()
     }
 
+    /// Clears the state of every resonator, without altering their pitches.
+    pub fn reset(&mut self) {
+        for res in &mut self.resonators {
+            res.l.reset();
+            res.r.reset();
+        }
+    }
+
     /// Randomizes the raw pitches of the resonator bank.
     pub fn randomize(&mut self) {
+        let rng = &mut self.rng;
+
         self.original_pitches.iter_mut().for_each(|p| {
-            *p = random_range(Self::NOTE_MIN, Self::NOTE_MAX);
+            *p = rng.gen_range(Self::NOTE_MIN..Self::NOTE_MAX);
+        });
+
+        self.decay_offsets.iter_mut().for_each(|offset| {
+            *offset = rng.gen_range(-1.0..1.0);
         });
+        self.decays_need_update = true;
 
         self.set_active_pitches();
         self.randomize_panning();
@@ -209,6 +275,119 @@ impl ResonatorBank {
         }
     }
 
+    /// Sets the time for each resonator to decay by 60 dB (T60), before damping
+    /// and randomisation are applied.
+    ///
+    /// Clamped to `[DECAY_MIN_SECS, DECAY_MAX_SECS]`.
+    pub fn set_decay_time(&mut self, decay_secs: f64) {
+        if epsilon_eq(self.params.decay_secs, decay_secs) {
+            return;
+        }
+
+        self.params.decay_secs =
+            decay_secs.clamp(Self::DECAY_MIN_SECS, Self::DECAY_MAX_SECS);
+        self.decays_need_update = true;
+    }
+
+    /// Sets the frequency-dependent damping of the bank, which tilts each
+    /// resonator's decay time so that higher resonators decay faster. At
+    /// `1.0`, the decay time halves for each octave above 1 kHz (and doubles
+    /// for each octave below it).
+    ///
+    /// Clamped to `[0.0 - 1.0]`.
+    pub fn set_damping(&mut self, damping: f64) {
+        if epsilon_eq(self.params.damping, damping) {
+            return;
+        }
+
+        self.params.damping = damping.clamp(0.0, 1.0);
+        self.decays_need_update = true;
+    }
+
+    /// Sets how much each resonator's decay time is randomly varied, up to
+    /// two octaves either way at `1.0`. The random offsets are regenerated by
+    /// [`randomize()`](Self::randomize).
+    ///
+    /// Clamped to `[0.0 - 1.0]`.
+    pub fn set_decay_random(&mut self, amount: f64) {
+        if epsilon_eq(self.params.decay_random, amount) {
+            return;
+        }
+
+        self.params.decay_random = amount.clamp(0.0, 1.0);
+        self.decays_need_update = true;
+    }
+
+    /// Sets the pitch layout which the field-derived pitches are blended with.
+    ///
+    /// Only active if the layout blend is above `0.0`.
+    pub fn set_pitch_layout(&mut self, layout: PitchLayout) {
+        if layout == self.params.pitch_layout {
+            return;
+        }
+
+        self.params.pitch_layout = layout;
+
+        if self.params.layout_blend > 0.0 {
+            self.set_active_pitches();
+        }
+    }
+
+    /// Sets how much each resonator's pitch is blended from its field-derived
+    /// pitch towards the pitch layout, where `1.0` is entirely the layout.
+    ///
+    /// Clamped to `[0.0 - 1.0]`.
+    pub fn set_layout_blend(&mut self, blend: f64) {
+        if epsilon_eq(self.params.layout_blend, blend) {
+            return;
+        }
+
+        self.params.layout_blend = blend.clamp(0.0, 1.0);
+        self.set_active_pitches();
+    }
+
+    /// Informs the bank that `note` has started, which retunes the pitch
+    /// layout to it.
+    pub fn note_on(&mut self, note: f64) {
+        self.params.layout_note = note;
+
+        // a new chord starts once every previous note has been released
+        if self.num_pressed == 0 {
+            self.held_notes.clear();
+        }
+        self.num_pressed += 1;
+
+        if self.held_notes.len() < Self::MAX_HELD_NOTES
+            && !self.held_notes.iter().any(|&n| epsilon_eq(n, note))
+        {
+            self.held_notes.push(note);
+        }
+
+        if self.params.layout_blend > 0.0 {
+            self.set_active_pitches();
+        }
+    }
+
+    /// Informs the bank that `note` has been released.
+    ///
+    /// The `Keyboard` layout keeps the last held notes once all notes have
+    /// been released.
+    pub fn note_off(&mut self, note: f64) {
+        self.num_pressed = self.num_pressed.saturating_sub(1);
+
+        if self.num_pressed == 0 {
+            return;
+        }
+
+        self.held_notes.retain(|&n| !epsilon_eq(n, note));
+
+        if self.params.layout_blend > 0.0
+            && self.params.pitch_layout == PitchLayout::Keyboard
+        {
+            self.set_active_pitches();
+        }
+    }
+
     /// Returns a mutable reference to the raw resonator pitches.
     pub fn original_pitches_mut(&mut self) -> &mut [f64] {
         &mut self.original_pitches
@@ -260,8 +439,10 @@ impl ResonatorBank {
 
     /// Randomises the panning values for each resonator.
     fn randomize_panning(&mut self) {
+        let rng = &mut self.rng;
+
         self.panning.iter_mut().for_each(|pan| {
-            pan.set_target_value(random_range(-1.0, 1.0));
+            pan.set_target_value(rng.gen_range(-1.0..1.0));
         });
 
         self.update_panning();
@@ -281,51 +462,80 @@ impl ResonatorBank {
             });
     }
 
-    /// Updates each resonator's pitch.
+    /// Updates each resonator's pitch and decay time.
     fn update_resonator_pitches(&mut self) {
         // avoid recalculating filter coefs if the pitches haven't changed
-        if !self.active_pitches[0].is_active() {
+        if !self.active_pitches[0].is_active() && !self.decays_need_update {
             return;
         }
         let nyquist = self.get_sample_rate() * 0.5;
+        let update_decays = self.decays_need_update;
 
-        self.resonators
-            .iter_mut()
-            .take(self.num_active)
-            .zip(self.active_pitches.iter_mut())
-            .for_each(|(res, p)| {
-                let note = p.next();
-                let freq = note_to_freq(note);
-
-                res.l.set_cutoff(freq.min(nyquist));
-                res.r.set_cutoff(freq.min(nyquist));
-            });
-
-        for (res, p) in self
+        for (i, (res, p)) in self
             .resonators
             .iter_mut()
-            .skip(self.num_active)
             .zip(self.active_pitches.iter_mut())
+            .enumerate()
         {
-            if !p.is_active() {
+            if i >= self.num_active && !p.is_active() && !update_decays {
                 continue;
             }
 
             let note = p.next();
-            let freq = note_to_freq(note);
-
-            res.l.set_cutoff(freq.min(nyquist));
-            res.r.set_cutoff(freq.min(nyquist));
+            let freq = note_to_freq(note).min(nyquist);
+
+            let decay =
+                Self::decay_time_for(&self.params, freq, self.decay_offsets[i]);
+            let resonance = res.l.resonance_for_decay(decay);
+
+            res.l.set_resonance(resonance);
+            res.r.set_resonance(resonance);
+            res.l.set_cutoff(freq);
+            res.r.set_cutoff(freq);
+
+            // keeps the peak gain roughly constant as the decay time changes
+            res.set_gain(
+                db_to_level(Self::REF_GAIN_DB) * (1.0 - resonance)
+                    / (1.0 - Self::REF_RESONANCE),
+            );
         }
+
+        self.decays_need_update = false;
+    }
+
+    /// Returns the decay time of a resonator at `freq` Hz with the random
+    /// decay offset `offset`.
+    fn decay_time_for(
+        params: &ResonatorBankParams,
+        freq: f64,
+        offset: f64,
+    ) -> f64 {
+        let damping = (Self::DAMPING_REF_FREQ / freq).powf(params.damping);
+        let random = (offset * params.decay_random * Self::DECAY_RANDOM_OCTAVES)
+            .exp2();
+
+        params.decay_secs * damping * random
+    }
+
+    /// Returns the pitch of the resonator at `idx` in the current pitch
+    /// layout.
+    fn layout_pitch(&self, idx: usize) -> f64 {
+        let note = match self.params.pitch_layout {
+            PitchLayout::Keyboard if !self.held_notes.is_empty() => {
+                // stack the held notes in octaves
+                let num_held = self.held_notes.len();
+                self.held_notes[idx % num_held] + 12.0 * (idx / num_held) as f64
+            }
+            layout => self.params.layout_note + layout.interval(idx),
+        };
+
+        note.min(Self::NOTE_MAX)
     }
 
     fn set_active_pitches(&mut self) {
-        for (active, &original) in self
-            .active_pitches
-            .iter_mut()
-            .take(self.num_active)
-            .zip(self.original_pitches.iter())
-        {
+        for i in 0..self.num_active {
+            let original = self.original_pitches[i];
+
             // apply frequency spread and then add shift
             let spread_shift = if original < Self::NOTE_MIDDLE {
                 map(
@@ -354,7 +564,7 @@ impl ResonatorBank {
                 )
             } + self.params.freq_shift;
 
-            if self.params.quantize_to_scale {
+            let field_pitch = if self.params.quantize_to_scale {
                 // quantize to scale
                 let quantized = self
                     .params
@@ -362,13 +572,25 @@ impl ResonatorBank {
                     .quantize_to_scale(spread_shift, self.params.root_note);
 
                 // apply inharmonic skew
-                active.set_target_value(lerp(
-                    quantized, original, self.params.inharm,
-                ));
+                lerp(quantized, original, self.params.inharm)
             }
             else {
-                active.set_target_value(spread_shift);
+                spread_shift
+            };
+
+            // blend towards the pitch layout
+            let pitch = if self.params.layout_blend > 0.0 {
+                lerp(
+                    field_pitch,
+                    self.layout_pitch(i),
+                    self.params.layout_blend,
+                )
             }
+            else {
+                field_pitch
+            };
+
+            self.active_pitches[i].set_target_value(pitch);
         }
     }
 }
@@ -412,3 +634,19 @@ impl Effect for ResonatorBank {
         self.resonators[0].get_sample_rate()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_randomization() {
+        let randomized = || {
+            let mut bank = ResonatorBank::new(48000.0, 16);
+            bank.randomize();
+            (bank.original_pitches, bank.decay_offsets)
+        };
+
+        assert_eq!(randomized(), randomized());
+    }
+}
@@ -69,11 +69,33 @@ impl TwoPoleResonator {
     }
 
     /// `resonance` is clamped between `0.0` and `1.0`.
+    ///
+    /// The new resonance is applied on the next call to
+    /// [`set_cutoff()`](Self::set_cutoff).
     pub fn set_resonance(&mut self, resonance: f64) {
         // self.resonance = resonance.clamp(0.0, 1.0);
         self.resonance = resonance;
     }
 
+    /// Returns the resonance (pole radius) required for the filter's impulse
+    /// response to decay by 60 dB over `decay_secs` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decay_secs` is not positive.
+    pub fn resonance_for_decay(&self, decay_secs: f64) -> f64 {
+        assert!(decay_secs > 0.0);
+        // ln(1000), i.e. -60 dB
+        const LN_1000: f64 = 6.907_755_278_982_137;
+
+        (-LN_1000 / (decay_secs * self.sample_rate)).exp()
+    }
+
+    /// Returns the current resonance (pole radius) of the filter.
+    pub fn resonance(&self) -> f64 {
+        self.resonance
+    }
+
     /// Resets the internal sample rate of the filter.
     ///
     /// # Panics
@@ -128,6 +150,12 @@ impl TwoPoleResonator {
     pub fn sample_rate(&self) -> f64 {
         self.sample_rate
     }
+
+    /// Clears the filter's delayed samples, without altering its parameters.
+    pub fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
 }
 
 impl Filter for TwoPoleResonator {
@@ -47,6 +47,11 @@ impl DCFilter {
         self.filters.resize(num_filters, Self::create_filter(sr));
     }
 
+    /// Clears the delayed samples of each filter.
+    pub fn reset(&mut self) {
+        self.filters.iter_mut().for_each(BiquadFilter::reset);
+    }
+
     fn create_filter(sample_rate: f64) -> BiquadFilter {
         let mut filter = BiquadFilter::new(sample_rate);
         filter.set_q(BUTTERWORTH_Q);
@@ -8,19 +8,21 @@ pub mod dynamics;
 pub mod filtering;
 pub mod modulation;
 pub mod oversampling;
+pub mod reverb;
 pub mod spectral;
 pub mod synthesis;
 pub mod util;
 
 pub use delay::{Delay, RingBuffer, StereoDelay};
-pub use distortion::Waveshaper;
+pub use distortion::{Decimator, Waveshaper};
 pub use dynamics::adsr::{AdsrEnvelope, AdsrParameters};
-pub use dynamics::Compressor;
+pub use dynamics::{Compressor, Limiter, SafetyMute};
 pub use filtering::{
     biquad::{BiquadFilter, BiquadParams},
     comb::{FirCombFilter, IirCombFilter},
+    eq::{EQBand, LinearPhaseEQ, ParametricEQ},
     first_order::FirstOrderFilter,
-    lrf::LinkwitzRileyFilter,
+    lrf::{Crossover, LinkwitzRileyFilter},
     resonator::{
         resonator_bank::{ResoBankData, ResonatorBank, ResonatorBankParams},
         two_pole_resonator::TwoPoleResonator,
@@ -29,9 +31,14 @@ pub use filtering::{
     svf::StateVariableFilter,
     Filter, FilterType, BUTTERWORTH_Q,
 };
+pub use modulation::{Chorus, Flanger, Phaser};
 pub use oversampling::{Oversampler, OversamplingBuffer};
+pub use reverb::Reverb;
 pub use spectral::{
-    spectral_filter::{mask::SpectralMask, SpectralFilter},
+    spectral_filter::{
+        mask::{SpectralMask, StereoMask},
+        SpectralFilter,
+    },
     StftHelper,
 };
 pub use synthesis::Generator;
@@ -1,52 +1,122 @@
-//! Unused chorus effect.
+//! A multi-tap stereo chorus.
 
 use super::*;
-use crate::dsp::synthesis::*;
 
+/// The delay of the first tap at rest, in milliseconds.
+const BASE_DELAY_MS: f64 = 10.0;
+/// The additional delay of each subsequent tap, in milliseconds.
+const TAP_SPREAD_MS: f64 = 3.0;
+/// The furthest the taps are modulated from their delay at full depth, in
+/// milliseconds.
+const MAX_MOD_MS: f64 = 8.0;
+
+/// A stereo chorus, which mixes several modulated delay taps. Each tap is
+/// modulated at a different LFO phase, and the right channel is offset from
+/// the left to widen the stereo image.
+///
+/// The output is fully wet.
 #[derive(Clone, Debug)]
 pub struct Chorus {
-    delay_taps: Vec<StereoWrapper<Delay>>,
-    lfo: Generator,
+    buffers: [RingBuffer; 2],
+    lfo: Phasor,
+    num_taps: usize,
     mod_depth: f64,
     mod_rate: f64,
     invert_delay_polarity: bool,
+    sample_rate: f64,
 }
 
 impl Chorus {
-    const DEFAULT_NUM_TAPS: usize = 3;
+    /// The default number of delay taps.
+    pub const DEFAULT_NUM_TAPS: usize = 3;
+    /// The maximum number of delay taps.
+    pub const MAX_NUM_TAPS: usize = 8;
 
     pub fn new(sample_rate: f64) -> Self {
+        let max_delay_ms = (Self::MAX_NUM_TAPS - 1) as f64
+            * TAP_SPREAD_MS
+            + BASE_DELAY_MS
+            + MAX_MOD_MS;
+        let buffer = modulation_buffer(max_delay_ms, sample_rate);
+
         Self {
-            delay_taps: vec![
-                StereoWrapper::from_single(Delay::new(250.0, sample_rate));
-                Self::DEFAULT_NUM_TAPS
-            ],
-            lfo: Generator::Sine(SineOsc::new(3.0, sample_rate)),
-            mod_depth: 1.0,
-            mod_rate: 3.0,
+            buffers: [buffer.clone(), buffer],
+            lfo: new_lfo(0.5, sample_rate),
+            num_taps: Self::DEFAULT_NUM_TAPS,
+            mod_depth: 0.5,
+            mod_rate: 0.5,
             invert_delay_polarity: false,
+            sample_rate,
         }
     }
 
+    /// Sets the number of delay taps, clamped between `1` and
+    /// [`MAX_NUM_TAPS`](Self::MAX_NUM_TAPS).
+    pub fn set_num_taps(&mut self, num_taps: usize) {
+        self.num_taps = num_taps.clamp(1, Self::MAX_NUM_TAPS);
+    }
+
+    /// Sets the rate of the modulation in hertz.
     pub fn set_mod_rate(&mut self, rate: f64) {
-        self.mod_rate = rate.clamp(0.1, 10.0);
+        self.mod_rate = set_lfo_rate(&mut self.lfo, rate, self.sample_rate);
     }
 
+    /// Sets the depth of the modulation, between `0.0` and `1.0`.
     pub fn set_mod_depth(&mut self, depth: f64) {
         self.mod_depth = depth.clamp(0.0, 1.0);
     }
 
+    /// Sets whether the delayed signal is inverted.
     pub fn invert_delay_polarity(&mut self, should_invert: bool) {
         self.invert_delay_polarity = should_invert;
     }
+
+    /// Clears the delay taps.
+    pub fn reset(&mut self) {
+        self.buffers.iter_mut().for_each(RingBuffer::clear);
+    }
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
 }
 
 impl Effect for Chorus {
     fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
-        unimplemented!("chorus is not yet implemented.")
+        let phase = self.lfo.phase;
+        self.lfo.increment_phase();
+
+        let ms_to_samples = self.sample_rate * 0.001;
+        let mut output = [0.0; 2];
+
+        for (ch, (buffer, input)) in
+            self.buffers.iter_mut().zip([in_l, in_r]).enumerate()
+        {
+            for tap in 0..self.num_taps {
+                let tap_phase = (ch as f64).mul_add(
+                    STEREO_PHASE_OFFSET,
+                    phase + tap as f64 / self.num_taps as f64,
+                );
+                let base_ms =
+                    (tap as f64).mul_add(TAP_SPREAD_MS, BASE_DELAY_MS);
+                let delay_ms =
+                    base_ms + lfo(tap_phase) * self.mod_depth * MAX_MOD_MS;
+
+                output[ch] += buffer.read_at(delay_ms * ms_to_samples);
+            }
+
+            buffer.push(input);
+        }
+
+        let polarity = if self.invert_delay_polarity { -1.0 } else { 1.0 };
+        let gain = polarity / (self.num_taps as f64).sqrt();
+
+        (output[0] * gain, output[1] * gain)
     }
 
     fn get_sample_rate(&self) -> f64 {
-        self.delay_taps[0].get_sample_rate()
+        self.sample_rate
     }
 }
@@ -0,0 +1,94 @@
+//! A stereo flanger.
+
+use super::*;
+
+/// The shortest delay of the flanger, in milliseconds.
+const MIN_DELAY_MS: f64 = 0.3;
+/// The longest delay of the flanger at full depth, in milliseconds.
+const MAX_DELAY_MS: f64 = 8.0;
+/// The maximum amount of feedback, which keeps the flanger stable.
+const MAX_FEEDBACK: f64 = 0.95;
+
+/// A stereo flanger: a short, modulated delay with feedback. Negative
+/// feedback produces a hollower sound.
+///
+/// The output is fully wet, so it should be mixed with the dry signal to
+/// produce the flanging notches.
+#[derive(Clone, Debug)]
+pub struct Flanger {
+    buffers: [RingBuffer; 2],
+    lfo: Phasor,
+    mod_depth: f64,
+    mod_rate: f64,
+    feedback: f64,
+    sample_rate: f64,
+}
+
+impl Flanger {
+    pub fn new(sample_rate: f64) -> Self {
+        let buffer = modulation_buffer(MAX_DELAY_MS, sample_rate);
+
+        Self {
+            buffers: [buffer.clone(), buffer],
+            lfo: new_lfo(0.2, sample_rate),
+            mod_depth: 0.7,
+            mod_rate: 0.2,
+            feedback: 0.5,
+            sample_rate,
+        }
+    }
+
+    /// Sets the rate of the modulation in hertz.
+    pub fn set_mod_rate(&mut self, rate: f64) {
+        self.mod_rate = set_lfo_rate(&mut self.lfo, rate, self.sample_rate);
+    }
+
+    /// Sets the depth of the modulation, between `0.0` and `1.0`.
+    pub fn set_mod_depth(&mut self, depth: f64) {
+        self.mod_depth = depth.clamp(0.0, 1.0);
+    }
+
+    /// Sets the feedback amount, between `-1.0` and `1.0`. The feedback is
+    /// limited to `±0.95` to keep the flanger stable.
+    pub fn set_feedback(&mut self, feedback: f64) {
+        self.feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+    }
+
+    /// Clears the delay lines.
+    pub fn reset(&mut self) {
+        self.buffers.iter_mut().for_each(RingBuffer::clear);
+    }
+}
+
+impl Default for Flanger {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+impl Effect for Flanger {
+    fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let phase = self.lfo.phase;
+        self.lfo.increment_phase();
+
+        let ms_to_samples = self.sample_rate * 0.001;
+        let mut output = [0.0; 2];
+
+        for (ch, (buffer, input)) in
+            self.buffers.iter_mut().zip([in_l, in_r]).enumerate()
+        {
+            let sweep = lfo((ch as f64).mul_add(STEREO_PHASE_OFFSET, phase));
+            let delay_ms = (sweep * self.mod_depth)
+                .mul_add(MAX_DELAY_MS - MIN_DELAY_MS, MIN_DELAY_MS);
+
+            output[ch] = buffer.read_at(delay_ms * ms_to_samples);
+            buffer.push(output[ch].mul_add(self.feedback, input));
+        }
+
+        (output[0], output[1])
+    }
+
+    fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+}
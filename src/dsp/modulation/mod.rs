@@ -1,4 +1,124 @@
-//! Time-based modulation effects. Unused in this project.
+//! Time-based modulation effects.
 
 use super::*;
+use crate::dsp::synthesis::{GeneratorProcessor, Phasor};
+use std::f64::consts::TAU;
+
 pub mod chorus;
+pub mod flanger;
+pub mod phaser;
+
+pub use chorus::Chorus;
+pub use flanger::Flanger;
+pub use phaser::Phaser;
+
+/// The slowest modulation rate in hertz.
+pub const MIN_MOD_RATE: f64 = 0.05;
+/// The fastest modulation rate in hertz.
+pub const MAX_MOD_RATE: f64 = 10.0;
+
+/// The LFO phase offset of the right channel, which widens the stereo image.
+const STEREO_PHASE_OFFSET: f64 = 0.25;
+
+/// Returns the value of a sine LFO at `phase`, between `0.0` and `1.0`. The
+/// LFO is at its minimum when `phase` is a whole number.
+fn lfo(phase: f64) -> f64 {
+    0.5f64.mul_add(-(TAU * phase).cos(), 0.5)
+}
+
+/// Returns a new LFO running at `rate_hz`.
+fn new_lfo(rate_hz: f64, sample_rate: f64) -> Phasor {
+    Phasor::new(rate_hz.clamp(MIN_MOD_RATE, MAX_MOD_RATE), sample_rate)
+}
+
+/// Sets the rate of `lfo` to `rate_hz`, clamped to the modulation rate range.
+/// Returns the clamped rate.
+fn set_lfo_rate(lfo: &mut Phasor, rate_hz: f64, sample_rate: f64) -> f64 {
+    let rate = rate_hz.clamp(MIN_MOD_RATE, MAX_MOD_RATE);
+    lfo.set_freq(rate, sample_rate);
+    rate
+}
+
+/// Returns a ring buffer which holds `max_delay_ms` of audio.
+fn modulation_buffer(max_delay_ms: f64, sample_rate: f64) -> RingBuffer {
+    let size = (max_delay_ms * 0.001 * sample_rate).ceil() as usize + 4;
+
+    RingBuffer::new(size, sample_rate)
+        .with_interpolation(InterpType::DefaultCubic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    fn rms(samples: &[f64]) -> f64 {
+        let sum: f64 = samples.iter().map(|x| x * x).sum();
+        (sum / samples.len() as f64).sqrt()
+    }
+
+    fn sine(freq: f64, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| (TAU * freq * i as f64 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    fn process(effect: &mut impl Effect, input: &[f64]) -> Vec<f64> {
+        input.iter().map(|&x| effect.process_stereo(x, x).0).collect()
+    }
+
+    #[test]
+    fn chorus_delays_each_tap() {
+        let mut chorus = Chorus::new(SAMPLE_RATE);
+        chorus.set_mod_depth(0.0);
+
+        let mut impulse = vec![0.0; 4096];
+        impulse[0] = 1.0;
+        let output = process(&mut chorus, &impulse);
+
+        let peaks: Vec<usize> = (1..output.len() - 1)
+            .filter(|&i| {
+                output[i].abs() > 0.1
+                    && output[i].abs() >= output[i - 1].abs()
+                    && output[i].abs() >= output[i + 1].abs()
+            })
+            .collect();
+
+        assert_eq!(peaks.len(), Chorus::DEFAULT_NUM_TAPS);
+        assert!(output[..peaks[0] - 4].iter().all(|x| x.abs() < 1e-9));
+    }
+
+    #[test]
+    fn flanger_feedback_is_stable() {
+        let mut flanger = Flanger::new(SAMPLE_RATE);
+        flanger.set_mod_depth(1.0);
+        flanger.set_mod_rate(MAX_MOD_RATE);
+        flanger.set_feedback(1.0);
+
+        let input: Vec<f64> = sine(220.0, 44100)
+            .iter()
+            .zip(sine(3170.0, 44100))
+            .map(|(a, b)| a + b)
+            .collect();
+        let output = process(&mut flanger, &input);
+
+        assert!(output.iter().all(|x| x.is_finite() && x.abs() < 100.0));
+        assert!(rms(&output) > 0.1);
+    }
+
+    #[test]
+    fn phaser_without_feedback_is_allpass() {
+        let mut phaser = Phaser::new(SAMPLE_RATE);
+        phaser.set_mod_depth(1.0);
+        phaser.set_feedback(0.0);
+
+        for freq in [200.0, 1000.0, 5000.0] {
+            let input = sine(freq, 44100);
+            let output = process(&mut phaser, &input);
+
+            let ratio = rms(&output[4096..]) / rms(&input[4096..]);
+            assert!((ratio - 1.0).abs() < 0.05, "{freq} Hz: {ratio}");
+        }
+    }
+}
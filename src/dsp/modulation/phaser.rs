@@ -0,0 +1,125 @@
+//! A stereo phaser.
+
+use super::*;
+
+/// The lowest frequency of the allpass sweep in hertz.
+const MIN_FREQ_HZ: f64 = 100.0;
+/// The highest frequency of the allpass sweep in hertz.
+const MAX_FREQ_HZ: f64 = 8000.0;
+/// The maximum amount of feedback, which keeps the phaser stable.
+const MAX_FEEDBACK: f64 = 0.95;
+
+/// A stereo phaser, made of cascaded first-order allpass filters whose
+/// frequency is swept by an LFO.
+///
+/// Every stage of a channel is tuned to the same frequency, so the allpass
+/// coefficient is computed once per channel per sample and shared by each
+/// stage.
+///
+/// The output is fully wet, so it should be mixed with the dry signal to
+/// produce the phasing notches.
+#[derive(Clone, Debug)]
+pub struct Phaser {
+    /// The allpass stages of each channel.
+    stages: [[FirstOrderFilter; Self::MAX_NUM_STAGES]; 2],
+    num_stages: usize,
+    lfo: Phasor,
+    mod_depth: f64,
+    mod_rate: f64,
+    feedback: f64,
+    last_output: [f64; 2],
+    sample_rate: f64,
+}
+
+impl Phaser {
+    /// The default number of allpass stages.
+    pub const DEFAULT_NUM_STAGES: usize = 6;
+    /// The maximum number of allpass stages.
+    pub const MAX_NUM_STAGES: usize = 12;
+
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            stages: std::array::from_fn(|_| {
+                std::array::from_fn(|_| FirstOrderFilter::new(sample_rate))
+            }),
+            num_stages: Self::DEFAULT_NUM_STAGES,
+            lfo: new_lfo(0.3, sample_rate),
+            mod_depth: 0.7,
+            mod_rate: 0.3,
+            feedback: 0.3,
+            last_output: [0.0; 2],
+            sample_rate,
+        }
+    }
+
+    /// Sets the number of allpass stages, clamped between `1` and
+    /// [`MAX_NUM_STAGES`](Self::MAX_NUM_STAGES). Each pair of stages adds a
+    /// notch to the phaser.
+    pub fn set_num_stages(&mut self, num_stages: usize) {
+        self.num_stages = num_stages.clamp(1, Self::MAX_NUM_STAGES);
+    }
+
+    /// Sets the rate of the modulation in hertz.
+    pub fn set_mod_rate(&mut self, rate: f64) {
+        self.mod_rate = set_lfo_rate(&mut self.lfo, rate, self.sample_rate);
+    }
+
+    /// Sets the depth of the modulation, between `0.0` and `1.0`.
+    pub fn set_mod_depth(&mut self, depth: f64) {
+        self.mod_depth = depth.clamp(0.0, 1.0);
+    }
+
+    /// Sets the feedback amount, between `-1.0` and `1.0`. The feedback is
+    /// limited to `±0.95` to keep the phaser stable.
+    pub fn set_feedback(&mut self, feedback: f64) {
+        self.feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+    }
+
+    /// Clears the state of the allpass filters.
+    pub fn reset(&mut self) {
+        self.stages
+            .iter_mut()
+            .flatten()
+            .for_each(FirstOrderFilter::reset);
+        self.last_output = [0.0; 2];
+    }
+}
+
+impl Default for Phaser {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+impl Effect for Phaser {
+    fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let phase = self.lfo.phase;
+        self.lfo.increment_phase();
+
+        let max_freq = MAX_FREQ_HZ.min(self.sample_rate * 0.45);
+
+        for (ch, input) in [in_l, in_r].into_iter().enumerate() {
+            // the sweep is centred, so a depth of zero rests between the
+            // lowest and highest frequencies
+            let sweep = lfo((ch as f64).mul_add(STEREO_PHASE_OFFSET, phase));
+            let position = (sweep - 0.5).mul_add(self.mod_depth, 0.5);
+            let freq = MIN_FREQ_HZ * (max_freq / MIN_FREQ_HZ).powf(position);
+            let coef = FirstOrderFilter::allpass_coef(freq, self.sample_rate);
+
+            let mut sample = self.last_output[ch].mul_add(self.feedback, input);
+
+            for stage in &mut self.stages[ch][..self.num_stages] {
+                stage.set_allpass_coef(coef);
+                sample = stage.process(sample);
+            }
+
+            self.last_output[ch] = sample;
+        }
+
+        (self.last_output[0], self.last_output[1])
+    }
+
+    fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+}
@@ -21,10 +21,10 @@ impl Lanczos3Oversampler {
     pub fn new(max_block_size: usize, max_factor: usize, quality_factor: u8) -> Self {
         assert_ne!(max_factor, 0);
         let mut stages = Vec::with_capacity(max_factor);
-        let upsampling_kernel = Arc::from(lanczos_kernel(quality_factor, 1.0, true));
+        let upsampling_kernel = Arc::from(normalized_kernel(quality_factor, 1.0));
         // the downsampling kernel is identical, but scaled by half to result in
         // unity gain after oversampling.
-        let downsampling_kernel = Arc::from(lanczos_kernel(quality_factor, 0.5, true));
+        let downsampling_kernel = Arc::from(normalized_kernel(quality_factor, 0.5));
 
         for stage in 0..max_factor {
             stages.push(Lanczos3Stage::new(
@@ -134,3 +134,115 @@ impl Lanczos3Oversampler {
         self.stages[0].downsample_to(block);
     }
 }
+
+/// Returns a Lanczos kernel whose taps between samples sum to its centre tap,
+/// `scale`, so that interpolation with it doesn't change the gain of a signal.
+fn normalized_kernel(quality_factor: u8, scale: f64) -> Vec<f64> {
+    let mut kernel = lanczos_kernel(quality_factor, scale, true);
+    let sum: f64 = kernel.iter().step_by(2).sum();
+
+    kernel.iter_mut().step_by(2).for_each(|k| *k *= scale / sum);
+    kernel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::Waveshaper;
+    use std::f64::consts::TAU;
+
+    const BLOCK_SIZE: usize = 256;
+    /// The number of samples analysed. Each test frequency sits exactly on a
+    /// bin of a DFT of this size.
+    const NUM_SAMPLES: usize = 4096;
+    /// The number of samples processed before analysis, so that the
+    /// oversampling filters have settled.
+    const WARMUP: usize = 1024;
+
+    /// Returns the power of `signal` at DFT bin `bin`.
+    fn bin_power(signal: &[f64], bin: usize) -> f64 {
+        let freq = TAU * bin as f64 / signal.len() as f64;
+        let (re, im) =
+            signal.iter().enumerate().fold((0.0, 0.0), |(re, im), (i, s)| {
+                let phase = freq * i as f64;
+                (re + s * phase.cos(), im + s * phase.sin())
+            });
+
+        (re * re + im * im) * 2.0 / signal.len() as f64
+    }
+
+    /// Returns the ratio of aliased power to harmonic power after hard
+    /// clipping a sine at DFT bin `bin`, in decibels.
+    fn aliasing_db(bin: usize, factor: usize) -> f64 {
+        let mut os = Lanczos3Oversampler::new(BLOCK_SIZE, 4, 3);
+        let mut ws = Waveshaper::new();
+        ws.set_xfer_function(|x, _| (x * 8.0).tanh());
+
+        let freq = TAU * bin as f64 / NUM_SAMPLES as f64;
+        let mut signal: Vec<f64> = (0..WARMUP + NUM_SAMPLES)
+            .map(|i| (freq * i as f64).sin() * 0.8)
+            .collect();
+
+        for block in signal.chunks_mut(BLOCK_SIZE) {
+            os.process(block, factor, |upsampled| {
+                upsampled.iter_mut().for_each(|s| *s = ws.process(*s));
+            });
+        }
+
+        let signal = &signal[WARMUP..];
+        let total = signal.iter().map(|s| s * s).sum::<f64>();
+
+        // every harmonic below the nyquist frequency is wanted
+        let harmonics: f64 = (bin..NUM_SAMPLES / 2)
+            .step_by(bin)
+            .map(|h| bin_power(signal, h))
+            .sum();
+
+        10.0 * ((total - harmonics).max(f64::EPSILON) / harmonics).log10()
+    }
+
+    #[test]
+    fn passes_signal_with_constant_latency() {
+        for factor in 0..=4 {
+            let mut os = Lanczos3Oversampler::new(BLOCK_SIZE, 4, 3);
+            let input: Vec<f64> =
+                (0..2048).map(|i| (TAU * 0.01 * i as f64).sin()).collect();
+
+            let mut output = input.clone();
+            for block in output.chunks_mut(BLOCK_SIZE) {
+                os.process(block, factor, |_| {});
+            }
+
+            let latency = os.latency(factor) as usize;
+            for i in WARMUP..output.len() {
+                assert!((output[i] - input[i - latency]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn oversampling_suppresses_aliasing() {
+        // a sweep of sines from roughly 6 kHz to 18 kHz at 44.1 kHz
+        let sweep = [557, 743, 1031, 1399, 1693];
+        let aliasing: Vec<Vec<f64>> = sweep
+            .iter()
+            .map(|&bin| (0..=4).map(|f| aliasing_db(bin, f)).collect())
+            .collect();
+
+        for factor in 1..=4 {
+            let suppression: Vec<f64> =
+                aliasing.iter().map(|db| db[0] - db[factor]).collect();
+
+            // the filters are gentle, so aliasing near nyquist is only
+            // partly suppressed
+            assert!(
+                suppression.iter().all(|&s| s > 5.0),
+                "factor {factor}: {suppression:?}"
+            );
+
+            let mean = suppression.iter().sum::<f64>() / sweep.len() as f64;
+            let expected = if factor == 1 { 8.0 } else { 15.0 };
+            assert!(mean > expected, "factor {factor}: {suppression:?}");
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! A Schroeder allpass diffuser.
+
+use super::*;
+
+/// A Schroeder allpass filter, which smears transients over its delay time
+/// without colouring the signal's magnitude response.
+#[derive(Clone, Debug)]
+pub struct Diffuser {
+    buffer: RingBuffer,
+    delay_samples: f64,
+    gain: f64,
+}
+
+impl Diffuser {
+    /// Returns a new diffuser with a delay of `delay_ms` and a feedback
+    /// `gain`, which should be less than `1.0`.
+    pub fn new(delay_ms: f64, gain: f64, sample_rate: f64) -> Self {
+        let delay_samples = (delay_ms * 0.001 * sample_rate).round().max(1.0);
+
+        Self {
+            buffer: RingBuffer::new(delay_samples as usize + 1, sample_rate)
+                .with_interpolation(InterpType::NoInterp),
+            delay_samples,
+            gain: gain.clamp(-0.99, 0.99),
+        }
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, input: f64) -> f64 {
+        let delayed = self.buffer.read_at(self.delay_samples);
+        let w = delayed.mul_add(self.gain, input);
+
+        self.buffer.push(w);
+        w.mul_add(-self.gain, delayed)
+    }
+
+    /// Clears the delay line.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+}
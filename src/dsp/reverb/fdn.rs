@@ -0,0 +1,252 @@
+//! A stereo feedback delay network reverb.
+
+use super::*;
+
+/// The number of delay lines in the feedback network.
+const NUM_LINES: usize = 8;
+/// The lengths of the delay lines at full size, in milliseconds. These are
+/// spread unevenly, with no simple ratios between them, so that their echoes
+/// don't line up. They aren't rounded to whole samples, as the lines are
+/// scaled by the size and modulated, so can't be kept mutually prime.
+const LINE_LENGTHS_MS: [f64; NUM_LINES] =
+    [44.9, 55.7, 61.6, 65.5, 80.0, 89.8, 101.6, 109.6];
+/// The rates of the delay line modulation LFOs in hertz.
+const LINE_MOD_RATES: [f64; NUM_LINES] =
+    [0.31, 0.43, 0.53, 0.67, 0.73, 0.89, 0.97, 1.13];
+/// The length of the delay lines at the smallest size, relative to full size.
+const MIN_SIZE_SCALE: f64 = 0.1;
+/// The furthest the delay lines are modulated at full modulation, in
+/// milliseconds.
+const MAX_MOD_MS: f64 = 1.0;
+
+/// The delays and gains of the input diffusers, as in Dattorro's plate.
+const DIFFUSERS: [(f64, f64); 4] =
+    [(4.77, 0.75), (3.60, 0.75), (12.73, 0.625), (9.31, 0.625)];
+
+/// The highest cutoff of the damping filters in hertz.
+const MAX_DAMPING_HZ: f64 = 18000.0;
+/// The lowest cutoff of the damping filters in hertz.
+const MIN_DAMPING_HZ: f64 = 500.0;
+
+/// A stereo reverb, made of a pre-delay, a chain of allpass diffusers and an
+/// eight-line feedback delay network with a Householder mixing matrix.
+///
+/// Each delay line is damped by a first-order lowpass filter and slowly
+/// modulated to avoid metallic ringing. The output is fully wet.
+#[derive(Clone, Debug)]
+pub struct Reverb {
+    pre_delay: [RingBuffer; 2],
+    diffusers: [Vec<Diffuser>; 2],
+
+    lines: Vec<RingBuffer>,
+    damping_filters: Vec<FirstOrderFilter>,
+    lfos: Vec<Phasor>,
+    /// The delay of each line in samples, before modulation.
+    line_delays: [f64; NUM_LINES],
+    /// The feedback gain of each line, which sets the decay time.
+    line_gains: [f64; NUM_LINES],
+
+    size: f64,
+    decay_secs: f64,
+    damping: f64,
+    pre_delay_ms: f64,
+    modulation: f64,
+
+    sample_rate: f64,
+}
+
+impl Reverb {
+    /// The shortest decay time in seconds.
+    pub const MIN_DECAY_SECS: f64 = 0.1;
+    /// The longest decay time in seconds.
+    pub const MAX_DECAY_SECS: f64 = 20.0;
+    /// The longest pre-delay in milliseconds.
+    pub const MAX_PRE_DELAY_MS: f64 = 250.0;
+
+    pub fn new(sample_rate: f64) -> Self {
+        let pre_delay_size =
+            (Self::MAX_PRE_DELAY_MS * 0.001 * sample_rate).ceil() as usize + 2;
+        let pre_delay = RingBuffer::new(pre_delay_size, sample_rate)
+            .with_interpolation(InterpType::Linear);
+
+        let diffusers: Vec<Diffuser> = DIFFUSERS
+            .iter()
+            .map(|&(delay_ms, gain)| Diffuser::new(delay_ms, gain, sample_rate))
+            .collect();
+
+        let lines = LINE_LENGTHS_MS
+            .iter()
+            .map(|&len_ms| {
+                let max_ms = len_ms + MAX_MOD_MS;
+                let size = (max_ms * 0.001 * sample_rate).ceil() as usize + 4;
+
+                RingBuffer::new(size, sample_rate)
+                    .with_interpolation(InterpType::DefaultCubic)
+            })
+            .collect();
+
+        let mut damping = FirstOrderFilter::new(sample_rate);
+        damping.set_type(FilterType::Lowpass);
+
+        let lfos = LINE_MOD_RATES
+            .iter()
+            .enumerate()
+            .map(|(i, &rate)| {
+                let mut lfo = Phasor::new(rate, sample_rate);
+                lfo.phase = i as f64 / NUM_LINES as f64;
+                lfo
+            })
+            .collect();
+
+        let mut reverb = Self {
+            pre_delay: [pre_delay.clone(), pre_delay],
+            diffusers: [diffusers.clone(), diffusers],
+
+            lines,
+            damping_filters: vec![damping; NUM_LINES],
+            lfos,
+            line_delays: [0.0; NUM_LINES],
+            line_gains: [0.0; NUM_LINES],
+
+            size: 0.5,
+            decay_secs: 2.0,
+            damping: 0.5,
+            pre_delay_ms: 20.0,
+            modulation: 0.3,
+
+            sample_rate,
+        };
+
+        reverb.set_damping(reverb.damping);
+        reverb.update_lines();
+
+        reverb
+    }
+
+    /// Sets the size of the reverb, between `0.0` and `1.0`, which scales the
+    /// lengths of the delay lines.
+    pub fn set_size(&mut self, size: f64) {
+        self.size = size.clamp(0.0, 1.0);
+        self.update_lines();
+    }
+
+    /// Sets the time for the reverb to decay by 60 dB, in seconds.
+    pub fn set_decay(&mut self, decay_secs: f64) {
+        self.decay_secs =
+            decay_secs.clamp(Self::MIN_DECAY_SECS, Self::MAX_DECAY_SECS);
+        self.update_lines();
+    }
+
+    /// Sets how quickly high frequencies decay, between `0.0` and `1.0`.
+    pub fn set_damping(&mut self, damping: f64) {
+        self.damping = damping.clamp(0.0, 1.0);
+
+        let max_hz = MAX_DAMPING_HZ.min(self.sample_rate * 0.45);
+        let cutoff = max_hz * (MIN_DAMPING_HZ / max_hz).powf(self.damping);
+
+        for filter in &mut self.damping_filters {
+            filter.set_freq(cutoff);
+        }
+    }
+
+    /// Sets the delay before the reverb starts, in milliseconds.
+    pub fn set_pre_delay(&mut self, pre_delay_ms: f64) {
+        self.pre_delay_ms = pre_delay_ms.clamp(0.0, Self::MAX_PRE_DELAY_MS);
+    }
+
+    /// Sets the depth of the delay line modulation, between `0.0` and `1.0`.
+    pub fn set_modulation(&mut self, modulation: f64) {
+        self.modulation = modulation.clamp(0.0, 1.0);
+    }
+
+    /// Clears the state of the reverb.
+    pub fn reset(&mut self) {
+        self.pre_delay.iter_mut().for_each(RingBuffer::clear);
+        self.lines.iter_mut().for_each(RingBuffer::clear);
+
+        for diffuser in self.diffusers.iter_mut().flatten() {
+            diffuser.reset();
+        }
+        for filter in &mut self.damping_filters {
+            filter.reset();
+        }
+    }
+
+    /// Recomputes the delay and feedback gain of each line from the size and
+    /// decay time.
+    fn update_lines(&mut self) {
+        let scale = (1.0 - MIN_SIZE_SCALE).mul_add(self.size, MIN_SIZE_SCALE);
+        let decay_samples = self.decay_secs * self.sample_rate;
+
+        for (i, &len_ms) in LINE_LENGTHS_MS.iter().enumerate() {
+            let delay = len_ms * scale * 0.001 * self.sample_rate;
+
+            self.line_delays[i] = delay;
+            // -60 dB over the decay time
+            self.line_gains[i] = 10.0f64.powf(-3.0 * delay / decay_samples);
+        }
+    }
+}
+
+impl Default for Reverb {
+    fn default() -> Self {
+        Self::new(unsafe { SAMPLE_RATE })
+    }
+}
+
+impl Effect for Reverb {
+    fn process_stereo(&mut self, in_l: f64, in_r: f64) -> (f64, f64) {
+        let pre_delay_samples =
+            (self.pre_delay_ms * 0.001 * self.sample_rate).max(1.0);
+        let mut input = [in_l, in_r];
+
+        for (ch, sample) in input.iter_mut().enumerate() {
+            let buffer = &mut self.pre_delay[ch];
+            let delayed = buffer.read_at(pre_delay_samples);
+            buffer.push(*sample);
+
+            *sample = self.diffusers[ch]
+                .iter_mut()
+                .fold(delayed, |x, diffuser| diffuser.process(x));
+        }
+
+        let mod_samples =
+            self.modulation * MAX_MOD_MS * 0.001 * self.sample_rate;
+        let mut outputs = [0.0; NUM_LINES];
+
+        for (i, output) in outputs.iter_mut().enumerate() {
+            let lfo = &mut self.lfos[i];
+            let offset = (TAU * lfo.phase).sin().mul_add(0.5, 0.5);
+            lfo.increment_phase();
+
+            let delay = offset.mul_add(mod_samples, self.line_delays[i]);
+            *output = self.lines[i].read_at(delay.max(2.0));
+        }
+
+        // the Householder matrix reflects each line's output about the mean
+        // of all of them, which mixes them while preserving their energy
+        let reflection = outputs.iter().sum::<f64>() * (2.0 / NUM_LINES as f64);
+        let (mut out_l, mut out_r) = (0.0, 0.0);
+
+        for (i, &output) in outputs.iter().enumerate() {
+            let sign = if i % 4 < 2 { 1.0 } else { -1.0 };
+
+            if i % 2 == 0 {
+                out_l += output * sign;
+            }
+            else {
+                out_r += output * sign;
+            }
+
+            let feedback = (output - reflection) * self.line_gains[i];
+            let damped = self.damping_filters[i].process(feedback);
+            self.lines[i].push(damped + input[i % 2] * sign);
+        }
+
+        (out_l * 0.5, out_r * 0.5)
+    }
+
+    fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+}
@@ -0,0 +1,74 @@
+//! Algorithmic reverb.
+
+use super::*;
+use crate::dsp::synthesis::{GeneratorProcessor, Phasor};
+use std::f64::consts::TAU;
+
+pub mod diffuser;
+pub mod fdn;
+
+pub use diffuser::Diffuser;
+pub use fdn::Reverb;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 44100.0;
+
+    fn impulse_response(reverb: &mut Reverb, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| {
+                let x = if i == 0 { 1.0 } else { 0.0 };
+                reverb.process_stereo(x, x).0
+            })
+            .collect()
+    }
+
+    fn energy(samples: &[f64]) -> f64 {
+        samples.iter().map(|x| x * x).sum()
+    }
+
+    #[test]
+    fn diffuser_is_allpass() {
+        let mut diffuser = Diffuser::new(5.0, 0.7, SAMPLE_RATE);
+
+        let mut impulse = vec![0.0; 44100];
+        impulse[0] = 1.0;
+        let output: Vec<f64> =
+            impulse.iter().map(|&x| diffuser.process(x)).collect();
+
+        assert!((energy(&output) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pre_delay_delays_the_reverb() {
+        let mut reverb = Reverb::new(SAMPLE_RATE);
+        reverb.set_pre_delay(100.0);
+
+        let output = impulse_response(&mut reverb, 8820);
+
+        assert!(output[..4410].iter().all(|x| x.abs() < 1e-12));
+        assert!(energy(&output[4410..]) > 1e-3);
+    }
+
+    #[test]
+    fn longer_decay_rings_longer() {
+        let tail_energy = |decay_secs| {
+            let mut reverb = Reverb::new(SAMPLE_RATE);
+            reverb.set_decay(decay_secs);
+
+            let output = impulse_response(&mut reverb, 88200);
+            assert!(output.iter().all(|x| x.is_finite()));
+
+            energy(&output[44100..])
+        };
+
+        let short = tail_energy(0.5);
+        let long = tail_energy(4.0);
+
+        // after a second, a 0.5 s decay has fallen by 120 dB
+        assert!(short < 1e-9);
+        assert!(long > 1e-3);
+    }
+}
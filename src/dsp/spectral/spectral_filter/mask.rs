@@ -77,6 +77,30 @@ impl SpectralMask {
         self.len()
     }
 
+    /// Fills the first `len` bins of the mask with `f`, which receives the
+    /// logarithmically-scaled, normalized frequency of each bin (where `0.0` is
+    /// 20 Hz and `1.0` is the Nyquist frequency).
+    ///
+    /// The 0 Hz bin is skipped, and any bins below 20 Hz are set to `0.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than the working size of the mask.
+    pub fn fill_log_bins<F: FnMut(f64) -> f64>(&mut self, len: usize, mut f: F) {
+        let sr = unsafe { SAMPLE_RATE };
+
+        // start at 1 to skip the 0 Hz component
+        for i in 1..len {
+            let bin_hz = Self::bin_freq(i, len, sr);
+            if bin_hz < 20.0 {
+                self.points[i] = 0.0;
+                continue;
+            }
+
+            self.points[i] = f(freq_log_norm(bin_hz, 20.0, sr));
+        }
+    }
+
     /// Returns the frequency of bin with index `idx`.
     ///
     /// # Panics
@@ -91,3 +115,61 @@ impl SpectralMask {
         k * (nyquist / size)
     }
 }
+
+/// A pair of [`SpectralMask`]s — one for each channel of a stereo signal.
+///
+/// Both masks share the same maximum and working sizes.
+#[derive(Clone, Debug, Default)]
+pub struct StereoMask {
+    /// The left channel's mask.
+    pub left: SpectralMask,
+    /// The right channel's mask.
+    pub right: SpectralMask,
+}
+
+impl StereoMask {
+    /// Creates a new `StereoMask` with `max_size` capacity for each channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is not a power-of-two value, or if it is greater
+    /// than 2^14 (16,384).
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            left: SpectralMask::new(max_size),
+            right: SpectralMask::new(max_size),
+        }
+    }
+
+    /// Returns a reference to the mask for channel `ch` — `0` is the left
+    /// channel, and any other value is the right channel.
+    pub fn channel(&self, ch: usize) -> &SpectralMask {
+        if ch == 0 {
+            &self.left
+        }
+        else {
+            &self.right
+        }
+    }
+
+    /// Returns a mutable reference to the mask for channel `ch` — `0` is the
+    /// left channel, and any other value is the right channel.
+    pub fn channel_mut(&mut self, ch: usize) -> &mut SpectralMask {
+        if ch == 0 {
+            &mut self.left
+        }
+        else {
+            &mut self.right
+        }
+    }
+
+    /// Copies the first `len` elements of the left mask into the right mask,
+    /// so that both channels are filtered identically.
+    ///
+    /// `len` is clamped to the working size of both masks.
+    pub fn copy_left_to_right(&mut self, len: usize) {
+        let len = len.min(self.left.len()).min(self.right.len());
+
+        self.right[..len].copy_from_slice(&self.left[..len]);
+    }
+}
@@ -16,6 +16,9 @@ use mask::*;
 
 /// A spectral filtering processor, which accepts a `SpectralMask` as a frequency
 /// mask and applies it to an audio signal in the frequency domain.
+///
+/// Each channel holds its own mask, so a [`StereoMask`] may be used to filter the
+/// left and right channels independently.
 pub struct SpectralFilter {
     /// stft processor
     stft: StftHelper,
@@ -39,8 +42,8 @@ pub struct SpectralFilter {
 
     mix: Smoother<f64>,
 
-    /// filter mask
-    mask: SpectralMask,
+    /// filter masks, one per channel
+    masks: Vec<SpectralMask>,
 }
 
 impl SpectralFilter {
@@ -77,8 +80,12 @@ impl SpectralFilter {
 
             mix: Smoother::new(30.0, 1.0, unsafe { SAMPLE_RATE }),
 
-            mask: SpectralMask::new(max_block_size)
-                .with_size(max_block_size / 2),
+            masks: (0..num_channels)
+                .map(|_| {
+                    SpectralMask::new(max_block_size)
+                        .with_size(max_block_size / 2)
+                })
+                .collect(),
         }
     }
 
@@ -110,22 +117,38 @@ impl SpectralFilter {
         self.fft = RealFftPlanner::new().plan_fft_forward(block_size);
         self.ifft = RealFftPlanner::new().plan_fft_inverse(block_size);
 
-        // mask
-        unsafe {
-            self.mask.set_len(block_size / 2);
+        // masks
+        for mask in &mut self.masks {
+            unsafe {
+                mask.set_len(block_size / 2);
+            }
         }
         // self.mask.resize(block_size / 2, 1.0);
     }
 
-    /// Clones `mask` into the filter.
+    /// Clones `mask` into the filter for every channel.
     ///
     /// Clones `min(self.block_size(), mask.len())` elements.
     ///
     /// (See [`set_block_size()`](Self::set_block_size))
     /// (See [`max_block_size()`](Self::max_block_size))
     pub fn set_mask(&mut self, mask: &SpectralMask) {
-        for (dst, &src) in self.mask.iter_mut().zip(mask.iter()) {
-            *dst = src;
+        for ch_mask in &mut self.masks {
+            for (dst, &src) in ch_mask.iter_mut().zip(mask.iter()) {
+                *dst = src;
+            }
+        }
+    }
+
+    /// Clones the left and right masks of `mask` into the filter. If the filter
+    /// has more than two channels, the right mask is used for the extra channels.
+    ///
+    /// Clones `min(self.block_size(), mask.left.len())` elements per channel.
+    pub fn set_stereo_mask(&mut self, mask: &StereoMask) {
+        for (ch, ch_mask) in self.masks.iter_mut().enumerate() {
+            for (dst, &src) in ch_mask.iter_mut().zip(mask.channel(ch).iter()) {
+                *dst = src;
+            }
         }
     }
 
@@ -139,7 +162,7 @@ impl SpectralFilter {
     #[allow(clippy::missing_panics_doc)] // this function will not panic.
     pub fn process_block<B>(&mut self, buffer: &mut B)
     where
-        B: StftInputMut,
+        B: StftInputMut + ?Sized,
     {
         self.store_dry(buffer);
 
@@ -158,7 +181,7 @@ impl SpectralFilter {
                 // process magnitudes
                 self.complex_buffers[ch_idx]
                     .iter_mut()
-                    .zip(self.mask.iter())
+                    .zip(self.masks[ch_idx].iter())
                     .for_each(|(bin, &mask)| {
                         *bin *= mask;
                     });
@@ -187,16 +210,16 @@ impl SpectralFilter {
 
     /// The current block size of the filter.
     pub fn block_size(&self) -> usize {
-        self.mask.len()
+        self.masks[0].len()
     }
 
-    /// Clears the filter's internal buffers.
+    /// Clears the filter's internal buffers. The masks are left unaltered.
     pub fn clear(&mut self) {
         self.complex_buffers
             .iter_mut()
             .for_each(|b| b.fill(Complex::new(0.0, 0.0)));
         self.stft.clear();
-        self.mask.fill(0.0);
+        self.dry_buffer.fill(0.0);
     }
 
     /// The compensation factor for a hanning window, resulting in unity gain for
@@ -207,7 +230,7 @@ impl SpectralFilter {
 
     /// Stores the input data into a temporary scratch buffer, used for
     /// dry/wet mixing.
-    fn store_dry<B: StftInput>(&mut self, buffer: &B) {
+    fn store_dry<B: StftInput + ?Sized>(&mut self, buffer: &B) {
         let num_ch = buffer.num_channels();
         let num_sm = buffer.num_samples();
 
@@ -224,7 +247,10 @@ impl SpectralFilter {
         }
     }
 
-    fn apply_mix<B: StftInputMut>(&mut self, buffer: &mut B) {
+    fn apply_mix<B>(&mut self, buffer: &mut B)
+    where
+        B: StftInputMut + ?Sized,
+    {
         let num_ch = buffer.num_channels();
         let num_sm = buffer.num_samples();
 
@@ -265,8 +291,12 @@ impl Default for SpectralFilter {
             fft: RealFftPlanner::new().plan_fft_forward(DEFAULT_BLOCK_SIZE),
             ifft: RealFftPlanner::new().plan_fft_inverse(DEFAULT_BLOCK_SIZE),
 
-            mask: SpectralMask::new(DEFAULT_BLOCK_SIZE)
-                .with_size(DEFAULT_BLOCK_SIZE / 2),
+            masks: (0..NUM_CHANNELS)
+                .map(|_| {
+                    SpectralMask::new(DEFAULT_BLOCK_SIZE)
+                        .with_size(DEFAULT_BLOCK_SIZE / 2)
+                })
+                .collect(),
 
             compensated_window_function: Vec::default(),
             window_function: Vec::default(),
@@ -144,7 +144,7 @@ impl StftHelper {
         overlap_factor: usize,
         mut callback: F,
     ) where
-        M: StftInputMut,
+        M: StftInputMut + ?Sized,
         F: FnMut(usize, &mut [f64]),
     {
         assert_eq!(main_buffer.num_channels(), self.num_channels());
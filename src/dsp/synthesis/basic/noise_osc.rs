@@ -5,11 +5,43 @@ use crate::prelude::*;
 
 /// A white noise oscillator.
 #[derive(Debug, Clone, Copy)]
-pub struct NoiseOsc;
+pub struct NoiseOsc {
+    rng: SeededRng,
+}
 
 impl NoiseOsc {
+    /// Creates a new noise oscillator, which always produces the same noise
+    /// from the same `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { rng: SeededRng::new(seed) }
+    }
+
     /// Produces a single noise sample at 0.0 dBFS.
-    pub fn process() -> f64 {
-        random_f64().mul_add(2.0, -1.0)
+    pub fn process(&mut self) -> f64 {
+        self.rng.next_f64().mul_add(2.0, -1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(mut osc: NoiseOsc) -> Vec<f64> {
+        (0..256).map(|_| osc.process()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_noise() {
+        assert_eq!(samples(NoiseOsc::new(42)), samples(NoiseOsc::new(42)));
+        assert_ne!(samples(NoiseOsc::new(42)), samples(NoiseOsc::new(43)));
+    }
+
+    #[test]
+    fn noise_is_bipolar() {
+        let noise = samples(NoiseOsc::new(1));
+
+        assert!(noise.iter().all(|s| (-1.0..=1.0).contains(s)));
+        assert!(noise.iter().any(|&s| s < -0.5));
+        assert!(noise.iter().any(|&s| s > 0.5));
     }
 }
@@ -14,7 +14,7 @@ pub enum Generator {
     /// A basic square wave generator.
     Square(SquareOsc),
     /// A basic white noise generator.
-    Noise,
+    Noise(NoiseOsc),
 }
 
 impl Generator {
@@ -24,7 +24,7 @@ impl Generator {
             Self::Tri(gen) => gen.process(),
             Self::Saw(gen) => gen.process(),
             Self::Square(gen) => gen.process(),
-            Self::Noise => (NoiseOsc::process(), NoiseOsc::process()),
+            Self::Noise(gen) => (gen.process(), gen.process()),
         }
     }
 
@@ -34,7 +34,7 @@ impl Generator {
             Self::Tri(gen) => gen.set_freq(freq_hz, sample_rate),
             Self::Saw(gen) => gen.set_freq(freq_hz, sample_rate),
             Self::Square(gen) => gen.set_freq(freq_hz, sample_rate),
-            Self::Noise => {}
+            Self::Noise(_) => {}
         }
     }
 }
@@ -1,4 +1,4 @@
-// Pseudo-perlin noise contour lines implementation.
+// Noise contour lines implementation.
 
 fn s_curve_round(in: f32, tension: f32) -> f32 {
     let x: f32 = clamp(in, -1.0, 1.0);
@@ -132,6 +132,17 @@ struct Params {
     width: u32;
     // The vertical resolution.
     height: u32;
+    // The noise function: 0 = perlin, 1 = simplex, 2 = worley, 3 = fbm,
+    // 4 = ridged.
+    noise: u32;
+    // The number of octaves used by fractal noise.
+    octaves: u32;
+    // The frequency multiplier between octaves of fractal noise.
+    lacunarity: f32;
+    // The amplitude multiplier between octaves of fractal noise.
+    persistence: f32;
+    // The amount of domain warping.
+    warp: f32;
 };
 
 // @group(0)
@@ -147,6 +158,150 @@ var<storage, read_write> output: Buffer;
 [[group(0), binding(1)]]
 var<uniform> params: Params;
 
+// 2D simplex noise, using the same rotating gradients as pseudo_noise3
+fn simplex_noise3(pos: vec3<f32>) -> f32 {
+    let F2: f32 = 0.36602540;
+    let G2: f32 = 0.21132487;
+
+    let s = (pos.x + pos.y) * F2;
+    let i: vec2<f32> = floor(pos.xy + s);
+    let t = (i.x + i.y) * G2;
+    let p0: vec2<f32> = pos.xy - (i - t);
+
+    var i1: vec2<f32>;
+    if (p0.x > p0.y) {
+        i1 = vec2<f32>(1.0, 0.0);
+    } else {
+        i1 = vec2<f32>(0.0, 1.0);
+    }
+
+    let p1: vec2<f32> = p0 - i1 + G2;
+    let p2: vec2<f32> = p0 - 1.0 + 2.0 * G2;
+
+    var n: f32 = 0.0;
+
+    let t0 = 0.5 - dot(p0, p0);
+    if (t0 >= 0.0) {
+        n = n + t0 * t0 * t0 * t0 * dot(grad(i, pos.z), p0);
+    }
+
+    let t1 = 0.5 - dot(p1, p1);
+    if (t1 >= 0.0) {
+        n = n + t1 * t1 * t1 * t1 * dot(grad(i + i1, pos.z), p1);
+    }
+
+    let t2 = 0.5 - dot(p2, p2);
+    if (t2 >= 0.0) {
+        let g = grad(i + vec2<f32>(1.0, 1.0), pos.z);
+        n = n + t2 * t2 * t2 * t2 * dot(g, p2);
+    }
+
+    return n * 70.0;
+}
+
+// Worley noise: the distance to the nearest of a set of points, one per grid
+// cell, which orbit their cells as z changes
+fn worley_noise3(pos: vec3<f32>) -> f32 {
+    let i: vec2<f32> = floor(pos.xy);
+    var min_dist_sq: f32 = 1000.0;
+
+    for (var dx: f32 = -1.0; dx <= 1.0; dx = dx + 1.0) {
+        for (var dy: f32 = -1.0; dy <= 1.0; dy = dy + 1.0) {
+            let c = i + vec2<f32>(dx, dy);
+            let h1 = hash2(c);
+            let h2 = hash2(c + vec2<f32>(57.0, 113.0));
+            let p = c + 0.5 + 0.4 * vec2<f32>(
+                sin(6.283185 * h1 + pos.z),
+                cos(6.283185 * h2 + pos.z),
+            );
+
+            let d = p - pos.xy;
+            min_dist_sq = min(min_dist_sq, dot(d, d));
+        }
+    }
+
+    return min(sqrt(min_dist_sq), 1.0) * 2.0 - 1.0;
+}
+
+// Fractal Brownian motion, from several octaves of pseudo_noise3
+fn fbm_noise3(pos: vec3<f32>) -> f32 {
+    var sum: f32 = 0.0;
+    var norm: f32 = 0.0;
+    var amp: f32 = 1.0;
+    var freq: f32 = 1.0;
+
+    for (var i: u32 = 0u; i < params.octaves; i = i + 1u) {
+        let offset = f32(i) * 17.31;
+        let p = vec3<f32>(pos.xy * freq + offset, pos.z);
+
+        sum = sum + amp * pseudo_noise3(p);
+        norm = norm + amp;
+        amp = amp * params.persistence;
+        freq = freq * params.lacunarity;
+    }
+
+    return sum / max(norm, 0.0000001);
+}
+
+// Ridged multifractal noise, from several octaves of pseudo_noise3
+fn ridged_noise3(pos: vec3<f32>) -> f32 {
+    var sum: f32 = 0.0;
+    var norm: f32 = 0.0;
+    var weight: f32 = 1.0;
+    var amp: f32 = 1.0;
+    var freq: f32 = 1.0;
+
+    for (var i: u32 = 0u; i < params.octaves; i = i + 1u) {
+        let offset = f32(i) * 17.31;
+        let p = vec3<f32>(pos.xy * freq + offset, pos.z);
+
+        let ridge = 1.0 - abs(pseudo_noise3(p));
+        let signal = ridge * ridge * weight;
+
+        // creases in lower octaves suppress detail in higher ones
+        weight = clamp(signal * 2.0, 0.0, 1.0);
+        sum = sum + amp * signal;
+        norm = norm + amp;
+        amp = amp * params.persistence;
+        freq = freq * params.lacunarity;
+    }
+
+    return sum / max(norm, 0.0000001) * 2.0 - 1.0;
+}
+
+// Offsets the position by a second noise field, scaled by the warp amount
+fn warp(pos: vec3<f32>) -> vec3<f32> {
+    if (params.warp <= 0.0) {
+        return pos;
+    }
+
+    let offset = vec2<f32>(
+        pseudo_noise3(vec3<f32>(pos.xy + vec2<f32>(5.2, 1.3), pos.z)),
+        pseudo_noise3(vec3<f32>(pos.xy + vec2<f32>(1.7, 9.2), pos.z)),
+    );
+
+    return vec3<f32>(pos.xy + params.warp * offset, pos.z);
+}
+
+fn noise3(in: vec3<f32>) -> f32 {
+    let pos = warp(in);
+
+    if (params.noise == 1u) {
+        return simplex_noise3(pos);
+    }
+    if (params.noise == 2u) {
+        return worley_noise3(vec3<f32>(pos.xy * 3.0, pos.z));
+    }
+    if (params.noise == 3u) {
+        return fbm_noise3(pos);
+    }
+    if (params.noise == 4u) {
+        return ridged_noise3(pos);
+    }
+
+    return pseudo_noise3(pos);
+}
+
 // @compute
 // @workgroup_size(1, 1, 1)
 [[stage(compute), workgroup_size(16, 16, 1)]]
@@ -155,7 +310,7 @@ fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {
     let uv: vec2<f32> = vec2<f32>(f32(id.x) / f32(params.width), f32(id.y) / f32(params.height));
 
     // generate raw noise value
-    let noise: f32 = pseudo_noise3(vec3<f32>(uv, params.z));
+    let noise: f32 = noise3(vec3<f32>(uv, params.z));
 
     // get contoured brightness value
     let br: f32 = brightness(params.num_contours, params.upper, noise);
@@ -0,0 +1,265 @@
+//! Noise contour line generator run on the CPU.
+//!
+//! This mirrors the algorithm in `contours.wgsl`, so that its output matches
+//! that of [`ContoursGPU`].
+
+use super::{
+    random_z, ContourNoiseParams, ContourNoiseParamsAtomic, ContoursGPU,
+};
+use crate::generative::{MaskBackend, MaskGenerator, MaskSource};
+use crate::prelude::*;
+use atomic_float::AtomicF32;
+use std::ops::RangeInclusive;
+use std::sync::{atomic::AtomicU32, Arc, Mutex};
+
+/// A noise contour line generator computed on the CPU.
+pub struct ContoursCPU {
+    num_contours: Arc<AtomicU32>,
+    upper: Arc<AtomicF32>,
+    z_increment: Arc<AtomicF32>,
+    z: AtomicF64,
+    rng: Mutex<SessionRng>,
+
+    noise_params: Arc<ContourNoiseParamsAtomic>,
+    /// The noise parameters used for the current step.
+    noise: ContourNoiseParams,
+}
+
+impl ContoursCPU {
+    pub fn new() -> Self {
+        let rng = Mutex::new(SessionRng::new("contours"));
+
+        Self {
+            num_contours: Arc::new(AtomicU32::new(8)),
+            upper: Arc::new(AtomicF32::new(0.6)),
+            z_increment: Arc::new(AtomicF32::new(0.2)),
+            z: AtomicF64::new(random_z(&rng)),
+            rng,
+
+            noise_params: Arc::new(ContourNoiseParamsAtomic::default()),
+            noise: ContourNoiseParams::default(),
+        }
+    }
+
+    pub fn with_z_increment(self, increment: f32) -> Self {
+        self.set_z_increment(increment);
+        self
+    }
+
+    pub fn with_num_contours(self, num_contours: u32) -> Self {
+        self.set_num_contours(num_contours);
+        self
+    }
+
+    pub fn with_contour_range(self, range: RangeInclusive<f32>) -> Self {
+        self.set_contour_range(range);
+        self
+    }
+
+    pub fn with_noise_params(mut self, params: ContourNoiseParams) -> Self {
+        self.noise_params.store(params);
+        self.noise = params;
+        self
+    }
+
+    pub fn set_num_contours(&self, num_contours: u32) {
+        self.num_contours.sr(num_contours);
+    }
+
+    pub fn set_contour_range(&self, range: RangeInclusive<f32>) {
+        let upper = *range.end();
+
+        if upper <= 1.0 {
+            self.upper.sr(upper);
+        }
+    }
+
+    pub fn set_z_increment(&self, increment: f32) {
+        self.z_increment.sr(increment);
+    }
+
+    pub fn randomize(&self) {
+        self.z.sr(random_z(&self.rng));
+    }
+
+    pub fn num_contours_arc(&self) -> Arc<AtomicU32> {
+        Arc::clone(&self.num_contours)
+    }
+
+    pub fn upper_arc(&self) -> Arc<AtomicF32> {
+        Arc::clone(&self.upper)
+    }
+
+    pub fn z_increment_arc(&self) -> Arc<AtomicF32> {
+        Arc::clone(&self.z_increment)
+    }
+
+    pub fn noise_params_arc(&self) -> Arc<ContourNoiseParamsAtomic> {
+        Arc::clone(&self.noise_params)
+    }
+
+    /// Maps `noise` to the brightness of the contour lines, which are feathered
+    /// at their edges.
+    fn brightness(num_contours: u32, upper: f64, noise: f64) -> f64 {
+        let s_curve = |x: f64| (x * 20.0).tanh() / 20.0f64.tanh();
+
+        let px = (((noise + 1.0) / 2.0) * num_contours as f64).fract();
+        let mid = upper * 0.5;
+
+        if (0.0..=mid).contains(&px) {
+            s_curve(map(px, 0.0, mid, 0.0, 0.5))
+        }
+        else if (mid..=upper).contains(&px) {
+            s_curve(map(px, upper, mid, 0.0, 0.5))
+        }
+        else {
+            0.0
+        }
+    }
+}
+
+impl Default for ContoursCPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaskSource for ContoursCPU {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        let noise = self.noise.sample(x, y, self.z.lr());
+
+        Self::brightness(self.num_contours.lr(), self.upper.lr() as f64, noise)
+            .clamp(0.0, 1.0)
+    }
+}
+
+impl MaskGenerator for ContoursCPU {
+    fn step(&mut self, delta_time: f64) {
+        let z = self.z.lr() + self.z_increment.lr() as f64 * delta_time;
+        self.z.sr(z);
+        self.noise = self.noise_params.load();
+    }
+}
+
+impl MaskBackend<ContoursGPU, ContoursCPU> {
+    pub fn randomize(&self) {
+        match self {
+            Self::Gpu(gpu) => gpu.randomize(),
+            Self::Cpu(cpu) => cpu.generator().randomize(),
+        }
+    }
+
+    pub fn num_contours_arc(&self) -> Arc<AtomicU32> {
+        match self {
+            Self::Gpu(gpu) => gpu.num_contours_arc(),
+            Self::Cpu(cpu) => cpu.generator().num_contours_arc(),
+        }
+    }
+
+    pub fn upper_arc(&self) -> Arc<AtomicF32> {
+        match self {
+            Self::Gpu(gpu) => gpu.upper_arc(),
+            Self::Cpu(cpu) => cpu.generator().upper_arc(),
+        }
+    }
+
+    pub fn z_increment_arc(&self) -> Arc<AtomicF32> {
+        match self {
+            Self::Gpu(gpu) => gpu.z_increment_arc(),
+            Self::Cpu(cpu) => cpu.generator().z_increment_arc(),
+        }
+    }
+
+    pub fn noise_params_arc(&self) -> Arc<ContourNoiseParamsAtomic> {
+        match self {
+            Self::Gpu(gpu) => gpu.noise_params_arc(),
+            Self::Cpu(cpu) => cpu.generator().noise_params_arc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ContourNoise;
+
+    const RES: usize = 64;
+
+    fn values(ctr: &ContoursCPU) -> Vec<f64> {
+        let mut v = Vec::with_capacity(RES * RES);
+
+        for x in 0..RES {
+            for y in 0..RES {
+                let (x, y) = (x as f64 / RES as f64, y as f64 / RES as f64);
+                v.push(ctr.value_at(x, y));
+            }
+        }
+
+        v
+    }
+
+    #[test]
+    fn values_in_range() {
+        let mut ctr = ContoursCPU::new();
+
+        for _ in 0..10 {
+            ctr.step(0.5);
+            assert!(values(&ctr).iter().all(|v| (0.0..=1.0).contains(v)));
+        }
+    }
+
+    #[test]
+    fn coverage_follows_thickness() {
+        let thin = ContoursCPU::new()
+            .with_num_contours(16)
+            .with_contour_range(0.0..=0.2);
+        let thick = ContoursCPU::new()
+            .with_num_contours(16)
+            .with_contour_range(0.0..=0.8);
+
+        let coverage = |ctr: &ContoursCPU| {
+            let v = values(ctr);
+            v.iter().filter(|&&x| x > 0.0).count() as f64 / v.len() as f64
+        };
+
+        // each contour covers roughly `upper` of its band
+        let (thin, thick) = (coverage(&thin), coverage(&thick));
+        assert!((0.05..=0.35).contains(&thin), "thin coverage: {thin}");
+        assert!((0.6..=0.95).contains(&thick), "thick coverage: {thick}");
+    }
+
+    #[test]
+    fn same_seed_same_output() {
+        let (a, b) = (ContoursCPU::new(), ContoursCPU::new());
+        assert_eq!(values(&a), values(&b));
+
+        a.randomize();
+        b.randomize();
+        assert_eq!(values(&a), values(&b));
+    }
+
+    #[test]
+    fn evolves_over_time() {
+        let mut ctr = ContoursCPU::new();
+        let before = values(&ctr);
+
+        ctr.step(1.0);
+        let after = values(&ctr);
+
+        let diff: f64 =
+            before.iter().zip(&after).map(|(a, b)| (a - b).abs()).sum();
+        assert!(diff > 1.0);
+    }
+
+    #[test]
+    fn noise_params_apply_on_step() {
+        let mut ctr = ContoursCPU::new();
+        let before = values(&ctr);
+
+        ctr.noise_params_arc().noise.sr(ContourNoise::Worley);
+        assert!(before == values(&ctr));
+
+        ctr.step(0.0);
+        assert!(before != values(&ctr));
+    }
+}
@@ -1,5 +1,9 @@
-//! Pseudo-perlin noise contour line generator run on the GPU.
+//! Noise contour line generator run on the GPU.
 
+use super::{
+    random_z, ContourNoiseParams, ContourNoiseParamsAtomic, MAX_OCTAVES,
+};
+use crate::app::ContourNoise;
 use crate::dsp::SpectralMask;
 use crate::prelude::*;
 use atomic_float::AtomicF32;
@@ -26,6 +30,16 @@ struct ContoursParams {
     width: u32,
     /// The vertical resolution.
     height: u32,
+    /// The noise function, as the index of its [`ContourNoise`] variant.
+    noise: u32,
+    /// The number of octaves used by fractal noise.
+    octaves: u32,
+    /// The frequency multiplier between octaves of fractal noise.
+    lacunarity: f32,
+    /// The amplitude multiplier between octaves of fractal noise.
+    persistence: f32,
+    /// The amount of domain warping.
+    warp: f32,
 }
 
 /// Atomic params so we don't need a Mutex!
@@ -33,9 +47,25 @@ struct ContoursParamsAtomic {
     num_contours: Arc<AtomicU32>,
     upper: Arc<AtomicF32>,
     z: AtomicF32,
+    noise: Arc<ContourNoiseParamsAtomic>,
 }
 
 impl ContoursParams {
+    fn new(w: u32, h: u32, noise: &ContourNoiseParams) -> Self {
+        Self {
+            num_contours: 8,
+            upper: 0.6,
+            z: 0.0,
+            width: w,
+            height: h,
+            noise: noise.noise as u32,
+            octaves: noise.octaves.clamp(1, MAX_OCTAVES),
+            lacunarity: noise.lacunarity,
+            persistence: noise.persistence,
+            warp: noise.warp,
+        }
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         unsafe { wgpu::bytes::from(self) }
     }
@@ -74,9 +104,7 @@ impl Compute {
         let ctr_data = ContoursParams {
             num_contours: 1,
             upper: 1.0,
-            z: 0.0,
-            width: w,
-            height: h,
+            ..ContoursParams::new(w, h, &ContourNoiseParams::default())
         };
         let ctr_data_bytes = ctr_data.as_bytes();
         let data_buffer = device.create_buffer_init(&BufferInitDescriptor {
@@ -128,7 +156,7 @@ impl Compute {
     }
 }
 
-/// A pseudo-perlin noise contour line generator computed on the GPU.
+/// A noise contour line generator computed on the GPU.
 pub struct ContoursGPU {
     rect: Rect,
 
@@ -137,6 +165,7 @@ pub struct ContoursGPU {
 
     params: ContoursParams,
     atomic_params: ContoursParamsAtomic,
+    rng: Mutex<SessionRng>,
 
     texture: wgpu::Texture,
 
@@ -152,6 +181,7 @@ impl ContoursGPU {
 
         let cs_desc = wgpu::include_wgsl!("./contours.wgsl");
         let cs_mod = unsafe { device.create_shader_module_unchecked(&cs_desc) };
+        let rng = Mutex::new(SessionRng::new("contours"));
 
         Self {
             rect,
@@ -160,19 +190,15 @@ impl ContoursGPU {
 
             z_increment: Arc::new(AtomicF32::new(0.2)),
 
-            params: ContoursParams {
-                num_contours: 8,
-                upper: 0.6,
-                z: 0.0,
-                width: w,
-                height: h,
-            },
+            params: ContoursParams::new(w, h, &ContourNoiseParams::default()),
 
             atomic_params: ContoursParamsAtomic {
                 num_contours: Arc::new(AtomicU32::new(8)),
                 upper: Arc::new(AtomicF32::new(0.6)),
-                z: AtomicF32::new(0.0),
+                z: AtomicF32::new(random_z(&rng) as f32),
+                noise: Arc::new(ContourNoiseParamsAtomic::default()),
             },
+            rng,
 
             texture: wgpu::TextureBuilder::new()
                 .size([w, h])
@@ -211,6 +237,12 @@ impl ContoursGPU {
         self
     }
 
+    pub fn with_noise_params(mut self, params: ContourNoiseParams) -> Self {
+        self.atomic_params.noise.store(params);
+        self.update_gpu_params(0.0);
+        self
+    }
+
     pub fn set_num_contours(&self, num_contours: u32) {
         self.atomic_params.num_contours.sr(num_contours);
     }
@@ -228,7 +260,7 @@ impl ContoursGPU {
     }
 
     pub fn randomize(&self) {
-        self.atomic_params.z.sr(random_range(-1000.0, 1000.0));
+        self.atomic_params.z.sr(random_z(&self.rng) as f32);
     }
 
     pub fn num_contours_arc(&self) -> Arc<AtomicU32> {
@@ -243,17 +275,22 @@ impl ContoursGPU {
         Arc::clone(&self.z_increment)
     }
 
+    pub fn noise_params_arc(&self) -> Arc<ContourNoiseParamsAtomic> {
+        Arc::clone(&self.atomic_params.noise)
+    }
+
     fn update_gpu_params(&mut self, delta_time: f32) {
         self.atomic_params.z.fetch_add(
             self.z_increment.lr() * delta_time,
             atomic::Ordering::Relaxed,
         );
 
+        let noise = self.atomic_params.noise.load();
         self.params = ContoursParams {
             num_contours: self.atomic_params.num_contours.lr(),
             upper: self.atomic_params.upper.lr(),
             z: self.atomic_params.z.lr(),
-            ..self.params
+            ..ContoursParams::new(self.params.width, self.params.height, &noise)
         };
     }
 
@@ -402,30 +439,10 @@ impl UIDraw for ContoursGPU {
     }
 }
 
-impl DrawMask for ContoursGPU {
-    fn column_to_mask(
-        &self,
-        mask: &mut crate::dsp::SpectralMask,
-        mask_len: usize,
-        x: f64,
-    ) {
-        if !(0.0..=1.0).contains(&x) {
-            return;
-        }
-
-        let sr = unsafe { SAMPLE_RATE };
-
-        for i in 1..mask_len {
-            let bin_hz = SpectralMask::bin_freq(i, mask_len, sr);
-            if bin_hz < 20.0 {
-                mask[i] = 0.0;
-                continue;
-            }
-            let y = 1.0 - freq_log_norm(bin_hz, 20.0, sr);
-
-            let br = self.get_value_bilinear(x, y);
-
-            mask[i] = br;
-        }
+impl MaskSource for ContoursGPU {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        self.get_value_bilinear(x, y)
     }
 }
+
+impl DrawMask for ContoursGPU {}
@@ -0,0 +1,323 @@
+//! Noise functions used by the contour line generators.
+//!
+//! These mirror the noise functions in `contours.wgsl`, so that the output of
+//! [`ContoursCPU`](super::ContoursCPU) matches that of
+//! [`ContoursGPU`](super::ContoursGPU).
+
+use crate::app::ContourNoise;
+use crate::prelude::*;
+use atomic::Atomic;
+use atomic_float::AtomicF32;
+use std::f64::consts::TAU;
+use std::sync::atomic::AtomicU32;
+
+/// The frequency of Worley noise, so that its cells are of a similar size to
+/// the features of the other noise functions.
+const WORLEY_FREQ: f64 = 3.0;
+/// The offset applied to the position of each octave, so that octaves do not
+/// line up at the origin.
+const OCTAVE_OFFSET: f64 = 17.31;
+/// The maximum number of octaves used by the fractal noise functions.
+pub const MAX_OCTAVES: u32 = 8;
+
+/// The noise function and its settings used by a contour line generator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContourNoiseParams {
+    /// The noise function.
+    pub noise: ContourNoise,
+    /// The number of octaves used by fractal noise.
+    pub octaves: u32,
+    /// The frequency multiplier between octaves of fractal noise.
+    pub lacunarity: f32,
+    /// The amplitude multiplier between octaves of fractal noise.
+    pub persistence: f32,
+    /// The amount that the noise is distorted by another noise field.
+    pub warp: f32,
+}
+
+impl ContourNoiseParams {
+    /// Returns the value of the noise at `(x, y)`, which is roughly between
+    /// `-1.0` and `1.0`. The noise evolves with `z`.
+    pub fn sample(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (x, y) = self.warp(x, y, z);
+
+        match self.noise {
+            ContourNoise::Perlin => perlin(x, y, z),
+            ContourNoise::Simplex => simplex(x, y, z),
+            ContourNoise::Worley => worley(x * WORLEY_FREQ, y * WORLEY_FREQ, z),
+            ContourNoise::Fbm => self.fbm(x, y, z),
+            ContourNoise::Ridged => self.ridged(x, y, z),
+        }
+    }
+
+    /// Offsets `(x, y)` by a second noise field, scaled by the warp amount.
+    fn warp(&self, x: f64, y: f64, z: f64) -> (f64, f64) {
+        let warp = self.warp as f64;
+
+        if warp <= 0.0 {
+            return (x, y);
+        }
+
+        (
+            warp.mul_add(perlin(x + 5.2, y + 1.3, z), x),
+            warp.mul_add(perlin(x + 1.7, y + 9.2, z), y),
+        )
+    }
+
+    /// Calls `f` with the position, amplitude and index of each octave.
+    fn octaves(&self, x: f64, y: f64, mut f: impl FnMut(f64, f64, f64, u32)) {
+        let (mut amp, mut freq) = (1.0, 1.0);
+
+        for i in 0..self.octaves.clamp(1, MAX_OCTAVES) {
+            let offset = i as f64 * OCTAVE_OFFSET;
+            f(x.mul_add(freq, offset), y.mul_add(freq, offset), amp, i);
+
+            amp *= self.persistence as f64;
+            freq *= self.lacunarity as f64;
+        }
+    }
+
+    fn fbm(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (mut sum, mut norm) = (0.0, 0.0);
+
+        self.octaves(x, y, |x, y, amp, _| {
+            sum += amp * perlin(x, y, z);
+            norm += amp;
+        });
+
+        sum / norm.max(f64::EPSILON)
+    }
+
+    fn ridged(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (mut sum, mut norm, mut weight) = (0.0, 0.0, 1.0);
+
+        self.octaves(x, y, |x, y, amp, _| {
+            let ridge = 1.0 - perlin(x, y, z).abs();
+            let signal = ridge * ridge * weight;
+
+            // creases in lower octaves suppress detail in higher ones
+            weight = (signal * 2.0).clamp(0.0, 1.0);
+            sum += amp * signal;
+            norm += amp;
+        });
+
+        (sum / norm.max(f64::EPSILON)).mul_add(2.0, -1.0)
+    }
+}
+
+impl Default for ContourNoiseParams {
+    fn default() -> Self {
+        Self {
+            noise: ContourNoise::default(),
+            octaves: 4,
+            lacunarity: 2.0,
+            persistence: 0.5,
+            warp: 0.0,
+        }
+    }
+}
+
+/// A [`ContourNoiseParams`] which may be shared between threads.
+pub struct ContourNoiseParamsAtomic {
+    pub noise: Atomic<ContourNoise>,
+    pub octaves: AtomicU32,
+    pub lacunarity: AtomicF32,
+    pub persistence: AtomicF32,
+    pub warp: AtomicF32,
+}
+
+impl ContourNoiseParamsAtomic {
+    pub fn new(params: ContourNoiseParams) -> Self {
+        Self {
+            noise: Atomic::new(params.noise),
+            octaves: AtomicU32::new(params.octaves),
+            lacunarity: AtomicF32::new(params.lacunarity),
+            persistence: AtomicF32::new(params.persistence),
+            warp: AtomicF32::new(params.warp),
+        }
+    }
+
+    pub fn load(&self) -> ContourNoiseParams {
+        ContourNoiseParams {
+            noise: self.noise.lr(),
+            octaves: self.octaves.lr(),
+            lacunarity: self.lacunarity.lr(),
+            persistence: self.persistence.lr(),
+            warp: self.warp.lr(),
+        }
+    }
+
+    pub fn store(&self, params: ContourNoiseParams) {
+        self.noise.sr(params.noise);
+        self.octaves.sr(params.octaves);
+        self.lacunarity.sr(params.lacunarity);
+        self.persistence.sr(params.persistence);
+        self.warp.sr(params.warp);
+    }
+}
+
+impl Default for ContourNoiseParamsAtomic {
+    fn default() -> Self {
+        Self::new(ContourNoiseParams::default())
+    }
+}
+
+/// A cheap pseudo-random hash of a 2D position, between `0.0` and `1.0`.
+fn hash(x: f64, y: f64) -> f64 {
+    let (mut p1, mut p2, mut p3) =
+        ((x * 0.1031).fract(), (y * 0.1031).fract(), (x * 0.1031).fract());
+    let dot =
+        p1.mul_add(p2 + 33.33, p2.mul_add(p3 + 33.33, p3 * (p1 + 33.33)));
+
+    p1 += dot;
+    p2 += dot;
+    p3 += dot;
+
+    ((p1 + p2) * p3).fract()
+}
+
+/// Returns the dot product of the gradient at the integer position
+/// `(ix, iy)` with the offset `(dx, dy)`. The gradients rotate with `z`.
+fn grad_dot(ix: f64, iy: f64, z: f64, dx: f64, dy: f64) -> f64 {
+    let rand = hash(ix, iy);
+    let angle = TAU.mul_add(rand, 4.0 * z * rand);
+
+    angle.cos().mul_add(dx, angle.sin() * dy)
+}
+
+/// A Perlin noise approximation.
+pub fn perlin(x: f64, y: f64, z: f64) -> f64 {
+    let (ix, iy) = (x.floor(), y.floor());
+    let (fx, fy) = (x - ix, y - iy);
+    let bx = fx * fx * 2.0f64.mul_add(-fx, 3.0);
+    let by = fy * fy * 2.0f64.mul_add(-fy, 3.0);
+
+    let top = lerp(
+        grad_dot(ix, iy, z, fx, fy),
+        grad_dot(ix + 1.0, iy, z, fx - 1.0, fy),
+        bx,
+    );
+    let bottom = lerp(
+        grad_dot(ix, iy + 1.0, z, fx, fy - 1.0),
+        grad_dot(ix + 1.0, iy + 1.0, z, fx - 1.0, fy - 1.0),
+        bx,
+    );
+
+    lerp(top, bottom, by) / 0.7
+}
+
+/// 2D simplex noise, using the same rotating gradients as [`perlin()`].
+pub fn simplex(x: f64, y: f64, z: f64) -> f64 {
+    // skewing factors between the square and triangular grids
+    const F2: f64 = 0.366_025_403_784_438_6;
+    const G2: f64 = 0.211_324_865_405_187_1;
+
+    let s = (x + y) * F2;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * G2;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+
+    // which triangle of the skewed cell we are in
+    let (i1, j1) = if x0 > y0 { (1.0, 0.0) } else { (0.0, 1.0) };
+
+    let corner = |ci: f64, cj: f64, dx: f64, dy: f64| {
+        let t = dy.mul_add(-dy, dx.mul_add(-dx, 0.5));
+
+        if t < 0.0 {
+            0.0
+        }
+        else {
+            t * t * t * t * grad_dot(ci, cj, z, dx, dy)
+        }
+    };
+
+    let n = corner(i, j, x0, y0)
+        + corner(i + i1, j + j1, x0 - i1 + G2, y0 - j1 + G2)
+        + corner(
+            i + 1.0,
+            j + 1.0,
+            2.0f64.mul_add(G2, x0 - 1.0),
+            2.0f64.mul_add(G2, y0 - 1.0),
+        );
+
+    n * 70.0
+}
+
+/// Worley noise: the distance to the nearest of a set of points, one per grid
+/// cell, which orbit their cells as `z` changes.
+pub fn worley(x: f64, y: f64, z: f64) -> f64 {
+    let (ix, iy) = (x.floor(), y.floor());
+    let mut min_dist_sq = f64::MAX;
+
+    for cx in [ix - 1.0, ix, ix + 1.0] {
+        for cy in [iy - 1.0, iy, iy + 1.0] {
+            let px = 0.4f64.mul_add(TAU.mul_add(hash(cx, cy), z).sin(), cx);
+            let py = 0.4f64
+                .mul_add(TAU.mul_add(hash(cx + 57.0, cy + 113.0), z).cos(), cy);
+
+            let (dx, dy) = (px + 0.5 - x, py + 0.5 - y);
+            min_dist_sq = min_dist_sq.min(dx.mul_add(dx, dy * dy));
+        }
+    }
+
+    min_dist_sq.sqrt().min(1.0).mul_add(2.0, -1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RES: usize = 32;
+
+    fn samples(params: &ContourNoiseParams, z: f64) -> Vec<f64> {
+        (0..RES * RES)
+            .map(|i| {
+                let x = (i % RES) as f64 / RES as f64;
+                let y = (i / RES) as f64 / RES as f64;
+                params.sample(x * 2.0, y * 2.0, z)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn all_noise_is_bounded_and_varied() {
+        for noise in [
+            ContourNoise::Perlin,
+            ContourNoise::Simplex,
+            ContourNoise::Worley,
+            ContourNoise::Fbm,
+            ContourNoise::Ridged,
+        ] {
+            let params = ContourNoiseParams { noise, ..Default::default() };
+            let values = samples(&params, 0.3);
+
+            let min = values.iter().copied().fold(f64::MAX, f64::min);
+            let max = values.iter().copied().fold(f64::MIN, f64::max);
+
+            assert!(min >= -1.5 && max <= 1.5, "{noise}: {min}..{max}");
+            assert!(max - min > 0.2, "{noise} is flat: {min}..{max}");
+        }
+    }
+
+    #[test]
+    fn single_octave_fbm_is_perlin() {
+        let params = ContourNoiseParams {
+            noise: ContourNoise::Fbm,
+            octaves: 1,
+            ..Default::default()
+        };
+
+        for (x, y) in [(0.1, 0.7), (0.45, 0.2), (0.9, 0.9)] {
+            assert_eq!(params.sample(x, y, 0.5), perlin(x, y, 0.5));
+        }
+    }
+
+    #[test]
+    fn warp_distorts_noise() {
+        let plain = ContourNoiseParams::default();
+        let warped = ContourNoiseParams { warp: 0.5, ..plain };
+
+        assert_eq!(samples(&plain, 0.0), samples(&plain, 0.0));
+        assert!(samples(&plain, 0.0) != samples(&warped, 0.0));
+    }
+}
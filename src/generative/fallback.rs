@@ -0,0 +1,187 @@
+//! CPU fallbacks for the GPU mask generators, used when no suitable GPU adapter
+//! is available.
+
+use super::{MaskFrame, MaskGenerator, MaskSource};
+use crate::prelude::*;
+use nannou::image::{ImageBuffer, Rgba};
+use nannou::prelude::*;
+use nannou::wgpu;
+
+/// The resolution of the image drawn for CPU-computed masks. This is lower
+/// than the mask's rect to keep the per-frame cost down, and is scaled up when
+/// drawn.
+const CPU_RENDER_SIZE: u32 = 128;
+
+/// Returns whether there is a hardware GPU adapter which supports compute
+/// shaders. If not, the CPU versions of each mask generator should be used.
+pub fn gpu_compute_available(app: &App) -> bool {
+    app.instance()
+        .enumerate_adapters(wgpu::Backends::all())
+        .any(|adapter| {
+            adapter
+                .get_downlevel_properties()
+                .flags
+                .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+                && adapter.get_info().device_type != wgpu::DeviceType::Cpu
+        })
+}
+
+/// A UI component which draws a [`MaskGenerator`] computed on the CPU.
+pub struct CpuMask<G: MaskGenerator> {
+    generator: G,
+    rect: Rect,
+
+    frame: MaskFrame,
+    texture: wgpu::Texture,
+    image_buffer: ImageBuffer<Rgba<u8>, Vec<u8>>,
+}
+
+impl<G: MaskGenerator> CpuMask<G> {
+    pub fn new(device: &wgpu::Device, rect: Rect, generator: G) -> Self {
+        Self {
+            generator,
+            rect,
+
+            frame: MaskFrame::new(
+                CPU_RENDER_SIZE as usize,
+                CPU_RENDER_SIZE as usize,
+            ),
+            texture: wgpu::TextureBuilder::new()
+                .size([CPU_RENDER_SIZE, CPU_RENDER_SIZE])
+                .mip_level_count(4)
+                .sample_count(1)
+                .format(wgpu::TextureFormat::Rgba8Unorm)
+                .usage(
+                    wgpu::TextureUsages::COPY_DST
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                )
+                .build(device),
+            image_buffer: ImageBuffer::from_fn(
+                CPU_RENDER_SIZE,
+                CPU_RENDER_SIZE,
+                |_, _| Rgba([0, 0, 0, u8::MAX]),
+            ),
+        }
+    }
+
+    /// Returns a reference to the internal generator.
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// Returns a mutable reference to the internal generator.
+    pub fn generator_mut(&mut self) -> &mut G {
+        &mut self.generator
+    }
+
+    fn update_image_buffer(&mut self) {
+        self.generator.render_frame(&mut self.frame);
+
+        for (pxl, &br) in
+            self.image_buffer.pixels_mut().zip(self.frame.data().iter())
+        {
+            let br = (br * 255.0) as u8;
+            pxl.0 = [br, br, br, u8::MAX];
+        }
+    }
+}
+
+impl<G: MaskGenerator> UIDraw for CpuMask<G> {
+    fn update(&mut self, _: &App, input_data: &InputData) {
+        self.generator.step(input_data.delta_time);
+        self.update_image_buffer();
+    }
+
+    fn draw(&self, app: &App, draw: &Draw, frame: &Frame) {
+        self.texture.upload_data(
+            app.main_window().device(),
+            &mut frame.command_encoder(),
+            self.image_buffer.as_flat_samples().as_slice(),
+        );
+
+        draw.texture(&self.texture)
+            .xy(self.rect.xy())
+            .wh(self.rect.wh());
+    }
+
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+}
+
+impl<G: MaskGenerator> MaskSource for CpuMask<G> {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        self.generator.value_at(x, y)
+    }
+}
+
+impl<G: MaskGenerator> DrawMask for CpuMask<G> {}
+
+/// A mask generator which is either computed on the GPU, or on the CPU if no
+/// suitable GPU is available — see [`gpu_compute_available()`].
+pub enum MaskBackend<G: DrawMask, C: MaskGenerator> {
+    Gpu(G),
+    Cpu(CpuMask<C>),
+}
+
+impl<G: DrawMask, C: MaskGenerator> MaskBackend<G, C> {
+    /// Returns the GPU generator if `use_gpu` is `true`, and otherwise the CPU
+    /// generator. Both are constructed lazily.
+    pub fn select<FG, FC>(
+        use_gpu: bool,
+        gpu: FG,
+        device: &wgpu::Device,
+        rect: Rect,
+        cpu: FC,
+    ) -> Self
+    where
+        FG: FnOnce() -> G,
+        FC: FnOnce() -> C,
+    {
+        if use_gpu {
+            Self::Gpu(gpu())
+        }
+        else {
+            Self::Cpu(CpuMask::new(device, rect, cpu()))
+        }
+    }
+
+    /// Whether the generator is computed on the GPU.
+    pub fn is_gpu(&self) -> bool {
+        matches!(self, Self::Gpu(_))
+    }
+}
+
+impl<G: DrawMask, C: MaskGenerator> UIDraw for MaskBackend<G, C> {
+    fn update(&mut self, app: &App, input_data: &InputData) {
+        match self {
+            Self::Gpu(gpu) => gpu.update(app, input_data),
+            Self::Cpu(cpu) => cpu.update(app, input_data),
+        }
+    }
+
+    fn draw(&self, app: &App, draw: &Draw, frame: &Frame) {
+        match self {
+            Self::Gpu(gpu) => gpu.draw(app, draw, frame),
+            Self::Cpu(cpu) => cpu.draw(app, draw, frame),
+        }
+    }
+
+    fn rect(&self) -> &Rect {
+        match self {
+            Self::Gpu(gpu) => gpu.rect(),
+            Self::Cpu(cpu) => cpu.rect(),
+        }
+    }
+}
+
+impl<G: DrawMask, C: MaskGenerator> MaskSource for MaskBackend<G, C> {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        match self {
+            Self::Gpu(gpu) => gpu.value_at(x, y),
+            Self::Cpu(cpu) => cpu.value_at(x, y),
+        }
+    }
+}
+
+impl<G: DrawMask, C: MaskGenerator> DrawMask for MaskBackend<G, C> {}
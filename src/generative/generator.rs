@@ -0,0 +1,166 @@
+//! Headless spectral mask generator traits and types, which are independent of
+//! any windowing or drawing code.
+
+use crate::dsp::SpectralMask;
+
+/// Trait for anything which may be read as a spectral mask.
+pub trait MaskSource {
+    /// Returns the brightness of the source at the normalized position
+    /// `(x, y)`, where `(0.0, 0.0)` is the top-left corner and `(1.0, 1.0)` is
+    /// the bottom-right corner. The returned value should be between `0.0` and
+    /// `1.0`.
+    fn value_at(&self, x: f64, y: f64) -> f64;
+
+    /// Maps the column at `x` to a `SpectralMask`. Frequency increases from
+    /// the bottom to the top of the column.
+    ///
+    /// If `x < 0.0 || 1.0 < x`, this method has no effect.
+    fn column_to_mask(&self, mask: &mut SpectralMask, len: usize, x: f64) {
+        if !(0.0..=1.0).contains(&x) {
+            return;
+        }
+
+        mask.fill_log_bins(len, |t| self.value_at(x, 1.0 - t));
+    }
+
+    /// Maps the row at `y` to a `SpectralMask`. Frequency increases from the
+    /// left to the right of the row.
+    ///
+    /// If `y < 0.0 || 1.0 < y`, this method has no effect.
+    fn row_to_mask(&self, mask: &mut SpectralMask, len: usize, y: f64) {
+        if !(0.0..=1.0).contains(&y) {
+            return;
+        }
+
+        mask.fill_log_bins(len, |t| self.value_at(t, y));
+    }
+}
+
+/// Trait for spectral mask generators which do not require a GPU or a window,
+/// and so may be used in offline renders and tests. The GUI draws these with
+/// [`CpuMask`](super::CpuMask).
+pub trait MaskGenerator: MaskSource + Send + Sync {
+    /// Advances the generator by `delta_time` seconds.
+    fn step(&mut self, delta_time: f64);
+
+    /// Renders the generator into `frame`, sampling the center of each pixel.
+    fn render_frame(&self, frame: &mut MaskFrame) {
+        let (w, h) = (frame.width(), frame.height());
+        let (wr, hr) = (1.0 / w as f64, 1.0 / h as f64);
+
+        for y in 0..h {
+            for x in 0..w {
+                let (xn, yn) = ((x as f64 + 0.5) * wr, (y as f64 + 0.5) * hr);
+                frame.set(x, y, self.value_at(xn, yn).clamp(0.0, 1.0));
+            }
+        }
+    }
+}
+
+/// A grayscale frame buffer of brightness values between `0.0` and `1.0`,
+/// stored in row-major order from the top-left.
+#[derive(Clone, Debug)]
+pub struct MaskFrame {
+    width: usize,
+    height: usize,
+    data: Vec<f64>,
+}
+
+impl MaskFrame {
+    /// Returns a new, black frame.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, data: vec![0.0; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the value of the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        self.data[self.idx(x, y)]
+    }
+
+    /// Sets the value of the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: f64) {
+        let idx = self.idx(x, y);
+        self.data[idx] = value;
+    }
+
+    /// Returns the pixels of the frame in row-major order.
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    fn idx(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width && y < self.height);
+        y * self.width + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mask which brightens from left to right and scrolls to the right.
+    struct Gradient {
+        offset: f64,
+    }
+
+    impl MaskSource for Gradient {
+        fn value_at(&self, x: f64, _: f64) -> f64 {
+            (x - self.offset).rem_euclid(1.0)
+        }
+    }
+
+    impl MaskGenerator for Gradient {
+        fn step(&mut self, delta_time: f64) {
+            self.offset += delta_time;
+        }
+    }
+
+    #[test]
+    fn render_frame() {
+        let mut gen = Gradient { offset: 0.0 };
+        let mut frame = MaskFrame::new(4, 2);
+
+        gen.render_frame(&mut frame);
+        assert_eq!(frame.data(), &[0.125, 0.375, 0.625, 0.875].repeat(2));
+
+        gen.step(0.25);
+        gen.render_frame(&mut frame);
+        assert_eq!(frame.get(0, 1), 0.875);
+        assert_eq!(frame.get(1, 0), 0.125);
+    }
+
+    #[test]
+    fn mask_from_column() {
+        let gen = Gradient { offset: 0.0 };
+        let mut mask = SpectralMask::new(64);
+
+        // bins below 20 Hz are always zero
+        let is_filled = |mask: &SpectralMask| {
+            mask[..64].iter().all(|&v| v == 0.0 || (v - 0.5).abs() < 1e-9)
+                && (mask[63] - 0.5).abs() < 1e-9
+        };
+
+        gen.column_to_mask(&mut mask, 64, 0.5);
+        assert!(is_filled(&mask));
+
+        // out-of-range columns leave the mask untouched
+        gen.column_to_mask(&mut mask, 64, 1.5);
+        assert!(is_filled(&mask));
+    }
+}
@@ -0,0 +1,160 @@
+//! Loading of still images and numbered image sequences.
+
+use nannou::image::{
+    self, imageops::FilterType, DynamicImage, GenericImageView, GrayImage,
+};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The maximum width and height of a loaded frame. Larger images are scaled
+/// down to fit, preserving their aspect ratio.
+pub const MAX_FRAME_SIZE: u32 = 512;
+
+/// The file extensions which may be loaded as frames.
+const SUPPORTED_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+#[derive(Debug)]
+pub enum ImageLoadError {
+    /// The file or folder could not be read.
+    Io(io::Error),
+    /// An image could not be decoded.
+    Image(image::ImageError),
+    /// The folder did not contain any supported images.
+    NoFrames,
+}
+
+impl fmt::Display for ImageLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Image(e) => write!(f, "{e}"),
+            Self::NoFrames => write!(f, "no PNG or JPEG images found"),
+        }
+    }
+}
+
+impl std::error::Error for ImageLoadError {}
+
+impl From<io::Error> for ImageLoadError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<image::ImageError> for ImageLoadError {
+    fn from(value: image::ImageError) -> Self {
+        Self::Image(value)
+    }
+}
+
+/// Loads the image at `path` as a single frame, or if `path` is a folder,
+/// loads every PNG and JPEG image in it as a sequence of frames, ordered by
+/// the number at the end of each file name.
+///
+/// # Errors
+///
+/// Returns an error if any image fails to load, or if a folder contains no
+/// supported images.
+pub fn load_frames(path: &Path) -> Result<Vec<GrayImage>, ImageLoadError> {
+    if !path.is_dir() {
+        return Ok(vec![to_luminance(image::open(path)?)]);
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| is_supported(p))
+        .collect();
+
+    if paths.is_empty() {
+        return Err(ImageLoadError::NoFrames);
+    }
+
+    sort_frame_paths(&mut paths);
+
+    paths
+        .iter()
+        .map(|p| Ok(to_luminance(image::open(p)?)))
+        .collect()
+}
+
+/// Converts `image` to 8-bit luminance, scaling it down to fit within
+/// [`MAX_FRAME_SIZE`] if needed.
+pub fn to_luminance(image: DynamicImage) -> GrayImage {
+    let image = if image.width() > MAX_FRAME_SIZE
+        || image.height() > MAX_FRAME_SIZE
+    {
+        image.resize(MAX_FRAME_SIZE, MAX_FRAME_SIZE, FilterType::Triangle)
+    }
+    else {
+        image
+    };
+
+    image.to_luma8()
+}
+
+/// Sorts `paths` by the number at the end of each file name, so that
+/// `frame_2.png` comes before `frame_10.png`. Unnumbered files come first,
+/// in name order.
+pub fn sort_frame_paths(paths: &mut [PathBuf]) {
+    paths.sort_by(|a, b| {
+        frame_number(a).cmp(&frame_number(b)).then_with(|| a.cmp(b))
+    });
+}
+
+/// Returns the number at the end of `path`'s file name (excluding the
+/// extension), if there is one.
+fn frame_number(path: &Path) -> Option<u64> {
+    let stem = path.file_stem()?.to_str()?;
+    let digits = stem.len()
+        - stem.chars().rev().take_while(char::is_ascii_digit).count();
+
+    stem[digits..].parse().ok()
+}
+
+fn is_supported(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| {
+        SUPPORTED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_sort_numerically() {
+        let mut paths: Vec<PathBuf> =
+            ["frame_10.png", "frame_2.png", "cover.png", "frame_1.jpg"]
+                .iter()
+                .map(PathBuf::from)
+                .collect();
+
+        sort_frame_paths(&mut paths);
+
+        assert_eq!(
+            paths,
+            ["cover.png", "frame_1.jpg", "frame_2.png", "frame_10.png"]
+                .iter()
+                .map(PathBuf::from)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn supported_extensions() {
+        assert!(is_supported(Path::new("a/b.PNG")));
+        assert!(is_supported(Path::new("b.jpeg")));
+        assert!(!is_supported(Path::new("b.gif")));
+        assert!(!is_supported(Path::new("frames")));
+    }
+
+    #[test]
+    fn large_images_are_scaled_down() {
+        let image = DynamicImage::new_rgb8(MAX_FRAME_SIZE * 2, MAX_FRAME_SIZE);
+        let luma = to_luminance(image);
+
+        assert_eq!(luma.dimensions(), (MAX_FRAME_SIZE, MAX_FRAME_SIZE / 2));
+    }
+}
@@ -0,0 +1,278 @@
+//! Image and image sequence masks.
+
+use crate::generative::{CpuMask, MaskGenerator, MaskSource};
+use crate::prelude::*;
+use nannou::image::{self, GrayImage};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+mod load;
+
+pub use load::{load_frames, ImageLoadError, MAX_FRAME_SIZE};
+
+/// The playback rate of image sequences.
+const FRAMES_PER_SEC: f64 = 24.0;
+
+/// The image shown before any other image is loaded.
+const DEFAULT_IMAGE_BYTES: &[u8] =
+    include_bytes!("../../../assets/icons/icon_256.png");
+
+/// An image mask, drawn by the GUI.
+pub type ImageMask = CpuMask<ImageSequence>;
+
+/// Brightness, contrast and gamma adjustments applied to an image's
+/// luminance.
+#[derive(Clone, Copy, Debug)]
+pub struct ImageAdjustment {
+    /// Added to the luminance, from `-1.0` to `1.0`.
+    pub brightness: f64,
+    /// Scales the luminance about `0.5`.
+    pub contrast: f64,
+    /// The exponent applied to the luminance. Values above `1.0` darken the
+    /// midtones.
+    pub gamma: f64,
+    /// Whether the result is inverted.
+    pub invert: bool,
+}
+
+impl ImageAdjustment {
+    /// Applies the adjustment to `luminance`, which should be between `0.0`
+    /// and `1.0`.
+    pub fn apply(&self, luminance: f64) -> f64 {
+        let value = self
+            .contrast
+            .mul_add(luminance - 0.5, 0.5 + self.brightness)
+            .clamp(0.0, 1.0)
+            .powf(self.gamma);
+
+        if self.invert {
+            1.0 - value
+        }
+        else {
+            value
+        }
+    }
+}
+
+impl Default for ImageAdjustment {
+    fn default() -> Self {
+        Self { brightness: 0.0, contrast: 1.0, gamma: 1.0, invert: false }
+    }
+}
+
+/// A still image or looping sequence of frames, converted to luminance. This
+/// is computed on the CPU.
+///
+/// Images are stretched to fill the mask.
+pub struct ImageSequence {
+    frames: Vec<GrayImage>,
+    /// The playback position in frames.
+    position: f64,
+    /// Frames which have been loaded in the background and are waiting to
+    /// replace the current frames.
+    loaded: Arc<Mutex<Option<Vec<GrayImage>>>>,
+
+    adjustment: ImageAdjustment,
+    brightness: Arc<AtomicF64>,
+    contrast: Arc<AtomicF64>,
+    gamma: Arc<AtomicF64>,
+    invert: Arc<AtomicBool>,
+    should_restart: Arc<AtomicBool>,
+}
+
+impl ImageSequence {
+    pub fn new() -> Self {
+        let default_image = image::load_from_memory(DEFAULT_IMAGE_BYTES)
+            .expect("failed to decode default image");
+
+        Self::from_frames(vec![load::to_luminance(default_image)])
+    }
+
+    /// Creates a new sequence from the given frames.
+    pub fn from_frames(frames: Vec<GrayImage>) -> Self {
+        let adj = ImageAdjustment::default();
+
+        Self {
+            frames,
+            position: 0.0,
+            loaded: Arc::new(Mutex::new(None)),
+
+            adjustment: adj,
+            brightness: Arc::new(AtomicF64::new(adj.brightness)),
+            contrast: Arc::new(AtomicF64::new(adj.contrast)),
+            gamma: Arc::new(AtomicF64::new(adj.gamma)),
+            invert: Arc::new(AtomicBool::new(adj.invert)),
+            should_restart: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Loads a new image, or a folder of numbered frames, on a background
+    /// thread — see [`load_frames()`]. The current frames are replaced once
+    /// loading has finished; if loading fails, they are kept and the error is
+    /// printed.
+    pub fn load<P: AsRef<Path>>(&self, path: P) {
+        let path = path.as_ref().to_path_buf();
+        let loaded = Arc::clone(&self.loaded);
+
+        thread::spawn(move || match load_frames(&path) {
+            Ok(frames) => {
+                if let Ok(mut guard) = loaded.lock() {
+                    *guard = Some(frames);
+                }
+            }
+            Err(e) => {
+                eprintln!("failed to load \"{}\": {e}", path.display());
+            }
+        });
+    }
+
+    /// Returns the number of frames in the sequence.
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Restarts the sequence from its first frame.
+    pub fn restart(&self) {
+        self.should_restart.sr(true);
+    }
+
+    pub fn brightness_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.brightness)
+    }
+
+    pub fn contrast_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.contrast)
+    }
+
+    pub fn gamma_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.gamma)
+    }
+
+    pub fn invert_arc(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.invert)
+    }
+
+    fn current_frame(&self) -> Option<&GrayImage> {
+        let len = self.frames.len();
+        (len != 0).then(|| &self.frames[self.position as usize % len])
+    }
+}
+
+impl Default for ImageSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaskSource for ImageSequence {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        let Some(frame) = self.current_frame() else {
+            return 0.0;
+        };
+        let (w, h) = frame.dimensions();
+        let luma = |x: u32, y: u32| frame.get_pixel(x, y).0[0] as f64 / 255.0;
+
+        // map to range
+        let x = x.clamp(0.0, 1.0) * (w - 1) as f64;
+        let y = y.clamp(0.0, 1.0) * (h - 1) as f64;
+
+        // indices
+        let (lx, ly) = (x.floor() as u32, y.floor() as u32);
+        let (ux, uy) = ((lx + 1).min(w - 1), (ly + 1).min(h - 1));
+
+        let top = lerp(luma(lx, ly), luma(ux, ly), x.fract());
+        let bottom = lerp(luma(lx, uy), luma(ux, uy), x.fract());
+
+        self.adjustment.apply(lerp(top, bottom, y.fract()))
+    }
+}
+
+impl MaskGenerator for ImageSequence {
+    fn step(&mut self, delta_time: f64) {
+        if let Ok(mut guard) = self.loaded.try_lock() {
+            if let Some(frames) = guard.take() {
+                self.frames = frames;
+                self.position = 0.0;
+            }
+        }
+
+        if self.should_restart.lr() {
+            self.position = 0.0;
+            self.should_restart.sr(false);
+        }
+
+        self.adjustment = ImageAdjustment {
+            brightness: self.brightness.lr(),
+            contrast: self.contrast.lr(),
+            gamma: self.gamma.lr(),
+            invert: self.invert.lr(),
+        };
+
+        let len = self.frames.len().max(1) as f64;
+        self.position = delta_time
+            .max(0.0)
+            .mul_add(FRAMES_PER_SEC, self.position)
+            .rem_euclid(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_frame(luma: u8) -> GrayImage {
+        GrayImage::from_pixel(4, 4, image::Luma([luma]))
+    }
+
+    #[test]
+    fn default_adjustment_is_identity() {
+        let adj = ImageAdjustment::default();
+
+        for luma in [0.0, 0.25, 0.5, 1.0] {
+            assert!((adj.apply(luma) - luma).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn adjustments() {
+        let inverted = ImageAdjustment { invert: true, ..Default::default() };
+        assert!((inverted.apply(0.25) - 0.75).abs() < 1e-12);
+
+        let flat = ImageAdjustment { contrast: 0.0, ..Default::default() };
+        assert!((flat.apply(0.9) - 0.5).abs() < 1e-12);
+
+        let bright = ImageAdjustment { brightness: 1.0, ..Default::default() };
+        assert!((bright.apply(0.1) - 1.0).abs() < 1e-12);
+
+        let dark = ImageAdjustment { gamma: 2.0, ..Default::default() };
+        assert!((dark.apply(0.5) - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sequence_plays_back_and_loops() {
+        let mut seq =
+            ImageSequence::from_frames(vec![solid_frame(0), solid_frame(255)]);
+        assert_eq!(seq.value_at(0.5, 0.5), 0.0);
+
+        seq.step(1.5 / FRAMES_PER_SEC);
+        assert_eq!(seq.value_at(0.5, 0.5), 1.0);
+
+        seq.step(1.0 / FRAMES_PER_SEC);
+        assert_eq!(seq.value_at(0.5, 0.5), 0.0);
+
+        seq.step(1.0 / FRAMES_PER_SEC);
+        seq.restart();
+        seq.step(0.0);
+        assert_eq!(seq.value_at(0.5, 0.5), 0.0);
+    }
+
+    #[test]
+    fn default_image_loads() {
+        let seq = ImageSequence::new();
+
+        assert_eq!(seq.num_frames(), 1);
+        assert!((0.0..=1.0).contains(&seq.value_at(0.3, 0.6)));
+    }
+}
@@ -2,13 +2,32 @@
 //! nice aesthetics.
 
 pub mod contours;
+pub mod fallback;
+pub mod generator;
+pub mod image_mask;
+pub mod reaction_diffusion;
+pub mod readout;
 pub mod smooth_life;
+pub mod spectral_feedback;
 pub mod vectors;
 pub mod voronoi;
 
-pub use contours::{Contours, ContoursGPU};
-pub use smooth_life::{SmoothLife, SmoothLifeGPU};
-pub use vectors::VectorField;
-pub use voronoi::VoronoiGPU;
+pub use contours::{ContourNoiseParams, ContoursCPU, ContoursGPU};
+pub use fallback::{gpu_compute_available, CpuMask, MaskBackend};
+pub use generator::{MaskFrame, MaskGenerator, MaskSource};
+pub use image_mask::{ImageMask, ImageSequence};
+pub use reaction_diffusion::{ReactionDiffusion, ReactionDiffusionMask};
+pub use readout::{MaskReadout, MAX_SCAN_LINES};
+pub use smooth_life::{SmoothLife, SmoothLifeCPU, SmoothLifeGPU};
+pub use spectral_feedback::{SpectralFeedback, SpectralFeedbackMask};
+pub use vectors::{FieldForces, VectorField};
+pub use voronoi::{VoronoiCPU, VoronoiGPU};
+
+/// A contour line mask computed on the GPU where possible.
+pub type ContoursMask = MaskBackend<ContoursGPU, ContoursCPU>;
+/// A SmoothLife mask computed on the GPU where possible.
+pub type SmoothLifeMask = MaskBackend<SmoothLifeGPU, SmoothLifeCPU>;
+/// A Voronoi noise mask computed on the GPU where possible.
+pub type VoronoiMask = MaskBackend<VoronoiGPU, VoronoiCPU>;
 
 // Note: perlin noise is already supported in Nannou
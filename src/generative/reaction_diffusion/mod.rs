@@ -0,0 +1,232 @@
+//! Gray-Scott reaction-diffusion algorithm.
+
+use crate::app::ReactionDiffusionPreset;
+use crate::generative::smooth_life::Grid;
+use crate::generative::{CpuMask, MaskGenerator, MaskSource};
+use crate::prelude::*;
+use atomic::Atomic;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+mod process;
+mod state;
+
+pub use process::GrayScottGenerator;
+pub use state::GrayScottState;
+
+/// The width and height of the simulation grid.
+const GRID_SIZE: usize = 128;
+/// The number of simulation iterations per second.
+const ITERATIONS_PER_SEC: f64 = 600.0;
+/// The maximum number of iterations run per step, so that long frames do not
+/// stall the GUI.
+const MAX_ITERATIONS_PER_STEP: usize = 40;
+/// The brightness of the mask per unit of chemical `v`, which rarely exceeds
+/// `0.4`.
+const BRIGHTNESS_SCALE: f64 = 3.0;
+
+/// A reaction-diffusion mask, drawn by the GUI.
+pub type ReactionDiffusionMask = CpuMask<ReactionDiffusion>;
+
+/// A [Gray-Scott](https://groups.csail.mit.edu/mac/projects/amorphous/GrayScott/)
+/// reaction-diffusion simulation, which grows slowly evolving stripes and
+/// spots. This is computed on the CPU.
+pub struct ReactionDiffusion {
+    generator: GrayScottGenerator,
+
+    feed: Arc<AtomicF64>,
+    kill: Arc<AtomicF64>,
+    diffusion_u: Arc<AtomicF64>,
+    diffusion_v: Arc<AtomicF64>,
+    preset: Arc<Atomic<ReactionDiffusionPreset>>,
+    current_preset: Option<ReactionDiffusionPreset>,
+    should_reset: Arc<AtomicBool>,
+
+    /// Fractional iterations carried over between steps.
+    iterations: f64,
+}
+
+impl ReactionDiffusion {
+    pub fn new() -> Self {
+        let state = GrayScottState::default();
+
+        Self {
+            generator: GrayScottGenerator::new(GRID_SIZE),
+
+            feed: Arc::new(AtomicF64::new(state.feed)),
+            kill: Arc::new(AtomicF64::new(state.kill)),
+            diffusion_u: Arc::new(AtomicF64::new(state.diffusion_u)),
+            diffusion_v: Arc::new(AtomicF64::new(state.diffusion_v)),
+            preset: Arc::new(Atomic::new(ReactionDiffusionPreset::default())),
+            current_preset: None,
+            should_reset: Arc::new(AtomicBool::new(false)),
+
+            iterations: 0.0,
+        }
+    }
+
+    pub fn with_preset(self, preset: ReactionDiffusionPreset) -> Self {
+        self.set_preset(preset);
+        self
+    }
+
+    pub fn set_feed(&self, feed: f64) {
+        self.feed.sr(feed);
+    }
+
+    pub fn set_kill(&self, kill: f64) {
+        self.kill.sr(kill);
+    }
+
+    /// Sets the diffusion rate of chemical `u`, which should be below `1.0`.
+    pub fn set_diffusion_u(&self, rate: f64) {
+        self.diffusion_u.sr(rate);
+    }
+
+    /// Sets the diffusion rate of chemical `v`, which should be below `1.0`.
+    pub fn set_diffusion_v(&self, rate: f64) {
+        self.diffusion_v.sr(rate);
+    }
+
+    /// Sets the preset of the simulation. This overrides the current feed and
+    /// kill rates when the simulation is next stepped, but not the diffusion
+    /// rates.
+    pub fn set_preset(&self, preset: ReactionDiffusionPreset) {
+        self.preset.sr(preset);
+    }
+
+    pub fn randomize(&self) {
+        self.should_reset.sr(true);
+    }
+
+    pub fn feed_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.feed)
+    }
+
+    pub fn kill_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.kill)
+    }
+
+    pub fn diffusion_u_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.diffusion_u)
+    }
+
+    pub fn diffusion_v_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.diffusion_v)
+    }
+
+    pub fn preset_arc(&self) -> Arc<Atomic<ReactionDiffusionPreset>> {
+        Arc::clone(&self.preset)
+    }
+
+    /// Applies the current preset if it has changed.
+    fn update_preset(&mut self) {
+        let preset = self.preset.lr();
+
+        if self.current_preset == Some(preset) {
+            return;
+        }
+
+        // presets only set the feed and kill rates
+        let state = GrayScottState::from_preset(preset);
+        self.feed.sr(state.feed);
+        self.kill.sr(state.kill);
+        self.current_preset = Some(preset);
+    }
+}
+
+impl Default for ReactionDiffusion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaskSource for ReactionDiffusion {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        (self.generator.get_value(x, y) * BRIGHTNESS_SCALE).clamp(0.0, 1.0)
+    }
+}
+
+impl MaskGenerator for ReactionDiffusion {
+    fn step(&mut self, delta_time: f64) {
+        self.update_preset();
+
+        if self.should_reset.lr() {
+            self.generator.reset();
+            self.should_reset.sr(false);
+        }
+
+        self.generator.state.feed = self.feed.lr();
+        self.generator.state.kill = self.kill.lr();
+        self.generator.state.diffusion_u = self.diffusion_u.lr();
+        self.generator.state.diffusion_v = self.diffusion_v.lr();
+
+        self.iterations += delta_time.max(0.0) * ITERATIONS_PER_SEC;
+        let iterations = (self.iterations as usize).min(MAX_ITERATIONS_PER_STEP);
+        self.iterations = self.iterations.fract();
+
+        self.generator.update(iterations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_values(rd: &ReactionDiffusion) -> Vec<f64> {
+        rd.generator.grid().iter().flatten().copied().collect()
+    }
+
+    #[test]
+    fn unreacted_state_is_stable() {
+        let state = GrayScottState::default();
+
+        assert_eq!(state.reaction(1.0, 0.0, 0.0, 0.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn patterns_grow_from_seeds() {
+        for preset in [
+            ReactionDiffusionPreset::Coral,
+            ReactionDiffusionPreset::Mitosis,
+            ReactionDiffusionPreset::Maze,
+            ReactionDiffusionPreset::Spots,
+        ] {
+            let mut rd = ReactionDiffusion::new().with_preset(preset);
+
+            for _ in 0..60 {
+                rd.step(1.0 / 60.0);
+            }
+
+            let values = grid_values(&rd);
+            let active = values.iter().filter(|&&v| v > 0.05).count();
+
+            assert!(values.iter().all(|v| (0.0..=1.0).contains(v)));
+            assert!(active > 0, "{preset} died out");
+        }
+    }
+
+    #[test]
+    fn preset_sets_feed_and_kill() {
+        let mut rd = ReactionDiffusion::new();
+        rd.set_feed(0.01);
+        rd.set_preset(ReactionDiffusionPreset::Maze);
+        rd.step(0.0);
+
+        assert_eq!(rd.feed_arc().lr(), GrayScottState::maze().feed);
+        assert_eq!(rd.kill_arc().lr(), GrayScottState::maze().kill);
+    }
+
+    #[test]
+    fn diffusion_rates_are_applied() {
+        let mut rd = ReactionDiffusion::new();
+        rd.set_diffusion_u(0.8);
+        rd.set_diffusion_v(0.3);
+        rd.set_preset(ReactionDiffusionPreset::Spots);
+        rd.step(0.0);
+
+        assert_eq!(rd.generator.state.diffusion_u, 0.8);
+        assert_eq!(rd.generator.state.diffusion_v, 0.3);
+        assert_eq!(rd.diffusion_u_arc().lr(), 0.8);
+    }
+}
@@ -0,0 +1,142 @@
+//! Single-threaded Gray-Scott reaction-diffusion generator.
+
+use super::*;
+use rand::Rng;
+
+/// The number of seeds placed on the grid when it is reset.
+const NUM_SEEDS: usize = 12;
+/// The width of each seed as a proportion of the grid width.
+const SEED_SIZE: f64 = 0.05;
+
+pub struct GrayScottGenerator {
+    /// The parameters of the simulation.
+    pub state: GrayScottState,
+    /// The concentration of chemical `u`.
+    u: Grid,
+    /// The concentration of chemical `v`.
+    v: Grid,
+    /// Scratch buffers for computing the next grids.
+    u_next: Grid,
+    v_next: Grid,
+    rng: SessionRng,
+}
+
+impl GrayScottGenerator {
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        let grid = Grid::new_square(size);
+
+        let mut s = Self {
+            state: GrayScottState::default(),
+            u: grid.clone(),
+            v: grid.clone(),
+            u_next: grid.clone(),
+            v_next: grid,
+            rng: SessionRng::new("reaction_diffusion"),
+        };
+
+        s.reset();
+        s
+    }
+
+    /// Runs `iterations` steps of the simulation.
+    pub fn update(&mut self, iterations: usize) {
+        for _ in 0..iterations {
+            self.compute_next();
+
+            std::mem::swap(&mut self.u, &mut self.u_next);
+            std::mem::swap(&mut self.v, &mut self.v_next);
+        }
+    }
+
+    /// Returns the grid of chemical `v`, which forms the visible pattern.
+    pub fn grid(&self) -> &Grid {
+        &self.v
+    }
+
+    /// Fills the grid with chemical `u`, and places small, randomly-positioned
+    /// seeds of chemical `v` for patterns to grow from.
+    pub fn reset(&mut self) {
+        self.u.value(1.0);
+        self.v.value(0.0);
+
+        let size = self.v.width();
+        let seed_size = ((size as f64 * SEED_SIZE).ceil() as usize).max(1);
+
+        for _ in 0..NUM_SEEDS {
+            let cx = self.rng.gen_range(0..size);
+            let cy = self.rng.gen_range(0..size);
+
+            for x in cx..cx + seed_size {
+                for y in cy..cy + seed_size {
+                    let (x, y) = (x % size, y % size);
+                    self.u[x][y] = 0.5;
+                    self.v[x][y] = 0.25 + self.rng.gen_range(0.0..0.01);
+                }
+            }
+        }
+    }
+
+    /// This method expects `x` an `y` to be in the range `0.0` to `1.0`.
+    pub fn get_value(&self, mut x: f64, mut y: f64) -> f64 {
+        let w = self.v.width();
+        let h = self.v.height();
+
+        // map to range
+        x = x.clamp(0.0, 1.0) * (w - 1) as f64;
+        y = y.clamp(0.0, 1.0) * (h - 1) as f64;
+
+        // interpolation values
+        let xt = x - x.floor();
+        let yt = y - y.floor();
+
+        // indices
+        let lx = x.floor() as usize;
+        let ux = (lx + 1).min(w - 1);
+        let ly = y.floor() as usize;
+        let uy = (ly + 1).min(h - 1);
+
+        // x-axis lerp
+        let top = lerp(self.v[lx][ly], self.v[ux][ly], xt);
+        let bottom = lerp(self.v[lx][uy], self.v[ux][uy], xt);
+
+        // y-axis lerp
+        lerp(top, bottom, yt)
+    }
+
+    fn compute_next(&mut self) {
+        let w = self.u.width();
+        let h = self.u.height();
+
+        for x in 0..w {
+            let (xl, xr) = ((x + w - 1) % w, (x + 1) % w);
+
+            for y in 0..h {
+                let (yu, yd) = ((y + h - 1) % h, (y + 1) % h);
+
+                let lap_u = laplacian(&self.u, (xl, x, xr), (yu, y, yd));
+                let lap_v = laplacian(&self.v, (xl, x, xr), (yu, y, yd));
+
+                let (u, v) = (self.u[x][y], self.v[x][y]);
+                let (du, dv) = self.state.reaction(u, v, lap_u, lap_v);
+
+                self.u_next[x][y] = (u + du).clamp(0.0, 1.0);
+                self.v_next[x][y] = (v + dv).clamp(0.0, 1.0);
+            }
+        }
+    }
+}
+
+/// The Laplacian of `grid` at the center of the 3x3 neighbourhood given by
+/// the `(left, center, right)` and `(up, center, down)` indices, using a
+/// weighted kernel which includes the diagonals.
+fn laplacian(
+    grid: &Grid,
+    (xl, x, xr): (usize, usize, usize),
+    (yu, y, yd): (usize, usize, usize),
+) -> f64 {
+    let adjacent = grid[xl][y] + grid[xr][y] + grid[x][yu] + grid[x][yd];
+    let diagonal = grid[xl][yu] + grid[xr][yu] + grid[xl][yd] + grid[xr][yd];
+
+    0.2f64.mul_add(adjacent, 0.05f64.mul_add(diagonal, -grid[x][y]))
+}
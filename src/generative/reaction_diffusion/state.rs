@@ -0,0 +1,76 @@
+//! State used by the Gray-Scott reaction-diffusion algorithm.
+
+use super::*;
+
+/// The parameters of a Gray-Scott reaction-diffusion simulation.
+///
+/// Diffusion rates are relative to a unit grid spacing and an iteration time
+/// step of `1.0`. With the weighted Laplacian kernel used here, the simulation
+/// is stable for rates up to `1.25`, so the full `0.0..=1.0` range is safe.
+#[derive(Clone, Copy, Debug)]
+pub struct GrayScottState {
+    /// The rate at which chemical `u` is replenished.
+    pub feed: f64,
+    /// The rate at which chemical `v` is removed.
+    pub kill: f64,
+
+    /// The diffusion rate of chemical `u`.
+    pub diffusion_u: f64,
+    /// The diffusion rate of chemical `v`.
+    pub diffusion_v: f64,
+}
+
+impl GrayScottState {
+    /// Branching, coral-like growth which fills the grid.
+    pub fn coral() -> Self {
+        Self { feed: 0.0545, kill: 0.062, ..Self::default() }
+    }
+
+    /// Spots which grow and divide like cells.
+    pub fn mitosis() -> Self {
+        Self { feed: 0.0367, kill: 0.0649, ..Self::default() }
+    }
+
+    /// Winding, labyrinthine stripes.
+    pub fn maze() -> Self {
+        Self { feed: 0.029, kill: 0.057, ..Self::default() }
+    }
+
+    /// Sparse, stable spots.
+    pub fn spots() -> Self {
+        Self { feed: 0.03, kill: 0.062, ..Self::default() }
+    }
+
+    pub fn from_preset(preset: ReactionDiffusionPreset) -> Self {
+        match preset {
+            ReactionDiffusionPreset::Coral => Self::coral(),
+            ReactionDiffusionPreset::Mitosis => Self::mitosis(),
+            ReactionDiffusionPreset::Maze => Self::maze(),
+            ReactionDiffusionPreset::Spots => Self::spots(),
+        }
+    }
+
+    /// The rate of change of `u` and `v`, given their concentrations and
+    /// Laplacians.
+    pub fn reaction(
+        &self,
+        u: f64,
+        v: f64,
+        lap_u: f64,
+        lap_v: f64,
+    ) -> (f64, f64) {
+        let uvv = u * v * v;
+
+        (
+            self.diffusion_u.mul_add(lap_u, self.feed.mul_add(1.0 - u, -uvv)),
+            self.diffusion_v
+                .mul_add(lap_v, (self.kill + self.feed).mul_add(-v, uvv)),
+        )
+    }
+}
+
+impl Default for GrayScottState {
+    fn default() -> Self {
+        Self { feed: 0.0545, kill: 0.062, diffusion_u: 1.0, diffusion_v: 0.5 }
+    }
+}
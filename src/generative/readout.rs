@@ -0,0 +1,186 @@
+//! Readout paths, which describe how a generative image is mapped to a
+//! spectral mask.
+
+use crate::app::{MaskReadoutMode, ScanLineBlend};
+use crate::dsp::SpectralMask;
+use crate::prelude::*;
+use nannou::prelude::{pt2, Rect};
+
+/// The maximum number of scan lines which may be read at once.
+pub const MAX_SCAN_LINES: usize = 8;
+
+/// Describes how a generative image is read into a `SpectralMask`: the shape
+/// of the readout path, and how many scan lines are read and blended together.
+///
+/// All positions are normalized, where `(0.0, 0.0)` is the top-left of the
+/// image and `(1.0, 1.0)` is the bottom-right.
+#[derive(Clone, Copy, Debug)]
+pub struct MaskReadout {
+    /// The shape of each scan line.
+    pub mode: MaskReadoutMode,
+    /// The number of scan lines, clamped to `[1, MAX_SCAN_LINES]`.
+    pub num_lines: usize,
+    /// The total distance between the first and last scan lines.
+    pub spread: f64,
+    /// How the scan lines are blended together.
+    pub blend: ScanLineBlend,
+}
+
+impl Default for MaskReadout {
+    fn default() -> Self {
+        Self {
+            mode: MaskReadoutMode::default(),
+            num_lines: 1,
+            spread: 0.0,
+            blend: ScanLineBlend::default(),
+        }
+    }
+}
+
+impl MaskReadout {
+    /// Returns the position within the image for the scan line at `pos`, at
+    /// the normalized frequency `t`.
+    pub fn point(&self, pos: f64, t: f64) -> (f64, f64) {
+        match self.mode {
+            MaskReadoutMode::Column => (pos, 1.0 - t),
+            MaskReadoutMode::Row => (t, pos),
+            MaskReadoutMode::Radial => {
+                let angle = pos * TAU;
+                let radius = t * 0.5;
+
+                (
+                    radius.mul_add(angle.cos(), 0.5),
+                    (-radius).mul_add(angle.sin(), 0.5),
+                )
+            }
+            MaskReadoutMode::Circular => {
+                let angle = t * TAU;
+                let radius = pos * 0.5;
+
+                (
+                    radius.mul_add(angle.cos(), 0.5),
+                    (-radius).mul_add(angle.sin(), 0.5),
+                )
+            }
+        }
+    }
+
+    /// The inverse of [`point()`](Self::point) — returns the scan line position
+    /// which passes closest to the position `(x, y)`.
+    pub fn position_from_point(&self, x: f64, y: f64) -> f64 {
+        match self.mode {
+            MaskReadoutMode::Column => x.rem_euclid(1.0),
+            MaskReadoutMode::Row => y.rem_euclid(1.0),
+            MaskReadoutMode::Radial => {
+                (0.5 - y).atan2(x - 0.5).rem_euclid(TAU) / TAU
+            }
+            MaskReadoutMode::Circular => {
+                ((x - 0.5).hypot(y - 0.5) * 2.0).min(1.0)
+            }
+        }
+    }
+
+    /// Returns the `(position, weight)` of each scan line around the main scan
+    /// line position `pos`, along with the number of active scan lines.
+    ///
+    /// Scan lines are evenly distributed over `spread`, and each position wraps
+    /// around the image.
+    pub fn lines(&self, pos: f64) -> ([(f64, f64); MAX_SCAN_LINES], usize) {
+        let num_lines = self.num_lines.clamp(1, MAX_SCAN_LINES);
+        let n = num_lines as f64;
+        let mut lines = [(0.0, 0.0); MAX_SCAN_LINES];
+
+        // the cross-fade "focus" passes over every line once per scan
+        let focus = pos.rem_euclid(1.0) * n;
+
+        for (k, line) in lines.iter_mut().take(num_lines).enumerate() {
+            let k = k as f64;
+            let offset = if num_lines == 1 {
+                0.0
+            }
+            else {
+                self.spread * (k / (n - 1.0) - 0.5)
+            };
+
+            let weight = match self.blend {
+                ScanLineBlend::Average => 1.0,
+                ScanLineBlend::Crossfade => {
+                    let dist = (k - focus).abs();
+                    (1.0 - dist.min(n - dist)).max(0.0)
+                }
+            };
+
+            *line = ((pos + offset).rem_euclid(1.0), weight);
+        }
+
+        (lines, num_lines)
+    }
+
+    /// Writes the readout of `generator` for the main scan line position `pos`
+    /// into the first `mask_len` bins of `mask`.
+    pub fn write_mask<M: MaskSource + ?Sized>(
+        &self,
+        generator: &M,
+        mask: &mut SpectralMask,
+        mask_len: usize,
+        pos: f64,
+    ) {
+        let (lines, num_lines) = self.lines(pos);
+
+        if num_lines == 1 {
+            match self.mode {
+                MaskReadoutMode::Column => {
+                    generator.column_to_mask(mask, mask_len, lines[0].0);
+                    return;
+                }
+                MaskReadoutMode::Row => {
+                    generator.row_to_mask(mask, mask_len, lines[0].0);
+                    return;
+                }
+                _ => (),
+            }
+        }
+
+        mask.fill_log_bins(mask_len, |t| {
+            let (mut sum, mut total_weight) = (0.0, 0.0);
+
+            for &(line_pos, weight) in &lines[..num_lines] {
+                if weight <= 0.0 {
+                    continue;
+                }
+
+                let (x, y) = self.point(line_pos, t);
+                sum += weight * generator.value_at(x, y);
+                total_weight += weight;
+            }
+
+            if total_weight > 0.0 {
+                sum / total_weight
+            }
+            else {
+                0.0
+            }
+        });
+    }
+
+    /// Returns points along the scan line at `pos` mapped to `rect`, which may
+    /// be used to draw the scan line.
+    pub fn path_points(&self, pos: f64, rect: &Rect) -> Vec<Vec2> {
+        let num_points = match self.mode {
+            MaskReadoutMode::Circular => 65,
+            _ => 2,
+        };
+
+        let (l, r) = (rect.left() as f64, rect.right() as f64);
+        let (t, b) = (rect.top() as f64, rect.bottom() as f64);
+
+        (0..num_points)
+            .map(|i| {
+                let (x, y) =
+                    self.point(pos, i as f64 / (num_points - 1) as f64);
+
+                pt2(lerp(l, r, x) as f32, lerp(t, b, y) as f32)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,174 @@
+//! SmoothLife generator run on the CPU.
+
+use super::*;
+use crate::generative::{MaskBackend, MaskGenerator, MaskSource};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// The width and height of the simulation grid. This is far smaller than the
+/// GPU version's grid, so the radii of each preset are scaled to match.
+const CPU_GRID_SIZE: usize = 64;
+
+/// The smallest outer radius of the simulation, in grid cells.
+const MIN_OUTER_RADIUS: f64 = 2.0;
+
+/// A SmoothLife generator computed on the CPU.
+///
+/// This runs on a lower-resolution grid than [`SmoothLifeGPU`], with the
+/// radii scaled to the size of `rect` so that it looks similar.
+pub struct SmoothLifeCPU {
+    generator: SmoothLifeGenerator,
+    /// The ratio of the grid size to the width of the drawn rect.
+    radius_scale: f64,
+
+    state: Arc<SLStateAtomic>,
+    should_reset: Arc<AtomicBool>,
+}
+
+impl SmoothLifeCPU {
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            generator: SmoothLifeGenerator::new(CPU_GRID_SIZE),
+            radius_scale: CPU_GRID_SIZE as f64 / rect.w().max(1.0) as f64,
+
+            state: Arc::new(SLStateAtomic::default()),
+            should_reset: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Uses `state` for the parameters of the simulation, so that they may be
+    /// shared with other threads.
+    pub fn with_state(mut self, state: Arc<SLStateAtomic>) -> Self {
+        self.state = state;
+        self
+    }
+
+    pub fn randomize(&self) {
+        self.should_reset.sr(true);
+    }
+
+    pub fn state_arc(&self) -> Arc<SLStateAtomic> {
+        Arc::clone(&self.state)
+    }
+}
+
+impl MaskSource for SmoothLifeCPU {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        self.generator.get_value(x, y)
+    }
+}
+
+impl MaskGenerator for SmoothLifeCPU {
+    fn step(&mut self, delta_time: f64) {
+        if let Some(seed) = self.state.take_seed() {
+            self.generator.restore_seed(seed);
+            self.should_reset.sr(true);
+        }
+
+        if self.should_reset.lr() {
+            self.generator.reset();
+            self.should_reset.sr(false);
+        }
+
+        // scale both radii by the same amount so that their ratio is kept
+        let mut state = self.state.load();
+        let ra = (state.radius_outer * self.radius_scale).max(MIN_OUTER_RADIUS);
+        state.radius_inner *= ra / state.radius_outer.max(f64::EPSILON);
+        state.radius_outer = ra;
+        self.generator.state = state;
+
+        self.generator.update(delta_time);
+    }
+}
+
+impl MaskBackend<SmoothLifeGPU, SmoothLifeCPU> {
+    pub fn randomize(&self) {
+        match self {
+            Self::Gpu(gpu) => gpu.randomize(),
+            Self::Cpu(cpu) => cpu.generator().randomize(),
+        }
+    }
+
+    pub fn state_arc(&self) -> Arc<SLStateAtomic> {
+        match self {
+            Self::Gpu(gpu) => gpu.state_arc(),
+            Self::Cpu(cpu) => cpu.generator().state_arc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::SmoothLifePreset;
+
+    fn grid_values(sl: &SmoothLifeCPU) -> Vec<f64> {
+        sl.generator.grid().iter().flatten().copied().collect()
+    }
+
+    fn mean_and_variance(values: &[f64]) -> (f64, f64) {
+        let len = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / len;
+        let var =
+            values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / len;
+
+        (mean, var)
+    }
+
+    #[test]
+    fn initial_grid_is_uniform_noise() {
+        let sl = SmoothLifeCPU::new(Rect::from_w_h(256.0, 256.0));
+        let (mean, var) = mean_and_variance(&grid_values(&sl));
+
+        assert!((mean - 0.5).abs() < 0.03, "mean: {mean}");
+        assert!((var - 1.0 / 12.0).abs() < 0.01, "variance: {var}");
+    }
+
+    #[test]
+    fn values_stay_in_range() {
+        for preset in [
+            SmoothLifePreset::Jitter,
+            SmoothLifePreset::Slime,
+            SmoothLifePreset::Corrupt,
+            SmoothLifePreset::Gliders,
+            SmoothLifePreset::Drift,
+            SmoothLifePreset::Fluid,
+        ] {
+            let mut sl = SmoothLifeCPU::new(Rect::from_w_h(256.0, 256.0));
+            sl.state_arc().store(SLState::from_preset(preset).unwrap());
+
+            for _ in 0..20 {
+                sl.step(1.0 / 60.0);
+            }
+
+            assert!(grid_values(&sl)
+                .iter()
+                .all(|v| v.is_finite() && (0.0..=1.0).contains(v)));
+            assert!((0.0..=1.0).contains(&sl.value_at(0.37, 0.81)));
+        }
+    }
+
+    #[test]
+    fn same_seed_same_grid() {
+        let rect = Rect::from_w_h(256.0, 256.0);
+        let mut a = SmoothLifeCPU::new(rect);
+        let mut b = SmoothLifeCPU::new(rect);
+        assert_eq!(grid_values(&a), grid_values(&b));
+
+        a.randomize();
+        b.randomize();
+        a.step(1.0 / 60.0);
+        b.step(1.0 / 60.0);
+        assert_eq!(grid_values(&a), grid_values(&b));
+    }
+
+    #[test]
+    fn grid_evolves() {
+        let mut sl = SmoothLifeCPU::new(Rect::from_w_h(256.0, 256.0));
+        let before = grid_values(&sl);
+
+        sl.step(1.0 / 60.0);
+
+        assert!(before != grid_values(&sl));
+    }
+}
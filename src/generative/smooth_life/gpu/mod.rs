@@ -1,14 +1,13 @@
 //! SmoothLife generator run on the GPU.
 
-use crate::app::SmoothLifePreset;
+use super::{SLState, SLStateAtomic};
 use crate::dsp::SpectralMask;
 use crate::prelude::*;
-use atomic::Atomic;
-use atomic_float::AtomicF32;
 use nannou::image::{ImageBuffer, Rgba};
 use nannou::prelude::*;
 use nannou::wgpu;
 use nannou_audio::stream::input;
+use rand::Rng;
 use std::mem::size_of;
 use std::sync::{
     atomic::{AtomicBool, AtomicU32},
@@ -62,7 +61,7 @@ impl Compute {
         });
 
         // state buffer
-        let sml_state = SmoothLifeState::slime(w, h);
+        let sml_state = SmoothLifeState::new(&SLState::slime(), w, h);
         let sml_state_bytes = sml_state.as_bytes();
         let state_buffer = device.create_buffer_init(&BufferInitDescriptor {
             label: Some("smoothlife data buffer"),
@@ -126,6 +125,7 @@ pub struct SmoothLifeGPU {
     compute: Compute,
 
     rect: Rect,
+    rng: SessionRng,
 
     image_buf: Arc<Mutex<ImageBuffer<Rgba<u8>, Vec<u8>>>>,
     texture: wgpu::Texture,
@@ -142,10 +142,11 @@ impl SmoothLifeGPU {
         let cs_mod = unsafe { device.create_shader_module_unchecked(&cs_desc) };
 
         Self {
-            state_gpu: SmoothLifeState::slime(w, h),
+            state_gpu: SmoothLifeState::new(&SLState::slime(), w, h),
             state_atomic: SmoothLifeStateAtomic::default(),
             compute: Compute::new(w, h, device, &cs_mod),
             rect,
+            rng: SessionRng::new("smooth_life"),
             image_buf: Arc::new(Mutex::new(ImageBuffer::from_fn(
                 w,
                 h,
@@ -164,71 +165,41 @@ impl SmoothLifeGPU {
         }
     }
 
-    pub fn set_speed(&self, speed: f32) {
-        self.state_atomic.speed.sr(speed);
-    }
-
-    pub fn set_outer_radius(&self, radius: f32) {
-        debug_assert!((0.0..=32.0).contains(&radius));
-
-        self.state_atomic.ra.sr(radius);
+    /// Uses `state` for the parameters of the simulation, so that they may be
+    /// shared with other threads.
+    pub fn with_state(mut self, state: Arc<SLStateAtomic>) -> Self {
+        self.state_atomic.state = state;
+        self
     }
 
     pub fn randomize(&self) {
         self.state_atomic.should_reset.sr(true);
     }
 
-    pub fn set_preset(&self, preset: SmoothLifePreset) {
-        self.state_atomic.preset.sr(preset);
-    }
-
-    pub fn preset_arc(&self) -> Arc<Atomic<SmoothLifePreset>> {
-        Arc::clone(&self.state_atomic.preset)
-    }
-
-    pub fn speed_arc(&self) -> Arc<AtomicF32> {
-        Arc::clone(&self.state_atomic.speed)
+    pub fn state_arc(&self) -> Arc<SLStateAtomic> {
+        Arc::clone(&self.state_atomic.state)
     }
 
     fn update_gpu_state(&mut self, delta_time: f32) {
         let w = self.rect.w() as u32;
         let h = self.rect.h() as u32;
 
-        if self.state_atomic.should_update_preset.lr() {
-            self.state_gpu = match self.state_atomic.preset.lr() {
-                SmoothLifePreset::Jitter => {
-                    self.state_atomic.ra.sr(15.0);
-                    self.state_atomic.speed.sr(9.0);
-                    SmoothLifeState::flow(w, h)
-                }
-                SmoothLifePreset::Slime => {
-                    self.state_atomic.ra.sr(26.0);
-                    self.state_atomic.speed.sr(5.0);
-                    SmoothLifeState::slime(w, h)
-                }
-                SmoothLifePreset::Corrupt => {
-                    self.state_atomic.ra.sr(40.0);
-                    self.state_atomic.speed.sr(12.0);
-                    SmoothLifeState::corrupt(w, h)
-                }
-            }
-        }
-
+        self.state_gpu =
+            SmoothLifeState::new(&self.state_atomic.state.load(), w, h);
         self.state_gpu.should_randomize = 0;
+        self.state_gpu.delta_time = delta_time;
+
+        if let Some(seed) = self.state_atomic.state.take_seed() {
+            self.rng.restore_seed(seed);
+            self.state_atomic.should_reset.sr(true);
+        }
 
         if self.state_atomic.should_reset.lr() {
             self.state_gpu.should_randomize = 1;
+            self.state_gpu.seed_x = self.rng.gen_range(0.0..1024.0);
+            self.state_gpu.seed_y = self.rng.gen_range(0.0..1024.0);
             self.state_atomic.should_reset.sr(false);
         }
-
-        let ra = self.state_atomic.ra.lr();
-        self.state_gpu = SmoothLifeState {
-            ra,
-            ri: ra / 3.0,
-            dt: self.state_atomic.speed.lr(),
-            delta_time,
-            ..self.state_gpu
-        };
     }
 
     fn get_value_bilinear(&self, mut x: f64, mut y: f64) -> f64 {
@@ -366,26 +337,10 @@ impl UIDraw for SmoothLifeGPU {
     }
 }
 
-impl DrawMask for SmoothLifeGPU {
-    fn column_to_mask(
-        &self,
-        mask: &mut crate::dsp::SpectralMask,
-        mask_len: usize,
-        x: f64,
-    ) {
-        if !(0.0..=1.0).contains(&x) {
-            return;
-        }
-
-        let sr = unsafe { SAMPLE_RATE };
-
-        for i in 1..mask_len {
-            let bin_hz = SpectralMask::bin_freq(i, mask_len, sr);
-            let y = 1.0 - freq_log_norm(bin_hz, 20.0, sr);
-
-            let br = self.get_value_bilinear(x, y);
-
-            mask[i] = br;
-        }
+impl MaskSource for SmoothLifeGPU {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        self.get_value_bilinear(x, y)
     }
 }
+
+impl DrawMask for SmoothLifeGPU {}
@@ -44,6 +44,9 @@ struct State {
     width: u32;
     height: u32;
     should_randomize: u32;
+
+    seed_x: f32;
+    seed_y: f32;
 };
 
 [[block]]
@@ -170,7 +173,8 @@ fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {
 
     var pxl: f32;
     if (state.should_randomize >= u32(1)) {
-        set_grid_main_at(x, y, hash2(uv * 256.0));
+        let seed = vec2<f32>(state.seed_x, state.seed_y);
+        set_grid_main_at(x, y, hash2(uv * 256.0 + seed));
     } else {
         compute_diff(x, y);
         apply_diff(x, y, dt);
@@ -1,7 +1,5 @@
 //! State used by the SmoothLife algorithms.
 
-use atomic::Atomic;
-
 use super::*;
 
 #[repr(C)]
@@ -25,73 +23,36 @@ pub struct SmoothLifeState {
     pub width: u32,
     pub height: u32,
     pub should_randomize: u32,
+
+    /// Offsets the noise used to randomize the grid.
+    pub seed_x: f32,
+    pub seed_y: f32,
 }
 
 impl SmoothLifeState {
-    pub fn flow(w: u32, h: u32) -> Self {
+    pub fn new(state: &SLState, w: u32, h: u32) -> Self {
         Self {
-            ri: 10.0 / 3.0,
-            ra: 10.0,
+            ri: state.radius_inner as f32,
+            ra: state.radius_outer as f32,
 
-            alpha_n: 0.033,
-            alpha_m: -7.947,
+            alpha_n: state.alpha_n as f32,
+            alpha_m: state.alpha_m as f32,
 
-            b1: -0.138,
-            b2: 0.265,
+            b1: state.b1 as f32,
+            b2: state.b2 as f32,
 
-            d1: 0.167,
-            d2: 0.445,
+            d1: state.d1 as f32,
+            d2: state.d2 as f32,
 
-            dt: 2.0,
+            dt: state.dt as f32,
             delta_time: 0.0,
 
             width: w,
             height: h,
             should_randomize: 1,
-        }
-    }
-
-    pub fn slime(w: u32, h: u32) -> Self {
-        Self {
-            ri: 3.667,
-            ra: 11.0,
-
-            alpha_n: 0.198,
-            alpha_m: 0.347,
 
-            b1: 0.078,
-            b2: 0.265,
-            d2: 0.845,
-            d1: 0.167,
-
-            dt: 2.0,
-            delta_time: 0.0,
-
-            width: w,
-            height: h,
-            should_randomize: 1,
-        }
-    }
-
-    pub fn corrupt(w: u32, h: u32) -> Self {
-        Self {
-            ri: 3.667,
-            ra: 11.0,
-
-            alpha_n: 0.028,
-            alpha_m: 0.147,
-
-            b1: 0.278,
-            b2: 0.365,
-            d1: 0.267,
-            d2: 0.445,
-
-            dt: 2.0,
-            delta_time: 0.0,
-
-            width: w,
-            height: h,
-            should_randomize: 1,
+            seed_x: 0.0,
+            seed_y: 0.0,
         }
     }
 
@@ -101,21 +62,15 @@ impl SmoothLifeState {
 }
 
 pub struct SmoothLifeStateAtomic {
-    pub speed: Arc<AtomicF32>,
-    pub ra: Arc<AtomicF32>,
-    pub preset: Arc<Atomic<SmoothLifePreset>>,
+    pub state: Arc<SLStateAtomic>,
     pub should_reset: Arc<AtomicBool>,
-    pub should_update_preset: AtomicBool,
 }
 
 impl Default for SmoothLifeStateAtomic {
     fn default() -> Self {
         Self {
-            speed: Arc::new(AtomicF32::new(8.0)),
-            ra: Arc::new(AtomicF32::new(32.0)),
-            preset: Arc::new(Atomic::new(SmoothLifePreset::default())),
+            state: Arc::new(SLStateAtomic::default()),
             should_reset: Arc::new(AtomicBool::new(true)),
-            should_update_preset: AtomicBool::new(true),
         }
     }
 }
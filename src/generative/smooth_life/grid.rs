@@ -1,6 +1,6 @@
 //! Two-dimensional grid used by the SmoothLife algorithm.
 
-use rand::{self, Rng};
+use rand::Rng;
 use std::ops::{Deref, DerefMut};
 
 #[derive(Clone, Debug)]
@@ -19,16 +19,21 @@ impl Grid {
         Self::new(size, size)
     }
 
-    pub fn with_random(mut self) -> Self {
-        self.randomize();
+    pub fn with_random(mut self, rng: &mut impl Rng) -> Self {
+        self.randomize(rng);
         self
     }
 
     /// # Panics
     ///
     /// Panics if the chunk is outside the bounds of the grid, or if `w < x || h < y`.
-    pub fn with_random_chunk(mut self, xy: (usize, usize), wh: (usize, usize)) -> Self {
-        self.randomize_chunk(xy, wh);
+    pub fn with_random_chunk(
+        mut self,
+        xy: (usize, usize),
+        wh: (usize, usize),
+        rng: &mut impl Rng,
+    ) -> Self {
+        self.randomize_chunk(xy, wh, rng);
         self
     }
 
@@ -45,21 +50,30 @@ impl Grid {
         self
     }
 
-    pub fn randomize(&mut self) {
-        self.iter_mut()
-            .for_each(|v| v.iter_mut().for_each(|x| *x = random_f64()));
+    pub fn randomize(&mut self, rng: &mut impl Rng) {
+        self.iter_mut().for_each(|v| {
+            v.iter_mut().for_each(|x| *x = rng.gen_range(0.0..=1.0));
+        });
     }
 
     /// # Panics
     ///
     /// Panics if the chunk is outside the bounds of the grid, or if `w < x || h < y`.
-    pub fn randomize_chunk(&mut self, xy: (usize, usize), wh: (usize, usize)) {
+    pub fn randomize_chunk(
+        &mut self,
+        xy: (usize, usize),
+        wh: (usize, usize),
+        rng: &mut impl Rng,
+    ) {
         let (x, y) = xy;
         let (w, h) = wh;
         assert!(x <= w && y <= h && w <= self.width() && h <= self.height());
 
         self.iter_mut().skip(x).take(w).for_each(|v| {
-            v.iter_mut().skip(y).take(h).for_each(|x| *x = random_f64());
+            v.iter_mut()
+                .skip(y)
+                .take(h)
+                .for_each(|x| *x = rng.gen_range(0.0..=1.0));
         });
     }
 
@@ -102,7 +116,3 @@ impl DerefMut for Grid {
         &mut self.data
     }
 }
-
-pub fn random_f64() -> f64 {
-    rand::thread_rng().gen_range(0.0..=1.0)
-}
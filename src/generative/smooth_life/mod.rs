@@ -6,17 +6,19 @@ use nannou::image::{ImageBuffer, Rgba};
 use nannou::prelude::*;
 use std::ops::{Add, Rem};
 
+mod cpu;
 mod gpu;
 mod grid;
 mod process;
 mod process_async;
 mod state;
 
+pub use cpu::SmoothLifeCPU;
 pub use gpu::SmoothLifeGPU;
-pub use grid::{random_f64, Grid};
+pub use grid::Grid;
 pub use process::SmoothLifeGenerator;
 pub use process_async::SmoothLifeGeneratorAsync;
-pub use state::SLState;
+pub use state::{SLState, SLStateAtomic, USER_PRESET_FILE};
 
 pub struct SmoothLife {
     generator: SmoothLifeGeneratorAsync,
@@ -141,7 +143,7 @@ impl UIDraw for SmoothLife {
     }
 }
 
-impl DrawMask for SmoothLife {
+impl MaskSource for SmoothLife {
     // fn update(&mut self, delta_time: f64) {
     //     self.generator.update(delta_time);
     //     self.update_image_buffer();
@@ -159,23 +161,9 @@ impl DrawMask for SmoothLife {
     //         .wh(self.rect.wh());
     // }
 
-    fn column_to_mask(
-        &self,
-        mask: &mut crate::dsp::SpectralMask,
-        mask_len: usize,
-        x: f64,
-    ) {
-        if !(0.0..=1.0).contains(&x) {
-            return;
-        }
-
-        let sr = unsafe { SAMPLE_RATE };
-
-        for i in 1..mask_len {
-            let bin_freq = SpectralMask::bin_freq(i, mask_len, sr);
-            let y = 1.0 - freq_log_norm(bin_freq, 20.0, sr);
-
-            mask[i] = self.generator.get_value_bilinear(x, y);
-        }
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        self.generator.get_value_bilinear(x, y)
     }
 }
+
+impl DrawMask for SmoothLife {}
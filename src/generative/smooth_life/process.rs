@@ -9,16 +9,19 @@ pub struct SmoothLifeGenerator {
     pub grid: Grid,
     /// Scratch buffer for computing the next grid.
     diff: Grid,
+    rng: SessionRng,
 }
 
 impl SmoothLifeGenerator {
     pub fn new(size: usize) -> Self {
-        let grid = Grid::new_square(size).with_random();
+        let mut rng = SessionRng::new("smooth_life");
+        let grid = Grid::new_square(size).with_random(&mut rng);
 
         Self {
             state: SLState::fluid(),
             diff: grid.clone(),
             grid,
+            rng,
         }
     }
 
@@ -36,10 +39,16 @@ impl SmoothLifeGenerator {
     }
 
     pub fn reset(&mut self) {
-        self.grid.randomize();
+        self.grid.randomize(&mut self.rng);
         self.sync_diff();
     }
 
+    /// Restarts the random stream from `session_seed`, so that the next reset
+    /// reproduces the grid of that session.
+    pub fn restore_seed(&mut self, session_seed: u64) {
+        self.rng.restore_seed(session_seed);
+    }
+
     pub fn sync_diff(&mut self) {
         self.diff
             .iter_mut()
@@ -61,9 +70,9 @@ impl SmoothLifeGenerator {
 
         // indices
         let lx = x.floor() as usize;
-        let ux = lx + 1;
+        let ux = (lx + 1).min(self.grid.width() - 1);
         let ly = y.floor() as usize;
-        let uy = ly + 1;
+        let uy = (ly + 1).min(self.grid.height() - 1);
 
         // x-axis lerp
         let top = lerp(self.grid[lx][ly], self.grid[ux][ly], xt);
@@ -83,16 +92,14 @@ impl SmoothLifeGenerator {
         for cx in 0..w {
             for cy in 0..h {
                 let (mut m, mut m_norm, mut n, mut n_norm) = (0.0, 0.0, 0.0, 0.0);
-                let ra_1 = ra - 1.0;
-                let min = (-ra_1) as usize;
-                let max = ra_1 as usize;
+                let max = (ra - 1.0).max(0.0) as isize;
 
-                for dx in min..=max {
-                    for dy in min..=max {
-                        let x = emod(cx + dx, w);
-                        let y = emod(cy + dy, h);
+                for dx in -max..=max {
+                    for dy in -max..=max {
+                        let x = emod(cx as isize + dx, w);
+                        let y = emod(cy as isize + dy, h);
 
-                        let d = (dx + dx + dy * dy) as f64;
+                        let d = (dx * dx + dy * dy) as f64;
 
                         if d <= ri * ri {
                             m += self.grid[x][y];
@@ -104,8 +111,9 @@ impl SmoothLifeGenerator {
                     }
                 }
 
-                n /= n_norm;
-                m /= m_norm;
+                // guard against empty neighbourhoods for very small radii
+                n /= f64::max(n_norm, 1.0);
+                m /= f64::max(m_norm, 1.0);
 
                 let q = self.state.transition(n, m);
                 self.diff[cx][cy] = 2.0f64.mul_add(q, -1.0);
@@ -136,8 +144,8 @@ where
     (value % bound + bound) % bound
 }
 
-fn emod(value: usize, bound: usize) -> usize {
-    (value % bound + bound) % bound
+fn emod(value: isize, bound: usize) -> usize {
+    value.rem_euclid(bound as isize) as usize
 }
 
 pub fn lerp(a: f64, b: f64, mut t: f64) -> f64 {
@@ -15,11 +15,14 @@ pub struct SmoothLifeGeneratorAsync {
     thread_buffers: Vec<Arc<Mutex<Vec<Vec<f64>>>>>,
     /// The internal thread pool.
     pool: ThreadPool,
+    rng: SessionRng,
 }
 
 impl SmoothLifeGeneratorAsync {
     pub fn new(size: usize) -> Self {
-        let grid = Arc::new(RwLock::new(Grid::new_square(size).with_random()));
+        let mut rng = SessionRng::new("smooth_life");
+        let grid = Grid::new_square(size).with_random(&mut rng);
+        let grid = Arc::new(RwLock::new(grid));
 
         let thread_buffers = 8;
         let thread_buf_height = size / thread_buffers;
@@ -40,6 +43,7 @@ impl SmoothLifeGeneratorAsync {
 
             pool: ThreadPool::build(16)
                 .expect("failed to build thread pool for SmoothLifeGenerator"),
+            rng,
         }
     }
 
@@ -68,7 +72,7 @@ impl SmoothLifeGeneratorAsync {
     }
 
     pub fn reset(&mut self) {
-        self.grid.write().unwrap().randomize();
+        self.grid.write().unwrap().randomize(&mut self.rng);
 
         for buf in self.thread_buffers.iter_mut() {
             let mut guard = buf.lock().unwrap();
@@ -1,24 +1,132 @@
 //! State used by the SmoothLife algorithm.
 
 use super::*;
+use crate::app::SmoothLifePreset;
+use atomic::Atomic;
+use std::fs;
+use std::io;
 use std::ops::{Add, Rem};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
 
+/// The file which the `User` preset is saved to, relative to the working
+/// directory.
+pub const USER_PRESET_FILE: &str = "smooth_life_preset.txt";
+
+/// The parameters of a SmoothLife simulation.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SLState {
+    /// The radius of the inner "cell" disk.
     pub radius_inner: f64,
+    /// The outer radius of the "neighbourhood" ring.
     pub radius_outer: f64,
 
+    /// The smoothness of the birth and death intervals.
     pub alpha_n: f64,
+    /// The smoothness of the transition between birth and death.
     pub alpha_m: f64,
 
+    /// The lower bound of the birth interval.
     pub b1: f64,
+    /// The upper bound of the birth interval.
     pub b2: f64,
+    /// The lower bound of the death interval.
     pub d1: f64,
+    /// The upper bound of the death interval.
     pub d2: f64,
 
+    /// The speed of the simulation.
     pub dt: f64,
 }
 
 impl SLState {
+    /// The name of each parameter as it appears in a saved preset.
+    const FIELD_NAMES: [&'static str; 9] = [
+        "radius_inner",
+        "radius_outer",
+        "alpha_n",
+        "alpha_m",
+        "b1",
+        "b2",
+        "d1",
+        "d2",
+        "dt",
+    ];
+    /// The name of the session seed as it appears in a saved preset.
+    const SEED_NAME: &'static str = "seed";
+
+    /// Distorted, jittering waves which move diagonally.
+    pub fn jitter() -> Self {
+        Self {
+            radius_inner: 5.0,
+            radius_outer: 15.0,
+            alpha_n: 0.033,
+            alpha_m: -7.947,
+            b1: -0.138,
+            b2: 0.265,
+            d1: 0.167,
+            d2: 0.445,
+            dt: 9.0,
+        }
+    }
+
+    /// Thick, smearing diagonal lines.
+    pub fn slime() -> Self {
+        Self {
+            radius_inner: 26.0 / 3.0,
+            radius_outer: 26.0,
+            alpha_n: 0.198,
+            alpha_m: 0.347,
+            b1: 0.078,
+            b2: 0.265,
+            d1: 0.167,
+            d2: 0.845,
+            dt: 5.0,
+        }
+    }
+
+    /// Flickering, unstable patterns. These are the parameters of
+    /// [`Self::gliders()`] with a much larger radius and speed.
+    pub fn corrupt() -> Self {
+        Self {
+            radius_inner: 40.0 / 3.0,
+            radius_outer: 40.0,
+            dt: 12.0,
+            ..Self::gliders()
+        }
+    }
+
+    /// The gliders from the
+    /// [SmoothLife paper](https://arxiv.org/abs/1111.1567), which form from
+    /// noise and travel in straight lines.
+    pub fn gliders() -> Self {
+        Self {
+            radius_inner: 7.0,
+            radius_outer: 21.0,
+            alpha_n: 0.028,
+            alpha_m: 0.147,
+            b1: 0.278,
+            b2: 0.365,
+            d1: 0.267,
+            d2: 0.445,
+            dt: 6.0,
+        }
+    }
+
+    /// Slowly drifting, merging blobs.
+    pub fn drift() -> Self {
+        Self {
+            radius_inner: 6.0,
+            radius_outer: 18.0,
+            b1: 0.257,
+            b2: 0.336,
+            d1: 0.365,
+            d2: 0.549,
+            ..Self::gliders()
+        }
+    }
+
+    /// Smooth, slowly flowing shapes.
     pub fn fluid() -> Self {
         Self {
             radius_inner: 10.0 / 3.0,
@@ -33,6 +141,117 @@ impl SLState {
         }
     }
 
+    /// Returns the state of `preset`, or `None` for
+    /// [`SmoothLifePreset::User`], which is loaded with [`Self::load()`].
+    pub fn from_preset(preset: SmoothLifePreset) -> Option<Self> {
+        match preset {
+            SmoothLifePreset::Jitter => Some(Self::jitter()),
+            SmoothLifePreset::Slime => Some(Self::slime()),
+            SmoothLifePreset::Corrupt => Some(Self::corrupt()),
+            SmoothLifePreset::Gliders => Some(Self::gliders()),
+            SmoothLifePreset::Drift => Some(Self::drift()),
+            SmoothLifePreset::Fluid => Some(Self::fluid()),
+            SmoothLifePreset::User => None,
+        }
+    }
+
+    /// Saves the state to `path` as plain text, with one `name = value` pair
+    /// per line. The current session seed is saved alongside it, so that the
+    /// session may be reproduced (see [`Self::load_seed()`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let seed = format!("{} = {}\n", Self::SEED_NAME, session_seed());
+
+        fs::write(path, self.to_text() + &seed)
+    }
+
+    /// Returns the session seed saved with the state at `path`, if there is
+    /// one.
+    pub fn load_seed<P: AsRef<Path>>(path: P) -> Option<u64> {
+        Self::seed_from_text(&fs::read_to_string(path).ok()?)
+    }
+
+    /// Loads a state previously written with [`Self::save()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if it does not contain
+    /// every parameter.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_text(&fs::read_to_string(path)?).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid SmoothLife preset",
+            )
+        })
+    }
+
+    fn to_text(self) -> String {
+        Self::FIELD_NAMES
+            .iter()
+            .zip(self.values())
+            .map(|(name, value)| format!("{name} = {value}\n"))
+            .collect()
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        let mut state = Self::default();
+        let mut found = [false; 9];
+
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (name, value) = line.split_once('=')?;
+            if name.trim() == Self::SEED_NAME {
+                continue;
+            }
+
+            let idx =
+                Self::FIELD_NAMES.iter().position(|&n| n == name.trim())?;
+
+            *state.values_mut()[idx] = value.trim().parse().ok()?;
+            found[idx] = true;
+        }
+
+        found.iter().all(|&f| f).then_some(state)
+    }
+
+    fn seed_from_text(text: &str) -> Option<u64> {
+        text.lines()
+            .filter_map(|line| line.split_once('='))
+            .find(|(name, _)| name.trim() == Self::SEED_NAME)
+            .and_then(|(_, value)| value.trim().parse().ok())
+    }
+
+    const fn values(&self) -> [f64; 9] {
+        [
+            self.radius_inner,
+            self.radius_outer,
+            self.alpha_n,
+            self.alpha_m,
+            self.b1,
+            self.b2,
+            self.d1,
+            self.d2,
+            self.dt,
+        ]
+    }
+
+    fn values_mut(&mut self) -> [&mut f64; 9] {
+        [
+            &mut self.radius_inner,
+            &mut self.radius_outer,
+            &mut self.alpha_n,
+            &mut self.alpha_m,
+            &mut self.b1,
+            &mut self.b2,
+            &mut self.d1,
+            &mut self.d2,
+            &mut self.dt,
+        ]
+    }
+
     /// [`Source`](https://arxiv.org/abs/1111.1567)
     pub fn transition(&self, n: f64, m: f64) -> f64 {
         self.sigmoid_n(
@@ -79,3 +298,132 @@ impl Default for SLState {
         }
     }
 }
+
+/// An [`SLState`] which may be shared between threads, so that the GUI can
+/// adjust each parameter of a running simulation.
+#[derive(Debug)]
+pub struct SLStateAtomic {
+    pub radius_inner: AtomicF64,
+    pub radius_outer: AtomicF64,
+
+    pub alpha_n: AtomicF64,
+    pub alpha_m: AtomicF64,
+
+    pub b1: AtomicF64,
+    pub b2: AtomicF64,
+    pub d1: AtomicF64,
+    pub d2: AtomicF64,
+
+    pub dt: AtomicF64,
+
+    /// A session seed to restart the simulation's random stream from.
+    seed: Atomic<u64>,
+    should_restore_seed: AtomicBool,
+}
+
+impl SLStateAtomic {
+    pub fn new(state: SLState) -> Self {
+        Self {
+            radius_inner: AtomicF64::new(state.radius_inner),
+            radius_outer: AtomicF64::new(state.radius_outer),
+            alpha_n: AtomicF64::new(state.alpha_n),
+            alpha_m: AtomicF64::new(state.alpha_m),
+            b1: AtomicF64::new(state.b1),
+            b2: AtomicF64::new(state.b2),
+            d1: AtomicF64::new(state.d1),
+            d2: AtomicF64::new(state.d2),
+            dt: AtomicF64::new(state.dt),
+
+            seed: Atomic::new(0),
+            should_restore_seed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn load(&self) -> SLState {
+        SLState {
+            radius_inner: self.radius_inner.lr(),
+            radius_outer: self.radius_outer.lr(),
+            alpha_n: self.alpha_n.lr(),
+            alpha_m: self.alpha_m.lr(),
+            b1: self.b1.lr(),
+            b2: self.b2.lr(),
+            d1: self.d1.lr(),
+            d2: self.d2.lr(),
+            dt: self.dt.lr(),
+        }
+    }
+
+    pub fn store(&self, state: SLState) {
+        self.radius_inner.sr(state.radius_inner);
+        self.radius_outer.sr(state.radius_outer);
+        self.alpha_n.sr(state.alpha_n);
+        self.alpha_m.sr(state.alpha_m);
+        self.b1.sr(state.b1);
+        self.b2.sr(state.b2);
+        self.d1.sr(state.d1);
+        self.d2.sr(state.d2);
+        self.dt.sr(state.dt);
+    }
+
+    /// Restarts the simulation from the session seed `seed` the next time
+    /// it is stepped, without changing the global session seed.
+    pub fn restore_seed(&self, seed: u64) {
+        self.seed.sr(seed);
+        self.should_restore_seed.sr(true);
+    }
+
+    /// Returns the seed passed to [`restore_seed()`](Self::restore_seed), if
+    /// it hasn't already been taken.
+    pub fn take_seed(&self) -> Option<u64> {
+        self.should_restore_seed.swap(false, Relaxed).then(|| self.seed.lr())
+    }
+}
+
+impl Default for SLStateAtomic {
+    fn default() -> Self {
+        Self::new(SLState::jitter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_state_round_trips() {
+        for state in [SLState::jitter(), SLState::drift(), SLState::fluid()] {
+            assert_eq!(SLState::from_text(&state.to_text()), Some(state));
+        }
+    }
+
+    #[test]
+    fn seed_is_saved_alongside_state() {
+        let text = format!("{}seed = 1234\n", SLState::slime().to_text());
+
+        assert_eq!(SLState::from_text(&text), Some(SLState::slime()));
+        assert_eq!(SLState::seed_from_text(&text), Some(1234));
+        assert_eq!(SLState::seed_from_text(&SLState::slime().to_text()), None);
+    }
+
+    #[test]
+    fn incomplete_state_fails_to_load() {
+        let text = SLState::gliders().to_text();
+        let missing_dt: String = text
+            .lines()
+            .filter(|l| !l.starts_with("dt"))
+            .map(|l| format!("{l}\n"))
+            .collect();
+
+        assert_eq!(SLState::from_text(&missing_dt), None);
+        assert_eq!(SLState::from_text("b1 = 0.2.1"), None);
+        assert_eq!(SLState::from_text(&format!("{text}\nspeed = 1")), None);
+    }
+
+    #[test]
+    fn atomic_state_stores_every_parameter() {
+        let state = SLStateAtomic::default();
+        state.store(SLState::slime());
+
+        assert_eq!(state.load(), SLState::slime());
+    }
+}
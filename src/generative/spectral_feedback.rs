@@ -0,0 +1,289 @@
+//! A scrolling spectrogram of the input signal, used as a spectral mask.
+
+use crate::generative::{CpuMask, MaskGenerator, MaskSource};
+use crate::prelude::*;
+use std::f64::consts::PI;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// The number of spectra held in the history, i.e. the width of the mask.
+const HISTORY_LEN: usize = 256;
+/// The number of frequency rows in each spectrum, i.e. the height of the mask.
+const NUM_ROWS: usize = 128;
+/// The rate at which the history scrolls, in columns per second.
+const COLUMNS_PER_SEC: f64 = 60.0;
+/// The lowest frequency shown, which matches the lowest frequency read by the
+/// spectral filter.
+const MIN_FREQ: f64 = 20.0;
+/// The level below which spectra are considered silent.
+const MIN_LEVEL_DB: f64 = -120.0;
+
+/// A spectral feedback mask, drawn by the GUI.
+pub type SpectralFeedbackMask = CpuMask<SpectralFeedback>;
+
+/// A scrolling spectrogram built from the recent history of a spectrum
+/// analyzer, so that the input signal can filter itself. Newer spectra appear
+/// on the right, and frequency increases from bottom to top. This is computed
+/// on the CPU.
+pub struct SpectralFeedback {
+    /// Each column of the spectrogram in decibels, stored as a ring buffer.
+    history: Vec<[f64; NUM_ROWS]>,
+    /// The index of the newest column.
+    write_pos: usize,
+    /// The most recently received spectrum, which is written to the history
+    /// as it scrolls.
+    latest: [f64; NUM_ROWS],
+    /// Fractional columns carried over between steps.
+    columns: f64,
+
+    decay: Arc<AtomicF64>,
+    threshold: Arc<AtomicF64>,
+    invert: Arc<AtomicBool>,
+    should_clear: Arc<AtomicBool>,
+}
+
+impl SpectralFeedback {
+    pub fn new() -> Self {
+        Self {
+            history: vec![[MIN_LEVEL_DB; NUM_ROWS]; HISTORY_LEN],
+            write_pos: 0,
+            latest: [MIN_LEVEL_DB; NUM_ROWS],
+            columns: 0.0,
+
+            decay: Arc::new(AtomicF64::new(2.0)),
+            threshold: Arc::new(AtomicF64::new(-60.0)),
+            invert: Arc::new(AtomicBool::new(false)),
+            should_clear: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Sets the most recent spectrum, which is added to the history as it
+    /// scrolls.
+    ///
+    /// `spectrum_db` should hold the magnitude of each bin of a real FFT in
+    /// decibels, from 0 Hz to the Nyquist frequency, as produced by
+    /// [`SpectrumAnalyzer`](crate::gui::spectrum::SpectrumAnalyzer). These
+    /// are converted to decibels relative to a full-scale sine wave.
+    pub fn set_spectrum(&mut self, spectrum_db: &[f64], sample_rate: f64) {
+        if spectrum_db.len() < 2 {
+            return;
+        }
+
+        let fft_size = ((spectrum_db.len() - 1) * 2) as f64;
+        let bin_width = sample_rate / fft_size;
+        // the magnitude of a full-scale sine wave with a sine window
+        let full_scale_db = level_to_db(fft_size / PI);
+        let last = spectrum_db.len() - 1;
+
+        let level = |idx: usize| {
+            (spectrum_db[idx.min(last)] - full_scale_db).max(MIN_LEVEL_DB)
+        };
+        let row_to_bin = |row: f64| {
+            let t = 1.0 - row / NUM_ROWS as f64;
+            freq_lin_from_log(t, MIN_FREQ, sample_rate) / bin_width
+        };
+
+        for (row, out) in self.latest.iter_mut().enumerate() {
+            let row = row as f64;
+            let (upper, lower) = (row_to_bin(row), row_to_bin(row + 1.0));
+
+            // rows which cover several bins use the loudest, so that narrow
+            // peaks are not missed
+            *out = if upper - lower > 1.0 {
+                (lower.round() as usize..=upper.round() as usize)
+                    .map(level)
+                    .fold(MIN_LEVEL_DB, f64::max)
+            }
+            else {
+                let bin = row_to_bin(row + 0.5);
+                let idx = bin.floor() as usize;
+                lerp(level(idx), level(idx + 1), bin.fract())
+            };
+        }
+    }
+
+    /// Clears the history.
+    pub fn clear(&self) {
+        self.should_clear.sr(true);
+    }
+
+    pub fn decay_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.decay)
+    }
+
+    pub fn threshold_arc(&self) -> Arc<AtomicF64> {
+        Arc::clone(&self.threshold)
+    }
+
+    pub fn invert_arc(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.invert)
+    }
+
+    /// Returns the brightness of a level in decibels, which fades out as it
+    /// ages.
+    fn brightness(&self, level_db: f64, age_secs: f64) -> f64 {
+        let threshold = self.threshold.lr().min(-f64::EPSILON);
+        let decay = self.decay.lr().max(f64::EPSILON);
+
+        let value = ((level_db - threshold) / -threshold).clamp(0.0, 1.0)
+            * (-age_secs / decay).exp();
+
+        if self.invert.lr() {
+            1.0 - value
+        }
+        else {
+            value
+        }
+    }
+
+    /// Returns the column `age` columns before the newest.
+    fn column(&self, age: usize) -> &[f64; NUM_ROWS] {
+        &self.history[(self.write_pos + HISTORY_LEN - age) % HISTORY_LEN]
+    }
+}
+
+impl Default for SpectralFeedback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaskSource for SpectralFeedback {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        // map to range
+        let age = (1.0 - x.clamp(0.0, 1.0)) * (HISTORY_LEN - 1) as f64;
+        let row = (y.clamp(0.0, 1.0) * NUM_ROWS as f64 - 0.5)
+            .clamp(0.0, (NUM_ROWS - 1) as f64);
+
+        // indices
+        let (la, lr) = (age.floor() as usize, row.floor() as usize);
+        let ua = (la + 1).min(HISTORY_LEN - 1);
+        let ur = (lr + 1).min(NUM_ROWS - 1);
+
+        let (newer, older) = (self.column(la), self.column(ua));
+        let newer = lerp(newer[lr], newer[ur], row.fract());
+        let older = lerp(older[lr], older[ur], row.fract());
+        let level = lerp(newer, older, age.fract());
+
+        self.brightness(level, age / COLUMNS_PER_SEC)
+    }
+}
+
+impl MaskGenerator for SpectralFeedback {
+    fn step(&mut self, delta_time: f64) {
+        if self.should_clear.lr() {
+            self.history.fill([MIN_LEVEL_DB; NUM_ROWS]);
+            self.latest.fill(MIN_LEVEL_DB);
+            self.should_clear.sr(false);
+        }
+
+        self.columns += delta_time.max(0.0) * COLUMNS_PER_SEC;
+        let columns = (self.columns as usize).min(HISTORY_LEN);
+        self.columns = self.columns.fract();
+
+        for _ in 0..columns {
+            self.write_pos = (self.write_pos + 1) % HISTORY_LEN;
+            self.history[self.write_pos] = self.latest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 48000.0;
+    const FFT_SIZE: usize = 2048;
+
+    /// A spectrum with full-scale partials within 10 % of `freq`.
+    fn band_spectrum(freq: f64) -> Vec<f64> {
+        bands_spectrum(&[(freq, 0.0)])
+    }
+
+    /// A spectrum with partials within 10 % of each `(freq, level_db)` band,
+    /// where `level_db` is relative to a full-scale sine wave.
+    fn bands_spectrum(bands: &[(f64, f64)]) -> Vec<f64> {
+        let bin_width = SAMPLE_RATE / FFT_SIZE as f64;
+        let full_scale = level_to_db(FFT_SIZE as f64 / PI);
+
+        (0..=FFT_SIZE / 2)
+            .map(|i| {
+                let bin_freq = i as f64 * bin_width;
+                bands
+                    .iter()
+                    .find(|(freq, _)| {
+                        (freq * 0.9..=freq * 1.1).contains(&bin_freq)
+                    })
+                    .map_or(f64::NEG_INFINITY, |(_, db)| full_scale + db)
+            })
+            .collect()
+    }
+
+    /// The row of the mask at `freq`.
+    fn freq_to_y(freq: f64) -> f64 {
+        1.0 - freq_log_norm(freq, MIN_FREQ, SAMPLE_RATE)
+    }
+
+    #[test]
+    fn spectrum_appears_at_right() {
+        let mut fb = SpectralFeedback::new();
+        fb.set_spectrum(&band_spectrum(1000.0), SAMPLE_RATE);
+        fb.step(1.0 / COLUMNS_PER_SEC);
+
+        assert!(fb.value_at(1.0, freq_to_y(1000.0)) > 0.5);
+        assert!(fb.value_at(1.0, freq_to_y(5000.0)) < 1e-6);
+        assert!(fb.value_at(0.0, freq_to_y(1000.0)) < 1e-6);
+    }
+
+    #[test]
+    fn history_scrolls_and_decays() {
+        let mut fb = SpectralFeedback::new();
+        fb.set_spectrum(&band_spectrum(1000.0), SAMPLE_RATE);
+        fb.step(1.0 / COLUMNS_PER_SEC);
+        fb.set_spectrum(&band_spectrum(5000.0), SAMPLE_RATE);
+
+        let y = freq_to_y(1000.0);
+        let fresh = fb.value_at(1.0, y);
+
+        fb.step(100.0 / COLUMNS_PER_SEC);
+        let x = 1.0 - 100.0 / (HISTORY_LEN - 1) as f64;
+        let aged = fb.value_at(x, y);
+
+        assert!(fb.value_at(1.0, y) < 1e-6);
+        assert!(0.0 < aged && aged < fresh);
+    }
+
+    #[test]
+    fn threshold_and_invert() {
+        // one band above the -40 dB threshold, and one below it
+        let mut fb = SpectralFeedback::new();
+        fb.threshold_arc().sr(-40.0);
+        let spectrum = bands_spectrum(&[(1000.0, -20.0), (5000.0, -50.0)]);
+        fb.set_spectrum(&spectrum, SAMPLE_RATE);
+        fb.step(1.0 / COLUMNS_PER_SEC);
+
+        let loud_y = freq_to_y(1000.0);
+        let quiet_y = freq_to_y(5000.0);
+        let silent_y = freq_to_y(200.0);
+
+        // levels are scaled from the threshold (0) to full scale (1)
+        assert!((fb.value_at(1.0, loud_y) - 0.5).abs() < 0.05);
+        assert!(fb.value_at(1.0, quiet_y) < 1e-6);
+        assert!(fb.value_at(1.0, silent_y) < 1e-6);
+
+        fb.invert_arc().sr(true);
+        assert!((fb.value_at(1.0, loud_y) - 0.5).abs() < 0.05);
+        assert!((fb.value_at(1.0, quiet_y) - 1.0).abs() < 1e-6);
+        assert!((fb.value_at(1.0, silent_y) - 1.0).abs() < 1e-6);
+
+        // lowering the threshold lets the quieter band through
+        fb.threshold_arc().sr(-60.0);
+        let inverted = fb.value_at(1.0, quiet_y);
+        fb.invert_arc().sr(false);
+        let passed = fb.value_at(1.0, quiet_y);
+
+        assert!(passed > 0.1);
+        assert!((inverted - (1.0 - passed)).abs() < 1e-9);
+        assert!(fb.value_at(1.0, silent_y) < 1e-6);
+    }
+}
@@ -0,0 +1,269 @@
+//! Optional forces which act on the points of a vector field.
+
+use super::Point;
+use nannou::prelude::*;
+
+/// The acceleration toward the mouse at full strength, in pixels per frame.
+const MOUSE_ACCEL: f32 = 0.4;
+/// The acceleration toward the centre of the field at full strength, per
+/// pixel of distance from it.
+const GRAVITY_ACCEL: f32 = 0.002;
+/// The distance within which points flock with each other, in pixels.
+const FLOCK_RADIUS: f32 = 48.0;
+/// How strongly flocking points steer toward the centre of their neighbours.
+const COHESION: f32 = 0.004;
+/// How strongly flocking points match the velocity of their neighbours.
+const ALIGNMENT: f32 = 0.05;
+/// How strongly flocking points avoid crowding their neighbours.
+const SEPARATION: f32 = 6.0;
+/// The diameter of each point used for collisions, relative to its radius.
+const COLLISION_DIAMETER: f32 = 3.0;
+/// The fastest that forces may move a point, in pixels per frame.
+const MAX_SPEED: f32 = 6.0;
+
+/// Forces which may act on the points of a
+/// [`VectorField`](super::VectorField). Each force is disabled at `0.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FieldForces {
+    /// Attraction toward the mouse while it is over the field. Negative
+    /// values repel points from it.
+    pub mouse: f32,
+    /// The strength of boids-style flocking between points.
+    pub flocking: f32,
+    /// How elastic collisions between points are.
+    pub collision: f32,
+    /// Attraction toward the centre of the field. Negative values push points
+    /// outward.
+    pub gravity: f32,
+}
+
+impl FieldForces {
+    /// Returns whether any of the forces are enabled.
+    pub fn is_active(&self) -> bool {
+        self.mouse != 0.0
+            || self.flocking != 0.0
+            || self.collision != 0.0
+            || self.gravity != 0.0
+    }
+
+    /// Applies the forces to the velocities of `points` for one frame.
+    ///
+    /// `mouse` is the position of the mouse if it is over the field, and
+    /// `held` is the index of a point which is being dragged, which is not
+    /// affected.
+    pub(super) fn apply(
+        &self,
+        points: &mut [Point],
+        rect: &Rect,
+        mouse: Option<Vec2>,
+        held: Option<usize>,
+        point_radius: f32,
+    ) {
+        if !self.is_active() {
+            return;
+        }
+
+        let accels: Vec<Vec2> = (0..points.len())
+            .map(|i| self.acceleration(points, i, rect, mouse))
+            .collect();
+
+        for (i, (point, accel)) in points.iter_mut().zip(accels).enumerate() {
+            if held != Some(i) {
+                point.vel = (point.vel + accel).clamp_length_max(MAX_SPEED);
+            }
+        }
+
+        if self.collision > 0.0 {
+            self.collide(points, held, point_radius * COLLISION_DIAMETER);
+        }
+    }
+
+    /// Returns the acceleration of the point at `idx` due to every force
+    /// except collisions.
+    fn acceleration(
+        &self,
+        points: &[Point],
+        idx: usize,
+        rect: &Rect,
+        mouse: Option<Vec2>,
+    ) -> Vec2 {
+        let Point { pos, vel, .. } = points[idx];
+        let mut accel = Vec2::ZERO;
+
+        if let Some(mouse) = mouse {
+            let offset = mouse - pos;
+            let falloff = 1.0 - (offset.length() / rect.w().max(rect.h()));
+
+            accel += offset.normalize_or_zero()
+                * (self.mouse * MOUSE_ACCEL * falloff.max(0.0));
+        }
+
+        accel += (rect.xy() - pos) * (self.gravity * GRAVITY_ACCEL);
+
+        if self.flocking != 0.0 {
+            accel += flock(points, idx, pos, vel) * self.flocking;
+        }
+
+        accel
+    }
+
+    /// Separates overlapping points, and bounces them off each other.
+    fn collide(&self, points: &mut [Point], held: Option<usize>, dist: f32) {
+        let restitution = self.collision.clamp(0.0, 1.0);
+
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                let offset = points[j].pos - points[i].pos;
+                let len = offset.length();
+
+                if len >= dist {
+                    continue;
+                }
+
+                let normal = if len > f32::EPSILON {
+                    offset / len
+                }
+                else {
+                    Vec2::X
+                };
+
+                // a held point doesn't move, so the other point takes all of
+                // the correction
+                let (wi, wj) = match held {
+                    Some(h) if h == i => (0.0, 1.0),
+                    Some(h) if h == j => (1.0, 0.0),
+                    _ => (0.5, 0.5),
+                };
+
+                let overlap = normal * (dist - len);
+                points[i].pos -= overlap * wi;
+                points[j].pos += overlap * wj;
+
+                let approach = (points[j].vel - points[i].vel).dot(normal);
+
+                if approach < 0.0 {
+                    let impulse = normal * ((1.0 + restitution) * approach);
+                    points[i].vel += impulse * wi;
+                    points[j].vel -= impulse * wj;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the boids-style steering of the point at `idx`, which is at `pos`
+/// with velocity `vel`.
+fn flock(points: &[Point], idx: usize, pos: Vec2, vel: Vec2) -> Vec2 {
+    let mut centre = Vec2::ZERO;
+    let mut heading = Vec2::ZERO;
+    let mut separation = Vec2::ZERO;
+    let mut neighbours = 0;
+
+    for (j, other) in points.iter().enumerate() {
+        let offset = pos - other.pos;
+        let dist_sq = offset.length_squared();
+
+        if j == idx || dist_sq > FLOCK_RADIUS * FLOCK_RADIUS {
+            continue;
+        }
+
+        centre += other.pos;
+        heading += other.vel;
+        separation += offset / dist_sq.max(1.0);
+        neighbours += 1;
+    }
+
+    if neighbours == 0 {
+        return Vec2::ZERO;
+    }
+
+    let n = neighbours as f32;
+
+    (centre / n - pos) * COHESION
+        + (heading / n - vel) * ALIGNMENT
+        + separation * SEPARATION
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32, vx: f32, vy: f32) -> Point {
+        Point {
+            pos: Vec2::new(x, y),
+            vel: Vec2::new(vx, vy),
+            deceleration_factor: 1.0,
+        }
+    }
+
+    fn rect() -> Rect {
+        Rect::from_w_h(256.0, 256.0)
+    }
+
+    #[test]
+    fn no_forces_leave_points_alone() {
+        let mut points =
+            vec![point(10.0, 0.0, 1.0, 0.0), point(11.0, 0.0, 0.0, 0.0)];
+
+        FieldForces::default().apply(&mut points, &rect(), None, None, 5.0);
+
+        assert_eq!(points[0].pos, Vec2::new(10.0, 0.0));
+        assert_eq!(points[0].vel, Vec2::new(1.0, 0.0));
+        assert_eq!(points[1].vel, Vec2::ZERO);
+    }
+
+    #[test]
+    fn gravity_and_mouse_attract_and_repel() {
+        let pull = |forces: FieldForces, mouse: Option<Vec2>| {
+            let mut points = vec![point(100.0, 0.0, 0.0, 0.0)];
+            forces.apply(&mut points, &rect(), mouse, None, 5.0);
+            points[0].vel.x
+        };
+
+        let gravity = FieldForces { gravity: 1.0, ..Default::default() };
+        assert!(pull(gravity, None) < 0.0);
+        assert!(pull(FieldForces { gravity: -1.0, ..gravity }, None) > 0.0);
+
+        let mouse = FieldForces { mouse: 1.0, ..Default::default() };
+        let right = Some(Vec2::new(120.0, 0.0));
+        assert!(pull(mouse, right) > 0.0);
+        assert!(pull(FieldForces { mouse: -1.0, ..mouse }, right) < 0.0);
+        assert_eq!(pull(mouse, None), 0.0);
+    }
+
+    #[test]
+    fn held_points_are_not_moved() {
+        let forces = FieldForces { gravity: 1.0, ..Default::default() };
+        let mut points = vec![point(100.0, 0.0, 0.0, 0.0)];
+
+        forces.apply(&mut points, &rect(), None, Some(0), 5.0);
+
+        assert_eq!(points[0].vel, Vec2::ZERO);
+    }
+
+    #[test]
+    fn collisions_separate_and_bounce_points() {
+        let forces = FieldForces { collision: 1.0, ..Default::default() };
+        let mut points =
+            vec![point(0.0, 0.0, 1.0, 0.0), point(5.0, 0.0, -1.0, 0.0)];
+
+        forces.apply(&mut points, &rect(), None, None, 5.0);
+
+        let dist = points[0].pos.distance(points[1].pos);
+        assert!((dist - 5.0 * COLLISION_DIAMETER).abs() < 1e-4);
+        assert!(points[0].vel.x < 0.0 && points[1].vel.x > 0.0);
+    }
+
+    #[test]
+    fn flocking_aligns_neighbours() {
+        let forces = FieldForces { flocking: 1.0, ..Default::default() };
+        let mut points =
+            vec![point(0.0, 0.0, 2.0, 0.0), point(0.0, 30.0, 0.0, 2.0)];
+
+        let before = points[0].vel.angle_between(points[1].vel);
+        forces.apply(&mut points, &rect(), None, None, 5.0);
+        let after = points[0].vel.angle_between(points[1].vel);
+
+        assert!(after < before);
+    }
+}
@@ -6,9 +6,14 @@ use crate::{
     prelude::*,
 };
 use nannou::prelude::*;
+use rand::Rng;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex};
 
+mod forces;
+
+pub use forces::FieldForces;
+
 const MAX_VELOCITY: f32 = 3.0;
 const MIN_START_VELOCITY: f32 = 1.5;
 
@@ -27,14 +32,13 @@ impl Point {
     }
 
     /// Randomises the velocity of the point.
-    pub fn randomize_velocity(&mut self) {
-        self.vel.x = random_range(-MAX_VELOCITY, MAX_VELOCITY) * 0.7;
-        self.vel.y = random_range(-MAX_VELOCITY, MAX_VELOCITY);
+    pub fn randomize_velocity(&mut self, rng: &mut impl Rng) {
+        self.vel = random_velocity(rng);
     }
 
     /// Randomises the deceleration factor of the point.
-    pub fn randomize_deceleration(&mut self) {
-        self.deceleration_factor = random_range(0.90, 0.999);
+    pub fn randomize_deceleration(&mut self, rng: &mut impl Rng) {
+        self.deceleration_factor = rng.gen_range(0.90..0.999);
     }
 
     /// Returns whether the point contains `pos`.
@@ -58,6 +62,7 @@ pub struct VectorField {
     /// The number of currently active vectors.
     pub num_active_points: usize,
     deceleration_scale: f32,
+    forces: FieldForces,
 
     /// Whether the mouse can interact with the field or not.
     pub can_mouse_interact: bool,
@@ -66,6 +71,7 @@ pub struct VectorField {
     clicked_idx: Option<usize>,
 
     rect: Rect,
+    rng: SessionRng,
 }
 
 impl VectorField {
@@ -85,10 +91,12 @@ impl VectorField {
             point_color: Rgba::new(1.0, 1.0, 1.0, 1.0),
             num_active_points: num_points,
             deceleration_scale: 1.0,
+            forces: FieldForces::default(),
             can_mouse_interact: true,
             points_overriden: false,
             clicked_idx: None,
             rect,
+            rng: SessionRng::new("vector_field"),
         };
 
         s.randomize_points();
@@ -109,6 +117,15 @@ impl VectorField {
         self
     }
 
+    /// Draws the random positions and velocities of points from the named
+    /// `stream` of the session seed, so that separate fields move
+    /// independently.
+    pub fn with_rng_stream(mut self, stream: &'static str) -> Self {
+        self.rng = SessionRng::new(stream);
+        self.randomize_points();
+        self
+    }
+
     /// Sets the initial number of active points.
     pub fn with_num_active_points(mut self, num_points: usize) -> Self {
         self.set_num_active_points(num_points);
@@ -178,11 +195,41 @@ impl VectorField {
         let len = self.num_active_points;
 
         for i in 0..len {
-            let pos = self.clamped_vec(random_vector(&self.rect));
+            let pos = random_vector(&self.rect, &mut self.rng);
+            let pos = self.clamped_vec(pos);
 
             self.points[i].set_pos(pos);
-            self.points[i].randomize_deceleration();
-            self.points[i].randomize_velocity();
+            self.points[i].randomize_deceleration(&mut self.rng);
+            self.points[i].randomize_velocity(&mut self.rng);
+        }
+    }
+
+    /// Moves each point to a random position with a random velocity of up to
+    /// `max_speed` along each axis, and overrides them from being updated
+    /// for the next call to [`update()`](UIDraw::update).
+    pub fn scatter_points(&mut self, max_speed: f32) {
+        let len = self.num_active_points;
+
+        for i in 0..len {
+            let pos = random_vector(&self.rect, &mut self.rng);
+            let pos = self.clamped_vec(pos);
+
+            self.points[i].set_pos(pos);
+        }
+
+        self.kick_points(max_speed);
+    }
+
+    /// Gives each point a random velocity of up to `max_speed` along each
+    /// axis, and overrides them from being updated for the next call to
+    /// [`update()`](UIDraw::update).
+    pub fn kick_points(&mut self, max_speed: f32) {
+        let len = self.num_active_points;
+        self.points_overriden = true;
+
+        for point in &mut self.points[..len] {
+            point.vel.x = self.rng.gen_range(-max_speed..=max_speed);
+            point.vel.y = self.rng.gen_range(-max_speed..=max_speed);
         }
     }
 
@@ -191,12 +238,7 @@ impl VectorField {
         let len = self.num_active_points;
 
         for i in 0..len {
-            let new_vel = Vec2::new(
-                random_range(-MAX_VELOCITY, MAX_VELOCITY) * 0.7,
-                random_range(-MAX_VELOCITY, MAX_VELOCITY),
-            );
-
-            self.points[i].vel += new_vel;
+            self.points[i].vel += random_velocity(&mut self.rng);
         }
     }
 
@@ -206,6 +248,11 @@ impl VectorField {
             (1.0 - friction.clamp(0.0, 1.0) * 0.20) as f32;
     }
 
+    /// Sets the forces which act on each point.
+    pub fn set_forces(&mut self, forces: FieldForces) {
+        self.forces = forces;
+    }
+
     fn clamped_vec(&self, point: Vec2) -> Vec2 {
         let padded = self.rect.pad(self.point_radius + 1.0);
         point.clamp(padded.bottom_left(), padded.top_right())
@@ -245,6 +292,19 @@ impl UIDraw for VectorField {
             self.can_mouse_interact = false;
         }
 
+        // the mouse only attracts points while none are being dragged
+        let mouse = (self.clicked_idx.is_none()
+            && self.rect.contains(input_data.mouse_pos))
+        .then_some(input_data.mouse_pos);
+
+        self.forces.apply(
+            &mut self.points[..len],
+            &self.rect,
+            mouse,
+            self.clicked_idx,
+            self.point_radius,
+        );
+
         for i in 0..len {
             if self.clicked_idx.is_none()
                 && input_data.is_left_clicked
@@ -268,7 +328,9 @@ impl UIDraw for VectorField {
 
             self.points[i].vel *= decel;
 
-            if self.points[i].vel.x < 0.04 && self.points[i].vel.y < 0.04 {
+            if self.points[i].vel.x.abs() < 0.04
+                && self.points[i].vel.y.abs() < 0.04
+            {
                 self.points[i].vel = Vec2::ZERO;
             }
         }
@@ -288,8 +350,33 @@ impl UIDraw for VectorField {
     }
 }
 
-fn random_vector(rect: &Rect) -> Vec2 {
-    let x = random_range(rect.left(), rect.right());
-    let y = random_range(rect.bottom(), rect.top());
+fn random_vector(rect: &Rect, rng: &mut impl Rng) -> Vec2 {
+    let x = rng.gen_range(rect.left()..=rect.right());
+    let y = rng.gen_range(rect.bottom()..=rect.top());
+    Vec2::new(x, y)
+}
+
+fn random_velocity(rng: &mut impl Rng) -> Vec2 {
+    let x = rng.gen_range(-MAX_VELOCITY..MAX_VELOCITY) * 0.7;
+    let y = rng.gen_range(-MAX_VELOCITY..MAX_VELOCITY);
     Vec2::new(x, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_points() {
+        let positions = |stream| {
+            let mut field = VectorField::new(16, Rect::from_w_h(256.0, 256.0))
+                .with_rng_stream(stream);
+            field.push_points();
+
+            field.points.iter().map(|p| (p.pos, p.vel)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(positions("a"), positions("a"));
+        assert_ne!(positions("a"), positions("b"));
+    }
+}
@@ -44,15 +44,10 @@ impl ComputeGeneral {
         let points_buffer = device.create_buffer_init(&BufferInitDescriptor {
             label: Some("voronoi points buffer"),
             contents: points_bytes,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         });
 
-        let state = VoronoiStateGeneral {
-            active_cells: MAX_NUM_POINTS as u32,
-            weight: 0.3,
-            width: w,
-            height: h,
-        };
+        let state = VoronoiStateGeneral::new(w, h);
         let state_bytes = state.as_bytes();
         let state_buffer = device.create_buffer_init(&BufferInitDescriptor {
             label: Some("voronoi state buffer"),
@@ -64,7 +59,7 @@ impl ComputeGeneral {
             // image
             .storage_buffer(wgpu::ShaderStages::COMPUTE, false, false)
             // points
-            .uniform_buffer(wgpu::ShaderStages::COMPUTE, false)
+            .storage_buffer(wgpu::ShaderStages::COMPUTE, false, true)
             // state
             .uniform_buffer(wgpu::ShaderStages::COMPUTE, false)
             .build(device);
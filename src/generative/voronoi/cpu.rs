@@ -0,0 +1,374 @@
+//! Voronoi noise algorithm computed on the CPU.
+//!
+//! This mirrors the algorithm in `voronoi.wgsl`, so that its output matches
+//! that of [`VoronoiGPU`].
+
+use super::*;
+use crate::generative::{MaskBackend, MaskGenerator, MaskSource};
+
+/// An implementation of Voronoi noise which is computed on the CPU.
+///
+/// This is designed to interface with `crate::generative::VectorField`.
+pub struct VoronoiCPU {
+    points: Points,
+    state: VoronoiStateGeneral,
+}
+
+/// The distances to the nearest and second-nearest points (F1 and F2), and
+/// the index of and offset from the nearest point.
+struct Nearest {
+    f1: f64,
+    f2: f64,
+    idx: usize,
+    relative: DVec2,
+}
+
+impl VoronoiCPU {
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            points: Points::new(),
+            state: VoronoiStateGeneral::new(rect.w() as u32, rect.h() as u32),
+        }
+    }
+
+    pub fn set_weight(&mut self, weight: f64) {
+        self.state.weight = weight.clamp(0.0, 1.0) as f32;
+    }
+
+    /// Sets the distance metric. `exponent` is only used by the Minkowski
+    /// metric.
+    pub fn set_metric(&mut self, metric: VoronoiMetric, exponent: f64) {
+        self.state.set_metric(metric, exponent);
+    }
+
+    pub fn set_mode(&mut self, mode: VoronoiMode) {
+        self.state.set_mode(mode);
+    }
+
+    /// Copy the 2D points from `Vectors` into the `Voronoi` generator.
+    pub fn copy_from_vectors(&mut self, vectors: &VectorField) {
+        self.state.set_active_cells(vectors.num_active_points);
+
+        self.points.copy_from_vectors(vectors);
+    }
+
+    fn num_active(&self) -> usize {
+        (self.state.active_cells as usize).min(MAX_NUM_POINTS)
+    }
+
+    /// Returns the point at `idx` in normalized coordinates.
+    fn uv_point(&self, idx: usize) -> DVec2 {
+        let Point { x, y } = self.points.points[idx];
+
+        DVec2::new(
+            x as f64 / self.state.width as f64,
+            y as f64 / self.state.height as f64,
+        )
+    }
+
+    /// Returns the length of `v` under the current distance metric.
+    fn distance(&self, v: DVec2) -> f64 {
+        const MANHATTAN: u32 = VoronoiMetric::Manhattan as u32;
+        const CHEBYSHEV: u32 = VoronoiMetric::Chebyshev as u32;
+        const MINKOWSKI: u32 = VoronoiMetric::Minkowski as u32;
+
+        let a = v.abs();
+
+        match self.state.metric {
+            MANHATTAN => a.x + a.y,
+            CHEBYSHEV => a.x.max(a.y),
+            MINKOWSKI => {
+                let p = self.state.exponent as f64;
+                (a.x.powf(p) + a.y.powf(p)).powf(p.recip())
+            }
+            _ => v.length(),
+        }
+    }
+
+    fn nearest(&self, pos: DVec2) -> Nearest {
+        let mut near =
+            Nearest { f1: 10000.0, f2: 10000.0, idx: 0, relative: DVec2::ZERO };
+
+        for i in 0..self.num_active() {
+            let relative = pos - self.uv_point(i);
+            let dist = self.distance(relative);
+
+            if dist < near.f1 {
+                near.f2 = near.f1;
+                near.f1 = dist;
+                near.idx = i;
+                near.relative = relative;
+            }
+            else if dist < near.f2 {
+                near.f2 = dist;
+            }
+        }
+
+        near
+    }
+
+    /// Returns the distance from `pos` to the nearest border between two
+    /// cells. This is exact for the Euclidean metric, and approximated with
+    /// half of F2 - F1 for the others.
+    fn border_distance(&self, pos: DVec2, near: &Nearest) -> f64 {
+        if self.state.metric != VoronoiMetric::Euclidean as u32 {
+            return 0.5 * (near.f2 - near.f1);
+        }
+
+        let min_relative = near.relative;
+        let mut min_dist = 10000.0f64;
+
+        for i in 0..self.num_active() {
+            let relative = pos - self.uv_point(i);
+
+            if (min_relative - relative).length_squared() > 0.000_001 {
+                min_dist = min_dist.min(
+                    (0.5 * (min_relative + relative))
+                        .dot((relative - min_relative).normalize()),
+                );
+            }
+        }
+
+        min_dist
+    }
+
+    fn borders(&self, voro: f64) -> f64 {
+        let weight = self.state.weight as f64;
+
+        let output = (voro
+            * 0.9f64.mul_add((lerp(465.0, 195.0, weight) * voro).sin(), 0.5))
+        .mul_add(1.5, -0.02);
+
+        let min = 0.02 * weight;
+        let t = smooth_step(min, min + 0.005, voro);
+
+        lerp(1.0, output, t)
+    }
+
+    fn f2_minus_f1(&self, near: &Nearest) -> f64 {
+        let width = lerp(0.02, 0.4, self.state.weight as f64);
+
+        1.0 - smooth_step(0.0, width, near.f2 - near.f1)
+    }
+
+    fn cell_fill(&self, voro: f64, near: &Nearest) -> f64 {
+        let min = 0.01 * self.state.weight as f64;
+
+        cell_hash(near.idx as u32) * smooth_step(min, min + 0.003, voro)
+    }
+}
+
+impl MaskSource for VoronoiCPU {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        const F2_MINUS_F1: u32 = VoronoiMode::F2MinusF1 as u32;
+        const CELL_FILL: u32 = VoronoiMode::CellFill as u32;
+
+        let pos = DVec2::new(x, y);
+        let near = self.nearest(pos);
+
+        let output = match self.state.mode {
+            F2_MINUS_F1 => self.f2_minus_f1(&near),
+            CELL_FILL => {
+                self.cell_fill(self.border_distance(pos, &near), &near)
+            }
+            _ => self.borders(self.border_distance(pos, &near)),
+        };
+
+        output.clamp(0.0, 1.0)
+    }
+}
+
+impl MaskGenerator for VoronoiCPU {
+    fn step(&mut self, _: f64) {}
+}
+
+/// Hermite interpolation between `edge0` and `edge1`, as in WGSL's
+/// `smoothStep`.
+fn smooth_step(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+
+    t * t * 2.0f64.mul_add(-t, 3.0)
+}
+
+/// A PCG hash of a cell index, between `0.0` and `1.0`.
+fn cell_hash(idx: u32) -> f64 {
+    let state = idx.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+    let word =
+        ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277_803_737);
+
+    ((word >> 22) ^ word) as f64 / u32::MAX as f64
+}
+
+impl MaskBackend<VoronoiGPU, VoronoiCPU> {
+    pub fn set_weight(&mut self, weight: f64) {
+        match self {
+            Self::Gpu(gpu) => gpu.set_weight(weight),
+            Self::Cpu(cpu) => cpu.generator_mut().set_weight(weight),
+        }
+    }
+
+    pub fn set_metric(&mut self, metric: VoronoiMetric, exponent: f64) {
+        match self {
+            Self::Gpu(gpu) => gpu.set_metric(metric, exponent),
+            Self::Cpu(cpu) => cpu.generator_mut().set_metric(metric, exponent),
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: VoronoiMode) {
+        match self {
+            Self::Gpu(gpu) => gpu.set_mode(mode),
+            Self::Cpu(cpu) => cpu.generator_mut().set_mode(mode),
+        }
+    }
+
+    pub fn copy_from_vectors(&mut self, vectors: &VectorField) {
+        match self {
+            Self::Gpu(gpu) => gpu.copy_from_vectors(vectors),
+            Self::Cpu(cpu) => cpu.generator_mut().copy_from_vectors(vectors),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RES: usize = 64;
+    const METRICS: [VoronoiMetric; 4] = [
+        VoronoiMetric::Euclidean,
+        VoronoiMetric::Manhattan,
+        VoronoiMetric::Chebyshev,
+        VoronoiMetric::Minkowski,
+    ];
+    const MODES: [VoronoiMode; 3] =
+        [VoronoiMode::Borders, VoronoiMode::F2MinusF1, VoronoiMode::CellFill];
+
+    /// A generator with two points on the horizontal center line, at a
+    /// quarter and three quarters of the width.
+    fn two_points() -> VoronoiCPU {
+        let mut voronoi = VoronoiCPU::new(Rect::from_w_h(256.0, 256.0));
+        voronoi.points.points[0].copy_from_vec2(Vec2::new(64.0, 128.0));
+        voronoi.points.points[1].copy_from_vec2(Vec2::new(192.0, 128.0));
+        voronoi.state.active_cells = 2;
+
+        voronoi
+    }
+
+    #[test]
+    fn border_is_bright() {
+        let voronoi = two_points();
+
+        for y in [0.1, 0.5, 0.9] {
+            assert!(voronoi.value_at(0.5, y) > 0.99);
+        }
+
+        assert!(voronoi.value_at(0.25, 0.5) < 0.9);
+        assert!(voronoi.value_at(0.75, 0.5) < 0.9);
+    }
+
+    #[test]
+    fn values_in_range() {
+        let mut voronoi = VoronoiCPU::new(Rect::from_w_h(256.0, 256.0));
+
+        for (i, pt) in voronoi.points.points.iter_mut().enumerate() {
+            let i = i as f32;
+            pt.copy_from_vec2(Vec2::new(
+                (i * 37.0) % 256.0,
+                (i * 91.0) % 256.0,
+            ));
+        }
+
+        for (metric, mode) in METRICS.into_iter().zip(MODES.into_iter().cycle())
+        {
+            voronoi.set_metric(metric, 3.0);
+            voronoi.set_mode(mode);
+
+            for weight in [0.0, 0.5, 1.0] {
+                voronoi.set_weight(weight);
+
+                for x in 0..RES {
+                    for y in 0..RES {
+                        let v = voronoi.value_at(
+                            x as f64 / RES as f64,
+                            y as f64 / RES as f64,
+                        );
+                        assert!((0.0..=1.0).contains(&v), "{metric}: {v}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn border_is_bright_for_all_metrics() {
+        let mut voronoi = two_points();
+
+        for metric in METRICS {
+            voronoi.set_metric(metric, 3.0);
+
+            for y in [0.1, 0.5, 0.9] {
+                assert!(voronoi.value_at(0.5, y) > 0.99, "{metric}");
+            }
+            assert!(voronoi.value_at(0.25, 0.5) < 0.9, "{metric}");
+        }
+    }
+
+    #[test]
+    fn f2_minus_f1_fades_from_borders() {
+        let mut voronoi = two_points();
+        voronoi.set_mode(VoronoiMode::F2MinusF1);
+
+        assert!(voronoi.value_at(0.5, 0.5) > 0.99);
+        assert!(voronoi.value_at(0.48, 0.5) > voronoi.value_at(0.4, 0.5));
+        assert!(voronoi.value_at(0.25, 0.5) < 0.01);
+    }
+
+    #[test]
+    fn cell_fill_is_constant_within_cells() {
+        let mut voronoi = two_points();
+        voronoi.set_mode(VoronoiMode::CellFill);
+        voronoi.set_weight(0.5);
+
+        let left = voronoi.value_at(0.25, 0.5);
+        let right = voronoi.value_at(0.75, 0.5);
+        assert!((left - right).abs() > f64::EPSILON);
+
+        for (x, y) in [(0.1, 0.1), (0.4, 0.9), (0.3, 0.3)] {
+            assert_eq!(voronoi.value_at(x, y), left);
+            assert_eq!(voronoi.value_at(1.0 - x, y), right);
+        }
+
+        // cells are separated by a dark gap
+        assert!(voronoi.value_at(0.5, 0.5) < 0.01);
+    }
+
+    #[test]
+    fn active_cells_are_limited() {
+        let mut voronoi = VoronoiCPU::new(Rect::from_w_h(256.0, 256.0));
+        voronoi.state.set_active_cells(MAX_NUM_POINTS * 2);
+
+        assert_eq!(voronoi.num_active(), MAX_NUM_POINTS);
+        assert!((0.0..=1.0).contains(&voronoi.value_at(0.3, 0.6)));
+    }
+
+    #[test]
+    fn borders_cover_small_area() {
+        let voronoi = two_points();
+        let mut bright = 0;
+
+        for x in 0..RES {
+            for y in 0..RES {
+                let v = voronoi
+                    .value_at(x as f64 / RES as f64, y as f64 / RES as f64);
+
+                if v > 0.99 {
+                    bright += 1;
+                }
+            }
+        }
+
+        // only the column(s) around the border should be fully lit
+        let bright_frac = bright as f64 / (RES * RES) as f64;
+        assert!(0.0 < bright_frac && bright_frac < 0.1, "{bright_frac}");
+    }
+}
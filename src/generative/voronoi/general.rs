@@ -33,12 +33,7 @@ impl VoronoiGPU {
         Self {
             rect,
             points: Points::new(),
-            state: VoronoiStateGeneral {
-                active_cells: MAX_NUM_POINTS as u32,
-                weight: 0.3,
-                width: w,
-                height: h,
-            },
+            state: VoronoiStateGeneral::new(w, h),
             compute: ComputeGeneral::new(w, h, device, &cs_mod),
 
             image_buf: Arc::new(Mutex::new(ImageBuffer::from_fn(
@@ -64,9 +59,19 @@ impl VoronoiGPU {
         self.state.weight = weight.clamp(0.0, 1.0) as f32;
     }
 
+    /// Sets the distance metric. `exponent` is only used by the Minkowski
+    /// metric.
+    pub fn set_metric(&mut self, metric: VoronoiMetric, exponent: f64) {
+        self.state.set_metric(metric, exponent);
+    }
+
+    pub fn set_mode(&mut self, mode: VoronoiMode) {
+        self.state.set_mode(mode);
+    }
+
     /// Copy the 2D points from `Vectors` into the `Voronoi` generator.
     pub fn copy_from_vectors(&mut self, vectors: &VectorField) {
-        self.state.active_cells = vectors.num_active_points as u32;
+        self.state.set_active_cells(vectors.num_active_points);
 
         self.points.copy_from_vectors(vectors);
     }
@@ -215,26 +220,10 @@ impl UIDraw for VoronoiGPU {
     }
 }
 
-impl DrawMask for VoronoiGPU {
-    fn column_to_mask(&self, mask: &mut SpectralMask, mask_len: usize, x: f64) {
-        if !(0.0..=1.0).contains(&x) {
-            return;
-        }
-
-        let sr = unsafe { SAMPLE_RATE };
-
-        for i in 1..mask_len {
-            let bin_hz = SpectralMask::bin_freq(i, mask_len, sr);
-            if bin_hz < 20.0 {
-                mask[i] = 0.0;
-                continue;
-            }
-
-            let y = 1.0 - freq_log_norm(bin_hz, 20.0, sr);
-
-            let br = self.get_value_bilinear(x, y);
-
-            mask[i] = br;
-        }
+impl MaskSource for VoronoiGPU {
+    fn value_at(&self, x: f64, y: f64) -> f64 {
+        self.get_value_bilinear(x, y)
     }
 }
+
+impl DrawMask for VoronoiGPU {}
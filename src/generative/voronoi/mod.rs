@@ -1,18 +1,25 @@
 //! Voronoi noise types and implementation.
 
+use crate::app::{VoronoiMetric, VoronoiMode};
 use crate::generative::VectorField;
 use crate::prelude::*;
 use nannou::image::{ImageBuffer, Rgba};
 use nannou::prelude::*;
 use nannou::wgpu;
+use std::ops::RangeInclusive;
 
 const VORONOI_SHADER_X_THREADS: u32 = 16;
 const VORONOI_SHADER_Y_THREADS: u32 = 16;
-const MAX_NUM_POINTS: usize = 32;
+/// The maximum number of points (and therefore cells) in a Voronoi generator.
+pub const MAX_NUM_POINTS: usize = 256;
+/// The range of exponents for the Minkowski distance metric.
+pub const MINKOWSKI_EXPONENT_RANGE: RangeInclusive<f64> = 0.5..=8.0;
 
 mod compute;
+pub mod cpu;
 pub mod general;
 
+pub use cpu::VoronoiCPU;
 pub use general::VoronoiGPU;
 
 /// 2D point representation compatible with the shader.
@@ -52,17 +59,13 @@ impl Points {
     }
 
     pub fn copy_from_vectors(&mut self, vectors: &VectorField) {
-        debug_assert!(vectors.points.capacity() >= MAX_NUM_POINTS);
         let tr = vectors.rect().top_right();
 
-        for i in 0..MAX_NUM_POINTS {
-            let point = Vec2::new(
-                256.0 - (tr.x - vectors.points[i].pos.x),
-                tr.y - vectors.points[i].pos.y,
-            );
-
-            self.points[i].copy_from_vec2(point);
-            // self.points[i].copy_from_vec2(tr - vectors.points[i].pos);
+        for (point, vector) in self.points.iter_mut().zip(&vectors.points) {
+            point.copy_from_vec2(Vec2::new(
+                256.0 - (tr.x - vector.pos.x),
+                tr.y - vector.pos.y,
+            ));
         }
     }
 
@@ -78,9 +81,40 @@ struct VoronoiStateGeneral {
     weight: f32,
     width: u32,
     height: u32,
+    metric: u32,
+    mode: u32,
+    exponent: f32,
 }
 
 impl VoronoiStateGeneral {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            active_cells: MAX_NUM_POINTS as u32,
+            weight: 0.3,
+            width,
+            height,
+            metric: VoronoiMetric::default() as u32,
+            mode: VoronoiMode::default() as u32,
+            exponent: 3.0,
+        }
+    }
+
+    pub fn set_active_cells(&mut self, num_cells: usize) {
+        self.active_cells = num_cells.min(MAX_NUM_POINTS) as u32;
+    }
+
+    pub fn set_metric(&mut self, metric: VoronoiMetric, exponent: f64) {
+        self.metric = metric as u32;
+        self.exponent = exponent.clamp(
+            *MINKOWSKI_EXPONENT_RANGE.start(),
+            *MINKOWSKI_EXPONENT_RANGE.end(),
+        ) as f32;
+    }
+
+    pub fn set_mode(&mut self, mode: VoronoiMode) {
+        self.mode = mode as u32;
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         unsafe { wgpu::bytes::from(self) }
     }
@@ -7,6 +7,9 @@
 // This version is adapted to cover points at arbitrary positions. In this example, an
 // array of 2-dimensional vectors is passed to the shader from the CPU, and the 
 // shader computes the Voronoi borders and isolines from those points.
+//
+// It also supports other distance metrics, the F2 - F1 distance, and filling
+// each cell with a random value.
 
 
 /// *** *** STATE *** *** ///
@@ -23,11 +26,11 @@ var<storage, read_write> output: Buffer;
 // The points from the CPU.
 [[block]]
 struct Points {
-    data: [[stride(8)]] array<vec2<f32>, 32>;
+    data: [[stride(8)]] array<vec2<f32>>;
 };
 
 [[group(0), binding(1)]]
-var<uniform> points: Points;
+var<storage, read> points: Points;
 
 // The algorithm state.
 [[block]]
@@ -40,6 +43,12 @@ struct State {
     width: u32;
     // The image height.
     height: u32;
+    // The distance metric (Euclidean, Manhattan, Chebyshev, Minkowski).
+    metric: u32;
+    // The output mode (borders, F2 - F1, cell fill).
+    mode: u32;
+    // The exponent of the Minkowski metric.
+    exponent: f32;
 };
 
 [[group(0), binding(2)]]
@@ -54,32 +63,76 @@ fn get_uv(x: vec2<f32>) -> vec2<f32> {
     return vec2<f32>(x.x / w, x.y / h);
 }
 
-fn voronoi(in: vec2<f32>) -> f32 {
-    var min_dist: f32 = 10000.0;
-    var min_relative: vec2<f32> = vec2<f32>(0.0, 0.0);
-    var min_idx: u32 = u32(32);
+fn distance_metric(v: vec2<f32>) -> f32 {
+    let a = abs(v);
+
+    switch (state.metric) {
+        case 1: { return a.x + a.y; }
+        case 2: { return max(a.x, a.y); }
+        case 3: {
+            let p = state.exponent;
+            return pow(pow(a.x, p) + pow(a.y, p), 1.0 / p);
+        }
+        default: { return length(v); }
+    }
+}
 
-    // find the point closest to the pixel
-    for (var i: u32 = u32(0); i < state.num_active; i = i + u32(1)) {
-        let point = get_uv(points.data[i]);
+// A PCG hash, used to give each cell a random value.
+fn hash(v: u32) -> f32 {
+    let s = v * 747796405u + 2891336453u;
+    let word = ((s >> ((s >> 28u) + 4u)) ^ s) * 277803737u;
 
-        let relative = in - point;
+    return f32((word >> 22u) ^ word) / 4294967295.0;
+}
 
-        let dist: f32 = dot(relative, relative);
+// The distances to the nearest and second-nearest points (F1 and F2), and the
+// index of the nearest point.
+struct Nearest {
+    f1: f32;
+    f2: f32;
+    idx: u32;
+    relative: vec2<f32>;
+};
 
-        if (dist < min_dist) {
-            min_dist = dist;
-            min_relative = relative;
-            min_idx = i;
+fn nearest(in: vec2<f32>) -> Nearest {
+    var near: Nearest;
+    near.f1 = 10000.0;
+    near.f2 = 10000.0;
+    near.idx = 0u;
+    near.relative = vec2<f32>(0.0, 0.0);
+
+    for (var i: u32 = 0u; i < state.num_active; i = i + 1u) {
+        let relative = in - get_uv(points.data[i]);
+        let dist = distance_metric(relative);
+
+        if (dist < near.f1) {
+            near.f2 = near.f1;
+            near.f1 = dist;
+            near.idx = i;
+            near.relative = relative;
+        }
+        else {
+            if (dist < near.f2) {
+                near.f2 = dist;
+            }
         }
     }
 
-    min_dist = 10000.0;
+    return near;
+}
 
-    for (var i: u32 = u32(0); i < state.num_active; i = i + u32(1)) {
-        let point = get_uv(points.data[i]);
+// The distance to the nearest border between two cells. This is exact for the
+// Euclidean metric, and approximated with half of F2 - F1 for the others.
+fn border_distance(in: vec2<f32>, near: Nearest) -> f32 {
+    if (state.metric != 0u) {
+        return 0.5 * (near.f2 - near.f1);
+    }
 
-        let relative = in - point;
+    let min_relative = near.relative;
+    var min_dist: f32 = 10000.0;
+
+    for (var i: u32 = 0u; i < state.num_active; i = i + 1u) {
+        let relative = in - get_uv(points.data[i]);
 
         if (dot(
                 min_relative - relative,
@@ -98,6 +151,27 @@ fn voronoi(in: vec2<f32>) -> f32 {
     return min_dist;
 }
 
+fn borders(voro: f32) -> f32 {
+    let output: f32 = voro * (0.5 + 0.9 * sin(mix(465.0, 195.0, state.weight) * voro)) * 1.5 - 0.02;
+
+    let min = 0.02 * state.weight;
+    let max = min + 0.005;
+
+    return mix(1.0, output, smoothStep(min, max, voro));
+}
+
+fn f2_minus_f1(near: Nearest) -> f32 {
+    let width = mix(0.02, 0.4, state.weight);
+
+    return 1.0 - smoothStep(0.0, width, near.f2 - near.f1);
+}
+
+fn cell_fill(voro: f32, near: Nearest) -> f32 {
+    let min = 0.01 * state.weight;
+
+    return hash(near.idx) * smoothStep(min, min + 0.003, voro);
+}
+
 /// *** *** MAIN *** *** ///
 
 fn set_output_at(x: u32, y: u32, value: f32) {
@@ -111,15 +185,16 @@ fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {
     let x = u32(id.x);
     let y = u32(id.y);
 
-    let voro = voronoi(uv);
-    let output: f32 = voro * (0.5 + 0.9 * sin(mix(465.0, 195.0, state.weight) * voro)) * 1.5 - 0.02;
+    let near = nearest(uv);
+    var output: f32;
 
-    let min = 0.02 * state.weight;
-    let max = min + 0.005;
-    // let output: f32 = mix(1.0, output, smoothStep(0.004, 0.009, voro));
-    let output: f32 = mix(1.0, output, smoothStep(min, max, voro));
+    switch (state.mode) {
+        case 1: { output = f2_minus_f1(near); }
+        case 2: { output = cell_fill(border_distance(uv, near), near); }
+        default: { output = borders(border_distance(uv, near)); }
+    }
 
-    set_output_at(x, y, output);
+    set_output_at(x, y, clamp(output, 0.0, 1.0));
 
     return;
 }
@@ -148,6 +148,12 @@ impl Button {
         self.enabled
     }
 
+    /// Sets the state of the button without calling the callback. Only
+    /// applies to toggleable buttons.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Returns a reference to the button's label text layout.
     pub fn label_layout(&self) -> &Layout {
         &self.label_layout
@@ -192,6 +198,25 @@ impl Button {
     pub fn was_just_changed(&self) -> bool {
         self.was_just_changed
     }
+
+    /// Draws the label of a toggleable button, which is otherwise only drawn
+    /// on the first frame.
+    pub fn redraw_label(&self, draw: &Draw) {
+        if !self.is_toggle {
+            return;
+        }
+
+        if let Some(label) = self.label.as_ref() {
+            let rect = self.rect;
+            let label_rect = rect.shift_y(rect.h() + rect.h() * 0.1);
+
+            draw.text(label)
+                .xy(label_rect.xy())
+                .wh(label_rect.wh())
+                .color(LABEL)
+                .layout(&self.label_layout);
+        }
+    }
 }
 
 impl UIDraw for Button {
@@ -1,60 +1,53 @@
-//! 3-band EQ UI component.
+//! Parametric EQ UI component.
 
 use super::*;
-use crate::dsp::{BiquadParams, Filter, FilterType};
+use crate::app::{EQParams, FilterSlope, UIParams};
+use crate::dsp::{FilterType, ParametricEQ, BUTTERWORTH_Q};
 use crate::gui::rdp::rdp;
-use crate::{app::UIParams, dsp::BiquadFilter};
 use atomic_float::AtomicF64;
-use std::f64::consts::SQRT_2;
-use std::sync::{atomic::AtomicBool, Arc};
-
-/// Used to determine the currently-clicked node.
-#[derive(Clone, Copy, Debug)]
-enum NodeType {
-    Low,
-    Peak,
-    High,
-}
+use std::sync::Arc;
+
+/// Used to map the y-position of a node to its Q (and back). Found
+/// experimenting on Desmos: <https://www.desmos.com/calculator/ddgep83pq2>
+const Q_SCALE_FACTOR: f32 = 3.8206;
+
+/// The size of the clickable area around each node.
+const NODE_SIZE: f32 = 14.0;
 
 /// Common filter params for each node.
 #[derive(Clone, Debug)]
 pub struct EQFilterParams {
     pub cutoff: f64,
     pub gain: f64,
+    /// The Q of the filter.
     pub q: f64,
 }
 
 /// A struct for managing the display of the draggable filter nodes and the
 /// filter magnitude response line.
+///
+/// Left-clicking an empty area of the spectrum adds a band, and
+/// right-clicking a node removes its band.
 #[derive(Clone, Debug)]
 pub struct EQDisplay {
-    /// The node position for the low filter.
-    low_filter_node: Vec2,
-    /// Whether the low filter is a shelf or not.
-    low_filter_is_shelf: Arc<AtomicBool>,
-    /// The general parameters of the low filter.
-    pub low_filter_params: EQFilterParams,
-    /// The low filter, used to compute the frequency response.
-    low_filter: BiquadFilter,
-
-    /// The node position for the peak filter.
-    peak_filter_node: Vec2,
-    /// The general parameters of the peak filter.
-    pub peak_filter_params: EQFilterParams,
-    /// The peak filter, used to compute the frequency response.
-    peak_filter: BiquadFilter,
-
-    /// The node position for the high filter.
-    high_filter_node: Vec2,
-    /// Whether the high filter is a shelf or not.
-    high_filter_is_shelf: Arc<AtomicBool>,
-    /// The general parameters of the high filter.
-    pub high_filter_params: EQFilterParams,
-    /// The high filter, used to compute the frequency response.
-    high_filter: BiquadFilter,
-
-    /// The currently-clicked node.
-    clicked_node: Option<NodeType>,
+    /// The node position for each band.
+    nodes: Vec<Vec2>,
+    /// The general parameters of each band.
+    pub band_params: Vec<EQFilterParams>,
+    /// The shared EQ parameters, used for the state and type of each band.
+    params: EQParams,
+    /// The EQ, used to compute the frequency response.
+    eq: ParametricEQ,
+
+    /// The index of the currently-clicked node.
+    clicked_node: Option<usize>,
+    /// Whether a band was removed in the last update.
+    band_was_removed: bool,
+
+    /// Whether the left mouse button was down in the last update.
+    was_left_clicked: bool,
+    /// Whether the right mouse button was down in the last update.
+    was_right_clicked: bool,
 
     /// The raw x-pos and mangitude points.
     filter_raw_points: Vec<[f64; 2]>,
@@ -81,74 +74,29 @@ impl EQDisplay {
     ) -> Self {
         let mid = rect.y();
         let left = rect.left();
-        let right = rect.right();
         let len = rect.w() as usize;
-
-        Self {
-            low_filter_node: Vec2::new(-244.94064, -175.0),
-            low_filter_is_shelf: Arc::clone(&params.low_filter_is_shelf),
-            low_filter_params: EQFilterParams {
-                cutoff: params.low_filter_cutoff.current_value(),
-                gain: params.low_filter_gain_db.current_value(),
-                q: params.low_filter_q.current_value().recip(),
-            },
-            low_filter: {
-                let mut filter = BiquadFilter::new(sample_rate.lr());
-                filter.set_params(&BiquadParams {
-                    freq: params.low_filter_cutoff.current_value(),
-                    gain: params.low_filter_gain_db.current_value(),
-                    q: params.low_filter_q.current_value().recip(),
-                    filter_type: if params.low_filter_is_shelf.lr() {
-                        FilterType::Lowshelf
-                    }
-                    else {
-                        FilterType::Highpass
-                    },
-                });
-                filter
-            },
-
-            peak_filter_node: Vec2::new(-176.9, -175.0),
-            peak_filter_params: EQFilterParams {
-                cutoff: params.peak_filter_cutoff.current_value(),
-                gain: params.peak_filter_gain_db.current_value(),
-                q: params.peak_filter_q.current_value().recip(),
-            },
-            peak_filter: {
-                let mut filter = BiquadFilter::new(sample_rate.lr());
-                filter.set_params(&BiquadParams {
-                    freq: params.peak_filter_cutoff.current_value(),
-                    gain: params.peak_filter_gain_db.current_value(),
-                    q: params.peak_filter_q.current_value().recip(),
-                    filter_type: FilterType::Peak,
-                });
-                filter
-            },
-
-            high_filter_node: Vec2::new(-108.4, -175.0),
-            high_filter_is_shelf: Arc::clone(&params.high_filter_is_shelf),
-            high_filter_params: EQFilterParams {
-                cutoff: params.high_filter_cutoff.current_value(),
-                gain: params.high_filter_gain_db.current_value(),
-                q: params.high_filter_q.current_value().recip(),
-            },
-            high_filter: {
-                let mut filter = BiquadFilter::new(sample_rate.lr());
-                filter.set_params(&BiquadParams {
-                    freq: params.high_filter_cutoff.current_value(),
-                    gain: params.high_filter_gain_db.current_value(),
-                    q: params.high_filter_q.current_value().recip(),
-                    filter_type: if params.high_filter_is_shelf.lr() {
-                        FilterType::Highshelf
-                    }
-                    else {
-                        FilterType::Lowpass
-                    },
-                });
-                filter
-            },
+        let params = params.eq_params.clone();
+        let num_bands = params.bands.len();
+
+        let mut s = Self {
+            nodes: vec![Vec2::ZERO; num_bands],
+            band_params: params
+                .bands
+                .iter()
+                .map(|band| EQFilterParams {
+                    cutoff: band.cutoff.current_value(),
+                    gain: band.gain_db.current_value(),
+                    q: band.q.current_value(),
+                })
+                .collect(),
+            eq: ParametricEQ::new(num_bands, sample_rate.lr()),
+            params,
 
             clicked_node: None,
+            band_was_removed: false,
+
+            was_left_clicked: false,
+            was_right_clicked: false,
 
             filter_raw_points: (0..len)
                 .map(|i| {
@@ -163,34 +111,44 @@ impl EQDisplay {
 
             rect,
             sample_rate,
-        }
+        };
+
+        s.update_nodes();
+        s
+    }
+
+    /// Returns the index of the selected band.
+    pub fn selected_band(&self) -> usize {
+        self.params.selected_band.lr()
+    }
+
+    /// Returns whether a band was removed in the last update.
+    pub fn band_was_removed(&self) -> bool {
+        self.band_was_removed
     }
 
     fn draw_filter_nodes(&self, draw: &Draw) {
-        let node_color_1 = Rgba::new(0.9, 0.4, 0.0, 0.5);
-        let node_color_2 = Rgba::new(0.4, 0.7, 0.0, 0.5);
-        let node_color_3 = Rgba::new(0.0, 0.4, 0.9, 0.5);
-
-        draw.ellipse()
-            .xy(self.low_filter_node)
-            .radius(7.0)
-            .color(node_color_1);
-
-        draw.ellipse()
-            .xy(self.peak_filter_node)
-            .radius(7.0)
-            .color(node_color_1);
-
-        draw.ellipse()
-            .xy(self.high_filter_node)
-            .radius(7.0)
-            .color(node_color_1);
+        let node_color = Rgba::new(0.9, 0.4, 0.0, 0.5);
+        let selected_color = Rgba::new(0.9, 0.4, 0.0, 0.9);
+        let selected = self.selected_band();
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            if !self.params.bands[i].enabled.lr() {
+                continue;
+            }
+
+            draw.ellipse().xy(*node).radius(7.0).color(if i == selected {
+                selected_color
+            }
+            else {
+                node_color
+            });
+        }
     }
 
     /// Updates the filter line based on the response of the internal filters.
     fn update_filter_line(&mut self) {
         let sr = self.sample_rate.lr();
-        let (l, r) = (self.rect.left() as f64, self.rect.right() as f64);
         let (b, t) = (self.rect.bottom() as f64, self.rect.top() as f64);
         let w = self.rect.w() as f64;
         let half_height = self.rect.h() as f64 * 0.5; // marks +- 30 db
@@ -200,11 +158,7 @@ impl EQDisplay {
         {
             let x = i as f64 / w;
             let freq = freq_lin_from_log(x, 25.0, sr);
-            let scaled = map(freq, 0.0, sr * 0.5, 0.0, PI_F64);
-
-            let mag_db = self.low_filter.response_at(scaled)
-                + self.peak_filter.response_at(scaled)
-                + self.high_filter.response_at(scaled);
+            let mag_db = self.eq.response_at(freq);
 
             point[1] = (mid
                 + map(mag_db, -30.0, 30.0, -half_height, half_height))
@@ -230,285 +184,197 @@ impl EQDisplay {
         }
     }
 
-    /// Updates the internal filters (and therefore the filter response line);
+    /// Updates the internal filters (and therefore the filter response line).
     fn update_filters(&mut self) {
-        let lf_shelf = self.low_filter_is_shelf.lr();
-        self.low_filter.set_params(&BiquadParams {
-            freq: self.low_filter_params.cutoff,
-            gain: self.low_filter_params.gain,
-            q: if lf_shelf { SQRT_2 } else { self.low_filter_params.q },
-            filter_type: if lf_shelf {
-                FilterType::Lowshelf
-            }
-            else {
-                FilterType::Highpass
-            },
-        });
-
-        self.peak_filter.set_params(&BiquadParams {
-            freq: self.peak_filter_params.cutoff,
-            gain: self.peak_filter_params.gain,
-            q: self.peak_filter_params.q,
-            filter_type: FilterType::Peak,
-        });
-
-        let hf_shelf = self.high_filter_is_shelf.lr();
-        self.high_filter.set_params(&BiquadParams {
-            freq: self.high_filter_params.cutoff,
-            gain: self.high_filter_params.gain,
-            q: if hf_shelf { SQRT_2 } else { self.high_filter_params.q },
-            filter_type: if hf_shelf {
-                FilterType::Highshelf
-            }
-            else {
-                FilterType::Lowpass
-            },
-        });
+        let bands = self.eq.bands_mut();
 
-        self.low_filter.process(0.0);
-        self.peak_filter.process(0.0);
-        self.high_filter.process(0.0);
+        for ((band, params), filter) in
+            bands.zip(&self.params.bands).zip(&self.band_params)
+        {
+            band.set_enabled(params.enabled.lr());
+            band.set_type(params.filter_type.lr());
+            band.set_slope(params.slope.lr());
+            band.set_freq(filter.cutoff);
+            band.set_gain_db(filter.gain);
+            band.set_q(filter.q);
+        }
+
+        self.eq.update_coefs();
     }
 
-    fn update_clicked_node(&mut self, mouse_pos: Vec2) {
-        if self.clicked_node.is_some() {
-            return;
-        }
+    /// Returns the index of the enabled node under `mouse_pos`, if any.
+    fn node_at(&self, mouse_pos: Vec2) -> Option<usize> {
+        // the last node is drawn on top, so check in reverse
+        (0..self.nodes.len()).rev().find(|&i| {
+            self.params.bands[i].enabled.lr()
+                && Rect::from_xy_wh(self.nodes[i], pt2(NODE_SIZE, NODE_SIZE))
+                    .contains(mouse_pos)
+        })
+    }
 
-        let low_rect = Rect::from_xy_wh(self.low_filter_node, pt2(14.0, 14.0));
-        let peak_rect =
-            Rect::from_xy_wh(self.peak_filter_node, pt2(14.0, 14.0));
-        let high_rect =
-            Rect::from_xy_wh(self.high_filter_node, pt2(14.0, 14.0));
+    /// Enables the first free band as a peak filter at `mouse_pos`, and
+    /// returns its index. Returns `None` if every band is in use.
+    fn add_band(&mut self, mouse_pos: Vec2) -> Option<usize> {
+        let idx = self.params.first_free_band()?;
+        let band = &self.params.bands[idx];
 
-        if low_rect.contains(mouse_pos) {
-            self.clicked_node = Some(NodeType::Low);
-        }
-        else if peak_rect.contains(mouse_pos) {
-            self.clicked_node = Some(NodeType::Peak);
+        let filter = EQFilterParams {
+            cutoff: self.freq_from_x(mouse_pos.x),
+            gain: self.gain_from_y(mouse_pos.y),
+            q: BUTTERWORTH_Q,
+        };
+
+        band.filter_type.sr(FilterType::Peak);
+        band.slope.sr(FilterSlope::default());
+        band.cutoff.reset_to(filter.cutoff);
+        band.gain_db.reset_to(filter.gain);
+        band.q.reset_to(filter.q);
+        band.enabled.sr(true);
+
+        self.band_params[idx] = filter;
+
+        Some(idx)
+    }
+
+    /// Disables the band at `idx`. If it was selected, the first remaining
+    /// band is selected instead.
+    fn remove_band(&mut self, idx: usize) {
+        self.params.bands[idx].enabled.sr(false);
+
+        if self.selected_band() == idx {
+            if let Some(next) =
+                self.params.bands.iter().position(|band| band.enabled.lr())
+            {
+                self.params.selected_band.sr(next);
+            }
         }
-        else if high_rect.contains(mouse_pos) {
-            self.clicked_node = Some(NodeType::High);
+
+        self.band_was_removed = true;
+    }
+
+    /// Moves the node at `idx` to `mouse_pos`, and updates its band.
+    fn drag_node(&mut self, idx: usize, mouse_pos: Vec2) {
+        let padded = self.rect.pad(8.0);
+        let node = mouse_pos.clamp(padded.bottom_left(), padded.top_right());
+        self.nodes[idx] = node;
+
+        let uses_gain = self.params.bands[idx].filter_type.lr().uses_gain();
+        let cutoff = self.freq_from_x(node.x);
+        let (gain, q) = (self.gain_from_y(node.y), self.q_from_y(node.y));
+
+        let filter = &mut self.band_params[idx];
+        filter.cutoff = cutoff;
+
+        if uses_gain {
+            filter.gain = gain;
         }
         else {
-            self.clicked_node = None;
+            filter.q = q;
         }
     }
 
     /// Updates the node positions from external parameter changes.
-    fn update_nodes_from_param_changes(&mut self, input: &InputData) {
-        const Q_SCALE_FACTOR: f32 = 3.8206;
-        let q_scale_tanh = -Q_SCALE_FACTOR.tanh();
-
-        let mp = input.mouse_pos;
-        let clicked = input.is_left_clicked;
+    fn update_nodes(&mut self) {
         let rect = self.rect;
         let padded = rect.pad(8.0);
         let sr = self.sample_rate.lr();
 
-        // Set node positions based on filter parameter updates.
-        // LOW FILTER
-        // frequency
-        let low_freq = self.low_filter_params.cutoff;
-        self.low_filter_node.x = rect.left()
-            + freq_log_norm(low_freq as f64, 25.0, sr) as f32 * rect.w();
-
-        // gain
-        if self.low_filter_is_shelf.lr() {
-            let gain = self.low_filter_params.gain as f32;
-            let norm = map_f32(gain, -24.0, 24.0, 0.02962963, 0.97037035);
-            self.low_filter_node.y =
-                map_f32(norm, 0.0, 1.0, rect.bottom(), rect.top());
-        }
-        // q
-        else {
-            let q = normalize_f32(
-                self.low_filter_params.q.recip() as f32,
-                0.3,
-                10.0,
-            );
-            let q_norm = (q_scale_tanh * (q - 1.0)).atanh() / Q_SCALE_FACTOR;
-            let norm = scale_f32(q_norm, 0.02962963, 0.97037035);
-            self.low_filter_node.y =
-                map_f32(norm, 1.0, 0.0, rect.bottom(), rect.top());
-        }
+        for (i, filter) in self.band_params.iter().enumerate() {
+            // the dragged node follows the mouse instead
+            if self.clicked_node == Some(i) {
+                continue;
+            }
 
-        // PEAK FILTER
-        // freq
-        let peak_freq = self.peak_filter_params.cutoff;
-        self.peak_filter_node.x = rect.left()
-            + freq_log_norm(peak_freq as f64, 25.0, sr) as f32 * rect.w();
-
-        // gain
-        let gain = self.peak_filter_params.gain as f32;
-        let norm = map_f32(gain, -24.0, 24.0, 0.02962963, 0.97037035);
-        self.peak_filter_node.y =
-            map_f32(norm, 0.0, 1.0, rect.bottom(), rect.top());
-
-        // HIGH FILTER
-
-        // freq
-        let high_freq = self.high_filter_params.cutoff;
-        self.high_filter_node.x = rect.left()
-            + freq_log_norm(high_freq as f64, 25.0, sr) as f32 * rect.w();
-
-        // gain
-        if self.high_filter_is_shelf.lr() {
-            let gain = self.high_filter_params.gain as f32;
-            let norm = map_f32(gain, -24.0, 24.0, 0.02962963, 0.97037035);
-            self.high_filter_node.y =
-                map_f32(norm, 0.0, 1.0, rect.bottom(), rect.top());
-        }
-        // q
-        else {
-            let q = normalize_f32(
-                self.high_filter_params.q.recip() as f32,
-                0.3,
-                10.0,
-            );
-            let q_norm = (q_scale_tanh * (q - 1.0)).atanh() / Q_SCALE_FACTOR;
-            let norm = scale_f32(q_norm, 0.02962963, 0.97037035);
-            self.high_filter_node.y =
-                map_f32(norm, 1.0, 0.0, rect.bottom(), rect.top());
+            let uses_gain = self.params.bands[i].filter_type.lr().uses_gain();
+            let x = rect.left()
+                + freq_log_norm(filter.cutoff, 25.0, sr) as f32 * rect.w();
+            let y = if uses_gain {
+                let norm = map_f32(
+                    filter.gain as f32,
+                    -24.0,
+                    24.0,
+                    0.02962963,
+                    0.97037035,
+                );
+                map_f32(norm, 0.0, 1.0, rect.bottom(), rect.top())
+            }
+            else {
+                let q_scale_tanh = -Q_SCALE_FACTOR.tanh();
+                let q = normalize_f32(filter.q as f32, 0.3, 10.0);
+                let q_norm =
+                    (q_scale_tanh * (q - 1.0)).atanh() / Q_SCALE_FACTOR;
+                let norm = scale_f32(q_norm, 0.02962963, 0.97037035);
+                map_f32(norm, 1.0, 0.0, rect.bottom(), rect.top())
+            };
+
+            // clamp nodes into the padded rect
+            self.nodes[i] =
+                pt2(x, y).clamp(padded.bottom_left(), padded.top_right());
         }
+    }
+
+    fn freq_from_x(&self, x: f32) -> f64 {
+        let xpos_norm = (x - self.rect.left()) / self.rect.w();
+        let sr = self.sample_rate.lr();
+        let freq = freq_lin_from_log(xpos_norm as f64, 25.0, sr);
 
-        // clamp nodes into the padded rect
-        self.low_filter_node = self
-            .low_filter_node
-            .clamp(padded.bottom_left(), padded.top_right());
-        self.peak_filter_node = self
-            .peak_filter_node
-            .clamp(padded.bottom_left(), padded.top_right());
-        self.high_filter_node = self
-            .high_filter_node
-            .clamp(padded.bottom_left(), padded.top_right());
+        freq.clamp(25.0, 20000.0)
+    }
+
+    /// The normalized y-position of `y` within the range of the nodes.
+    fn ypos_norm(&self, y: f32) -> f32 {
+        normalize_f32(
+            (y - self.rect.bottom()) / self.rect.h(),
+            0.02962963,
+            0.97037035,
+        )
+    }
+
+    fn gain_from_y(&self, y: f32) -> f64 {
+        scale_f32(self.ypos_norm(y), -24.0, 24.0) as f64
+    }
+
+    fn q_from_y(&self, y: f32) -> f64 {
+        let q_scale_tanh = -Q_SCALE_FACTOR.tanh();
+        let ypos_norm = self.ypos_norm(y);
+
+        scale_f32(
+            1.0 + ((1.0 - ypos_norm) * Q_SCALE_FACTOR).tanh() / q_scale_tanh,
+            0.3,
+            10.0,
+        ) as f64
     }
 }
 
 impl UIDraw for EQDisplay {
-    /// Y-pos to Q (and back) conversions found experimenting on Desmos:
-    /// <https://www.desmos.com/calculator/ddgep83pq2>
-    #[allow(clippy::too_many_lines)]
     fn update(&mut self, app: &App, input: &InputData) {
-        const Q_SCALE_FACTOR: f32 = 3.8206;
-        let q_scale_tanh = -Q_SCALE_FACTOR.tanh();
-
         let mp = input.mouse_pos;
         let clicked = input.is_left_clicked;
-        let rect = self.rect;
-        let padded = rect.pad(8.0);
-        let sr = self.sample_rate.lr();
+        let right_clicked = input.is_right_clicked;
+
+        let left_pressed = clicked && !self.was_left_clicked;
+        let right_pressed = right_clicked && !self.was_right_clicked;
+        self.was_left_clicked = clicked;
+        self.was_right_clicked = right_clicked;
+        self.band_was_removed = false;
 
-        // horrendous indenting but it works
-        // TODO refactor, please...
         if clicked {
-            if rect.contains(mp) || self.spectrum_is_clicked {
+            if self.rect.contains(mp) || self.spectrum_is_clicked {
                 if !self.clicked_outside_of_spectrum {
-                    self.update_clicked_node(input.mouse_pos);
-
-                    // if a node is being dragged, update the relevant FilterParams.
-                    if let Some(node) = self.clicked_node {
-                        match node {
-                            NodeType::Low => {
-                                self.low_filter_node = mp.clamp(
-                                    padded.bottom_left(),
-                                    padded.top_right(),
-                                );
-
-                                // frequency
-                                let xpos_norm = (self.low_filter_node.x
-                                    - rect.left())
-                                    / rect.w();
-                                let freq = freq_lin_from_log(
-                                    xpos_norm as f64, 25.0, sr,
-                                );
-                                self.low_filter_params.cutoff =
-                                    freq.clamp(25.0, 20000.0);
-
-                                // gain
-                                let ypos_norm = normalize_f32(
-                                    (self.low_filter_node.y - rect.bottom())
-                                        / rect.h(),
-                                    0.02962963,
-                                    0.97037035,
-                                );
-                                let gain = scale_f32(ypos_norm, -24.0, 24.0);
-                                self.low_filter_params.gain = gain as f64;
-
-                                // q
-                                let q = scale_f32(
-                                    1.0 + ((1.0 - ypos_norm) * Q_SCALE_FACTOR)
-                                        .tanh()
-                                        / q_scale_tanh,
-                                    0.3,
-                                    10.0,
-                                );
-                                self.low_filter_params.q = (q as f64).recip();
-                            }
-                            NodeType::Peak => {
-                                self.peak_filter_node = mp.clamp(
-                                    padded.bottom_left(),
-                                    padded.top_right(),
-                                );
-
-                                // frequency
-                                let xpos_norm = (self.peak_filter_node.x
-                                    - rect.left())
-                                    / rect.w();
-                                let freq = freq_lin_from_log(
-                                    xpos_norm as f64, 25.0, sr,
-                                );
-                                self.peak_filter_params.cutoff =
-                                    freq.clamp(25.0, 20000.0);
-
-                                // gain
-                                let ypos_norm = normalize_f32(
-                                    (self.peak_filter_node.y - rect.bottom())
-                                        / rect.h(),
-                                    0.02962963,
-                                    0.97037035,
-                                );
-                                let gain = scale_f32(ypos_norm, -24.0, 24.0);
-                                self.peak_filter_params.gain = gain as f64;
-                            }
-                            NodeType::High => {
-                                self.high_filter_node = mp.clamp(
-                                    padded.bottom_left(),
-                                    padded.top_right(),
-                                );
-
-                                // frequency
-                                let xpos_norm = (self.high_filter_node.x
-                                    - rect.left())
-                                    / rect.w();
-                                let freq = freq_lin_from_log(
-                                    xpos_norm as f64, 25.0, sr,
-                                );
-                                self.high_filter_params.cutoff =
-                                    freq.clamp(25.0, 20000.0);
-
-                                // gain
-                                let ypos_norm = normalize_f32(
-                                    (self.high_filter_node.y - rect.bottom())
-                                        / rect.h(),
-                                    0.02962963,
-                                    0.97037035,
-                                );
-                                let gain = scale_f32(ypos_norm, -24.0, 24.0);
-                                self.high_filter_params.gain = gain as f64;
-
-                                // q
-                                let q = scale_f32(
-                                    1.0 + ((1.0 - ypos_norm) * Q_SCALE_FACTOR)
-                                        .tanh()
-                                        / q_scale_tanh,
-                                    0.3,
-                                    10.0,
-                                );
-                                self.high_filter_params.q = (q as f64).recip();
-                            }
+                    // select the clicked node, or add a new one
+                    if left_pressed {
+                        self.clicked_node =
+                            self.node_at(mp).or_else(|| self.add_band(mp));
+
+                        if let Some(idx) = self.clicked_node {
+                            self.params.selected_band.sr(idx);
                         }
                     }
+
+                    // if a node is being dragged, update its band
+                    if let Some(idx) = self.clicked_node {
+                        self.drag_node(idx, mp);
+                    }
                 }
 
                 self.spectrum_is_clicked = true;
@@ -521,12 +387,15 @@ impl UIDraw for EQDisplay {
             self.spectrum_is_clicked = false;
             self.clicked_outside_of_spectrum = false;
             self.clicked_node = None;
-        }
 
-        if clicked && self.clicked_outside_of_spectrum {
-            self.update_nodes_from_param_changes(input);
+            if right_pressed && self.rect.contains(mp) {
+                if let Some(idx) = self.node_at(mp) {
+                    self.remove_band(idx);
+                }
+            }
         }
 
+        self.update_nodes();
         self.update_filters();
         self.update_filter_line();
     }
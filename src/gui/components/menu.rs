@@ -147,6 +147,15 @@ impl<E: MenuEnum> Menu<E> {
         self.callback = Some(Box::new(callback));
     }
 
+    /// Sets the current item of the menu without calling the callback.
+    pub fn set_variant(&mut self, variant: E) {
+        if variant.idx() != self.variant.idx() {
+            self.variant = variant;
+            self.update_current_name();
+            self.needs_redraw = true;
+        }
+    }
+
     /// Returns the current item of the menu.
     pub fn output(&self) -> E {
         self.variant
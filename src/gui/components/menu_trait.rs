@@ -1,6 +1,7 @@
 //! Trait for enums which may be represented by a [`Menu`](super::Menu).
 
 use crate::app::*;
+use crate::dsp::FilterType;
 use std::fmt::{Debug, Display};
 
 /// A trait for preparing an enum for use with the [`Menu`] UI component.
@@ -29,7 +30,7 @@ pub trait MenuEnum: Display + Clone + Copy + Default + Debug {
 
 impl MenuEnum for GenerativeAlgo {
     fn num_variants() -> usize {
-        3
+        6
     }
 
     fn idx(&self) -> usize {
@@ -37,6 +38,9 @@ impl MenuEnum for GenerativeAlgo {
             Self::Contours => 0,
             Self::SmoothLife => 1,
             Self::Voronoi => 2,
+            Self::ReactionDiffusion => 3,
+            Self::Image => 4,
+            Self::SpectralFeedback => 5,
         }
     }
 
@@ -45,21 +49,170 @@ impl MenuEnum for GenerativeAlgo {
             0 => Some(Self::Contours),
             1 => Some(Self::SmoothLife),
             2 => Some(Self::Voronoi),
+            3 => Some(Self::ReactionDiffusion),
+            4 => Some(Self::Image),
+            5 => Some(Self::SpectralFeedback),
             _ => None,
         }
     }
 }
 
-impl MenuEnum for SmoothLifePreset {
+impl MenuEnum for MaskReadoutMode {
+    fn num_variants() -> usize {
+        4
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Column => 0,
+            Self::Row => 1,
+            Self::Radial => 2,
+            Self::Circular => 3,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Column),
+            1 => Some(Self::Row),
+            2 => Some(Self::Radial),
+            3 => Some(Self::Circular),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for ScanLineBlend {
+    fn num_variants() -> usize {
+        2
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Average => 0,
+            Self::Crossfade => 1,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Average),
+            1 => Some(Self::Crossfade),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for ScanLineMotion {
+    fn num_variants() -> usize {
+        2
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Loop => 0,
+            Self::PingPong => 1,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Loop),
+            1 => Some(Self::PingPong),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for ContourNoise {
+    fn num_variants() -> usize {
+        5
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Perlin => 0,
+            Self::Simplex => 1,
+            Self::Worley => 2,
+            Self::Fbm => 3,
+            Self::Ridged => 4,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Perlin),
+            1 => Some(Self::Simplex),
+            2 => Some(Self::Worley),
+            3 => Some(Self::Fbm),
+            4 => Some(Self::Ridged),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for VoronoiMetric {
+    fn num_variants() -> usize {
+        4
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Euclidean => 0,
+            Self::Manhattan => 1,
+            Self::Chebyshev => 2,
+            Self::Minkowski => 3,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Euclidean),
+            1 => Some(Self::Manhattan),
+            2 => Some(Self::Chebyshev),
+            3 => Some(Self::Minkowski),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for VoronoiMode {
     fn num_variants() -> usize {
         3
     }
 
+    fn idx(&self) -> usize {
+        match self {
+            Self::Borders => 0,
+            Self::F2MinusF1 => 1,
+            Self::CellFill => 2,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Borders),
+            1 => Some(Self::F2MinusF1),
+            2 => Some(Self::CellFill),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for SmoothLifePreset {
+    fn num_variants() -> usize {
+        7
+    }
+
     fn idx(&self) -> usize {
         match self {
             Self::Jitter => 0,
             Self::Slime => 1,
             Self::Corrupt => 2,
+            Self::Gliders => 3,
+            Self::Drift => 4,
+            Self::Fluid => 5,
+            Self::User => 6,
         }
     }
 
@@ -68,6 +221,35 @@ impl MenuEnum for SmoothLifePreset {
             0 => Some(Self::Jitter),
             1 => Some(Self::Slime),
             2 => Some(Self::Corrupt),
+            3 => Some(Self::Gliders),
+            4 => Some(Self::Drift),
+            5 => Some(Self::Fluid),
+            6 => Some(Self::User),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for ReactionDiffusionPreset {
+    fn num_variants() -> usize {
+        4
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Coral => 0,
+            Self::Mitosis => 1,
+            Self::Maze => 2,
+            Self::Spots => 3,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Coral),
+            1 => Some(Self::Mitosis),
+            2 => Some(Self::Maze),
+            3 => Some(Self::Spots),
             _ => None,
         }
     }
@@ -96,6 +278,87 @@ impl MenuEnum for SpectrogramView {
     }
 }
 
+impl MenuEnum for ModulationType {
+    fn num_variants() -> usize {
+        4
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::None => 0,
+            Self::Chorus => 1,
+            Self::Flanger => 2,
+            Self::Phaser => 3,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::None),
+            1 => Some(Self::Chorus),
+            2 => Some(Self::Flanger),
+            3 => Some(Self::Phaser),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for FxPage {
+    fn num_variants() -> usize {
+        8
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Main => 0,
+            Self::Delay => 1,
+            Self::Crush => 2,
+            Self::Modulation => 3,
+            Self::Reverb => 4,
+            Self::Compressor => 5,
+            Self::DistBands => 6,
+            Self::CompBands => 7,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Main),
+            1 => Some(Self::Delay),
+            2 => Some(Self::Crush),
+            3 => Some(Self::Modulation),
+            4 => Some(Self::Reverb),
+            5 => Some(Self::Compressor),
+            6 => Some(Self::DistBands),
+            7 => Some(Self::CompBands),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for DitherType {
+    fn num_variants() -> usize {
+        3
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::None => 0,
+            Self::Rectangular => 1,
+            Self::Triangular => 2,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::None),
+            1 => Some(Self::Rectangular),
+            2 => Some(Self::Triangular),
+            _ => None,
+        }
+    }
+}
+
 impl MenuEnum for DistortionType {
     fn num_variants() -> usize {
         5
@@ -265,3 +528,59 @@ impl MenuEnum for ExciterOscillator {
         }
     }
 }
+
+impl MenuEnum for FilterType {
+    fn num_variants() -> usize {
+        8
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Peak => 0,
+            Self::Lowpass => 1,
+            Self::Highpass => 2,
+            Self::Lowshelf => 3,
+            Self::Highshelf => 4,
+            Self::Bandpass => 5,
+            Self::Notch => 6,
+            Self::Allpass => 7,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Peak),
+            1 => Some(Self::Lowpass),
+            2 => Some(Self::Highpass),
+            3 => Some(Self::Lowshelf),
+            4 => Some(Self::Highshelf),
+            5 => Some(Self::Bandpass),
+            6 => Some(Self::Notch),
+            7 => Some(Self::Allpass),
+            _ => None,
+        }
+    }
+}
+
+impl MenuEnum for FilterSlope {
+    fn num_variants() -> usize {
+        3
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Self::Db12 => 0,
+            Self::Db24 => 1,
+            Self::Db48 => 2,
+        }
+    }
+
+    fn from_idx(idx: usize) -> Option<Self> {
+        match idx {
+            0 => Some(Self::Db12),
+            1 => Some(Self::Db24),
+            2 => Some(Self::Db48),
+            _ => None,
+        }
+    }
+}
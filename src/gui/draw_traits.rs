@@ -1,6 +1,6 @@
 //! GUI-related traits and types.
 
-use crate::dsp::SpectralMask;
+use crate::generative::MaskSource;
 use nannou::prelude::*;
 use std::sync::Arc;
 
@@ -101,10 +101,8 @@ pub trait UIDraw {
     fn rect(&self) -> &Rect;
 }
 
-/// Trait for UI components which act as spectral masks.
-pub trait DrawMask: UIDraw {
-    /// A method to map columnar data from `self` to a `SpectralMask`.
-    fn column_to_mask(&self, mask: &mut SpectralMask, len: usize, x: f64) {}
-    /// A method to map tabular data from `self` to a `SpectralMask`.
-    fn row_to_mask(&self, mask: &mut SpectralMask, len: usize, y: f64) {}
-}
+/// Trait for UI components which act as spectral masks. The mask itself is
+/// read through [`MaskSource`]; headless generators should implement
+/// [`MaskGenerator`](crate::generative::MaskGenerator) instead, which the GUI
+/// draws with [`CpuMask`](crate::generative::CpuMask).
+pub trait DrawMask: UIDraw + MaskSource {}
@@ -112,6 +112,11 @@ impl SpectrumAnalyzer {
         &self.rect
     }
 
+    /// Returns the averaged magnitude of each frequency bin in decibels.
+    pub fn spectrum_db(&self) -> &[f64] {
+        &self.averaged_data
+    }
+
     pub fn set_line_weight(&mut self, weight: f32) {
         self.line_weight = weight;
     }
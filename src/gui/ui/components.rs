@@ -2,26 +2,65 @@
 
 use super::*;
 use crate::app::audio::AudioMessageSenders;
-use crate::dsp::{ResonatorBankParams, SpectralMask};
-use crate::generative::{ContoursGPU, SmoothLifeGPU};
+use crate::dsp::modulation::{MAX_MOD_RATE, MIN_MOD_RATE};
+use crate::dsp::{
+    Compressor, Decimator, FilterType, Limiter, ResonatorBank,
+    ResonatorBankParams, Reverb, SpectralMask,
+};
+use crate::generative::reaction_diffusion::GrayScottState;
+use crate::generative::smooth_life::{SLState, SLStateAtomic, USER_PRESET_FILE};
+use crate::generative::contours::MAX_OCTAVES;
+use crate::generative::voronoi::{MAX_NUM_POINTS, MINKOWSKI_EXPONENT_RANGE};
+use crate::generative::{
+    ContoursMask, ImageMask, ReactionDiffusionMask, SpectralFeedbackMask,
+    MAX_SCAN_LINES,
+};
+use crate::app::musical::rhythm16::Rhythm16th;
+use crate::app::musical::transport::{
+    bar_beat_sixteenth, is_beat, sixteenth_idx,
+};
+use crate::gui::colors::{SELECTED, VALUE};
 use crate::{app::*, fonts::*};
 use atomic::Atomic;
 use nannou::prelude::*;
 use nannou::text::{Font, Justify, Layout};
+use std::ops::RangeInclusive;
 use std::rc::Rc;
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::{atomic::AtomicBool, Arc, Mutex, RwLock};
 use triple_buffer::Input;
 
 /// All the UI components for the device.
 pub struct UIComponents {
+    // ### TRANSPORT ###
+    /// f64
+    transport_bpm: TextSlider,
+    /// toggle
+    transport_play: Button,
+    transport_position: Label,
+    /// The last sixteenth note shown by the transport position readout.
+    transport_sixteenth: Option<usize>,
+
     // ### SPECTRAL FILTER ###
     mask_label: Label,
     pub mask_algorithm: Menu<GenerativeAlgo>,
     /// float
     mask_scan_line_speed: TextSlider,
     /// toggle
+    mask_scan_line_sync: Button,
+    /// NoteDivision
+    mask_scan_line_division: TextSlider,
+    /// toggle
     mask_is_post_fx: Button,
     mask_mix: TextSlider,
+    /// float
+    mask_stereo_width: TextSlider,
+    pub mask_readout_mode: Menu<MaskReadoutMode>,
+    /// u32
+    mask_scan_line_count: TextSlider,
+    /// float
+    mask_scan_line_spread: TextSlider,
+    pub mask_scan_line_blend: Menu<ScanLineBlend>,
+    pub mask_scan_line_motion: Menu<ScanLineMotion>,
     pub mask_resolution: Menu<SpectralFilterSize>,
     /// trigger
     mask_reset: Button,
@@ -33,17 +72,82 @@ pub struct UIComponents {
     pub contour_thickness: TextSlider,
     /// float
     pub contour_speed: TextSlider,
+    contour_noise: Menu<ContourNoise>,
+    /// u32
+    contour_octaves: TextSlider,
+    /// float
+    contour_lacunarity: TextSlider,
+    /// float
+    contour_persistence: TextSlider,
+    /// float
+    contour_warp: TextSlider,
 
     // ### Smooth life algorithm
     /// usize
     pub smoothlife_resolution: Menu<SmoothLifeSize>,
+    pub smoothlife_preset: Menu<SmoothLifePreset>,
+    /// Saves the current state as the `User` preset.
+    smoothlife_save: Button,
+    /// float
+    smoothlife_outer_radius: TextSlider,
+    /// float
+    smoothlife_inner_radius: TextSlider,
     /// float
     pub smoothlife_speed: TextSlider,
-    pub smoothlife_preset: Menu<SmoothLifePreset>,
+    /// float
+    smoothlife_b1: TextSlider,
+    /// float
+    smoothlife_b2: TextSlider,
+    /// float
+    smoothlife_alpha_n: TextSlider,
+    /// float
+    smoothlife_d1: TextSlider,
+    /// float
+    smoothlife_d2: TextSlider,
+    /// float
+    smoothlife_alpha_m: TextSlider,
+    /// The preset which the state sliders were last set from.
+    smoothlife_shown_preset: SmoothLifePreset,
+    /// The shared state of the SmoothLife simulation, which loaded presets
+    /// restore their seed to.
+    smoothlife_state: Arc<SLStateAtomic>,
 
     voronoi_cell_count: TextSlider,
     voronoi_cell_speed: TextSlider,
     voronoi_border_weight: TextSlider,
+    voronoi_metric: Menu<VoronoiMetric>,
+    /// float
+    voronoi_exponent: TextSlider,
+    voronoi_mode: Menu<VoronoiMode>,
+
+    // ### Reaction-diffusion algorithm
+    pub reaction_diffusion_preset: Menu<ReactionDiffusionPreset>,
+    /// float
+    pub reaction_diffusion_feed: TextSlider,
+    /// float
+    pub reaction_diffusion_kill: TextSlider,
+    /// float
+    reaction_diffusion_diffusion_u: TextSlider,
+    /// float
+    reaction_diffusion_diffusion_v: TextSlider,
+    /// The preset which the feed and kill sliders were last set from.
+    reaction_diffusion_shown_preset: ReactionDiffusionPreset,
+
+    // ### Image algorithm
+    /// float
+    image_brightness: TextSlider,
+    /// float
+    image_contrast: TextSlider,
+    /// float
+    image_gamma: TextSlider,
+    image_invert: Button,
+
+    // ### Spectral feedback algorithm
+    /// float
+    spectral_feedback_decay: TextSlider,
+    /// float
+    spectral_feedback_threshold: TextSlider,
+    spectral_feedback_invert: Button,
 
     // ### SPECTROGRAMS ###
     pub spectrogram_label: Label,
@@ -66,8 +170,18 @@ pub struct UIComponents {
     reso_bank_inharm: TextSlider,
     /// f64
     reso_bank_pan: TextSlider,
+    /// f64
+    reso_bank_decay: TextSlider,
+    /// f64
+    reso_bank_damping: TextSlider,
+    /// f64
+    reso_bank_decay_random: TextSlider,
     /// toggle
     reso_bank_quantize: Button,
+    /// enum
+    reso_bank_pitch_layout: TextSlider,
+    /// f64
+    reso_bank_layout_blend: TextSlider,
     /// trigger
     reso_bank_randomize: Button,
     /// trigger
@@ -81,39 +195,21 @@ pub struct UIComponents {
     pub reso_bank_resonator_count: TextSlider,
     // f64
     reso_bank_field_friction: TextSlider,
+    // f64
+    reso_bank_field_mouse: TextSlider,
+    // f64
+    reso_bank_field_flocking: TextSlider,
+    // f64
+    reso_bank_field_collision: TextSlider,
+    // f64
+    reso_bank_field_gravity: TextSlider,
 
     // ### POST EFFECTS ###
     effects_label: Label,
 
-    // ### Low filter
-    low_filter_label: Label,
-    /// toggle
-    pub low_filter_type: Button,
-    /// f64 (smoother callback)
-    pub low_filter_cutoff: TextSlider,
-    /// f64 (smoother callback)
-    pub low_filter_q: TextSlider,
-    // f64 (smoother callback)
-    pub low_filter_gain: TextSlider,
-
-    peak_filter_label: Label,
-    // f64 (smoother callback)
-    pub peak_filter_cutoff: TextSlider,
-    // f64 (smoother callback)
-    pub peak_filter_gain: TextSlider,
-    // f64 (smoother callback)
-    pub peak_filter_q: TextSlider,
-
-    // ### High filter
-    high_filter_label: Label,
-    /// toggle
-    pub high_filter_type: Button,
-    /// f64 (smoother callback)
-    pub high_filter_cutoff: TextSlider,
-    /// f64 (smoother callback)
-    pub high_filter_q: TextSlider,
-    // f64 (smoother callback)
-    pub high_filter_gain: TextSlider,
+    // ### EQ
+    /// The editor for the selected EQ band.
+    pub eq_band: EQBandComponents,
 
     // ### Stereo delay
     delay_label: Label,
@@ -125,12 +221,44 @@ pub struct UIComponents {
     delay_mix: TextSlider,
     /// toggle
     delay_is_ping_pong: Button,
+    /// toggle
+    delay_sync: Button,
+    /// NoteDivision
+    delay_division: TextSlider,
+    delay_page_label: Label,
+    /// f64 (smoother callback)
+    delay_low_cut: TextSlider,
+    /// f64 (smoother callback)
+    delay_high_cut: TextSlider,
+    /// f64 (smoother callback)
+    delay_saturation: TextSlider,
+    /// f64 (smoother callback)
+    delay_wow_flutter: TextSlider,
+    /// f64
+    delay_time_r_ms: TextSlider,
+    /// NoteDivision
+    delay_division_r: TextSlider,
+    /// toggle
+    delay_link: Button,
 
     // ### Distortion
     dist_label: Label,
     /// f64 (smoother callback)
     dist_amount: TextSlider,
     pub dist_type: Menu<DistortionType>,
+    dist_oversampling: TextSlider,
+
+    // ### Crush
+    crush_label: Label,
+    /// f64 (smoother callback)
+    crush_rate: TextSlider,
+    /// f64 (smoother callback)
+    crush_bits: TextSlider,
+    /// f64 (smoother callback)
+    crush_jitter: TextSlider,
+    pub crush_dither: Menu<DitherType>,
+    /// toggle
+    crush_anti_alias: Button,
 
     // ### Compression
     comp_label: Label,
@@ -142,6 +270,75 @@ pub struct UIComponents {
     comp_attack: TextSlider,
     /// f64 (slider callback)
     comp_release: TextSlider,
+    comp_page_label: Label,
+    /// f64 (smoother callback)
+    comp_knee: TextSlider,
+    /// f64 (smoother callback)
+    comp_stereo_link: TextSlider,
+    /// f64 (smoother callback)
+    comp_makeup: TextSlider,
+    /// f64
+    comp_lookahead: TextSlider,
+    /// f64 (smoother callback)
+    comp_sidechain_hpf: TextSlider,
+    comp_gain_reduction: Label,
+    /// The last gain reduction shown by the readout, in tenths of a decibel.
+    comp_gain_reduction_tenths: Option<i64>,
+    /// toggle
+    comp_auto_makeup: Button,
+    /// toggle
+    comp_use_rms: Button,
+    /// toggle
+    comp_exciter_key: Button,
+
+    // ### Multiband
+    dist_bands: MultibandComponents,
+    comp_bands: MultibandComponents,
+
+    // ### Limiter
+    limiter_label: Label,
+    /// f64 (smoother callback)
+    limiter_ceiling: TextSlider,
+    /// f64 (smoother callback)
+    limiter_release: TextSlider,
+    /// Shown while the safety mute is silencing the output.
+    safety_status: Label,
+    safety_muted: bool,
+    latency: Label,
+    /// The latency shown by the readout, in tenths of a millisecond.
+    latency_tenths: Option<i64>,
+
+    // ### Modulation
+    mod_label: Label,
+    pub mod_type: Menu<ModulationType>,
+    /// f64 (smoother callback)
+    mod_rate: TextSlider,
+    mod_sync: Button,
+    mod_division: TextSlider,
+    /// f64 (smoother callback)
+    pub mod_depth: TextSlider,
+    /// f64 (smoother callback)
+    mod_feedback: TextSlider,
+    /// f64 (smoother callback)
+    mod_mix: TextSlider,
+
+    // ### Reverb
+    reverb_label: Label,
+    /// f64 (smoother callback)
+    reverb_size: TextSlider,
+    /// f64 (smoother callback)
+    reverb_decay: TextSlider,
+    /// f64 (smoother callback)
+    reverb_damping: TextSlider,
+    /// f64 (smoother callback)
+    reverb_pre_delay: TextSlider,
+    /// f64 (smoother callback)
+    reverb_modulation: TextSlider,
+    /// f64 (smoother callback)
+    reverb_mix: TextSlider,
+
+    // ### FX page
+    pub fx_page: Menu<FxPage>,
 
     // ### Pre-FX gain
     pre_fx_gain: TextSlider,
@@ -171,6 +368,37 @@ fn small_label_layout() -> Layout {
     }
 }
 
+/// A slider with small text and a label above it.
+fn small_slider(rect: Rect, label: &str) -> TextSlider {
+    TextSlider::new(0.0, rect)
+        .with_label(label)
+        .with_label_layout(small_label_layout())
+        .with_value_layout(Layout {
+            font: Some(
+                Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                    .expect("failed to load font bytes"),
+            ),
+            ..small_value_layout()
+        })
+}
+
+/// A small slider which controls one parameter of the SmoothLife state.
+fn smoothlife_slider(
+    rect: Rect,
+    label: &str,
+    range: RangeInclusive<f64>,
+    state: &Arc<SLStateAtomic>,
+    param: fn(&SLStateAtomic) -> &AtomicF64,
+) -> TextSlider {
+    let state = Arc::clone(state);
+
+    small_slider(rect, label)
+        .with_output_range(range)
+        .with_value_chars(5)
+        .with_default_value(param(&state).lr())
+        .with_callback(move |_, value| param(&state).sr(value))
+}
+
 fn main_value_layout() -> Layout {
     Layout {
         font: Some(
@@ -204,6 +432,21 @@ fn big_label_layout() -> Layout {
     }
 }
 
+/// The output range of a text slider which selects a `NoteDivision`.
+fn division_range() -> std::ops::RangeInclusive<f64> {
+    0.0..=(NoteDivision::ALL.len() - 1) as f64
+}
+
+/// Converts the value of a division text slider to a `NoteDivision`.
+fn division_from_value(value: f64) -> NoteDivision {
+    NoteDivision::from_idx(value.round() as usize).unwrap_or_default()
+}
+
+/// Converts the value of a pitch layout text slider to a `PitchLayout`.
+fn pitch_layout_from_value(value: f64) -> PitchLayout {
+    PitchLayout::from_idx(value.round() as usize).unwrap_or_default()
+}
+
 impl UIComponents {
     // this should probably be broken down into smaller modules...
     #[allow(clippy::too_many_lines, clippy::missing_panics_doc)]
@@ -266,6 +509,50 @@ impl UIComponents {
                     scan_line_speed.sr(scale(raw_val, -1.0, 1.0));
                 })
             },
+            mask_scan_line_sync: {
+                let scan_line_sync = Arc::clone(&params.mask_scan_line_sync);
+                Button::new(ui_layout.mask_general.scan_line_sync)
+                    .with_enabled_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_disabled_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_enabled_text("Synced")
+                    .with_disabled_text("Free")
+                    .with_state(scan_line_sync.lr())
+                    .with_callback(move |state| scan_line_sync.sr(state))
+            },
+            mask_scan_line_division: {
+                let scan_line_division =
+                    Arc::clone(&params.mask_scan_line_division);
+                TextSlider::new(0.0, ui_layout.mask_general.scan_line_speed)
+                    .with_output_range(division_range())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_sensitivity(0.005)
+                    .with_integer_rounding()
+                    .with_default_value(scan_line_division.lr().idx() as f64)
+                    .with_callback(move |_, value| {
+                        scan_line_division.sr(division_from_value(value));
+                    })
+                    .with_formatting_callback(|_, val| {
+                        division_from_value(val).to_string()
+                    })
+            },
             mask_is_post_fx: {
                 let mask_is_post_fx = Arc::clone(&params.mask_is_post_fx);
                 Button::new(ui_layout.mask_general.is_post_fx)
@@ -305,6 +592,119 @@ impl UIComponents {
                         format!("{:.0} %", val * 100.0)
                     })
             },
+            mask_stereo_width: {
+                let stereo_width = Arc::clone(&params.mask_stereo_width);
+                TextSlider::new(0.0, ui_layout.mask_general.stereo_width)
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_prefix("W ")
+                    .with_default_value(stereo_width.lr())
+                    .with_callback(move |_, val| {
+                        stereo_width.sr(val);
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.0}%", val * 100.0)
+                    })
+            },
+            mask_readout_mode: {
+                let readout_mode = Arc::clone(&params.mask_readout_mode);
+                Menu::new(ui_layout.mask_general.readout)
+                    .with_label("Readout")
+                    .with_label_layout(small_label_layout())
+                    .with_item_text_layout(small_value_layout())
+                    .with_selected_item_text_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .initial_variant(readout_mode.lr())
+                    .with_callback(move |selected| {
+                        readout_mode.sr(selected);
+                    })
+            },
+            mask_scan_line_count: {
+                let scan_line_count = Arc::clone(&params.mask_scan_line_count);
+                TextSlider::new(0.0, ui_layout.mask_general.scan_line_count)
+                    .with_label("Lines")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_output_range(1.0..=MAX_SCAN_LINES as f64)
+                    .with_integer_rounding()
+                    .with_default_value(scan_line_count.lr() as f64)
+                    .with_callback(move |_, value| {
+                        scan_line_count.sr(value as u32);
+                    })
+            },
+            mask_scan_line_spread: {
+                let scan_line_spread =
+                    Arc::clone(&params.mask_scan_line_spread);
+                TextSlider::new(0.0, ui_layout.mask_general.scan_line_spread)
+                    .with_label("Spread")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_default_value(scan_line_spread.lr())
+                    .with_callback(move |_, value| {
+                        scan_line_spread.sr(value);
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.0}%", val * 100.0)
+                    })
+            },
+            mask_scan_line_blend: {
+                let scan_line_blend = Arc::clone(&params.mask_scan_line_blend);
+                Menu::new(ui_layout.mask_general.scan_line_blend)
+                    .with_label("Blend")
+                    .with_label_layout(small_label_layout())
+                    .with_item_text_layout(small_value_layout())
+                    .with_selected_item_text_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .initial_variant(scan_line_blend.lr())
+                    .with_callback(move |selected| {
+                        scan_line_blend.sr(selected);
+                    })
+            },
+            mask_scan_line_motion: {
+                let scan_line_motion = Arc::clone(&params.mask_scan_line_motion);
+                Menu::new(ui_layout.mask_general.scan_line_motion)
+                    .with_label("Motion")
+                    .with_label_layout(small_label_layout())
+                    .with_item_text_layout(small_value_layout())
+                    .with_selected_item_text_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .initial_variant(scan_line_motion.lr())
+                    .with_callback(move |selected| {
+                        scan_line_motion.sr(selected);
+                    })
+            },
             mask_reset: Button::new(ui_layout.mask_general.reset)
                 .with_label_layout(main_value_layout())
                 .with_label("Regenerate")
@@ -355,6 +755,54 @@ impl UIComponents {
                     .with_default_value(contour_speed.lr())
                     .with_callback(move |_, value| contour_speed.sr(value))
             },
+            contour_noise: {
+                let contour_noise = Arc::clone(&params.contour_noise);
+                Menu::new(ui_layout.contour.noise)
+                    .with_label("Noise")
+                    .with_label_layout(small_label_layout())
+                    .with_item_text_layout(small_value_layout())
+                    .with_selected_item_text_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .initial_variant(contour_noise.lr())
+                    .with_callback(move |selected| contour_noise.sr(selected))
+            },
+            contour_octaves: {
+                let octaves = Arc::clone(&params.contour_octaves);
+                small_slider(ui_layout.contour.octaves, "Octaves")
+                    .with_output_range(1.0..=MAX_OCTAVES as f64)
+                    .with_integer_rounding()
+                    .with_default_value(octaves.lr() as f64)
+                    .with_callback(move |_, value| octaves.sr(value as u32))
+            },
+            contour_lacunarity: {
+                let lacunarity = Arc::clone(&params.contour_lacunarity);
+                small_slider(ui_layout.contour.lacunarity, "Lacun.")
+                    .with_output_range(1.0..=4.0)
+                    .with_value_chars(4)
+                    .with_default_value(lacunarity.lr())
+                    .with_callback(move |_, value| lacunarity.sr(value))
+            },
+            contour_persistence: {
+                let persistence = Arc::clone(&params.contour_persistence);
+                small_slider(ui_layout.contour.persistence, "Persist.")
+                    .with_output_range(0.0..=1.0)
+                    .with_value_chars(4)
+                    .with_default_value(persistence.lr())
+                    .with_callback(move |_, value| persistence.sr(value))
+            },
+            contour_warp: {
+                let warp = Arc::clone(&params.contour_warp);
+                small_slider(ui_layout.contour.warp, "Warp")
+                    .with_output_range(0.0..=1.0)
+                    .with_value_chars(4)
+                    .with_default_value(warp.lr())
+                    .with_callback(move |_, value| warp.sr(value))
+            },
 
             smoothlife_resolution: {
                 let smoothlife_resolution =
@@ -371,21 +819,6 @@ impl UIComponents {
                     .with_item_text_layout(main_value_layout())
                     .with_selected_item_text_layout(main_value_layout())
             },
-            smoothlife_speed: {
-                let smoothlife_speed = Arc::clone(&params.smoothlife_speed);
-                TextSlider::new(0.0, ui_layout.smooth_life.speed)
-                    .with_label("Speed")
-                    .with_label_layout(Layout {
-                        justify: Justify::Left,
-                        ..main_label_layout()
-                    })
-                    .with_value_layout(main_value_layout())
-                    .with_output_range(1.0..=10.0)
-                    .with_value_chars(4)
-                    .with_suffix(" x")
-                    .with_default_value(smoothlife_speed.lr())
-                    .with_callback(move |_, value| smoothlife_speed.sr(value))
-            },
             smoothlife_preset: {
                 let smoothlife_preset = Arc::clone(&params.smoothlife_preset);
                 Menu::new(ui_layout.smooth_life.preset)
@@ -399,7 +832,85 @@ impl UIComponents {
                     })
                     .with_item_text_layout(main_value_layout())
                     .with_selected_item_text_layout(main_value_layout())
+                    .initial_variant(params.smoothlife_preset.lr())
+            },
+            smoothlife_save: {
+                let state = Arc::clone(&params.smoothlife_state);
+                Button::new(ui_layout.smooth_life.save)
+                    .with_label("Save")
+                    .with_label_layout(main_value_layout())
+                    .toggleable(false)
+                    .with_callback(move |_| {
+                        if let Err(e) = state.load().save(USER_PRESET_FILE) {
+                            eprintln!("failed to save SmoothLife preset: {e}");
+                        }
+                    })
             },
+            smoothlife_outer_radius: smoothlife_slider(
+                ui_layout.smooth_life.outer_radius,
+                "Outer",
+                2.0..=48.0,
+                &params.smoothlife_state,
+                |state| &state.radius_outer,
+            ),
+            smoothlife_inner_radius: smoothlife_slider(
+                ui_layout.smooth_life.inner_radius,
+                "Inner",
+                1.0..=24.0,
+                &params.smoothlife_state,
+                |state| &state.radius_inner,
+            ),
+            smoothlife_speed: smoothlife_slider(
+                ui_layout.smooth_life.speed,
+                "Speed",
+                0.5..=20.0,
+                &params.smoothlife_state,
+                |state| &state.dt,
+            ),
+            smoothlife_b1: smoothlife_slider(
+                ui_layout.smooth_life.b1,
+                "B1",
+                -0.5..=1.0,
+                &params.smoothlife_state,
+                |state| &state.b1,
+            ),
+            smoothlife_b2: smoothlife_slider(
+                ui_layout.smooth_life.b2,
+                "B2",
+                -0.5..=1.0,
+                &params.smoothlife_state,
+                |state| &state.b2,
+            ),
+            smoothlife_alpha_n: smoothlife_slider(
+                ui_layout.smooth_life.alpha_n,
+                "AlphaN",
+                0.001..=0.5,
+                &params.smoothlife_state,
+                |state| &state.alpha_n,
+            ),
+            smoothlife_d1: smoothlife_slider(
+                ui_layout.smooth_life.d1,
+                "D1",
+                -0.5..=1.0,
+                &params.smoothlife_state,
+                |state| &state.d1,
+            ),
+            smoothlife_d2: smoothlife_slider(
+                ui_layout.smooth_life.d2,
+                "D2",
+                -0.5..=1.0,
+                &params.smoothlife_state,
+                |state| &state.d2,
+            ),
+            smoothlife_alpha_m: smoothlife_slider(
+                ui_layout.smooth_life.alpha_m,
+                "AlphaM",
+                -8.0..=8.0,
+                &params.smoothlife_state,
+                |state| &state.alpha_m,
+            ),
+            smoothlife_shown_preset: params.smoothlife_preset.lr(),
+            smoothlife_state: Arc::clone(&params.smoothlife_state),
 
             voronoi_cell_count: {
                 let cell_count = Arc::clone(&params.voronoi_cell_count);
@@ -410,10 +921,10 @@ impl UIComponents {
                         ..main_label_layout()
                     })
                     .with_value_layout(main_value_layout())
-                    .with_output_range(2.0..=32.0)
+                    .with_output_range(2.0..=MAX_NUM_POINTS as f64)
                     .with_integer_rounding()
                     .with_default_value(cell_count.lr() as f64)
-                    .with_value_chars(2)
+                    .with_value_chars(3)
                     .with_callback(move |_, val| {
                         cell_count.sr(val as u32);
                     })
@@ -451,16 +962,225 @@ impl UIComponents {
                         weight.sr(value);
                     })
             },
+            voronoi_metric: {
+                let metric = Arc::clone(&params.voronoi_metric);
+                Menu::new(ui_layout.voronoi.metric)
+                    .with_label("Metric")
+                    .with_label_layout(small_label_layout())
+                    .with_item_text_layout(small_value_layout())
+                    .with_selected_item_text_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .initial_variant(metric.lr())
+                    .with_callback(move |selected| metric.sr(selected))
+            },
+            voronoi_exponent: {
+                let exponent = Arc::clone(&params.voronoi_exponent);
+                small_slider(ui_layout.voronoi.exponent, "Exp.")
+                    .with_output_range(MINKOWSKI_EXPONENT_RANGE)
+                    .with_value_chars(4)
+                    .with_default_value(exponent.lr())
+                    .with_callback(move |_, value| exponent.sr(value))
+            },
+            voronoi_mode: {
+                let mode = Arc::clone(&params.voronoi_mode);
+                Menu::new(ui_layout.voronoi.mode)
+                    .with_label("Mode")
+                    .with_label_layout(small_label_layout())
+                    .with_item_text_layout(small_value_layout())
+                    .with_selected_item_text_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .initial_variant(mode.lr())
+                    .with_callback(move |selected| mode.sr(selected))
+            },
 
-            spectrogram_label: Label::new(ui_layout.spectrogram.label)
-                .with_text("PARAMETRIC EQ")
-                .with_text_layout(big_label_layout()),
-            spectrogram_resolution: {
-                let spectrogram_resolution =
-                    Arc::clone(&params.spectrogram_resolution);
-                Menu::new(ui_layout.spectrogram.resolution)
+            reaction_diffusion_preset: {
+                let preset = Arc::clone(&params.reaction_diffusion_preset);
+                Menu::new(ui_layout.reaction_diffusion.preset)
                     .with_callback(move |selected| {
-                        spectrogram_resolution.sr(selected);
+                        preset.sr(selected);
+                    })
+                    .with_label("Preset")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_item_text_layout(main_value_layout())
+                    .with_selected_item_text_layout(main_value_layout())
+                    .initial_variant(params.reaction_diffusion_preset.lr())
+            },
+            reaction_diffusion_feed: {
+                let feed = Arc::clone(&params.reaction_diffusion_feed);
+                TextSlider::new(0.0, ui_layout.reaction_diffusion.feed)
+                    .with_label("Feed")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(6)
+                    .with_output_range(0.01..=0.1)
+                    .with_default_value(feed.lr())
+                    .with_callback(move |_, value| feed.sr(value))
+            },
+            reaction_diffusion_kill: {
+                let kill = Arc::clone(&params.reaction_diffusion_kill);
+                TextSlider::new(0.0, ui_layout.reaction_diffusion.kill)
+                    .with_label("Kill")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(6)
+                    .with_output_range(0.04..=0.075)
+                    .with_default_value(kill.lr())
+                    .with_callback(move |_, value| kill.sr(value))
+            },
+            reaction_diffusion_diffusion_u: {
+                let rate =
+                    Arc::clone(&params.reaction_diffusion_diffusion_u);
+                let rect = ui_layout.reaction_diffusion.diffusion_u;
+                small_slider(rect, "Diff. U")
+                    .with_output_range(0.1..=1.0)
+                    .with_value_chars(4)
+                    .with_default_value(rate.lr())
+                    .with_callback(move |_, value| rate.sr(value))
+            },
+            reaction_diffusion_diffusion_v: {
+                let rate =
+                    Arc::clone(&params.reaction_diffusion_diffusion_v);
+                let rect = ui_layout.reaction_diffusion.diffusion_v;
+                small_slider(rect, "Diff. V")
+                    .with_output_range(0.1..=1.0)
+                    .with_value_chars(4)
+                    .with_default_value(rate.lr())
+                    .with_callback(move |_, value| rate.sr(value))
+            },
+            reaction_diffusion_shown_preset: params
+                .reaction_diffusion_preset
+                .lr(),
+
+            image_brightness: {
+                let brightness = Arc::clone(&params.image_brightness);
+                TextSlider::new(0.0, ui_layout.image.brightness)
+                    .with_label("Brightness")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(5)
+                    .with_output_range(-1.0..=1.0)
+                    .with_positive_value_prefix()
+                    .with_default_value(brightness.lr())
+                    .with_callback(move |_, value| brightness.sr(value))
+            },
+            image_contrast: {
+                let contrast = Arc::clone(&params.image_contrast);
+                TextSlider::new(0.0, ui_layout.image.contrast)
+                    .with_label("Contrast")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(4)
+                    .with_output_range(0.0..=4.0)
+                    .with_default_value(contrast.lr())
+                    .with_callback(move |_, value| contrast.sr(value))
+            },
+            image_gamma: {
+                let gamma = Arc::clone(&params.image_gamma);
+                TextSlider::new(0.0, ui_layout.image.gamma)
+                    .with_label("Gamma")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(4)
+                    .with_output_range(0.2..=4.0)
+                    .with_default_value(gamma.lr())
+                    .with_callback(move |_, value| gamma.sr(value))
+            },
+            image_invert: {
+                let invert = Arc::clone(&params.image_invert);
+                Button::new(ui_layout.image.invert)
+                    .with_label("Invert")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(invert.lr())
+                    .with_callback(move |state| invert.sr(state))
+            },
+
+            spectral_feedback_decay: {
+                let decay = Arc::clone(&params.spectral_feedback_decay);
+                TextSlider::new(0.0, ui_layout.spectral_feedback.decay)
+                    .with_label("Decay")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(4)
+                    .with_suffix(" s")
+                    .with_output_range(0.1..=10.0)
+                    .with_log_scaling()
+                    .with_default_value(decay.lr())
+                    .with_callback(move |_, value| decay.sr(value))
+            },
+            spectral_feedback_threshold: {
+                let threshold = Arc::clone(&params.spectral_feedback_threshold);
+                TextSlider::new(0.0, ui_layout.spectral_feedback.threshold)
+                    .with_label("Threshold")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(3)
+                    .with_suffix(" dB")
+                    .with_output_range(-96.0..=-6.0)
+                    .with_default_value(threshold.lr())
+                    .with_callback(move |_, value| threshold.sr(value))
+            },
+            spectral_feedback_invert: {
+                let invert = Arc::clone(&params.spectral_feedback_invert);
+                Button::new(ui_layout.spectral_feedback.invert)
+                    .with_label("Invert")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(invert.lr())
+                    .with_callback(move |state| invert.sr(state))
+            },
+
+            spectrogram_label: Label::new(ui_layout.spectrogram.label)
+                .with_text("PARAMETRIC EQ")
+                .with_text_layout(big_label_layout()),
+            spectrogram_resolution: {
+                let spectrogram_resolution =
+                    Arc::clone(&params.spectrogram_resolution);
+                Menu::new(ui_layout.spectrogram.resolution)
+                    .with_callback(move |selected| {
+                        spectrogram_resolution.sr(selected);
                     })
                     .with_label("Resolution")
                     .with_label_layout(main_label_layout())
@@ -597,6 +1317,76 @@ impl UIComponents {
                         reso_bank_pan.set_target_value(value)
                     })
             },
+            reso_bank_decay: {
+                let reso_bank_decay = Arc::clone(&params.reso_bank_decay);
+                TextSlider::new(0.0, ui_layout.reso_bank.decay)
+                    .with_label("Decay")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_output_range(
+                        ResonatorBank::DECAY_MIN_SECS
+                            ..=ResonatorBank::DECAY_MAX_SECS,
+                    )
+                    .with_log_scaling()
+                    .with_default_value(reso_bank_decay.current_value())
+                    .with_callback(move |_, value| {
+                        reso_bank_decay.set_target_value(value)
+                    })
+                    .with_formatting_callback(|_, val| {
+                        if val < 10.0 {
+                            format!("{val:.2} s")
+                        }
+                        else {
+                            format!("{val:.1} s")
+                        }
+                    })
+            },
+            reso_bank_damping: {
+                let reso_bank_damping = Arc::clone(&params.reso_bank_damping);
+                TextSlider::new(0.0, ui_layout.reso_bank.damping)
+                    .with_label("Damping")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_default_value(reso_bank_damping.current_value())
+                    .with_callback(move |_, value| {
+                        reso_bank_damping.set_target_value(value)
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.0}%", val * 100.0)
+                    })
+            },
+            reso_bank_decay_random: {
+                let decay_random = Arc::clone(&params.reso_bank_decay_random);
+                TextSlider::new(0.0, ui_layout.reso_bank.decay_random)
+                    .with_label("Random")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_default_value(decay_random.current_value())
+                    .with_callback(move |_, value| {
+                        decay_random.set_target_value(value)
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.0}%", val * 100.0)
+                    })
+            },
             reso_bank_quantize: {
                 let reso_bank_quantize = Arc::clone(&params.reso_bank_quantize);
                 Button::new(ui_layout.reso_bank.quantize)
@@ -619,6 +1409,51 @@ impl UIComponents {
                     .with_enabled_text("Quantise On")
                     .with_disabled_text("Quantise Off")
             },
+            reso_bank_pitch_layout: {
+                let pitch_layout = Arc::clone(&params.reso_bank_pitch_layout);
+                TextSlider::new(0.0, ui_layout.reso_bank.pitch_layout)
+                    .with_label("Layout")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_output_range(
+                        0.0..=(PitchLayout::ALL.len() - 1) as f64,
+                    )
+                    .with_sensitivity(0.005)
+                    .with_integer_rounding()
+                    .with_default_value(pitch_layout.lr().idx() as f64)
+                    .with_callback(move |_, value| {
+                        pitch_layout.sr(pitch_layout_from_value(value));
+                    })
+                    .with_formatting_callback(|_, val| {
+                        pitch_layout_from_value(val).to_string()
+                    })
+            },
+            reso_bank_layout_blend: {
+                let layout_blend = Arc::clone(&params.reso_bank_layout_blend);
+                TextSlider::new(0.0, ui_layout.reso_bank.layout_blend)
+                    .with_label("Blend")
+                    .with_label_layout(small_label_layout())
+                    .with_value_layout(Layout {
+                        font: Some(
+                            Font::from_bytes(BOLD_FONT_MONO_BYTES)
+                                .expect("failed to load font bytes"),
+                        ),
+                        ..small_value_layout()
+                    })
+                    .with_default_value(layout_blend.current_value())
+                    .with_callback(move |_, value| {
+                        layout_blend.set_target_value(value)
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.0}%", val * 100.0)
+                    })
+            },
             reso_bank_randomize: Button::new(ui_layout.reso_bank.randomise)
                 .with_label("Regenerate")
                 .with_label_layout(main_value_layout())
@@ -660,6 +1495,38 @@ impl UIComponents {
                         friction.sr(value);
                     })
             },
+            reso_bank_field_mouse: {
+                let mouse = Arc::clone(&params.reso_bank_field_mouse);
+                small_slider(ui_layout.reso_bank.field_mouse, "Mouse")
+                    .with_output_range(-1.0..=1.0)
+                    .with_value_chars(5)
+                    .with_default_value(mouse.lr())
+                    .with_callback(move |_, value| mouse.sr(value))
+            },
+            reso_bank_field_flocking: {
+                let flocking = Arc::clone(&params.reso_bank_field_flocking);
+                small_slider(ui_layout.reso_bank.field_flocking, "Flock")
+                    .with_output_range(0.0..=1.0)
+                    .with_value_chars(4)
+                    .with_default_value(flocking.lr())
+                    .with_callback(move |_, value| flocking.sr(value))
+            },
+            reso_bank_field_collision: {
+                let collision = Arc::clone(&params.reso_bank_field_collision);
+                small_slider(ui_layout.reso_bank.field_collision, "Collide")
+                    .with_output_range(0.0..=1.0)
+                    .with_value_chars(4)
+                    .with_default_value(collision.lr())
+                    .with_callback(move |_, value| collision.sr(value))
+            },
+            reso_bank_field_gravity: {
+                let gravity = Arc::clone(&params.reso_bank_field_gravity);
+                small_slider(ui_layout.reso_bank.field_gravity, "Gravity")
+                    .with_output_range(-1.0..=1.0)
+                    .with_value_chars(5)
+                    .with_default_value(gravity.lr())
+                    .with_callback(move |_, value| gravity.sr(value))
+            },
 
             reso_bank_mix: {
                 let mix = Arc::clone(&params.reso_bank_mix);
@@ -698,272 +1565,196 @@ impl UIComponents {
                 .with_text("EFFECTS")
                 .with_text_layout(big_label_layout()),
 
-            low_filter_label: Label::new(ui_layout.low_filter.label)
-                .with_text("LOW FILTER")
+            eq_band: EQBandComponents::new(&params.eq_params, &ui_layout.eq),
+
+            delay_label: Label::new(ui_layout.delay.label)
+                .with_text("DELAY")
                 .with_text_layout(big_label_layout()),
-            low_filter_type: {
-                let low_filter_type = Arc::clone(&params.low_filter_is_shelf);
-                Button::new(ui_layout.low_filter.f_type)
-                    .with_label("Type")
-                    .with_label_layout(Layout {
-                        justify: Justify::Right,
-                        ..main_label_layout()
-                    })
-                    .with_enabled_text("Shelf")
-                    .with_disabled_text("Cut")
-                    .with_enabled_layout(main_value_layout())
-                    .with_disabled_layout(main_value_layout())
-                    .with_state(low_filter_type.lr())
-                    .with_callback(move |state| low_filter_type.sr(state))
-            },
-            low_filter_cutoff: {
-                let low_filter_cutoff = Arc::clone(&params.low_filter_cutoff);
-                TextSlider::new(0.0, ui_layout.low_filter.cutoff_hz)
-                    .with_sensitivity(0.0015)
-                    .with_label("Cutoff")
-                    .with_label_layout(Layout {
-                        justify: Justify::Right,
-                        ..main_label_layout()
-                    })
+            delay_time_ms: {
+                let pp_delay_time_ms = Arc::clone(&params.delay_time_ms);
+                TextSlider::new(0.0, ui_layout.delay.time_ms)
+                    .with_label("Time")
+                    .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_output_range(3.4868..=135.075_366) // 10Hz to 20kHz
-                    .with_default_value(freq_to_note(
-                        low_filter_cutoff.current_value(),
-                    ))
+                    .with_suffix(" ms")
+                    .with_output_range(10.0..=999.9)
+                    .with_value_chars(5)
+                    .with_default_value(pp_delay_time_ms.lr())
                     .with_callback(move |_, value| {
-                        low_filter_cutoff.set_target_value(note_to_freq(value));
-                    })
-                    .with_formatting_callback(|raw_value, output_value| {
-                        let freq = note_to_freq(output_value);
-                        format_hz_value(freq)
+                        pp_delay_time_ms.sr(value);
                     })
             },
-            low_filter_q: {
-                let low_filter_q = Arc::clone(&params.low_filter_q);
-                TextSlider::new(0.0, ui_layout.low_filter.q)
-                    .with_sensitivity(0.002)
-                    .with_label("Q")
-                    .with_label_layout(Layout {
-                        justify: Justify::Right,
-                        ..main_label_layout()
-                    })
+            delay_feedback: {
+                let pp_delay_feedback = Arc::clone(&params.delay_feedback);
+                TextSlider::new(0.0, ui_layout.delay.feedback)
+                    .with_label("Feedback")
+                    .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_value_chars(4)
-                    .with_output_range(0.3..=10.0)
-                    .with_default_value(low_filter_q.current_value())
-                    .with_callback(move |_, value| {
-                        low_filter_q.set_target_value(value.recip());
+                    .with_suffix(" %")
+                    .with_value_chars(5)
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(
+                        pp_delay_feedback.current_value() * 100.0,
+                    )
+                    .with_callback(move |value, _| {
+                        pp_delay_feedback.set_target_value(value);
                     })
             },
-            low_filter_gain: {
-                let low_filter_gain = Arc::clone(&params.low_filter_gain_db);
-                TextSlider::new(0.0, ui_layout.low_filter.gain)
-                    .with_sensitivity(0.002)
-                    .with_label("Gain")
-                    .with_positive_value_prefix()
-                    .with_label_layout(Layout {
-                        justify: Justify::Right,
-                        ..main_label_layout()
-                    })
+            delay_mix: {
+                let pp_delay_mix = Arc::clone(&params.delay_mix);
+                TextSlider::new(0.0, ui_layout.delay.mix)
+                    .with_label("Mix")
+                    .with_suffix(" %")
+                    .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_output_range(-24.0..=24.0)
+                    .with_output_range(0.0..=100.0)
                     .with_value_chars(5)
-                    .with_suffix(" dB")
-                    .with_default_value(low_filter_gain.current_value())
-                    .with_callback(move |_, value| {
-                        low_filter_gain.set_target_value(value);
+                    .with_default_value(pp_delay_mix.current_value() * 100.0)
+                    .with_callback(move |value, _| {
+                        pp_delay_mix.set_target_value(value);
                     })
             },
-
-            peak_filter_label: Label::new(ui_layout.peak_filter.label)
-                .with_text("PEAK FILTER")
-                .with_text_layout(big_label_layout()),
-            peak_filter_cutoff: {
-                let peak_filter_cutoff = Arc::clone(&params.peak_filter_cutoff);
-
-                TextSlider::new(0.0, ui_layout.peak_filter.cutoff_hz)
-                    .with_sensitivity(0.0015)
-                    .with_label("Cutoff")
+            delay_is_ping_pong: {
+                let use_ping_pong = Arc::clone(&params.use_ping_pong);
+                Button::new(ui_layout.delay.use_ping_pong)
+                    .with_label("Ping-pong")
                     .with_label_layout(main_label_layout())
-                    .with_value_layout(main_value_layout())
-                    .with_output_range(3.4868..=135.075_366) // 200Hz to 20kHz
-                    .with_default_value(freq_to_note(
-                        peak_filter_cutoff.current_value(),
-                    ))
-                    .with_callback(move |_, value| {
-                        peak_filter_cutoff
-                            .set_target_value(note_to_freq(value));
-                    })
-                    .with_formatting_callback(|_, output_value| {
-                        let freq = note_to_freq(output_value);
-                        format_hz_value(freq)
-                    })
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(true)
+                    .with_callback(move |state| use_ping_pong.sr(state))
             },
-            peak_filter_q: {
-                let peak_filter_q = Arc::clone(&params.peak_filter_q);
-                TextSlider::new(0.0, ui_layout.peak_filter.q)
-                    .with_sensitivity(0.002)
-                    .with_label("Q")
+
+            delay_sync: {
+                let delay_sync = Arc::clone(&params.delay_sync);
+                Button::new(ui_layout.delay.sync)
+                    .with_label("Sync")
+                    .with_label_layout(main_label_layout())
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(delay_sync.lr())
+                    .with_callback(move |state| delay_sync.sr(state))
+            },
+            delay_division: {
+                let delay_division = Arc::clone(&params.delay_division);
+                TextSlider::new(0.0, ui_layout.delay.time_ms)
+                    .with_label("Time")
                     .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_value_chars(4)
-                    .with_output_range(0.3..=10.0)
-                    .with_default_value(peak_filter_q.current_value())
+                    .with_output_range(division_range())
+                    .with_sensitivity(0.005)
+                    .with_integer_rounding()
+                    .with_default_value(delay_division.lr().idx() as f64)
                     .with_callback(move |_, value| {
-                        peak_filter_q.set_target_value(value.recip());
+                        delay_division.sr(division_from_value(value));
+                    })
+                    .with_formatting_callback(|_, val| {
+                        division_from_value(val).to_string()
                     })
             },
-            peak_filter_gain: {
-                let peak_filter_gain = Arc::clone(&params.peak_filter_gain_db);
-                TextSlider::new(0.0, ui_layout.peak_filter.gain)
-                    .with_sensitivity(0.002)
-                    .with_label("Gain")
-                    .with_positive_value_prefix()
+            delay_page_label: Label::new(ui_layout.delay.page_label)
+                .with_text("DELAY")
+                .with_text_layout(big_label_layout()),
+            delay_low_cut: {
+                let delay_low_cut = Arc::clone(&params.delay_low_cut);
+                TextSlider::new(0.0, ui_layout.delay.low_cut)
+                    .with_label("Low Cut")
                     .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_output_range(-24.0..=24.0)
-                    .with_value_chars(5)
-                    .with_suffix(" dB")
-                    .with_default_value(peak_filter_gain.current_value())
+                    .with_output_range(20.0f64.log2()..=20000.0f64.log2())
+                    .with_default_value(delay_low_cut.current_value().log2())
                     .with_callback(move |_, value| {
-                        peak_filter_gain.set_target_value(value);
+                        delay_low_cut.set_target_value(value.exp2());
                     })
-            },
-
-            high_filter_label: Label::new(ui_layout.high_filter.label)
-                .with_text("HIGH FILTER")
-                .with_text_layout(big_label_layout()),
-            high_filter_type: {
-                let high_filter_type = Arc::clone(&params.high_filter_is_shelf);
-                Button::new(ui_layout.high_filter.f_type)
-                    .with_label("Type")
-                    .with_label_layout(Layout {
-                        justify: Justify::Left,
-                        ..main_label_layout()
+                    .with_formatting_callback(|_, val| {
+                        format_hz_value(val.exp2())
                     })
-                    .with_enabled_text("Shelf")
-                    .with_disabled_text("Cut")
-                    .with_enabled_layout(main_value_layout())
-                    .with_disabled_layout(main_value_layout())
-                    .with_state(high_filter_type.lr())
-                    .with_callback(move |state| high_filter_type.sr(state))
             },
-            high_filter_cutoff: {
-                let high_filter_cutoff = Arc::clone(&params.high_filter_cutoff);
-                TextSlider::new(0.0, ui_layout.high_filter.cutoff_hz)
-                    .with_sensitivity(0.0015)
-                    .with_label("Cutoff")
-                    .with_label_layout(Layout {
-                        justify: Justify::Left,
-                        ..main_label_layout()
-                    })
+            delay_high_cut: {
+                let delay_high_cut = Arc::clone(&params.delay_high_cut);
+                TextSlider::new(0.0, ui_layout.delay.high_cut)
+                    .with_label("High Cut")
+                    .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_output_range(3.4868..=135.075_366) // 200Hz to 20kHz
-                    .with_default_value(freq_to_note(
-                        high_filter_cutoff.current_value(),
-                    ))
+                    .with_output_range(20.0f64.log2()..=20000.0f64.log2())
+                    .with_default_value(delay_high_cut.current_value().log2())
                     .with_callback(move |_, value| {
-                        high_filter_cutoff
-                            .set_target_value(note_to_freq(value));
+                        delay_high_cut.set_target_value(value.exp2());
                     })
-                    .with_formatting_callback(|raw_value, output_value| {
-                        let freq = note_to_freq(output_value);
-                        format_hz_value(freq)
+                    .with_formatting_callback(|_, val| {
+                        format_hz_value(val.exp2())
                     })
             },
-            high_filter_q: {
-                let high_filter_q = Arc::clone(&params.high_filter_q);
-                TextSlider::new(0.0, ui_layout.high_filter.q)
-                    .with_sensitivity(0.002)
-                    .with_label("Q")
-                    .with_label_layout(Layout {
-                        justify: Justify::Left,
-                        ..main_label_layout()
-                    })
+            delay_saturation: {
+                let delay_saturation = Arc::clone(&params.delay_saturation);
+                TextSlider::new(0.0, ui_layout.delay.saturation)
+                    .with_label("Saturation")
+                    .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_value_chars(4)
-                    .with_output_range(0.3..=10.0)
-                    .with_default_value(high_filter_q.current_value())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(
+                        delay_saturation.current_value() * 100.0,
+                    )
                     .with_callback(move |_, value| {
-                        high_filter_q.set_target_value(value.recip());
+                        delay_saturation.set_target_value(value / 100.0);
                     })
             },
-            high_filter_gain: {
-                let high_filter_gain = Arc::clone(&params.high_filter_gain_db);
-                TextSlider::new(0.0, ui_layout.high_filter.gain)
-                    .with_sensitivity(0.002)
-                    .with_label("Gain")
-                    .with_positive_value_prefix()
-                    .with_label_layout(Layout {
-                        justify: Justify::Left,
-                        ..main_label_layout()
-                    })
+            delay_wow_flutter: {
+                let delay_wow_flutter = Arc::clone(&params.delay_wow_flutter);
+                TextSlider::new(0.0, ui_layout.delay.wow_flutter)
+                    .with_label("Wow/Flutter")
+                    .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_output_range(-24.0..=24.0)
-                    .with_value_chars(5)
-                    .with_suffix(" dB")
-                    .with_default_value(high_filter_gain.current_value())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(
+                        delay_wow_flutter.current_value() * 100.0,
+                    )
                     .with_callback(move |_, value| {
-                        high_filter_gain.set_target_value(value);
+                        delay_wow_flutter.set_target_value(value / 100.0);
                     })
             },
-
-            delay_label: Label::new(ui_layout.delay.label)
-                .with_text("DELAY")
-                .with_text_layout(big_label_layout()),
-            delay_time_ms: {
-                let pp_delay_time_ms = Arc::clone(&params.delay_time_ms);
-                TextSlider::new(0.0, ui_layout.delay.time_ms)
-                    .with_label("Time")
+            delay_time_r_ms: {
+                let delay_time_r_ms = Arc::clone(&params.delay_time_r_ms);
+                TextSlider::new(0.0, ui_layout.delay.time_r)
+                    .with_label("Time R")
                     .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
                     .with_suffix(" ms")
                     .with_output_range(10.0..=999.9)
                     .with_value_chars(5)
-                    .with_default_value(pp_delay_time_ms.lr())
+                    .with_default_value(delay_time_r_ms.lr())
                     .with_callback(move |_, value| {
-                        pp_delay_time_ms.sr(value);
+                        delay_time_r_ms.sr(value);
                     })
             },
-            delay_feedback: {
-                let pp_delay_feedback = Arc::clone(&params.delay_feedback);
-                TextSlider::new(0.0, ui_layout.delay.feedback)
-                    .with_label("Feedback")
+            delay_division_r: {
+                let delay_division_r = Arc::clone(&params.delay_division_r);
+                TextSlider::new(0.0, ui_layout.delay.time_r)
+                    .with_label("Time R")
                     .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_suffix(" %")
-                    .with_value_chars(5)
-                    .with_output_range(0.0..=100.0)
-                    .with_default_value(
-                        pp_delay_feedback.current_value() * 100.0,
-                    )
-                    .with_callback(move |value, _| {
-                        pp_delay_feedback.set_target_value(value);
+                    .with_output_range(division_range())
+                    .with_sensitivity(0.005)
+                    .with_integer_rounding()
+                    .with_default_value(delay_division_r.lr().idx() as f64)
+                    .with_callback(move |_, value| {
+                        delay_division_r.sr(division_from_value(value));
                     })
-            },
-            delay_mix: {
-                let pp_delay_mix = Arc::clone(&params.delay_mix);
-                TextSlider::new(0.0, ui_layout.delay.mix)
-                    .with_label("Mix")
-                    .with_suffix(" %")
-                    .with_label_layout(main_label_layout())
-                    .with_value_layout(main_value_layout())
-                    .with_output_range(0.0..=100.0)
-                    .with_value_chars(5)
-                    .with_default_value(pp_delay_mix.current_value() * 100.0)
-                    .with_callback(move |value, _| {
-                        pp_delay_mix.set_target_value(value);
+                    .with_formatting_callback(|_, val| {
+                        division_from_value(val).to_string()
                     })
             },
-            delay_is_ping_pong: {
-                let use_ping_pong = Arc::clone(&params.use_ping_pong);
-                Button::new(ui_layout.delay.use_ping_pong)
-                    .with_label("Ping-pong")
+            delay_link: {
+                let delay_link = Arc::clone(&params.delay_link);
+                Button::new(ui_layout.delay.link)
+                    .with_label("Link")
                     .with_label_layout(main_label_layout())
                     .with_enabled_layout(main_value_layout())
                     .with_disabled_layout(main_value_layout())
-                    .with_state(true)
-                    .with_callback(move |state| use_ping_pong.sr(state))
+                    .with_state(delay_link.lr())
+                    .with_callback(move |state| delay_link.sr(state))
             },
 
             dist_label: Label::new(ui_layout.distortion.label)
@@ -993,6 +1784,23 @@ impl UIComponents {
                     })
                     .with_label("Type")
             },
+            dist_oversampling: {
+                let dist_oversampling = Arc::clone(&params.dist_oversampling);
+                TextSlider::new(0.0, ui_layout.distortion.oversampling)
+                    .with_label("Oversample")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(0.0..=MAX_OVERSAMPLING_FACTOR as f64)
+                    .with_sensitivity(0.005)
+                    .with_integer_rounding()
+                    .with_default_value(dist_oversampling.lr() as f64)
+                    .with_callback(move |_, value| {
+                        dist_oversampling.sr(value as usize);
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{}x", 1 << val as usize)
+                    })
+            },
 
             comp_label: Label::new(ui_layout.compression.label)
                 .with_text("COMPRESSION")
@@ -1083,6 +1891,196 @@ impl UIComponents {
                         comp_release.set_target_value(value);
                     })
             },
+            comp_page_label: Label::new(ui_layout.compression.page_label)
+                .with_text("COMPRESSION")
+                .with_text_layout(big_label_layout()),
+            comp_knee: {
+                let comp_knee = Arc::clone(&params.comp_knee_db);
+                TextSlider::new(0.0, ui_layout.compression.knee)
+                    .with_label("Knee")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" dB")
+                    .with_output_range(0.0..=24.0)
+                    .with_default_value(comp_knee.current_value())
+                    .with_callback(move |_, value| {
+                        comp_knee.set_target_value(value);
+                    })
+            },
+            comp_stereo_link: {
+                let comp_stereo_link = Arc::clone(&params.comp_stereo_link);
+                TextSlider::new(0.0, ui_layout.compression.stereo_link)
+                    .with_label("Link")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(
+                        comp_stereo_link.current_value() * 100.0,
+                    )
+                    .with_callback(move |_, value| {
+                        comp_stereo_link.set_target_value(value / 100.0);
+                    })
+            },
+            comp_makeup: {
+                let comp_makeup = Arc::clone(&params.comp_makeup_db);
+                TextSlider::new(0.0, ui_layout.compression.makeup)
+                    .with_label("Makeup")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" dB")
+                    .with_output_range(0.0..=24.0)
+                    .with_default_value(comp_makeup.current_value())
+                    .with_callback(move |_, value| {
+                        comp_makeup.set_target_value(value);
+                    })
+            },
+            comp_lookahead: {
+                let comp_lookahead = Arc::clone(&params.comp_lookahead_ms);
+                TextSlider::new(0.0, ui_layout.compression.lookahead)
+                    .with_label("Lookahead")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" ms")
+                    .with_output_range(0.0..=Compressor::MAX_LOOKAHEAD_MS)
+                    .with_default_value(comp_lookahead.lr())
+                    .with_callback(move |_, value| {
+                        comp_lookahead.sr(value);
+                    })
+            },
+            comp_sidechain_hpf: {
+                let comp_hpf = Arc::clone(&params.comp_sidechain_hpf);
+                TextSlider::new(0.0, ui_layout.compression.sidechain_hpf)
+                    .with_label("SC HPF")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(
+                        Compressor::MIN_SIDECHAIN_HPF.log2()
+                            ..=1000.0f64.log2(),
+                    )
+                    .with_default_value(comp_hpf.current_value().log2())
+                    .with_callback(move |_, value| {
+                        comp_hpf.set_target_value(value.exp2());
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format_hz_value(val.exp2())
+                    })
+            },
+            comp_gain_reduction: Label::new(
+                ui_layout.compression.gain_reduction,
+            )
+            .with_text("GR 0.0 dB")
+            .with_text_layout(main_value_layout())
+            .with_text_color(VALUE),
+            comp_gain_reduction_tenths: None,
+            comp_auto_makeup: {
+                let comp_auto_makeup = Arc::clone(&params.comp_auto_makeup);
+                Button::new(ui_layout.compression.auto_makeup)
+                    .with_label("Auto")
+                    .with_label_layout(main_label_layout())
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(comp_auto_makeup.lr())
+                    .with_callback(move |state| comp_auto_makeup.sr(state))
+            },
+            comp_use_rms: {
+                let comp_use_rms = Arc::clone(&params.comp_use_rms);
+                Button::new(ui_layout.compression.use_rms)
+                    .with_label("RMS")
+                    .with_label_layout(main_label_layout())
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(comp_use_rms.lr())
+                    .with_callback(move |state| comp_use_rms.sr(state))
+            },
+            comp_exciter_key: {
+                let comp_exciter_key = Arc::clone(&params.comp_exciter_key);
+                Button::new(ui_layout.compression.exciter_key)
+                    .with_label("Key")
+                    .with_label_layout(main_label_layout())
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(comp_exciter_key.lr())
+                    .with_callback(move |state| comp_exciter_key.sr(state))
+            },
+            dist_bands: MultibandComponents::new(
+                "DIST BANDS",
+                &params.dist_bands,
+                &ui_layout.multiband,
+            ),
+            comp_bands: MultibandComponents::new(
+                "COMP BANDS",
+                &params.comp_bands,
+                &ui_layout.multiband,
+            ),
+            limiter_label: Label::new(ui_layout.compression.limiter_label)
+                .with_text("LIMITER")
+                .with_text_layout(big_label_layout()),
+            limiter_ceiling: {
+                let limiter_ceiling = Arc::clone(&params.limiter_ceiling_db);
+                TextSlider::new(0.0, ui_layout.compression.limiter_ceiling)
+                    .with_label("Ceiling")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" dB")
+                    .with_output_range(-24.0..=0.0)
+                    .with_default_value(limiter_ceiling.current_value())
+                    .with_callback(move |_, value| {
+                        limiter_ceiling.set_target_value(value);
+                    })
+            },
+            limiter_release: {
+                let limiter_release = Arc::clone(&params.limiter_release_ms);
+                TextSlider::new(0.0, ui_layout.compression.limiter_release)
+                    .with_label("Release")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" ms")
+                    .with_output_range(
+                        Limiter::MIN_RELEASE_MS..=Limiter::MAX_RELEASE_MS,
+                    )
+                    .with_value_chars(6)
+                    .with_default_value(limiter_release.current_value())
+                    .with_callback(move |_, value| {
+                        limiter_release.set_target_value(value);
+                    })
+            },
+            safety_status: Label::new(ui_layout.other.safety_status)
+                .with_text("")
+                .with_text_layout(main_value_layout())
+                .with_text_color(SELECTED),
+            safety_muted: false,
+            latency: Label::new(ui_layout.other.latency)
+                .with_text("")
+                .with_text_layout(main_value_layout()),
+            latency_tenths: None,
+
+            transport_bpm: {
+                let bpm = Arc::clone(&params.bpm);
+                TextSlider::new(0.0, ui_layout.transport.bpm)
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(20.0..=300.0)
+                    .with_value_chars(5)
+                    .with_suffix(" BPM")
+                    .with_sensitivity(0.001)
+                    .with_default_value(DEFAULT_BPM)
+                    .with_callback(move |_, value| bpm.sr(value))
+            },
+            transport_play: {
+                let transport_playing = Arc::clone(&params.transport_playing);
+                Button::new(ui_layout.transport.play)
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_enabled_text("Stop")
+                    .with_disabled_text("Play")
+                    .with_state(transport_playing.lr())
+                    .with_callback(move |state| transport_playing.sr(state))
+            },
+            transport_position: Label::new(ui_layout.transport.position)
+                .with_text("1.1.1")
+                .with_text_layout(main_value_layout())
+                .with_text_color(VALUE),
+            transport_sixteenth: None,
 
             master_gain: {
                 let master_gain = Arc::clone(&params.master_gain);
@@ -1129,55 +2127,327 @@ impl UIComponents {
                         master_gain.set_target_value(db_to_level(val));
                     })
             },
-            pre_fx_gain: {
-                let pre_fx_gain = Arc::clone(&params.pre_fx_gain);
-                TextSlider::new(0.0, ui_layout.other.pre_fx_gain)
-                    .with_label("Pre-FX Gain")
+            crush_label: Label::new(ui_layout.crush.label)
+                .with_text("CRUSH")
+                .with_text_layout(big_label_layout()),
+            crush_rate: {
+                let crush_rate = Arc::clone(&params.crush_rate);
+                TextSlider::new(0.0, ui_layout.crush.rate)
+                    .with_label("Rate")
                     .with_label_layout(main_label_layout())
                     .with_value_layout(main_value_layout())
-                    .with_output_range(-36.0..=36.0)
-                    .with_default_value(0.0)
-                    .with_sensitivity(0.002)
-                    .with_callback(move |_, val| {
-                        pre_fx_gain.set_target_value(db_to_level(val));
+                    .with_output_range(
+                        Decimator::MIN_RATE.log2()..=Decimator::MAX_RATE.log2(),
+                    )
+                    .with_default_value(crush_rate.current_value().log2())
+                    .with_callback(move |_, value| {
+                        crush_rate.set_target_value(value.exp2());
                     })
                     .with_formatting_callback(|_, val| {
-                        if (0.0..=0.01).contains(&val) {
-                            return String::from("0.00 dB");
-                        }
-
-                        let val_str = if val.is_sign_negative() {
-                            format!("{val:.10}")
-                        }
-                        else {
-                            format!("+{val:.10}")
-                        };
-
-                        let mut decimal_idx = val_str.find('.').unwrap();
-
-                        // 5
-                        let truncate_to = if decimal_idx == 4 {
-                            6
-                        }
-                        else if decimal_idx > 5 {
-                            decimal_idx
-                        }
-                        else {
-                            5
-                        };
-
-                        let mut out = val_str[..truncate_to].to_string();
-                        out.push_str(" dB");
-                        out
+                        format_hz_value(val.exp2())
                     })
             },
-        }
-    }
-
-    pub fn attach_reso_bank_randomize_callback<F: Fn(bool) + 'static>(
-        mut self,
-        cb: F,
-    ) -> Self {
+            crush_bits: {
+                let crush_bits = Arc::clone(&params.crush_bits);
+                TextSlider::new(0.0, ui_layout.crush.bits)
+                    .with_label("Bits")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(
+                        Decimator::MIN_BIT_DEPTH..=Decimator::MAX_BIT_DEPTH,
+                    )
+                    .with_default_value(crush_bits.current_value())
+                    .with_callback(move |_, value| {
+                        crush_bits.set_target_value(value);
+                    })
+                    .with_formatting_callback(|_, val| format!("{val:.1}"))
+            },
+            crush_jitter: {
+                let crush_jitter = Arc::clone(&params.crush_jitter);
+                TextSlider::new(0.0, ui_layout.crush.jitter)
+                    .with_label("Jitter")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(crush_jitter.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        crush_jitter.set_target_value(value / 100.0);
+                    })
+            },
+            crush_dither: {
+                let crush_dither = Arc::clone(&params.crush_dither);
+                Menu::new(ui_layout.crush.dither)
+                    .with_label_layout(main_label_layout())
+                    .with_item_text_layout(main_value_layout())
+                    .with_selected_item_text_layout(main_value_layout())
+                    .with_callback(move |selected| {
+                        crush_dither.sr(selected);
+                    })
+                    .with_label("Dither")
+                    .initial_variant(params.crush_dither.lr())
+            },
+            crush_anti_alias: {
+                let crush_anti_alias = Arc::clone(&params.crush_anti_alias);
+                Button::new(ui_layout.crush.anti_alias)
+                    .with_label("Anti-alias")
+                    .with_label_layout(main_label_layout())
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(crush_anti_alias.lr())
+                    .with_callback(move |state| crush_anti_alias.sr(state))
+            },
+
+            mod_label: Label::new(ui_layout.modulation.label)
+                .with_text("MODULATION")
+                .with_text_layout(big_label_layout()),
+            mod_type: {
+                let mod_type = Arc::clone(&params.mod_type);
+                Menu::new(ui_layout.modulation.mod_type)
+                    .with_label_layout(main_label_layout())
+                    .with_item_text_layout(main_value_layout())
+                    .with_selected_item_text_layout(main_value_layout())
+                    .with_callback(move |selected| {
+                        mod_type.sr(selected);
+                    })
+                    .with_label("Type")
+                    .initial_variant(params.mod_type.lr())
+            },
+            mod_rate: {
+                let mod_rate = Arc::clone(&params.mod_rate);
+                TextSlider::new(0.0, ui_layout.modulation.rate)
+                    .with_label("Rate")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(
+                        MIN_MOD_RATE.log2()..=MAX_MOD_RATE.log2(),
+                    )
+                    .with_default_value(mod_rate.current_value().log2())
+                    .with_callback(move |_, value| {
+                        mod_rate.set_target_value(value.exp2());
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.2} Hz", val.exp2())
+                    })
+            },
+            mod_sync: {
+                let mod_sync = Arc::clone(&params.mod_sync);
+                Button::new(ui_layout.modulation.sync)
+                    .with_label("Sync")
+                    .with_label_layout(main_label_layout())
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(mod_sync.lr())
+                    .with_callback(move |state| mod_sync.sr(state))
+            },
+            mod_division: {
+                let mod_division = Arc::clone(&params.mod_division);
+                TextSlider::new(0.0, ui_layout.modulation.rate)
+                    .with_label("Rate")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(division_range())
+                    .with_sensitivity(0.005)
+                    .with_integer_rounding()
+                    .with_default_value(mod_division.lr().idx() as f64)
+                    .with_callback(move |_, value| {
+                        mod_division.sr(division_from_value(value));
+                    })
+                    .with_formatting_callback(|_, val| {
+                        division_from_value(val).to_string()
+                    })
+            },
+            mod_depth: {
+                let mod_depth = Arc::clone(&params.mod_depth);
+                TextSlider::new(0.0, ui_layout.modulation.depth)
+                    .with_label("Depth")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(mod_depth.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        mod_depth.set_target_value(value / 100.0);
+                    })
+            },
+            mod_feedback: {
+                let mod_feedback = Arc::clone(&params.mod_feedback);
+                TextSlider::new(0.0, ui_layout.modulation.feedback)
+                    .with_label("Feedback")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(-95.0..=95.0)
+                    .with_default_value(mod_feedback.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        mod_feedback.set_target_value(value / 100.0);
+                    })
+            },
+            mod_mix: {
+                let mod_mix = Arc::clone(&params.mod_mix);
+                TextSlider::new(0.0, ui_layout.modulation.mix)
+                    .with_label("Mix")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(mod_mix.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        mod_mix.set_target_value(value / 100.0);
+                    })
+            },
+
+            reverb_label: Label::new(ui_layout.reverb.label)
+                .with_text("REVERB")
+                .with_text_layout(big_label_layout()),
+            reverb_size: {
+                let reverb_size = Arc::clone(&params.reverb_size);
+                TextSlider::new(0.0, ui_layout.reverb.size)
+                    .with_label("Size")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(reverb_size.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        reverb_size.set_target_value(value / 100.0);
+                    })
+            },
+            reverb_decay: {
+                let reverb_decay = Arc::clone(&params.reverb_decay);
+                TextSlider::new(0.0, ui_layout.reverb.decay)
+                    .with_label("Decay")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(
+                        Reverb::MIN_DECAY_SECS.log2()
+                            ..=Reverb::MAX_DECAY_SECS.log2(),
+                    )
+                    .with_default_value(reverb_decay.current_value().log2())
+                    .with_callback(move |_, value| {
+                        reverb_decay.set_target_value(value.exp2());
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format!("{:.2} s", val.exp2())
+                    })
+            },
+            reverb_damping: {
+                let reverb_damping = Arc::clone(&params.reverb_damping);
+                TextSlider::new(0.0, ui_layout.reverb.damping)
+                    .with_label("Damping")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(reverb_damping.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        reverb_damping.set_target_value(value / 100.0);
+                    })
+            },
+            reverb_pre_delay: {
+                let reverb_pre_delay = Arc::clone(&params.reverb_pre_delay);
+                TextSlider::new(0.0, ui_layout.reverb.pre_delay)
+                    .with_label("Pre-delay")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" ms")
+                    .with_output_range(0.0..=Reverb::MAX_PRE_DELAY_MS)
+                    .with_value_chars(5)
+                    .with_default_value(reverb_pre_delay.current_value())
+                    .with_callback(move |_, value| {
+                        reverb_pre_delay.set_target_value(value);
+                    })
+            },
+            reverb_modulation: {
+                let reverb_modulation = Arc::clone(&params.reverb_modulation);
+                TextSlider::new(0.0, ui_layout.reverb.modulation)
+                    .with_label("Modulation")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(
+                        reverb_modulation.current_value() * 100.0,
+                    )
+                    .with_callback(move |_, value| {
+                        reverb_modulation.set_target_value(value / 100.0);
+                    })
+            },
+            reverb_mix: {
+                let reverb_mix = Arc::clone(&params.reverb_mix);
+                TextSlider::new(0.0, ui_layout.reverb.mix)
+                    .with_label("Mix")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" %")
+                    .with_output_range(0.0..=100.0)
+                    .with_default_value(reverb_mix.current_value() * 100.0)
+                    .with_callback(move |_, value| {
+                        reverb_mix.set_target_value(value / 100.0);
+                    })
+            },
+
+            fx_page: {
+                let fx_page = Arc::clone(&params.fx_page);
+                Menu::new(ui_layout.other.fx_page)
+                    .with_label_layout(main_label_layout())
+                    .with_item_text_layout(main_value_layout())
+                    .with_selected_item_text_layout(main_value_layout())
+                    .with_callback(move |selected| {
+                        fx_page.sr(selected);
+                    })
+                    .with_label("Page")
+                    .initial_variant(params.fx_page.lr())
+            },
+            pre_fx_gain: {
+                let pre_fx_gain = Arc::clone(&params.pre_fx_gain);
+                TextSlider::new(0.0, ui_layout.other.pre_fx_gain)
+                    .with_label("Pre-FX Gain")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(-36.0..=36.0)
+                    .with_default_value(0.0)
+                    .with_sensitivity(0.002)
+                    .with_callback(move |_, val| {
+                        pre_fx_gain.set_target_value(db_to_level(val));
+                    })
+                    .with_formatting_callback(|_, val| {
+                        if (0.0..=0.01).contains(&val) {
+                            return String::from("0.00 dB");
+                        }
+
+                        let val_str = if val.is_sign_negative() {
+                            format!("{val:.10}")
+                        }
+                        else {
+                            format!("+{val:.10}")
+                        };
+
+                        let mut decimal_idx = val_str.find('.').unwrap();
+
+                        // 5
+                        let truncate_to = if decimal_idx == 4 {
+                            6
+                        }
+                        else if decimal_idx > 5 {
+                            decimal_idx
+                        }
+                        else {
+                            5
+                        };
+
+                        let mut out = val_str[..truncate_to].to_string();
+                        out.push_str(" dB");
+                        out
+                    })
+            },
+        }
+    }
+
+    pub fn attach_reso_bank_randomize_callback<F: Fn(bool) + 'static>(
+        mut self,
+        cb: F,
+    ) -> Self {
         self.reso_bank_randomize.set_callback(cb);
         self
     }
@@ -1198,11 +2468,55 @@ impl UIComponents {
         self
     }
 
+    /// Sets every SmoothLife state slider, which also updates the shared
+    /// state via their callbacks.
+    fn set_smoothlife_state(&mut self, state: SLState) {
+        self.smoothlife_outer_radius.set_value(state.radius_outer);
+        self.smoothlife_inner_radius.set_value(state.radius_inner);
+        self.smoothlife_speed.set_value(state.dt);
+        self.smoothlife_b1.set_value(state.b1);
+        self.smoothlife_b2.set_value(state.b2);
+        self.smoothlife_alpha_n.set_value(state.alpha_n);
+        self.smoothlife_d1.set_value(state.d1);
+        self.smoothlife_d2.set_value(state.d2);
+        self.smoothlife_alpha_m.set_value(state.alpha_m);
+    }
+
+    fn smoothlife_sliders(&self) -> [&TextSlider; 9] {
+        [
+            &self.smoothlife_outer_radius,
+            &self.smoothlife_inner_radius,
+            &self.smoothlife_speed,
+            &self.smoothlife_b1,
+            &self.smoothlife_b2,
+            &self.smoothlife_alpha_n,
+            &self.smoothlife_d1,
+            &self.smoothlife_d2,
+            &self.smoothlife_alpha_m,
+        ]
+    }
+
+    fn smoothlife_sliders_mut(&mut self) -> [&mut TextSlider; 9] {
+        [
+            &mut self.smoothlife_outer_radius,
+            &mut self.smoothlife_inner_radius,
+            &mut self.smoothlife_speed,
+            &mut self.smoothlife_b1,
+            &mut self.smoothlife_b2,
+            &mut self.smoothlife_alpha_n,
+            &mut self.smoothlife_d1,
+            &mut self.smoothlife_d2,
+            &mut self.smoothlife_alpha_m,
+        ]
+    }
+
     #[allow(clippy::missing_panics_doc, clippy::significant_drop_tightening)]
     pub fn setup_mask_callbacks(
         mut self,
-        contours: Arc<RwLock<ContoursGPU>>,
-        smooth_life: Arc<RwLock<SmoothLifeGPU>>,
+        contours: Arc<RwLock<ContoursMask>>,
+        reaction_diffusion: Arc<RwLock<ReactionDiffusionMask>>,
+        image: Arc<RwLock<ImageMask>>,
+        spectral_feedback: Arc<RwLock<SpectralFeedbackMask>>,
         params: &UIParams,
     ) -> Self {
         let ctr = contours.read().unwrap();
@@ -1222,16 +2536,130 @@ impl UIComponents {
             ctr_upper.sr(val as f32);
         });
 
-        let sml = smooth_life.read().unwrap();
-        let sml_speed = sml.speed_arc();
-        let sml_preset = sml.preset_arc();
+        let ctr_noise = ctr.noise_params_arc();
+
+        let noise = Arc::clone(&params.contour_noise);
+        let ctr_noise_cl = Arc::clone(&ctr_noise);
+        self.contour_noise.set_callback(move |selected| {
+            ctr_noise_cl.noise.sr(selected);
+            noise.sr(selected);
+        });
 
-        self.smoothlife_speed.set_callback(move |_, val| {
-            sml_speed.sr(val as f32);
+        let octaves = Arc::clone(&params.contour_octaves);
+        let ctr_noise_cl = Arc::clone(&ctr_noise);
+        self.contour_octaves.set_callback(move |_, val| {
+            ctr_noise_cl.octaves.sr(val as u32);
+            octaves.sr(val as u32);
         });
 
-        self.smoothlife_preset.set_callback(move |selected| {
-            sml_preset.sr(selected);
+        let lacunarity = Arc::clone(&params.contour_lacunarity);
+        let ctr_noise_cl = Arc::clone(&ctr_noise);
+        self.contour_lacunarity.set_callback(move |_, val| {
+            ctr_noise_cl.lacunarity.sr(val as f32);
+            lacunarity.sr(val);
+        });
+
+        let persistence = Arc::clone(&params.contour_persistence);
+        let ctr_noise_cl = Arc::clone(&ctr_noise);
+        self.contour_persistence.set_callback(move |_, val| {
+            ctr_noise_cl.persistence.sr(val as f32);
+            persistence.sr(val);
+        });
+
+        let warp = Arc::clone(&params.contour_warp);
+        self.contour_warp.set_callback(move |_, val| {
+            ctr_noise.warp.sr(val as f32);
+            warp.sr(val);
+        });
+
+        let rd = reaction_diffusion.read().unwrap();
+        let rd_feed = rd.generator().feed_arc();
+        let rd_kill = rd.generator().kill_arc();
+        let rd_diffusion_u = rd.generator().diffusion_u_arc();
+        let rd_diffusion_v = rd.generator().diffusion_v_arc();
+        let rd_preset = rd.generator().preset_arc();
+
+        let feed = Arc::clone(&params.reaction_diffusion_feed);
+        self.reaction_diffusion_feed.set_callback(move |_, val| {
+            rd_feed.sr(val);
+            feed.sr(val);
+        });
+
+        let kill = Arc::clone(&params.reaction_diffusion_kill);
+        self.reaction_diffusion_kill.set_callback(move |_, val| {
+            rd_kill.sr(val);
+            kill.sr(val);
+        });
+
+        let diffusion_u = Arc::clone(&params.reaction_diffusion_diffusion_u);
+        self.reaction_diffusion_diffusion_u.set_callback(move |_, val| {
+            rd_diffusion_u.sr(val);
+            diffusion_u.sr(val);
+        });
+
+        let diffusion_v = Arc::clone(&params.reaction_diffusion_diffusion_v);
+        self.reaction_diffusion_diffusion_v.set_callback(move |_, val| {
+            rd_diffusion_v.sr(val);
+            diffusion_v.sr(val);
+        });
+
+        let preset = Arc::clone(&params.reaction_diffusion_preset);
+        self.reaction_diffusion_preset.set_callback(move |selected| {
+            rd_preset.sr(selected);
+            preset.sr(selected);
+        });
+
+        let img = image.read().unwrap();
+        let img_brightness = img.generator().brightness_arc();
+        let img_contrast = img.generator().contrast_arc();
+        let img_gamma = img.generator().gamma_arc();
+        let img_invert = img.generator().invert_arc();
+
+        let brightness = Arc::clone(&params.image_brightness);
+        self.image_brightness.set_callback(move |_, val| {
+            img_brightness.sr(val);
+            brightness.sr(val);
+        });
+
+        let contrast = Arc::clone(&params.image_contrast);
+        self.image_contrast.set_callback(move |_, val| {
+            img_contrast.sr(val);
+            contrast.sr(val);
+        });
+
+        let gamma = Arc::clone(&params.image_gamma);
+        self.image_gamma.set_callback(move |_, val| {
+            img_gamma.sr(val);
+            gamma.sr(val);
+        });
+
+        let invert = Arc::clone(&params.image_invert);
+        self.image_invert.set_callback(move |state| {
+            img_invert.sr(state);
+            invert.sr(state);
+        });
+
+        let fb = spectral_feedback.read().unwrap();
+        let fb_decay = fb.generator().decay_arc();
+        let fb_threshold = fb.generator().threshold_arc();
+        let fb_invert = fb.generator().invert_arc();
+
+        let decay = Arc::clone(&params.spectral_feedback_decay);
+        self.spectral_feedback_decay.set_callback(move |_, val| {
+            fb_decay.sr(val);
+            decay.sr(val);
+        });
+
+        let threshold = Arc::clone(&params.spectral_feedback_threshold);
+        self.spectral_feedback_threshold.set_callback(move |_, val| {
+            fb_threshold.sr(val);
+            threshold.sr(val);
+        });
+
+        let invert = Arc::clone(&params.spectral_feedback_invert);
+        self.spectral_feedback_invert.set_callback(move |state| {
+            fb_invert.sr(state);
+            invert.sr(state);
         });
 
         self
@@ -1254,21 +2682,284 @@ impl UIComponents {
         self.mask_label.draw(app, draw, frame);
         self.spectrogram_label.draw(app, draw, frame);
         self.reso_bank_label.draw(app, draw, frame);
-        self.low_filter_label.draw(app, draw, frame);
-        self.peak_filter_label.draw(app, draw, frame);
-        self.high_filter_label.draw(app, draw, frame);
-        self.dist_label.draw(app, draw, frame);
-        self.delay_label.draw(app, draw, frame);
-        self.comp_label.draw(app, draw, frame);
         self.effects_label.draw(app, draw, frame);
     }
+
+    /// Updates the transport position readout from the transport's `position`
+    /// in beats. The readout is highlighted on the first sixteenth of each
+    /// beat.
+    pub fn set_transport_position(&mut self, position: f64) {
+        let idx = sixteenth_idx(position);
+
+        if self.transport_sixteenth == Some(idx) {
+            return;
+        }
+
+        let (bar, beat, sixteenth) = bar_beat_sixteenth(position);
+        let on_beat = is_beat(position, &Rhythm16th::QUARTER_NOTE);
+
+        self.transport_position
+            .set_text(&format!("{bar}.{beat}.{sixteenth}"));
+        self.transport_position
+            .set_text_color(if on_beat { SELECTED } else { VALUE });
+        self.transport_position.needs_redraw = true;
+        self.transport_sixteenth = Some(idx);
+    }
+
+    /// Updates the compressor's gain reduction readout from
+    /// `gain_reduction_db`, which is positive.
+    pub fn set_comp_gain_reduction(&mut self, gain_reduction_db: f64) {
+        let tenths = (gain_reduction_db * 10.0).round() as i64;
+
+        if self.comp_gain_reduction_tenths == Some(tenths) {
+            return;
+        }
+
+        self.comp_gain_reduction
+            .set_text(&format!("GR {:.1} dB", tenths as f64 / 10.0));
+        self.comp_gain_reduction.needs_redraw = true;
+        self.comp_gain_reduction_tenths = Some(tenths);
+    }
+
+    /// Shows or hides the safety mute warning.
+    pub fn set_safety_muted(&mut self, is_muted: bool) {
+        if self.safety_muted == is_muted {
+            return;
+        }
+
+        self.safety_status
+            .set_text(if is_muted { "SAFETY MUTE" } else { "" });
+        self.safety_status.needs_redraw = true;
+        self.safety_muted = is_muted;
+    }
+
+    /// Updates the latency readout from `latency_samples` at `sample_rate`.
+    pub fn set_latency(&mut self, latency_samples: u32, sample_rate: f64) {
+        let latency_ms = latency_samples as f64 * 1000.0 / sample_rate;
+        let tenths = (latency_ms * 10.0).round() as i64;
+
+        if self.latency_tenths == Some(tenths) {
+            return;
+        }
+
+        self.latency
+            .set_text(&format!("Latency {:.1} ms", tenths as f64 / 10.0));
+        self.latency.needs_redraw = true;
+        self.latency_tenths = Some(tenths);
+    }
+
+    /// Flags every component of the current FX page to be redrawn, which is
+    /// needed while the page menu is open or has just changed pages.
+    fn redraw_fx_page(&mut self) {
+        match self.fx_page.output() {
+            FxPage::Main => {
+                self.delay_label.needs_redraw = true;
+                self.delay_time_ms.needs_redraw = true;
+                self.delay_division.needs_redraw = true;
+                self.delay_feedback.needs_redraw = true;
+                self.delay_mix.needs_redraw = true;
+                self.delay_is_ping_pong.needs_redraw = true;
+                self.delay_sync.needs_redraw = true;
+
+                self.dist_label.needs_redraw = true;
+                self.dist_amount.needs_redraw = true;
+                self.dist_type.needs_redraw = true;
+                self.dist_oversampling.needs_redraw = true;
+
+                self.comp_label.needs_redraw = true;
+                self.comp_thresh.needs_redraw = true;
+                self.comp_ratio.needs_redraw = true;
+                self.comp_attack.needs_redraw = true;
+                self.comp_release.needs_redraw = true;
+            }
+            FxPage::Delay => {
+                self.delay_page_label.needs_redraw = true;
+                self.delay_low_cut.needs_redraw = true;
+                self.delay_high_cut.needs_redraw = true;
+                self.delay_saturation.needs_redraw = true;
+                self.delay_wow_flutter.needs_redraw = true;
+                self.delay_time_r_ms.needs_redraw = true;
+                self.delay_division_r.needs_redraw = true;
+                self.delay_link.needs_redraw = true;
+            }
+            FxPage::Crush => {
+                self.crush_label.needs_redraw = true;
+                self.crush_rate.needs_redraw = true;
+                self.crush_bits.needs_redraw = true;
+                self.crush_jitter.needs_redraw = true;
+                self.crush_dither.needs_redraw = true;
+                self.crush_anti_alias.needs_redraw = true;
+            }
+            FxPage::Modulation => {
+                self.mod_label.needs_redraw = true;
+                self.mod_type.needs_redraw = true;
+                self.mod_rate.needs_redraw = true;
+                self.mod_division.needs_redraw = true;
+                self.mod_sync.needs_redraw = true;
+                self.mod_depth.needs_redraw = true;
+                self.mod_feedback.needs_redraw = true;
+                self.mod_mix.needs_redraw = true;
+            }
+            FxPage::Reverb => {
+                self.reverb_label.needs_redraw = true;
+                self.reverb_size.needs_redraw = true;
+                self.reverb_decay.needs_redraw = true;
+                self.reverb_damping.needs_redraw = true;
+                self.reverb_pre_delay.needs_redraw = true;
+                self.reverb_modulation.needs_redraw = true;
+                self.reverb_mix.needs_redraw = true;
+            }
+            FxPage::Compressor => {
+                self.comp_page_label.needs_redraw = true;
+                self.comp_knee.needs_redraw = true;
+                self.comp_stereo_link.needs_redraw = true;
+                self.comp_makeup.needs_redraw = true;
+                self.comp_lookahead.needs_redraw = true;
+                self.comp_sidechain_hpf.needs_redraw = true;
+                self.comp_gain_reduction.needs_redraw = true;
+                self.comp_auto_makeup.needs_redraw = true;
+                self.comp_use_rms.needs_redraw = true;
+                self.comp_exciter_key.needs_redraw = true;
+                self.limiter_label.needs_redraw = true;
+                self.limiter_ceiling.needs_redraw = true;
+                self.limiter_release.needs_redraw = true;
+            }
+            FxPage::DistBands => self.dist_bands.set_needs_redraw(),
+            FxPage::CompBands => self.comp_bands.set_needs_redraw(),
+        }
+    }
+
+    /// Draws the slider, menu and toggle labels of the current FX page, which
+    /// are otherwise only drawn on the first frame.
+    fn redraw_fx_page_labels(&self, draw: &Draw) {
+        match self.fx_page.output() {
+            FxPage::Main => {
+                if self.delay_sync.enabled() {
+                    self.delay_division.redraw_label(draw);
+                }
+                else {
+                    self.delay_time_ms.redraw_label(draw);
+                }
+                self.delay_feedback.redraw_label(draw);
+                self.delay_mix.redraw_label(draw);
+                self.delay_is_ping_pong.redraw_label(draw);
+                self.delay_sync.redraw_label(draw);
+
+                self.dist_amount.redraw_label(draw);
+                self.dist_type.redraw_label(draw);
+                self.dist_oversampling.redraw_label(draw);
+
+                self.comp_thresh.redraw_label(draw);
+                self.comp_ratio.redraw_label(draw);
+                self.comp_attack.redraw_label(draw);
+                self.comp_release.redraw_label(draw);
+            }
+            FxPage::Delay => {
+                self.delay_low_cut.redraw_label(draw);
+                self.delay_high_cut.redraw_label(draw);
+                self.delay_saturation.redraw_label(draw);
+                self.delay_wow_flutter.redraw_label(draw);
+                if self.delay_sync.enabled() {
+                    self.delay_division_r.redraw_label(draw);
+                }
+                else {
+                    self.delay_time_r_ms.redraw_label(draw);
+                }
+                self.delay_link.redraw_label(draw);
+            }
+            FxPage::Crush => {
+                self.crush_rate.redraw_label(draw);
+                self.crush_bits.redraw_label(draw);
+                self.crush_jitter.redraw_label(draw);
+                self.crush_dither.redraw_label(draw);
+                self.crush_anti_alias.redraw_label(draw);
+            }
+            FxPage::Modulation => {
+                self.mod_type.redraw_label(draw);
+                if self.mod_sync.enabled() {
+                    self.mod_division.redraw_label(draw);
+                }
+                else {
+                    self.mod_rate.redraw_label(draw);
+                }
+                self.mod_sync.redraw_label(draw);
+                self.mod_depth.redraw_label(draw);
+                self.mod_feedback.redraw_label(draw);
+                self.mod_mix.redraw_label(draw);
+            }
+            FxPage::Reverb => {
+                self.reverb_size.redraw_label(draw);
+                self.reverb_decay.redraw_label(draw);
+                self.reverb_damping.redraw_label(draw);
+                self.reverb_pre_delay.redraw_label(draw);
+                self.reverb_modulation.redraw_label(draw);
+                self.reverb_mix.redraw_label(draw);
+            }
+            FxPage::Compressor => {
+                self.comp_knee.redraw_label(draw);
+                self.comp_stereo_link.redraw_label(draw);
+                self.comp_makeup.redraw_label(draw);
+                self.comp_lookahead.redraw_label(draw);
+                self.comp_sidechain_hpf.redraw_label(draw);
+                self.comp_auto_makeup.redraw_label(draw);
+                self.comp_use_rms.redraw_label(draw);
+                self.comp_exciter_key.redraw_label(draw);
+                self.limiter_ceiling.redraw_label(draw);
+                self.limiter_release.redraw_label(draw);
+            }
+            FxPage::DistBands => self.dist_bands.redraw_labels(draw),
+            FxPage::CompBands => self.comp_bands.redraw_labels(draw),
+        }
+    }
 }
 
 impl UIDraw for UIComponents {
     fn update(&mut self, app: &App, input_data: &InputData) {
+        self.transport_bpm.update(app, input_data);
+        self.transport_play.update(app, input_data);
+        self.transport_position.needs_redraw = false;
+        self.delay_label.needs_redraw = false;
+        self.delay_page_label.needs_redraw = false;
+        self.dist_label.needs_redraw = false;
+        self.comp_label.needs_redraw = false;
+        self.comp_page_label.needs_redraw = false;
+        self.limiter_label.needs_redraw = false;
+        self.dist_bands.page_label.needs_redraw = false;
+        self.comp_bands.page_label.needs_redraw = false;
+        self.safety_status.needs_redraw = false;
+        self.latency.needs_redraw = false;
+        self.eq_band.label.needs_redraw = false;
+        self.crush_label.needs_redraw = false;
+        self.mod_label.needs_redraw = false;
+        self.reverb_label.needs_redraw = false;
+
         self.mask_algorithm.update(app, input_data);
-        self.mask_scan_line_speed.update(app, input_data);
+
+        self.mask_scan_line_sync.update(app, input_data);
+        let just_changed = self.mask_scan_line_sync.was_just_changed();
+
+        if self.mask_scan_line_sync.enabled() {
+            self.mask_scan_line_division.update(app, input_data);
+
+            if just_changed {
+                self.mask_scan_line_division.needs_redraw = true;
+            }
+        }
+        else {
+            self.mask_scan_line_speed.update(app, input_data);
+
+            if just_changed {
+                self.mask_scan_line_speed.needs_redraw = true;
+            }
+        }
+
         self.mask_mix.update(app, input_data);
+        self.mask_stereo_width.update(app, input_data);
+        self.mask_readout_mode.update(app, input_data);
+        self.mask_scan_line_count.update(app, input_data);
+        self.mask_scan_line_spread.update(app, input_data);
+        self.mask_scan_line_blend.update(app, input_data);
+        self.mask_scan_line_motion.update(app, input_data);
         self.mask_resolution.update(app, input_data);
         self.mask_reset.update(app, input_data);
 
@@ -1278,23 +2969,131 @@ impl UIDraw for UIComponents {
                 self.contour_thickness.update(app, input_data);
                 self.contour_speed.update(app, input_data);
 
+                self.contour_noise.update(app, input_data);
+
+                // the noise menu covers some of the sliders when open
+                if !self.contour_noise.is_open() {
+                    self.contour_octaves.update(app, input_data);
+                    self.contour_lacunarity.update(app, input_data);
+                    self.contour_persistence.update(app, input_data);
+                }
+                self.contour_warp.update(app, input_data);
+
                 self.contour_count.needs_redraw = true;
                 self.contour_thickness.needs_redraw = true;
                 self.contour_speed.needs_redraw = true;
+                self.contour_noise.needs_redraw = true;
+                self.contour_octaves.needs_redraw = true;
+                self.contour_lacunarity.needs_redraw = true;
+                self.contour_persistence.needs_redraw = true;
+                self.contour_warp.needs_redraw = true;
             }
             GenerativeAlgo::SmoothLife => {
                 self.smoothlife_preset.update(app, input_data);
+                self.smoothlife_save.update(app, input_data);
+
+                // the preset menu covers some of the sliders when open
+                if !self.smoothlife_preset.is_open() {
+                    for slider in self.smoothlife_sliders_mut() {
+                        slider.update(app, input_data);
+                    }
+                }
+
+                // presets override the whole state
+                let preset = self.smoothlife_preset.output();
+                if preset != self.smoothlife_shown_preset {
+                    let sl_state = &self.smoothlife_state;
+                    let state = SLState::from_preset(preset).or_else(|| {
+                        // restart the simulation from the seed the preset
+                        // was saved with
+                        if let Some(seed) = SLState::load_seed(USER_PRESET_FILE)
+                        {
+                            sl_state.restore_seed(seed);
+                        }
+
+                        SLState::load(USER_PRESET_FILE)
+                            .map_err(|e| {
+                                eprintln!(
+                                    "failed to load SmoothLife preset: {e}"
+                                );
+                            })
+                            .ok()
+                    });
+
+                    if let Some(state) = state {
+                        self.set_smoothlife_state(state);
+                    }
+
+                    self.smoothlife_shown_preset = preset;
+                }
 
                 self.smoothlife_preset.needs_redraw = true;
+                self.smoothlife_save.needs_redraw = true;
+                for slider in self.smoothlife_sliders_mut() {
+                    slider.needs_redraw = true;
+                }
             }
             GenerativeAlgo::Voronoi => {
                 self.voronoi_border_weight.update(app, input_data);
                 self.voronoi_cell_speed.update(app, input_data);
                 self.voronoi_cell_count.update(app, input_data);
 
+                self.voronoi_metric.update(app, input_data);
+
+                // the metric menu covers the other controls when open
+                if !self.voronoi_metric.is_open() {
+                    self.voronoi_exponent.update(app, input_data);
+                    self.voronoi_mode.update(app, input_data);
+                }
+
                 self.voronoi_border_weight.needs_redraw = true;
                 self.voronoi_cell_speed.needs_redraw = true;
                 self.voronoi_cell_count.needs_redraw = true;
+                self.voronoi_metric.needs_redraw = true;
+                self.voronoi_exponent.needs_redraw = true;
+                self.voronoi_mode.needs_redraw = true;
+            }
+            GenerativeAlgo::ReactionDiffusion => {
+                self.reaction_diffusion_preset.update(app, input_data);
+                self.reaction_diffusion_feed.update(app, input_data);
+                self.reaction_diffusion_kill.update(app, input_data);
+                self.reaction_diffusion_diffusion_u.update(app, input_data);
+                self.reaction_diffusion_diffusion_v.update(app, input_data);
+
+                // presets override the feed and kill rates
+                let preset = self.reaction_diffusion_preset.output();
+                if preset != self.reaction_diffusion_shown_preset {
+                    let state = GrayScottState::from_preset(preset);
+                    self.reaction_diffusion_feed.set_value(state.feed);
+                    self.reaction_diffusion_kill.set_value(state.kill);
+                    self.reaction_diffusion_shown_preset = preset;
+                }
+
+                self.reaction_diffusion_preset.needs_redraw = true;
+                self.reaction_diffusion_feed.needs_redraw = true;
+                self.reaction_diffusion_kill.needs_redraw = true;
+                self.reaction_diffusion_diffusion_u.needs_redraw = true;
+                self.reaction_diffusion_diffusion_v.needs_redraw = true;
+            }
+            GenerativeAlgo::Image => {
+                self.image_brightness.update(app, input_data);
+                self.image_contrast.update(app, input_data);
+                self.image_gamma.update(app, input_data);
+                self.image_invert.update(app, input_data);
+
+                self.image_brightness.needs_redraw = true;
+                self.image_contrast.needs_redraw = true;
+                self.image_gamma.needs_redraw = true;
+                self.image_invert.needs_redraw = true;
+            }
+            GenerativeAlgo::SpectralFeedback => {
+                self.spectral_feedback_decay.update(app, input_data);
+                self.spectral_feedback_threshold.update(app, input_data);
+                self.spectral_feedback_invert.update(app, input_data);
+
+                self.spectral_feedback_decay.needs_redraw = true;
+                self.spectral_feedback_threshold.needs_redraw = true;
+                self.spectral_feedback_invert.needs_redraw = true;
             }
         }
 
@@ -1307,76 +3106,146 @@ impl UIDraw for UIComponents {
         self.reso_bank_shift.update(app, input_data);
         self.reso_bank_inharm.update(app, input_data);
         self.reso_bank_pan.update(app, input_data);
+        self.reso_bank_decay.update(app, input_data);
+        self.reso_bank_damping.update(app, input_data);
+        self.reso_bank_decay_random.update(app, input_data);
         self.reso_bank_quantize.update(app, input_data);
+        self.reso_bank_pitch_layout.update(app, input_data);
+        self.reso_bank_layout_blend.update(app, input_data);
         self.reso_bank_randomize.update(app, input_data);
         self.reso_bank_push.update(app, input_data);
 
         self.reso_bank_resonator_count.update(app, input_data);
         self.reso_bank_field_friction.update(app, input_data);
+        self.reso_bank_field_mouse.update(app, input_data);
+        self.reso_bank_field_flocking.update(app, input_data);
+        self.reso_bank_field_collision.update(app, input_data);
+        self.reso_bank_field_gravity.update(app, input_data);
         self.reso_bank_mix.update(app, input_data);
         self.exciter_osc.update(app, input_data);
         if self.exciter_osc.needs_redraw() {
             self.spectrogram_label.needs_redraw = true;
         }
 
-        self.low_filter_type.update(app, input_data);
-        self.low_filter_cutoff.update(app, input_data);
-        let just_changed = self.low_filter_type.was_just_changed();
-
-        if self.low_filter_type.enabled() {
-            self.low_filter_gain.update(app, input_data);
-
-            if just_changed {
-                self.low_filter_gain.needs_redraw = true;
+        self.eq_band.update(app, input_data);
+
+        self.fx_page.update(app, input_data);
+
+        match self.fx_page.output() {
+            FxPage::Main => {
+                self.delay_sync.update(app, input_data);
+                let just_changed = self.delay_sync.was_just_changed();
+
+                if self.delay_sync.enabled() {
+                    self.delay_division.update(app, input_data);
+
+                    if just_changed {
+                        self.delay_division.needs_redraw = true;
+                    }
+                }
+                else {
+                    self.delay_time_ms.update(app, input_data);
+
+                    if just_changed {
+                        self.delay_time_ms.needs_redraw = true;
+                    }
+                }
+
+                self.delay_feedback.update(app, input_data);
+                self.delay_mix.update(app, input_data);
+                self.delay_is_ping_pong.update(app, input_data);
+
+                self.dist_amount.update(app, input_data);
+                self.dist_oversampling.update(app, input_data);
+                self.dist_type.update(app, input_data);
+                if self.dist_type.needs_redraw() {
+                    self.delay_feedback.needs_redraw = true;
+                }
+
+                self.comp_thresh.update(app, input_data);
+                self.comp_ratio.update(app, input_data);
+                self.comp_attack.update(app, input_data);
+                self.comp_release.update(app, input_data);
             }
-        }
-        else {
-            self.low_filter_q.update(app, input_data);
-
-            if just_changed {
-                self.low_filter_q.needs_redraw = true;
+            FxPage::Delay => {
+                self.delay_low_cut.update(app, input_data);
+                self.delay_high_cut.update(app, input_data);
+                self.delay_saturation.update(app, input_data);
+                self.delay_wow_flutter.update(app, input_data);
+                self.delay_link.update(app, input_data);
+
+                // the delay ignores the right channel's time while linked
+                if self.delay_sync.enabled() {
+                    self.delay_division_r.update(app, input_data);
+                }
+                else {
+                    self.delay_time_r_ms.update(app, input_data);
+                }
             }
-        }
-
-        self.high_filter_type.update(app, input_data);
-        self.high_filter_cutoff.update(app, input_data);
-        let just_changed = self.high_filter_type.was_just_changed();
-
-        if self.high_filter_type.enabled() {
-            self.high_filter_gain.update(app, input_data);
-
-            if just_changed {
-                self.high_filter_gain.needs_redraw = true;
+            FxPage::Crush => {
+                self.crush_rate.update(app, input_data);
+                self.crush_bits.update(app, input_data);
+                self.crush_jitter.update(app, input_data);
+                self.crush_anti_alias.update(app, input_data);
+                self.crush_dither.update(app, input_data);
             }
-        }
-        else {
-            self.high_filter_q.update(app, input_data);
-
-            if just_changed {
-                self.high_filter_q.needs_redraw = true;
+            FxPage::Modulation => {
+                self.mod_type.update(app, input_data);
+
+                self.mod_sync.update(app, input_data);
+                let just_changed = self.mod_sync.was_just_changed();
+
+                if self.mod_sync.enabled() {
+                    self.mod_division.update(app, input_data);
+
+                    if just_changed {
+                        self.mod_division.needs_redraw = true;
+                    }
+                }
+                else {
+                    self.mod_rate.update(app, input_data);
+
+                    if just_changed {
+                        self.mod_rate.needs_redraw = true;
+                    }
+                }
+
+                self.mod_depth.update(app, input_data);
+                self.mod_feedback.update(app, input_data);
+                self.mod_mix.update(app, input_data);
+
+                if self.mod_type.needs_redraw() {
+                    self.mod_depth.needs_redraw = true;
+                }
             }
+            FxPage::Reverb => {
+                self.reverb_size.update(app, input_data);
+                self.reverb_decay.update(app, input_data);
+                self.reverb_damping.update(app, input_data);
+                self.reverb_pre_delay.update(app, input_data);
+                self.reverb_modulation.update(app, input_data);
+                self.reverb_mix.update(app, input_data);
+            }
+            FxPage::Compressor => {
+                self.comp_knee.update(app, input_data);
+                self.comp_stereo_link.update(app, input_data);
+                self.comp_makeup.update(app, input_data);
+                self.comp_lookahead.update(app, input_data);
+                self.comp_sidechain_hpf.update(app, input_data);
+                self.comp_auto_makeup.update(app, input_data);
+                self.comp_use_rms.update(app, input_data);
+                self.comp_exciter_key.update(app, input_data);
+                self.limiter_ceiling.update(app, input_data);
+                self.limiter_release.update(app, input_data);
+            }
+            FxPage::DistBands => self.dist_bands.update(app, input_data),
+            FxPage::CompBands => self.comp_bands.update(app, input_data),
         }
 
-        self.peak_filter_cutoff.update(app, input_data);
-        self.peak_filter_gain.update(app, input_data);
-        self.peak_filter_q.update(app, input_data);
-
-        self.delay_time_ms.update(app, input_data);
-        self.delay_feedback.update(app, input_data);
-        self.delay_mix.update(app, input_data);
-        self.delay_is_ping_pong.update(app, input_data);
-
-        self.dist_amount.update(app, input_data);
-        self.dist_type.update(app, input_data);
-        if self.dist_type.needs_redraw() {
-            self.delay_feedback.needs_redraw = true;
+        if self.fx_page.needs_redraw() {
+            self.redraw_fx_page();
         }
 
-        self.comp_thresh.update(app, input_data);
-        self.comp_ratio.update(app, input_data);
-        self.comp_attack.update(app, input_data);
-        self.comp_release.update(app, input_data);
-
         self.pre_fx_gain.update(app, input_data);
         self.master_gain.update(app, input_data);
     }
@@ -1386,36 +3255,35 @@ impl UIDraw for UIComponents {
             self.draw_labels(app, draw, frame);
         }
 
-        if self.low_filter_type.enabled() {
-            self.low_filter_gain.draw(app, draw, frame);
-        }
-        else {
-            self.low_filter_q.draw(app, draw, frame);
-        }
+        self.transport_bpm.draw(app, draw, frame);
+        self.transport_play.draw(app, draw, frame);
+        self.transport_position.draw(app, draw, frame);
+        self.safety_status.draw(app, draw, frame);
+        self.latency.draw(app, draw, frame);
 
-        self.high_filter_type.draw(app, draw, frame);
-        self.high_filter_cutoff.draw(app, draw, frame);
+        self.mask_scan_line_sync.draw(app, draw, frame);
 
-        if self.high_filter_type.enabled() {
-            self.high_filter_gain.draw(app, draw, frame);
+        if self.mask_scan_line_sync.enabled() {
+            self.mask_scan_line_division.draw(app, draw, frame);
         }
         else {
-            self.high_filter_q.draw(app, draw, frame);
+            self.mask_scan_line_speed.draw(app, draw, frame);
         }
 
-        self.peak_filter_q.draw(app, draw, frame);
-        self.peak_filter_cutoff.draw(app, draw, frame);
-        self.peak_filter_gain.draw(app, draw, frame);
-
-        self.mask_scan_line_speed.draw(app, draw, frame);
         self.mask_mix.draw(app, draw, frame);
+        self.mask_stereo_width.draw(app, draw, frame);
+        self.mask_scan_line_count.draw(app, draw, frame);
+        self.mask_scan_line_spread.draw(app, draw, frame);
+        self.mask_readout_mode.draw(app, draw, frame); // menu
+        self.mask_scan_line_blend.draw(app, draw, frame); // menu
+        self.mask_scan_line_motion.draw(app, draw, frame); // menu
         self.mask_resolution.draw(app, draw, frame);
         self.mask_reset.draw(app, draw, frame);
 
         let th_rect = self.contour_thickness.rect();
         draw.rect()
-            .xy(pt2(213.0, 145.0))
-            .wh(pt2(155.0, 200.0))
+            .xy(pt2(213.0, 132.5))
+            .wh(pt2(155.0, 225.0))
             .color(BLACK);
 
         match self.mask_algorithm.output() {
@@ -1424,14 +3292,31 @@ impl UIDraw for UIComponents {
                 self.contour_thickness.draw(app, draw, frame);
                 self.contour_speed.draw(app, draw, frame);
 
+                self.contour_octaves.draw(app, draw, frame);
+                self.contour_lacunarity.draw(app, draw, frame);
+                self.contour_persistence.draw(app, draw, frame);
+                self.contour_warp.draw(app, draw, frame);
+
                 self.contour_count.redraw_label(draw);
                 self.contour_thickness.redraw_label(draw);
                 self.contour_speed.redraw_label(draw);
+                self.contour_octaves.redraw_label(draw);
+                self.contour_lacunarity.redraw_label(draw);
+                self.contour_persistence.redraw_label(draw);
+                self.contour_warp.redraw_label(draw);
+
+                self.contour_noise.draw(app, draw, frame);
+                self.contour_noise.redraw_label(draw);
             }
             GenerativeAlgo::SmoothLife => {
                 // unused components
                 // self.smoothlife_resolution.draw(app, draw, frame);
-                // self.smoothlife_speed.draw(app, draw, frame);
+                for slider in self.smoothlife_sliders() {
+                    slider.draw(app, draw, frame);
+                    slider.redraw_label(draw);
+                }
+
+                self.smoothlife_save.draw(app, draw, frame);
                 self.smoothlife_preset.draw(app, draw, frame);
                 self.smoothlife_preset.redraw_label(draw);
             }
@@ -1443,6 +3328,45 @@ impl UIDraw for UIComponents {
                 self.voronoi_cell_speed.redraw_label(draw);
                 self.voronoi_border_weight.redraw_label(draw);
                 self.voronoi_cell_count.redraw_label(draw);
+
+                self.voronoi_exponent.draw(app, draw, frame);
+                self.voronoi_exponent.redraw_label(draw);
+
+                self.voronoi_mode.draw(app, draw, frame);
+                self.voronoi_mode.redraw_label(draw);
+                self.voronoi_metric.draw(app, draw, frame);
+                self.voronoi_metric.redraw_label(draw);
+            }
+            GenerativeAlgo::ReactionDiffusion => {
+                self.reaction_diffusion_feed.draw(app, draw, frame);
+                self.reaction_diffusion_kill.draw(app, draw, frame);
+                self.reaction_diffusion_diffusion_u.draw(app, draw, frame);
+                self.reaction_diffusion_diffusion_v.draw(app, draw, frame);
+                self.reaction_diffusion_preset.draw(app, draw, frame); // menu
+
+                self.reaction_diffusion_feed.redraw_label(draw);
+                self.reaction_diffusion_kill.redraw_label(draw);
+                self.reaction_diffusion_diffusion_u.redraw_label(draw);
+                self.reaction_diffusion_diffusion_v.redraw_label(draw);
+                self.reaction_diffusion_preset.redraw_label(draw);
+            }
+            GenerativeAlgo::Image => {
+                self.image_brightness.draw(app, draw, frame);
+                self.image_contrast.draw(app, draw, frame);
+                self.image_gamma.draw(app, draw, frame);
+                self.image_invert.draw(app, draw, frame);
+
+                self.image_brightness.redraw_label(draw);
+                self.image_contrast.redraw_label(draw);
+                self.image_gamma.redraw_label(draw);
+            }
+            GenerativeAlgo::SpectralFeedback => {
+                self.spectral_feedback_decay.draw(app, draw, frame);
+                self.spectral_feedback_threshold.draw(app, draw, frame);
+                self.spectral_feedback_invert.draw(app, draw, frame);
+
+                self.spectral_feedback_decay.redraw_label(draw);
+                self.spectral_feedback_threshold.redraw_label(draw);
             }
         }
         self.mask_algorithm.draw(app, draw, frame); // menu
@@ -1457,13 +3381,22 @@ impl UIDraw for UIComponents {
         self.reso_bank_shift.draw(app, draw, frame);
         self.reso_bank_inharm.draw(app, draw, frame);
         self.reso_bank_pan.draw(app, draw, frame);
+        self.reso_bank_decay.draw(app, draw, frame);
+        self.reso_bank_damping.draw(app, draw, frame);
+        self.reso_bank_decay_random.draw(app, draw, frame);
         self.reso_bank_quantize.draw(app, draw, frame);
+        self.reso_bank_pitch_layout.draw(app, draw, frame);
+        self.reso_bank_layout_blend.draw(app, draw, frame);
         self.reso_bank_randomize.draw(app, draw, frame);
         self.reso_bank_push.draw(app, draw, frame);
         self.reso_bank_scale.draw(app, draw, frame); // menu
 
         self.reso_bank_resonator_count.draw(app, draw, frame);
         self.reso_bank_field_friction.draw(app, draw, frame);
+        self.reso_bank_field_mouse.draw(app, draw, frame);
+        self.reso_bank_field_flocking.draw(app, draw, frame);
+        self.reso_bank_field_collision.draw(app, draw, frame);
+        self.reso_bank_field_gravity.draw(app, draw, frame);
         self.reso_bank_mix.draw(app, draw, frame);
 
         if self.exciter_osc.needs_redraw() {
@@ -1471,25 +3404,120 @@ impl UIDraw for UIComponents {
         }
         self.exciter_osc.draw(app, draw, frame);
 
-        self.low_filter_type.draw(app, draw, frame);
-        self.low_filter_cutoff.draw(app, draw, frame);
+        self.eq_band.draw(app, draw, frame);
+
+        let page_changed = self.fx_page.needs_redraw();
+
+        match self.fx_page.output() {
+            FxPage::Main => {
+                self.delay_label.draw(app, draw, frame);
+                self.dist_label.draw(app, draw, frame);
+                self.comp_label.draw(app, draw, frame);
+
+                self.dist_amount.draw(app, draw, frame);
+                self.dist_oversampling.draw(app, draw, frame);
+
+                if self.delay_sync.enabled() {
+                    self.delay_division.draw(app, draw, frame);
+                }
+                else {
+                    self.delay_time_ms.draw(app, draw, frame);
+                }
+                self.delay_sync.draw(app, draw, frame);
+                self.delay_feedback.draw(app, draw, frame);
+                self.delay_mix.draw(app, draw, frame);
+                self.delay_is_ping_pong.draw(app, draw, frame);
+
+                if self.dist_type.needs_redraw() {
+                    self.delay_feedback.redraw_label(draw);
+                }
+                self.dist_type.draw(app, draw, frame); // menu
 
-        self.dist_amount.draw(app, draw, frame);
+                self.comp_thresh.draw(app, draw, frame);
+                self.comp_ratio.draw(app, draw, frame);
+                self.comp_attack.draw(app, draw, frame);
+                self.comp_release.draw(app, draw, frame);
 
-        self.delay_time_ms.draw(app, draw, frame);
-        self.delay_feedback.draw(app, draw, frame);
-        self.delay_mix.draw(app, draw, frame);
-        self.delay_is_ping_pong.draw(app, draw, frame);
+            }
+            FxPage::Delay => {
+                self.delay_page_label.draw(app, draw, frame);
+
+                self.delay_low_cut.draw(app, draw, frame);
+                self.delay_high_cut.draw(app, draw, frame);
+                self.delay_saturation.draw(app, draw, frame);
+                self.delay_wow_flutter.draw(app, draw, frame);
+                if self.delay_sync.enabled() {
+                    self.delay_division_r.draw(app, draw, frame);
+                }
+                else {
+                    self.delay_time_r_ms.draw(app, draw, frame);
+                }
+                self.delay_link.draw(app, draw, frame);
+            }
+            FxPage::Crush => {
+                self.crush_label.draw(app, draw, frame);
+
+                self.crush_rate.draw(app, draw, frame);
+                self.crush_bits.draw(app, draw, frame);
+                self.crush_jitter.draw(app, draw, frame);
+                self.crush_anti_alias.draw(app, draw, frame);
+                self.crush_dither.draw(app, draw, frame); // menu
+            }
+            FxPage::Modulation => {
+                self.mod_label.draw(app, draw, frame);
+
+                if self.mod_sync.enabled() {
+                    self.mod_division.draw(app, draw, frame);
+                }
+                else {
+                    self.mod_rate.draw(app, draw, frame);
+                }
+                self.mod_sync.draw(app, draw, frame);
+                self.mod_depth.draw(app, draw, frame);
+                self.mod_feedback.draw(app, draw, frame);
+                self.mod_mix.draw(app, draw, frame);
+
+                if self.mod_type.needs_redraw() {
+                    self.mod_depth.redraw_label(draw);
+                }
+                self.mod_type.draw(app, draw, frame); // menu
+            }
+            FxPage::Reverb => {
+                self.reverb_label.draw(app, draw, frame);
+
+                self.reverb_size.draw(app, draw, frame);
+                self.reverb_decay.draw(app, draw, frame);
+                self.reverb_damping.draw(app, draw, frame);
+                self.reverb_pre_delay.draw(app, draw, frame);
+                self.reverb_modulation.draw(app, draw, frame);
+                self.reverb_mix.draw(app, draw, frame);
+            }
+            FxPage::Compressor => {
+                self.comp_page_label.draw(app, draw, frame);
+
+                self.comp_knee.draw(app, draw, frame);
+                self.comp_stereo_link.draw(app, draw, frame);
+                self.comp_makeup.draw(app, draw, frame);
+                self.comp_lookahead.draw(app, draw, frame);
+                self.comp_sidechain_hpf.draw(app, draw, frame);
+                self.comp_gain_reduction.draw(app, draw, frame);
+                self.comp_auto_makeup.draw(app, draw, frame);
+                self.comp_use_rms.draw(app, draw, frame);
+                self.comp_exciter_key.draw(app, draw, frame);
+
+                self.limiter_label.draw(app, draw, frame);
+                self.limiter_ceiling.draw(app, draw, frame);
+                self.limiter_release.draw(app, draw, frame);
+            }
+            FxPage::DistBands => self.dist_bands.draw(app, draw, frame),
+            FxPage::CompBands => self.comp_bands.draw(app, draw, frame),
+        }
 
-        if self.dist_type.needs_redraw() {
-            self.delay_feedback.redraw_label(draw);
+        if page_changed {
+            self.redraw_fx_page_labels(draw);
         }
-        self.dist_type.draw(app, draw, frame); // menu
 
-        self.comp_thresh.draw(app, draw, frame);
-        self.comp_ratio.draw(app, draw, frame);
-        self.comp_attack.draw(app, draw, frame);
-        self.comp_release.draw(app, draw, frame);
+        self.fx_page.draw(app, draw, frame); // menu
 
         self.pre_fx_gain.draw(app, draw, frame);
         self.master_gain.draw(app, draw, frame);
@@ -1500,6 +3528,396 @@ impl UIDraw for UIComponents {
     }
 }
 
+/// The controls of a multiband processor's FX page.
+struct MultibandComponents {
+    page_label: Label,
+    /// f64 (smoother callback)
+    crossover_freqs: [TextSlider; NUM_BANDS - 1],
+    /// f64 (smoother callback)
+    gains: [TextSlider; NUM_BANDS],
+    /// toggle
+    bypass: [Button; NUM_BANDS],
+    /// toggle
+    solo: [Button; NUM_BANDS],
+    /// toggle
+    enabled: Button,
+}
+
+impl MultibandComponents {
+    /// The names of each band, from lowest to highest.
+    const BAND_NAMES: [&'static str; NUM_BANDS] = ["Low", "Mid", "High"];
+    /// The names and ranges of each crossover frequency, in hertz.
+    const CROSSOVERS: [(&'static str, f64, f64); NUM_BANDS - 1] =
+        [("Low/Mid", 40.0, 1000.0), ("Mid/High", 1000.0, 16000.0)];
+
+    fn new(
+        title: &str,
+        params: &MultibandParams,
+        layout: &MultibandUILayout,
+    ) -> Self {
+        let toggle = |rect: Rect, label: &str, state: &Arc<AtomicBool>| {
+            let state = Arc::clone(state);
+            Button::new(rect)
+                .with_label(label)
+                .with_label_layout(main_label_layout())
+                .with_enabled_layout(main_value_layout())
+                .with_disabled_layout(main_value_layout())
+                .with_state(state.lr())
+                .with_callback(move |enabled| state.sr(enabled))
+        };
+
+        Self {
+            page_label: Label::new(layout.page_label)
+                .with_text(title)
+                .with_text_layout(big_label_layout()),
+            crossover_freqs: std::array::from_fn(|i| {
+                let freq = Arc::clone(&params.crossover_freqs[i]);
+                let (label, min, max) = Self::CROSSOVERS[i];
+
+                TextSlider::new(0.0, layout.crossover_freqs[i])
+                    .with_label(label)
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(min.log2()..=max.log2())
+                    .with_default_value(freq.current_value().log2())
+                    .with_callback(move |_, value| {
+                        freq.set_target_value(value.exp2());
+                    })
+                    .with_formatting_callback(|_, val| {
+                        format_hz_value(val.exp2())
+                    })
+            }),
+            gains: std::array::from_fn(|band| {
+                let gain = Arc::clone(&params.gain_db[band]);
+
+                TextSlider::new(0.0, layout.gains[band])
+                    .with_label(Self::BAND_NAMES[band])
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_suffix(" dB")
+                    .with_output_range(-24.0..=12.0)
+                    .with_default_value(gain.current_value())
+                    .with_callback(move |_, value| {
+                        gain.set_target_value(value);
+                    })
+            }),
+            bypass: std::array::from_fn(|band| {
+                toggle(layout.bypass[band], "Byp", &params.bypass[band])
+            }),
+            solo: std::array::from_fn(|band| {
+                toggle(layout.solo[band], "Solo", &params.solo[band])
+            }),
+            enabled: toggle(layout.enabled, "On", &params.enabled),
+        }
+    }
+
+    fn set_needs_redraw(&mut self) {
+        self.page_label.needs_redraw = true;
+        self.enabled.needs_redraw = true;
+
+        for slider in self.crossover_freqs.iter_mut().chain(&mut self.gains) {
+            slider.needs_redraw = true;
+        }
+        for button in self.bypass.iter_mut().chain(&mut self.solo) {
+            button.needs_redraw = true;
+        }
+    }
+
+    fn redraw_labels(&self, draw: &Draw) {
+        self.enabled.redraw_label(draw);
+
+        for slider in self.crossover_freqs.iter().chain(&self.gains) {
+            slider.redraw_label(draw);
+        }
+        for button in self.bypass.iter().chain(&self.solo) {
+            button.redraw_label(draw);
+        }
+    }
+
+    fn update(&mut self, app: &App, input_data: &InputData) {
+        self.enabled.update(app, input_data);
+
+        for slider in self.crossover_freqs.iter_mut().chain(&mut self.gains) {
+            slider.update(app, input_data);
+        }
+        for button in self.bypass.iter_mut().chain(&mut self.solo) {
+            button.update(app, input_data);
+        }
+    }
+
+    fn draw(&self, app: &App, draw: &Draw, frame: &Frame) {
+        self.page_label.draw(app, draw, frame);
+        self.enabled.draw(app, draw, frame);
+
+        for slider in self.crossover_freqs.iter().chain(&self.gains) {
+            slider.draw(app, draw, frame);
+        }
+        for button in self.bypass.iter().chain(&self.solo) {
+            button.draw(app, draw, frame);
+        }
+    }
+}
+
+/// The editor for the selected band of the parametric EQ. Its controls
+/// always act on the band selected in the [`EQDisplay`].
+pub struct EQBandComponents {
+    label: Label,
+    /// menu
+    f_type: Menu<FilterType>,
+    /// menu
+    slope: Menu<FilterSlope>,
+    /// toggle
+    enabled: Button,
+    /// f64 (smoother callback)
+    pub cutoff: TextSlider,
+    /// f64 (smoother callback)
+    pub gain: TextSlider,
+    /// f64 (smoother callback)
+    pub q: TextSlider,
+    /// toggle
+    dynamic: Button,
+    /// f64
+    pub threshold: TextSlider,
+    /// toggle (shared by every band)
+    linear_phase: Button,
+
+    /// The index of the band shown in the editor.
+    shown_band: usize,
+}
+
+impl EQBandComponents {
+    fn new(params: &EQParams, layout: &EQUILayout) -> Self {
+        let shown_band = params.selected_band.lr();
+        let band = &params.bands[shown_band];
+        let selected = || {
+            (params.bands.clone(), Arc::clone(&params.selected_band))
+        };
+
+        Self {
+            label: Label::new(layout.label)
+                .with_text(&Self::label_text(shown_band))
+                .with_text_layout(big_label_layout()),
+            f_type: {
+                let (bands, selected) = selected();
+                Menu::new(layout.f_type)
+                    .with_label("Type")
+                    .with_label_layout(Layout {
+                        justify: Justify::Right,
+                        ..main_label_layout()
+                    })
+                    .with_item_text_layout(main_value_layout())
+                    .initial_variant(band.filter_type.lr())
+                    .with_callback(move |filter_type| {
+                        bands[selected.lr()].filter_type.sr(filter_type);
+                    })
+            },
+            slope: {
+                let (bands, selected) = selected();
+                Menu::new(layout.slope)
+                    .with_label("Slope")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_item_text_layout(main_value_layout())
+                    .initial_variant(band.slope.lr())
+                    .with_callback(move |slope| {
+                        bands[selected.lr()].slope.sr(slope);
+                    })
+            },
+            enabled: {
+                let (bands, selected) = selected();
+                Button::new(layout.enabled)
+                    .with_label("On")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(band.enabled.lr())
+                    .with_callback(move |state| {
+                        bands[selected.lr()].enabled.sr(state);
+                    })
+            },
+            cutoff: {
+                let (bands, selected) = selected();
+                TextSlider::new(0.0, layout.cutoff_hz)
+                    .with_sensitivity(0.0015)
+                    .with_label("Cutoff")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(3.4868..=135.075_366) // 10Hz to 20kHz
+                    .with_default_value(freq_to_note(
+                        band.cutoff.current_value(),
+                    ))
+                    .with_callback(move |_, value| {
+                        bands[selected.lr()]
+                            .cutoff
+                            .set_target_value(note_to_freq(value));
+                    })
+                    .with_formatting_callback(|_, output_value| {
+                        let freq = note_to_freq(output_value);
+                        format_hz_value(freq)
+                    })
+            },
+            gain: {
+                let (bands, selected) = selected();
+                TextSlider::new(0.0, layout.gain)
+                    .with_sensitivity(0.002)
+                    .with_label("Gain")
+                    .with_positive_value_prefix()
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(-24.0..=24.0)
+                    .with_value_chars(5)
+                    .with_suffix(" dB")
+                    .with_default_value(band.gain_db.current_value())
+                    .with_callback(move |_, value| {
+                        bands[selected.lr()].gain_db.set_target_value(value);
+                    })
+            },
+            q: {
+                let (bands, selected) = selected();
+                TextSlider::new(0.0, layout.q)
+                    .with_sensitivity(0.002)
+                    .with_label("Q")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_value_chars(4)
+                    .with_output_range(0.3..=10.0)
+                    .with_default_value(band.q.current_value())
+                    .with_callback(move |_, value| {
+                        bands[selected.lr()].q.set_target_value(value);
+                    })
+            },
+            dynamic: {
+                let (bands, selected) = selected();
+                Button::new(layout.dynamic)
+                    .with_label("Dyn")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(band.dynamic.lr())
+                    .with_callback(move |state| {
+                        bands[selected.lr()].dynamic.sr(state);
+                    })
+            },
+            threshold: {
+                let (bands, selected) = selected();
+                TextSlider::new(0.0, layout.threshold)
+                    .with_sensitivity(0.002)
+                    .with_label("Thresh")
+                    .with_label_layout(main_label_layout())
+                    .with_value_layout(main_value_layout())
+                    .with_output_range(-60.0..=0.0)
+                    .with_value_chars(5)
+                    .with_suffix(" dB")
+                    .with_default_value(band.threshold_db.lr())
+                    .with_callback(move |_, value| {
+                        bands[selected.lr()].threshold_db.sr(value);
+                    })
+            },
+            linear_phase: {
+                let linear_phase = Arc::clone(&params.linear_phase);
+                Button::new(layout.linear_phase)
+                    .with_label("Lin")
+                    .with_label_layout(Layout {
+                        justify: Justify::Left,
+                        ..main_label_layout()
+                    })
+                    .with_enabled_layout(main_value_layout())
+                    .with_disabled_layout(main_value_layout())
+                    .with_state(linear_phase.lr())
+                    .with_callback(move |state| linear_phase.sr(state))
+            },
+
+            shown_band,
+        }
+    }
+
+    fn label_text(band: usize) -> String {
+        format!("EQ BAND {}", band + 1)
+    }
+
+    /// Returns the index of the band shown in the editor.
+    pub fn shown_band(&self) -> usize {
+        self.shown_band
+    }
+
+    /// Shows the settings of `band` in the editor, where `filter` holds its
+    /// cutoff, gain and Q.
+    pub fn show_band(
+        &mut self,
+        band: usize,
+        params: &EQParams,
+        filter: &EQFilterParams,
+    ) {
+        if self.shown_band != band {
+            self.shown_band = band;
+            self.label.set_text(&Self::label_text(band));
+            self.label.needs_redraw = true;
+        }
+
+        let band = &params.bands[band];
+        self.f_type.set_variant(band.filter_type.lr());
+        self.slope.set_variant(band.slope.lr());
+        self.enabled.set_enabled(band.enabled.lr());
+
+        self.dynamic.set_enabled(band.dynamic.lr());
+
+        self.cutoff.set_value(freq_to_note(filter.cutoff));
+        self.gain.set_value(filter.gain);
+        self.q.set_value(filter.q);
+        self.threshold.set_value(band.threshold_db.lr());
+    }
+
+    /// Returns whether either of the editor's menus is open.
+    pub fn is_menu_open(&self) -> bool {
+        self.f_type.is_open() || self.slope.is_open()
+    }
+
+    /// Clears the area under each menu which needs to be redrawn.
+    pub fn redraw_under_menus(&self, draw: &Draw) {
+        if self.f_type.needs_redraw() {
+            let rect = self.f_type.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
+        }
+        if self.slope.needs_redraw() {
+            let rect = self.slope.rect();
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(BLACK);
+        }
+    }
+
+    fn update(&mut self, app: &App, input_data: &InputData) {
+        self.f_type.update(app, input_data);
+        self.slope.update(app, input_data);
+        self.enabled.update(app, input_data);
+        self.cutoff.update(app, input_data);
+        self.gain.update(app, input_data);
+        self.q.update(app, input_data);
+        self.dynamic.update(app, input_data);
+        self.threshold.update(app, input_data);
+        self.linear_phase.update(app, input_data);
+    }
+
+    fn draw(&self, app: &App, draw: &Draw, frame: &Frame) {
+        self.label.draw(app, draw, frame);
+        self.enabled.draw(app, draw, frame);
+        self.cutoff.draw(app, draw, frame);
+        self.gain.draw(app, draw, frame);
+        self.q.draw(app, draw, frame);
+        self.dynamic.draw(app, draw, frame);
+        self.threshold.draw(app, draw, frame);
+        self.linear_phase.draw(app, draw, frame);
+        self.slope.draw(app, draw, frame); // menu
+        self.f_type.draw(app, draw, frame); // menu
+    }
+}
+
 fn format_hz_value(freq_hz: f64) -> String {
     if freq_hz < 100.0 {
         format!("{freq_hz:.2} Hz")
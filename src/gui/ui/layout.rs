@@ -20,6 +20,12 @@ fn small_width_chars(max_chars: u32) -> f32 {
     max_chars as f32 * 8.5 + padding
 }
 
+/// The area of the effect controls which changes with the selected
+/// [`FxPage`], i.e. everything below the pre-FX gain.
+pub fn fx_page_area() -> Rect {
+    Rect::from_corners(pt2(375.0, -400.0), pt2(700.0, 200.0))
+}
+
 pub struct MaskUILayout {
     pub label: Rect,
     pub algorithm: Rect,
@@ -27,6 +33,13 @@ pub struct MaskUILayout {
     pub resolution: Rect,
     pub is_post_fx: Rect,
     pub mix: Rect,
+    pub stereo_width: Rect,
+    pub readout: Rect,
+    pub scan_line_count: Rect,
+    pub scan_line_spread: Rect,
+    pub scan_line_blend: Rect,
+    pub scan_line_motion: Rect,
+    pub scan_line_sync: Rect,
     pub reset: Rect,
 }
 
@@ -55,13 +68,42 @@ impl Default for MaskUILayout {
             pt2(mx_w, SMALL_HEIGHT),
         );
 
+        let sw_w = small_width_chars(5);
+        let width_rect = Rect::from_xy_wh(
+            pt2(128.0 - sw_w / 2.0, 28.0),
+            pt2(sw_w, SMALL_HEIGHT),
+        );
+
+        // scan line controls, in a column to the right of the algorithm menu
+        let sc_x = 340.0;
+        let sc_w = small_width_chars(8);
+
+        let readout_rect = Rect::from_xy_wh(
+            pt2(sc_x, 300.0 - SMALL_HEIGHT * 2.0),
+            pt2(sc_w, SMALL_HEIGHT * 4.0),
+        );
+        let count_rect =
+            Rect::from_xy_wh(pt2(sc_x, 200.0), pt2(sc_w, SMALL_HEIGHT));
+        let spread_rect =
+            Rect::from_xy_wh(pt2(sc_x, 155.0), pt2(sc_w, SMALL_HEIGHT));
+        let blend_rect = Rect::from_xy_wh(
+            pt2(sc_x, 120.0 - SMALL_HEIGHT),
+            pt2(sc_w, SMALL_HEIGHT * 2.0),
+        );
+        let motion_rect = Rect::from_xy_wh(
+            pt2(sc_x, 65.0 - SMALL_HEIGHT),
+            pt2(sc_w, SMALL_HEIGHT * 2.0),
+        );
+        let sync_rect =
+            Rect::from_xy_wh(pt2(sc_x, 10.0), pt2(sc_w, SMALL_HEIGHT));
+
         let label_rect =
             Rect::from_xy_wh(pt2(0.0, 366.0), pt2(180.0, MAIN_HEIGHT));
 
         let al_w = main_width_chars(11);
         let algo_rect = Rect::from_xy_wh(
-            pt2(128.0 + al_w / 2.0 + 10.0, 310.0 - MAIN_HEIGHT * 1.5 - 26.0),
-            pt2(al_w, MAIN_HEIGHT * 3.0),
+            pt2(128.0 + al_w / 2.0 + 10.0, 310.0 - MAIN_HEIGHT * 3.0 - 26.0),
+            pt2(al_w, MAIN_HEIGHT * 6.0),
         );
 
         Self {
@@ -71,6 +113,13 @@ impl Default for MaskUILayout {
             resolution: reso_rect,
             is_post_fx: def_rect(),
             mix: mix_rect,
+            stereo_width: width_rect,
+            readout: readout_rect,
+            scan_line_count: count_rect,
+            scan_line_spread: spread_rect,
+            scan_line_blend: blend_rect,
+            scan_line_motion: motion_rect,
+            scan_line_sync: sync_rect,
             reset: reset_rect,
         }
     }
@@ -80,6 +129,12 @@ pub struct ContourUILayout {
     pub count: Rect,
     pub thickness: Rect,
     pub speed: Rect,
+
+    pub noise: Rect,
+    pub octaves: Rect,
+    pub lacunarity: Rect,
+    pub persistence: Rect,
+    pub warp: Rect,
 }
 
 impl Default for ContourUILayout {
@@ -107,29 +162,59 @@ impl Default for ContourUILayout {
             pt2(ct_w, MAIN_HEIGHT),
         );
 
-        Self { count: count_rect, thickness: thick_rect, speed: speed_rect }
+        // the noise controls, in a column to the right of the others
+        let ns_w = small_width_chars(5);
+        let ns_x = 290.0 - ns_w / 2.0;
+        let noise_rect = Rect::from_xy_wh(
+            pt2(ns_x, 218.0 - SMALL_HEIGHT * 2.5),
+            pt2(ns_w, SMALL_HEIGHT * 5.0),
+        );
+        let slider = |y: f32| {
+            Rect::from_xy_wh(pt2(ns_x, y), pt2(ns_w, SMALL_HEIGHT))
+        };
+
+        Self {
+            count: count_rect,
+            thickness: thick_rect,
+            speed: speed_rect,
+
+            noise: noise_rect,
+            octaves: slider(165.0),
+            lacunarity: slider(125.0),
+            persistence: slider(85.0),
+            warp: slider(45.0),
+        }
     }
 }
 
 pub struct SmoothLifeUILayout {
     pub resolution: Rect,
-    pub speed: Rect,
     pub preset: Rect,
+    pub save: Rect,
+
+    pub outer_radius: Rect,
+    pub inner_radius: Rect,
+    pub speed: Rect,
+    pub b1: Rect,
+    pub b2: Rect,
+    pub alpha_n: Rect,
+    pub d1: Rect,
+    pub d2: Rect,
+    pub alpha_m: Rect,
 }
 
 impl Default for SmoothLifeUILayout {
     fn default() -> Self {
         let pr_w = main_width_chars(7);
         let preset_rect = Rect::from_xy_wh(
-            pt2(128.0 + pr_w / 2.0 + 10.0, 190.0 - MAIN_HEIGHT / 2.0),
-            pt2(pr_w, MAIN_HEIGHT * 3.0),
+            pt2(128.0 + pr_w / 2.0 + 10.0, 190.0 - MAIN_HEIGHT * 2.5),
+            pt2(pr_w, MAIN_HEIGHT * 7.0),
         );
 
-        // unused
-        let sp_w = main_width_chars(6);
-        let speed_rect = Rect::from_xy_wh(
-            pt2(128.0 + sp_w / 2.0 + 10.0, 190.0 + MAIN_HEIGHT / 2.0),
-            pt2(sp_w, MAIN_HEIGHT),
+        let sv_w = small_width_chars(4);
+        let save_rect = Rect::from_xy_wh(
+            pt2(290.0 - sv_w / 2.0, 190.0 + MAIN_HEIGHT / 2.0),
+            pt2(sv_w, MAIN_HEIGHT),
         );
 
         // unused
@@ -139,7 +224,29 @@ impl Default for SmoothLifeUILayout {
             pt2(rs_w, MAIN_HEIGHT * 6.0),
         );
 
-        Self { resolution: reso_rect, speed: speed_rect, preset: preset_rect }
+        // the state parameters, in a 3x3 grid below the preset menu
+        let sl_w = small_width_chars(4);
+        let slider = |col: f32, row: f32| {
+            let x = 128.0 + sl_w / 2.0 + 10.0 + col * (sl_w + 6.0);
+            let y = 150.0 - row * 42.0;
+            Rect::from_xy_wh(pt2(x, y), pt2(sl_w, SMALL_HEIGHT))
+        };
+
+        Self {
+            resolution: reso_rect,
+            preset: preset_rect,
+            save: save_rect,
+
+            outer_radius: slider(0.0, 0.0),
+            inner_radius: slider(1.0, 0.0),
+            speed: slider(2.0, 0.0),
+            b1: slider(0.0, 1.0),
+            b2: slider(1.0, 1.0),
+            alpha_n: slider(2.0, 1.0),
+            d1: slider(0.0, 2.0),
+            d2: slider(1.0, 2.0),
+            alpha_m: slider(2.0, 2.0),
+        }
     }
 }
 
@@ -147,6 +254,10 @@ pub struct VoronoiUILayout {
     pub cell_speed: Rect,
     pub border_weight: Rect,
     pub cell_count: Rect,
+
+    pub metric: Rect,
+    pub exponent: Rect,
+    pub mode: Rect,
 }
 
 impl Default for VoronoiUILayout {
@@ -163,16 +274,167 @@ impl Default for VoronoiUILayout {
             pt2(wt_w, MAIN_HEIGHT),
         );
 
-        let ct_w = main_width_chars(2);
+        let ct_w = main_width_chars(3);
         let count_rect = Rect::from_xy_wh(
             pt2(128.0 + ct_w / 2.0 + 10.0, 50.0 + MAIN_HEIGHT / 2.0),
             pt2(ct_w, MAIN_HEIGHT),
         );
 
+        // the metric and mode controls, in a column to the right of the others
+        let mt_w = small_width_chars(9);
+        let metric_rect = Rect::from_xy_wh(
+            pt2(290.0 - mt_w / 2.0, 170.0 - SMALL_HEIGHT * 2.0),
+            pt2(mt_w, SMALL_HEIGHT * 4.0),
+        );
+
+        let ex_w = small_width_chars(4);
+        let exponent_rect = Rect::from_xy_wh(
+            pt2(290.0 - ex_w / 2.0, 121.0),
+            pt2(ex_w, SMALL_HEIGHT),
+        );
+
+        let md_w = small_width_chars(7);
+        let mode_rect = Rect::from_xy_wh(
+            pt2(290.0 - md_w / 2.0, 80.0 - SMALL_HEIGHT * 1.5),
+            pt2(md_w, SMALL_HEIGHT * 3.0),
+        );
+
         Self {
             cell_speed: speed_rect,
             border_weight: weight_rect,
             cell_count: count_rect,
+
+            metric: metric_rect,
+            exponent: exponent_rect,
+            mode: mode_rect,
+        }
+    }
+}
+
+pub struct ReactionDiffusionUILayout {
+    pub preset: Rect,
+    pub feed: Rect,
+    pub kill: Rect,
+
+    pub diffusion_u: Rect,
+    pub diffusion_v: Rect,
+}
+
+impl Default for ReactionDiffusionUILayout {
+    fn default() -> Self {
+        let pr_w = main_width_chars(7);
+        let preset_rect = Rect::from_xy_wh(
+            pt2(128.0 + pr_w / 2.0 + 10.0, 190.0 - MAIN_HEIGHT),
+            pt2(pr_w, MAIN_HEIGHT * 4.0),
+        );
+
+        let fd_w = main_width_chars(6);
+        let feed_rect = Rect::from_xy_wh(
+            pt2(128.0 + fd_w / 2.0 + 10.0, 120.0 + MAIN_HEIGHT / 2.0),
+            pt2(fd_w, MAIN_HEIGHT),
+        );
+
+        let kl_w = main_width_chars(6);
+        let kill_rect = Rect::from_xy_wh(
+            pt2(128.0 + kl_w / 2.0 + 10.0, 50.0 + MAIN_HEIGHT / 2.0),
+            pt2(kl_w, MAIN_HEIGHT),
+        );
+
+        // the diffusion rates, in a column to the right of the others
+        let df_w = small_width_chars(4);
+        let diffusion_u_rect = Rect::from_xy_wh(
+            pt2(290.0 - df_w / 2.0, 121.0),
+            pt2(df_w, SMALL_HEIGHT),
+        );
+        let diffusion_v_rect = Rect::from_xy_wh(
+            pt2(290.0 - df_w / 2.0, 64.0),
+            pt2(df_w, SMALL_HEIGHT),
+        );
+
+        Self {
+            preset: preset_rect,
+            feed: feed_rect,
+            kill: kill_rect,
+
+            diffusion_u: diffusion_u_rect,
+            diffusion_v: diffusion_v_rect,
+        }
+    }
+}
+
+pub struct ImageUILayout {
+    pub brightness: Rect,
+    pub contrast: Rect,
+    pub gamma: Rect,
+    pub invert: Rect,
+}
+
+impl Default for ImageUILayout {
+    fn default() -> Self {
+        let br_w = main_width_chars(5);
+        let brightness_rect = Rect::from_xy_wh(
+            pt2(128.0 + br_w / 2.0 + 10.0, 190.0 + MAIN_HEIGHT / 2.0),
+            pt2(br_w, MAIN_HEIGHT),
+        );
+
+        let ct_w = main_width_chars(4);
+        let contrast_rect = Rect::from_xy_wh(
+            pt2(128.0 + ct_w / 2.0 + 10.0, 120.0 + MAIN_HEIGHT / 2.0),
+            pt2(ct_w, MAIN_HEIGHT),
+        );
+
+        let gm_w = main_width_chars(4);
+        let gamma_rect = Rect::from_xy_wh(
+            pt2(128.0 + gm_w / 2.0 + 10.0, 50.0 + MAIN_HEIGHT / 2.0),
+            pt2(gm_w, MAIN_HEIGHT),
+        );
+
+        // to the right of the gamma slider
+        let iv_w = main_width_chars(3);
+        let invert_rect = Rect::from_xy_wh(
+            pt2(128.0 + gm_w + iv_w / 2.0 + 30.0, 50.0 + MAIN_HEIGHT / 2.0),
+            pt2(iv_w, MAIN_HEIGHT),
+        );
+
+        Self {
+            brightness: brightness_rect,
+            contrast: contrast_rect,
+            gamma: gamma_rect,
+            invert: invert_rect,
+        }
+    }
+}
+
+pub struct SpectralFeedbackUILayout {
+    pub decay: Rect,
+    pub threshold: Rect,
+    pub invert: Rect,
+}
+
+impl Default for SpectralFeedbackUILayout {
+    fn default() -> Self {
+        let dc_w = main_width_chars(6);
+        let decay_rect = Rect::from_xy_wh(
+            pt2(128.0 + dc_w / 2.0 + 10.0, 190.0 + MAIN_HEIGHT / 2.0),
+            pt2(dc_w, MAIN_HEIGHT),
+        );
+
+        let th_w = main_width_chars(6);
+        let threshold_rect = Rect::from_xy_wh(
+            pt2(128.0 + th_w / 2.0 + 10.0, 120.0 + MAIN_HEIGHT / 2.0),
+            pt2(th_w, MAIN_HEIGHT),
+        );
+
+        let iv_w = main_width_chars(3);
+        let invert_rect = Rect::from_xy_wh(
+            pt2(128.0 + iv_w / 2.0 + 10.0, 50.0 + MAIN_HEIGHT / 2.0),
+            pt2(iv_w, MAIN_HEIGHT),
+        );
+
+        Self {
+            decay: decay_rect,
+            threshold: threshold_rect,
+            invert: invert_rect,
         }
     }
 }
@@ -240,7 +502,12 @@ pub struct ResoBankUILayout {
     pub shift: Rect,
     pub inharm: Rect,
     pub pan: Rect,
+    pub decay: Rect,
+    pub damping: Rect,
+    pub decay_random: Rect,
     pub quantize: Rect,
+    pub pitch_layout: Rect,
+    pub layout_blend: Rect,
     pub randomise: Rect,
     pub push: Rect,
 
@@ -248,6 +515,10 @@ pub struct ResoBankUILayout {
     pub cell_jitter: Rect,
     pub cell_count: Rect,
     pub field_friction: Rect,
+    pub field_mouse: Rect,
+    pub field_flocking: Rect,
+    pub field_collision: Rect,
+    pub field_gravity: Rect,
 
     pub mix: Rect,
     pub exciter: Rect,
@@ -299,6 +570,22 @@ impl Default for ResoBankUILayout {
             pt2(pn_w, MAIN_HEIGHT),
         );
 
+        let dc_w = small_width_chars(6);
+        let decay_rect = Rect::from_xy_wh(
+            pt2(-540.0 + dc_w / 2.0, -20.0),
+            pt2(dc_w, SMALL_HEIGHT),
+        );
+        let damping_rect = decay_rect.shift_x(75.0);
+        let decay_random_rect = damping_rect.shift_x(75.0);
+
+        // pitch layout controls, below the resonator settings column
+        let pl_w = small_width_chars(8);
+        let pitch_layout_rect = Rect::from_xy_wh(
+            pt2(-540.0 - pl_w / 2.0 - 10.0, 20.0),
+            pt2(pl_w, SMALL_HEIGHT),
+        );
+        let layout_blend_rect = pitch_layout_rect.shift_y(-38.0);
+
         let qn_w = small_width_chars(12);
         let quant_rect = Rect::from_xy_wh(
             pt2(-398.0 + qn_w / 2.0, 323.0),
@@ -329,6 +616,16 @@ impl Default for ResoBankUILayout {
             pt2(fr_w, MAIN_HEIGHT),
         );
 
+        // the field forces, in a column between the field settings and the
+        // spectral mask
+        let fc_w = small_width_chars(5);
+        let force_rect = |y: f32| {
+            Rect::from_xy_wh(
+                pt2(-138.0 - fc_w / 2.0, y),
+                pt2(fc_w, SMALL_HEIGHT),
+            )
+        };
+
         let mx_w = main_width_chars(7);
         let mix_rect = Rect::from_xy_wh(
             pt2(-284.0 + mx_w / 2.0 + 10.0, 120.0 + MAIN_HEIGHT / 2.0),
@@ -350,12 +647,18 @@ impl Default for ResoBankUILayout {
             root_note: root_rect,
             scale: scale_rect,
             quantize: quant_rect,
+            pitch_layout: pitch_layout_rect,
+            layout_blend: layout_blend_rect,
 
             shift: shift_rect,
             spread: spread_rect,
             inharm: inharm_rect,
             pan: pan_rect,
 
+            decay: decay_rect,
+            damping: damping_rect,
+            decay_random: decay_random_rect,
+
             randomise: reset_rect,
             push: push_rect,
 
@@ -363,6 +666,10 @@ impl Default for ResoBankUILayout {
             cell_count: def_rect(),
             cell_jitter: def_rect(),
             field_friction: friction_rect,
+            field_mouse: force_rect(293.0),
+            field_flocking: force_rect(257.0),
+            field_collision: force_rect(221.0),
+            field_gravity: force_rect(185.0),
 
             mix: mix_rect,
             exciter: exciter_rect,
@@ -370,15 +677,20 @@ impl Default for ResoBankUILayout {
     }
 }
 
-pub struct LowFilterUILayout {
+pub struct EQUILayout {
     pub label: Rect,
     pub f_type: Rect,
+    pub slope: Rect,
+    pub enabled: Rect,
     pub cutoff_hz: Rect,
-    pub q: Rect,
     pub gain: Rect,
+    pub q: Rect,
+    pub dynamic: Rect,
+    pub threshold: Rect,
+    pub linear_phase: Rect,
 }
 
-impl Default for LowFilterUILayout {
+impl Default for EQUILayout {
     fn default() -> Self {
         let label_rect =
             Rect::from_xy_wh(pt2(-615.0, -45.0), pt2(120.0, MAIN_HEIGHT));
@@ -386,55 +698,30 @@ impl Default for LowFilterUILayout {
         // let spectrum_rect =
         //     Rect::from_corners(pt2(-540.0, -310.0), pt2(128.0, -40.0));
 
-        let ty_w = main_width_chars(5);
+        let ty_w = main_width_chars(10);
         let type_rect = Rect::from_xy_wh(
-            pt2(-540.0 - ty_w / 2.0 - 10.0, -120.0 + MAIN_HEIGHT / 2.0),
-            pt2(ty_w, MAIN_HEIGHT),
+            pt2(
+                -540.0 - ty_w / 2.0 - 10.0,
+                -120.0 + MAIN_HEIGHT / 2.0 - MAIN_HEIGHT * 3.5,
+            ),
+            pt2(ty_w, MAIN_HEIGHT * 8.0),
         );
 
-        let co_w = main_width_chars(9);
-        let cutoff_rect = Rect::from_xy_wh(
-            pt2(-540.0 - co_w / 2.0 - 10.0, -215.0 + MAIN_HEIGHT / 2.0),
-            pt2(co_w, MAIN_HEIGHT),
+        let sl_w = main_width_chars(5);
+        let slope_rect = Rect::from_xy_wh(
+            pt2(
+                128.0 + sl_w / 2.0 + 10.0,
+                -120.0 + MAIN_HEIGHT / 2.0 - MAIN_HEIGHT,
+            ),
+            pt2(sl_w, MAIN_HEIGHT * 3.0),
         );
 
-        let q_w = main_width_chars(4);
-        let q_rect = Rect::from_xy_wh(
-            pt2(-540.0 - q_w / 2.0 - 10.0, -310.0 + MAIN_HEIGHT / 2.0),
-            pt2(q_w, MAIN_HEIGHT),
+        let en_w = main_width_chars(3);
+        let enabled_rect = Rect::from_xy_wh(
+            pt2(128.0 + en_w / 2.0 + 10.0, -215.0 + MAIN_HEIGHT / 2.0),
+            pt2(en_w, MAIN_HEIGHT),
         );
 
-        let gn_w = main_width_chars(8);
-        let gain_rect = Rect::from_xy_wh(
-            pt2(-540.0 - gn_w / 2.0 - 10.0, -310.0 + MAIN_HEIGHT / 2.0),
-            pt2(gn_w, MAIN_HEIGHT),
-        );
-
-        Self {
-            label: label_rect,
-            f_type: type_rect,
-            cutoff_hz: cutoff_rect,
-            q: q_rect,
-            gain: gain_rect,
-        }
-    }
-}
-
-pub struct PeakFilterUILayout {
-    pub label: Rect,
-    pub cutoff_hz: Rect,
-    pub gain: Rect,
-    pub q: Rect,
-}
-
-impl Default for PeakFilterUILayout {
-    fn default() -> Self {
-        let spectrogram_view_pos = pt2(-206.0, -364.0 - MAIN_HEIGHT);
-
-        // let label_rect =
-        let label_rect =
-            Rect::from_xy_wh(pt2(-470.0, -350.0), pt2(100.0, MAIN_HEIGHT));
-
         let co_w = main_width_chars(9);
         let cutoff_rect = Rect::from_xy_wh(
             pt2(-260.0, -364.0),
@@ -453,58 +740,38 @@ impl Default for PeakFilterUILayout {
             pt2(q_w, MAIN_HEIGHT),
         );
 
-        Self {
-            label: label_rect,
-            cutoff_hz: cutoff_rect,
-            gain: gain_rect,
-            q: q_rect,
-        }
-    }
-}
-
-pub struct HighFilterUILayout {
-    pub label: Rect,
-    pub f_type: Rect,
-    pub cutoff_hz: Rect,
-    pub q: Rect,
-    pub gain: Rect,
-}
-
-impl Default for HighFilterUILayout {
-    fn default() -> Self {
-        let label_rect =
-            Rect::from_xy_wh(pt2(210.0, -45.0), pt2(120.0, MAIN_HEIGHT));
-
-        let ty_w = main_width_chars(5);
-        let type_rect = Rect::from_xy_wh(
-            pt2(128.0 + ty_w / 2.0 + 10.0, -120.0 + MAIN_HEIGHT / 2.0),
-            pt2(ty_w, MAIN_HEIGHT),
-        );
-
-        let co_w = main_width_chars(9);
-        let cutoff_rect = Rect::from_xy_wh(
-            pt2(128.0 + co_w / 2.0 + 10.0, -215.0 + MAIN_HEIGHT / 2.0),
-            pt2(co_w, MAIN_HEIGHT),
+        let dy_w = main_width_chars(3);
+        let dynamic_rect = Rect::from_xy_wh(
+            pt2(128.0 + dy_w / 2.0 + 10.0, -310.0 + MAIN_HEIGHT / 2.0),
+            pt2(dy_w, MAIN_HEIGHT),
         );
 
-        let q_w = main_width_chars(4);
-        let q_rect = Rect::from_xy_wh(
-            pt2(128.0 + q_w / 2.0 + 10.0, -310.0 + MAIN_HEIGHT / 2.0),
-            pt2(q_w, MAIN_HEIGHT),
+        let th_w = main_width_chars(8);
+        let threshold_rect = Rect::from_xy_wh(
+            pt2(270.0, -364.0),
+            pt2(th_w, MAIN_HEIGHT),
         );
 
-        let gn_w = main_width_chars(8);
-        let gain_rect = Rect::from_xy_wh(
-            pt2(128.0 + gn_w / 2.0 + 10.0, -310.0 + MAIN_HEIGHT / 2.0),
-            pt2(gn_w, MAIN_HEIGHT),
+        let lp_w = main_width_chars(3);
+        let linear_phase_rect = Rect::from_xy_wh(
+            pt2(
+                128.0 + sl_w + lp_w / 2.0 + 40.0,
+                -120.0 + MAIN_HEIGHT / 2.0,
+            ),
+            pt2(lp_w, MAIN_HEIGHT),
         );
 
         Self {
             label: label_rect,
             f_type: type_rect,
+            slope: slope_rect,
+            enabled: enabled_rect,
             cutoff_hz: cutoff_rect,
-            q: q_rect,
             gain: gain_rect,
+            q: q_rect,
+            dynamic: dynamic_rect,
+            threshold: threshold_rect,
+            linear_phase: linear_phase_rect,
         }
     }
 }
@@ -515,6 +782,16 @@ pub struct DelayUILayout {
     pub feedback: Rect,
     pub mix: Rect,
     pub use_ping_pong: Rect,
+    pub sync: Rect,
+
+    // delay page
+    pub page_label: Rect,
+    pub low_cut: Rect,
+    pub high_cut: Rect,
+    pub saturation: Rect,
+    pub wow_flutter: Rect,
+    pub time_r: Rect,
+    pub link: Rect,
 }
 
 impl Default for DelayUILayout {
@@ -528,7 +805,11 @@ impl Default for DelayUILayout {
 
         let pp_w = main_width_chars(3);
         let ping_rect =
-            Rect::from_xy_wh(pt2(440.0, -100.0), pt2(pp_w, MAIN_HEIGHT));
+            Rect::from_xy_wh(pt2(415.0, -100.0), pt2(pp_w, MAIN_HEIGHT));
+
+        let sy_w = main_width_chars(3);
+        let sync_rect =
+            Rect::from_xy_wh(pt2(480.0, -100.0), pt2(sy_w, MAIN_HEIGHT));
 
         let fb_w = main_width_chars(7);
         let feed_rect =
@@ -538,12 +819,48 @@ impl Default for DelayUILayout {
         let mix_rect =
             Rect::from_xy_wh(pt2(570.0, -100.0), pt2(mx_w, MAIN_HEIGHT));
 
+        let page_label_rect =
+            Rect::from_xy_wh(pt2(500.0, 170.0), pt2(120.0, MAIN_HEIGHT));
+
+        let lc_w = main_width_chars(8);
+        let low_cut_rect =
+            Rect::from_xy_wh(pt2(442.0, 105.0), pt2(lc_w, MAIN_HEIGHT));
+
+        let hc_w = main_width_chars(8);
+        let high_cut_rect =
+            Rect::from_xy_wh(pt2(572.0, 105.0), pt2(hc_w, MAIN_HEIGHT));
+
+        let st_w = main_width_chars(7);
+        let saturation_rect =
+            Rect::from_xy_wh(pt2(442.0, 35.0), pt2(st_w, MAIN_HEIGHT));
+
+        let wf_w = main_width_chars(7);
+        let wow_flutter_rect =
+            Rect::from_xy_wh(pt2(572.0, 35.0), pt2(wf_w, MAIN_HEIGHT));
+
+        let tr_w = main_width_chars(8);
+        let time_r_rect =
+            Rect::from_xy_wh(pt2(442.0, -35.0), pt2(tr_w, MAIN_HEIGHT));
+
+        let lk_w = main_width_chars(3);
+        let link_rect =
+            Rect::from_xy_wh(pt2(572.0, -35.0), pt2(lk_w, MAIN_HEIGHT));
+
         Self {
             label: label_rect,
             time_ms: time_rect,
             feedback: feed_rect,
             mix: mix_rect,
             use_ping_pong: ping_rect,
+            sync: sync_rect,
+
+            page_label: page_label_rect,
+            low_cut: low_cut_rect,
+            high_cut: high_cut_rect,
+            saturation: saturation_rect,
+            wow_flutter: wow_flutter_rect,
+            time_r: time_r_rect,
+            link: link_rect,
         }
     }
 }
@@ -552,6 +869,7 @@ pub struct DistortionUILayout {
     pub label: Rect,
     pub amount: Rect,
     pub dist_type: Rect,
+    pub oversampling: Rect,
 }
 
 impl Default for DistortionUILayout {
@@ -569,7 +887,167 @@ impl Default for DistortionUILayout {
             pt2(ty_w, MAIN_HEIGHT * 5.0),
         );
 
-        Self { label: label_rect, amount: amount_rect, dist_type: type_rect }
+        let os_w = main_width_chars(3);
+        let os_rect =
+            Rect::from_xy_wh(pt2(655.0, 105.0), pt2(os_w, MAIN_HEIGHT));
+
+        Self {
+            label: label_rect,
+            amount: amount_rect,
+            dist_type: type_rect,
+            oversampling: os_rect,
+        }
+    }
+}
+
+pub struct CrushUILayout {
+    pub label: Rect,
+    pub rate: Rect,
+    pub bits: Rect,
+    pub jitter: Rect,
+    pub dither: Rect,
+    pub anti_alias: Rect,
+}
+
+impl Default for CrushUILayout {
+    fn default() -> Self {
+        let label_rect =
+            Rect::from_xy_wh(pt2(500.0, 170.0), pt2(120.0, MAIN_HEIGHT));
+
+        let rt_w = main_width_chars(8);
+        let rate_rect =
+            Rect::from_xy_wh(pt2(442.0, 105.0), pt2(rt_w, MAIN_HEIGHT));
+
+        let bt_w = main_width_chars(7);
+        let bits_rect =
+            Rect::from_xy_wh(pt2(572.0, 105.0), pt2(bt_w, MAIN_HEIGHT));
+
+        let jt_w = main_width_chars(7);
+        let jitter_rect =
+            Rect::from_xy_wh(pt2(442.0, 35.0), pt2(jt_w, MAIN_HEIGHT));
+
+        let dt_w = main_width_chars(7);
+        let dither_rect = Rect::from_xy_wh(
+            pt2(572.0, 35.0 - MAIN_HEIGHT),
+            pt2(dt_w, MAIN_HEIGHT * 3.0),
+        );
+
+        let aa_w = main_width_chars(3);
+        let aa_rect =
+            Rect::from_xy_wh(pt2(415.0, -35.0), pt2(aa_w, MAIN_HEIGHT));
+
+        Self {
+            label: label_rect,
+            rate: rate_rect,
+            bits: bits_rect,
+            jitter: jitter_rect,
+            dither: dither_rect,
+            anti_alias: aa_rect,
+        }
+    }
+}
+
+pub struct ModulationUILayout {
+    pub label: Rect,
+    pub mod_type: Rect,
+    pub rate: Rect,
+    pub sync: Rect,
+    pub depth: Rect,
+    pub feedback: Rect,
+    pub mix: Rect,
+}
+
+impl Default for ModulationUILayout {
+    fn default() -> Self {
+        let label_rect =
+            Rect::from_xy_wh(pt2(500.0, 170.0), pt2(120.0, MAIN_HEIGHT));
+
+        let ty_w = main_width_chars(7);
+        let type_rect = Rect::from_xy_wh(
+            pt2(442.0, 105.0 - MAIN_HEIGHT * 1.5),
+            pt2(ty_w, MAIN_HEIGHT * 4.0),
+        );
+
+        let rt_w = main_width_chars(7);
+        let rate_rect =
+            Rect::from_xy_wh(pt2(572.0, 105.0), pt2(rt_w, MAIN_HEIGHT));
+
+        let dp_w = main_width_chars(7);
+        let depth_rect =
+            Rect::from_xy_wh(pt2(442.0, 35.0), pt2(dp_w, MAIN_HEIGHT));
+
+        let fb_w = main_width_chars(7);
+        let feed_rect =
+            Rect::from_xy_wh(pt2(572.0, 35.0), pt2(fb_w, MAIN_HEIGHT));
+
+        let mx_w = main_width_chars(7);
+        let mix_rect =
+            Rect::from_xy_wh(pt2(442.0, -35.0), pt2(mx_w, MAIN_HEIGHT));
+
+        let sy_w = main_width_chars(3);
+        let sync_rect =
+            Rect::from_xy_wh(pt2(545.0, -35.0), pt2(sy_w, MAIN_HEIGHT));
+
+        Self {
+            label: label_rect,
+            mod_type: type_rect,
+            rate: rate_rect,
+            sync: sync_rect,
+            depth: depth_rect,
+            feedback: feed_rect,
+            mix: mix_rect,
+        }
+    }
+}
+
+pub struct ReverbUILayout {
+    pub label: Rect,
+    pub size: Rect,
+    pub decay: Rect,
+    pub damping: Rect,
+    pub pre_delay: Rect,
+    pub modulation: Rect,
+    pub mix: Rect,
+}
+
+impl Default for ReverbUILayout {
+    fn default() -> Self {
+        let label_rect =
+            Rect::from_xy_wh(pt2(500.0, 170.0), pt2(120.0, MAIN_HEIGHT));
+
+        let sz_w = main_width_chars(7);
+        let size_rect =
+            Rect::from_xy_wh(pt2(442.0, 105.0), pt2(sz_w, MAIN_HEIGHT));
+
+        let dc_w = main_width_chars(7);
+        let decay_rect =
+            Rect::from_xy_wh(pt2(572.0, 105.0), pt2(dc_w, MAIN_HEIGHT));
+
+        let dm_w = main_width_chars(7);
+        let damping_rect =
+            Rect::from_xy_wh(pt2(442.0, 35.0), pt2(dm_w, MAIN_HEIGHT));
+
+        let pd_w = main_width_chars(7);
+        let pre_delay_rect =
+            Rect::from_xy_wh(pt2(572.0, 35.0), pt2(pd_w, MAIN_HEIGHT));
+
+        let md_w = main_width_chars(7);
+        let mod_rect =
+            Rect::from_xy_wh(pt2(442.0, -35.0), pt2(md_w, MAIN_HEIGHT));
+
+        let mx_w = main_width_chars(7);
+        let mix_rect =
+            Rect::from_xy_wh(pt2(572.0, -35.0), pt2(mx_w, MAIN_HEIGHT));
+
+        Self {
+            label: label_rect,
+            size: size_rect,
+            decay: decay_rect,
+            damping: damping_rect,
+            pre_delay: pre_delay_rect,
+            modulation: mod_rect,
+            mix: mix_rect,
+        }
     }
 }
 
@@ -579,6 +1057,23 @@ pub struct CompressionUILayout {
     pub ratio: Rect,
     pub attack: Rect,
     pub release: Rect,
+
+    // compressor page
+    pub page_label: Rect,
+    pub knee: Rect,
+    pub stereo_link: Rect,
+    pub makeup: Rect,
+    pub lookahead: Rect,
+    pub sidechain_hpf: Rect,
+    pub gain_reduction: Rect,
+    pub auto_makeup: Rect,
+    pub use_rms: Rect,
+    pub exciter_key: Rect,
+
+    // limiter
+    pub limiter_label: Rect,
+    pub limiter_ceiling: Rect,
+    pub limiter_release: Rect,
 }
 
 impl Default for CompressionUILayout {
@@ -602,20 +1097,164 @@ impl Default for CompressionUILayout {
         let rel_rect =
             Rect::from_xy_wh(pt2(565.0, -320.0), pt2(rl_w, MAIN_HEIGHT));
 
+        let page_label_rect =
+            Rect::from_xy_wh(pt2(500.0, 170.0), pt2(120.0, MAIN_HEIGHT));
+
+        let kn_w = main_width_chars(7);
+        let knee_rect =
+            Rect::from_xy_wh(pt2(442.0, 105.0), pt2(kn_w, MAIN_HEIGHT));
+
+        let sl_w = main_width_chars(7);
+        let link_rect =
+            Rect::from_xy_wh(pt2(572.0, 105.0), pt2(sl_w, MAIN_HEIGHT));
+
+        let mk_w = main_width_chars(8);
+        let makeup_rect =
+            Rect::from_xy_wh(pt2(442.0, 35.0), pt2(mk_w, MAIN_HEIGHT));
+
+        let la_w = main_width_chars(7);
+        let lookahead_rect =
+            Rect::from_xy_wh(pt2(572.0, 35.0), pt2(la_w, MAIN_HEIGHT));
+
+        let hp_w = main_width_chars(8);
+        let hpf_rect =
+            Rect::from_xy_wh(pt2(442.0, -35.0), pt2(hp_w, MAIN_HEIGHT));
+
+        let gr_w = main_width_chars(10);
+        let gr_rect =
+            Rect::from_xy_wh(pt2(572.0, -35.0), pt2(gr_w, MAIN_HEIGHT));
+
+        let am_w = main_width_chars(3);
+        let auto_rect =
+            Rect::from_xy_wh(pt2(415.0, -105.0), pt2(am_w, MAIN_HEIGHT));
+
+        let rm_w = main_width_chars(3);
+        let rms_rect =
+            Rect::from_xy_wh(pt2(480.0, -105.0), pt2(rm_w, MAIN_HEIGHT));
+
+        let ky_w = main_width_chars(3);
+        let key_rect =
+            Rect::from_xy_wh(pt2(545.0, -105.0), pt2(ky_w, MAIN_HEIGHT));
+
+        let limiter_label_rect =
+            Rect::from_xy_wh(pt2(500.0, -175.0), pt2(120.0, MAIN_HEIGHT));
+
+        let lc_w = main_width_chars(8);
+        let ceiling_rect =
+            Rect::from_xy_wh(pt2(442.0, -240.0), pt2(lc_w, MAIN_HEIGHT));
+
+        let lr_w = main_width_chars(8);
+        let limiter_release_rect =
+            Rect::from_xy_wh(pt2(572.0, -240.0), pt2(lr_w, MAIN_HEIGHT));
+
         Self {
             label: label_rect,
             threshold: thrs_rect,
             ratio: ratio_rect,
             attack: att_rect,
             release: rel_rect,
+
+            page_label: page_label_rect,
+            knee: knee_rect,
+            stereo_link: link_rect,
+            makeup: makeup_rect,
+            lookahead: lookahead_rect,
+            sidechain_hpf: hpf_rect,
+            gain_reduction: gr_rect,
+            auto_makeup: auto_rect,
+            use_rms: rms_rect,
+            exciter_key: key_rect,
+
+            limiter_label: limiter_label_rect,
+            limiter_ceiling: ceiling_rect,
+            limiter_release: limiter_release_rect,
+        }
+    }
+}
+
+pub struct TransportUILayout {
+    pub bpm: Rect,
+    pub play: Rect,
+    pub position: Rect,
+}
+
+impl Default for TransportUILayout {
+    fn default() -> Self {
+        let bp_w = main_width_chars(9);
+        let bpm_rect =
+            Rect::from_xy_wh(pt2(240.0, 360.0), pt2(bp_w, MAIN_HEIGHT));
+
+        let pl_w = main_width_chars(4);
+        let play_rect =
+            Rect::from_xy_wh(pt2(345.0, 360.0), pt2(pl_w, MAIN_HEIGHT));
+
+        let position_rect =
+            Rect::from_xy_wh(pt2(450.0, 360.0), pt2(110.0, MAIN_HEIGHT));
+
+        Self { bpm: bpm_rect, play: play_rect, position: position_rect }
+    }
+}
+
+/// The layout of the multiband distortion and compression pages, which share
+/// the same controls.
+pub struct MultibandUILayout {
+    pub page_label: Rect,
+    pub crossover_freqs: [Rect; NUM_BANDS - 1],
+    pub gains: [Rect; NUM_BANDS],
+    pub bypass: [Rect; NUM_BANDS],
+    pub solo: [Rect; NUM_BANDS],
+    pub enabled: Rect,
+}
+
+impl Default for MultibandUILayout {
+    fn default() -> Self {
+        let page_label_rect =
+            Rect::from_xy_wh(pt2(500.0, 170.0), pt2(120.0, MAIN_HEIGHT));
+
+        let xo_w = main_width_chars(8);
+        let crossover_rects = [
+            Rect::from_xy_wh(pt2(442.0, 105.0), pt2(xo_w, MAIN_HEIGHT)),
+            Rect::from_xy_wh(pt2(572.0, 105.0), pt2(xo_w, MAIN_HEIGHT)),
+        ];
+
+        // one row for each band
+        let row_y = |band: usize| 35.0 - 70.0 * band as f32;
+
+        let gn_w = main_width_chars(8);
+        let gain_rects = std::array::from_fn(|band| {
+            Rect::from_xy_wh(pt2(442.0, row_y(band)), pt2(gn_w, MAIN_HEIGHT))
+        });
+
+        let bt_w = main_width_chars(3);
+        let bypass_rects = std::array::from_fn(|band| {
+            Rect::from_xy_wh(pt2(545.0, row_y(band)), pt2(bt_w, MAIN_HEIGHT))
+        });
+        let solo_rects = std::array::from_fn(|band| {
+            Rect::from_xy_wh(pt2(610.0, row_y(band)), pt2(bt_w, MAIN_HEIGHT))
+        });
+
+        let en_w = main_width_chars(3);
+        let enabled_rect =
+            Rect::from_xy_wh(pt2(415.0, -175.0), pt2(en_w, MAIN_HEIGHT));
+
+        Self {
+            page_label: page_label_rect,
+            crossover_freqs: crossover_rects,
+            gains: gain_rects,
+            bypass: bypass_rects,
+            solo: solo_rects,
+            enabled: enabled_rect,
         }
     }
 }
 
 pub struct OtherUILayout {
     pub effects_label: Rect,
+    pub fx_page: Rect,
     pub master_gain: Rect,
     pub pre_fx_gain: Rect,
+    pub safety_status: Rect,
+    pub latency: Rect,
 }
 
 impl Default for OtherUILayout {
@@ -630,10 +1269,27 @@ impl Default for OtherUILayout {
         let pre_rect =
             Rect::from_xy_wh(pt2(500.0, 230.0), pt2(120.0, MAIN_HEIGHT));
 
+        let pg_w = main_width_chars(7);
+        let page_rect = Rect::from_xy_wh(
+            pt2(630.0, 230.0 - MAIN_HEIGHT * 3.5),
+            pt2(pg_w, MAIN_HEIGHT * 8.0),
+        );
+
+        let sf_w = main_width_chars(11);
+        let safety_rect =
+            Rect::from_xy_wh(pt2(630.0, 300.0), pt2(sf_w, MAIN_HEIGHT));
+
+        let lt_w = main_width_chars(11);
+        let latency_rect =
+            Rect::from_xy_wh(pt2(630.0, 265.0), pt2(lt_w, MAIN_HEIGHT));
+
         Self {
             effects_label: fx_rect,
+            fx_page: page_rect,
             master_gain: m_gain_rect,
             pre_fx_gain: pre_rect,
+            safety_status: safety_rect,
+            latency: latency_rect,
         }
     }
 }
@@ -646,13 +1302,19 @@ pub struct UILayout {
     pub contour: ContourUILayout,
     pub smooth_life: SmoothLifeUILayout,
     pub voronoi: VoronoiUILayout,
+    pub reaction_diffusion: ReactionDiffusionUILayout,
+    pub image: ImageUILayout,
+    pub spectral_feedback: SpectralFeedbackUILayout,
     pub spectrogram: SpectrogramUILayout,
     pub reso_bank: ResoBankUILayout,
-    pub low_filter: LowFilterUILayout,
-    pub peak_filter: PeakFilterUILayout,
-    pub high_filter: HighFilterUILayout,
+    pub eq: EQUILayout,
     pub delay: DelayUILayout,
     pub distortion: DistortionUILayout,
+    pub crush: CrushUILayout,
+    pub modulation: ModulationUILayout,
+    pub reverb: ReverbUILayout,
     pub compression: CompressionUILayout,
+    pub multiband: MultibandUILayout,
+    pub transport: TransportUILayout,
     pub other: OtherUILayout,
 }
@@ -8,3 +8,5 @@ pub mod layout;
 
 pub use components::*;
 use layout::*;
+
+pub use layout::fx_page_area;
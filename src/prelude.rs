@@ -3,7 +3,8 @@
 use crate::app::audio::NoteHandler;
 use std::sync::{Arc, Mutex};
 
-pub use crate::app::{audio::NoteEvent, Scale};
+pub use crate::app::{audio::NoteEvent, PitchLayout, Scale};
+pub use crate::generative::{MaskGenerator, MaskSource};
 pub use crate::gui::{DrawMask, InputData, UIDraw};
 pub use crate::settings::*;
 pub use crate::simd::{SimdBuffer, SimdType};
@@ -93,6 +93,12 @@ pub const MAX_OVERSAMPLING_FACTOR: usize = 4; // 16x oversampling
 /// The default oversampling factor (i.e. this is `2² == 4x` oversampling).
 pub const DEFAULT_OVERSAMPLING_FACTOR: usize = 2; // 4x oversampling
 
+/// The number of bands of the multiband distortion and compression.
+pub const NUM_BANDS: usize = 3;
+
+/// The maximum number of bands of the parametric EQ.
+pub const MAX_EQ_BANDS: usize = 8;
+
 /// It doesn't make much sense to be able to queue lots of note events per audio
 /// callback, so this cap is used to restrict how many should exist for each buffer.
 pub const MAX_NOTE_EVENTS_PER_BUFFER: usize = 12;
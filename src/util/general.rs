@@ -163,16 +163,14 @@ pub fn lanczos_kernel(a_factor: u8, scale: f64, trim_zeroes: bool) -> Vec<f64> {
 
     (if trim_zeroes { 1..num_stages - 1 } else { 0..num_stages })
         .map(|i| {
-            if i % 2 == 0 { 0.0 }
-            else {
-                let x = 2.0f64.mul_add(-a, i as f64) / 2.0;
-
-                if x == 0.0 { 1.0 }
-                else if -a < x && x < a {
-                    sinc(PI * x) * sinc((PI * x) / a) * scale
-                }
-                else { 0.0 }
+            let x = 2.0f64.mul_add(-a, i as f64) / 2.0;
+
+            if x == 0.0 { scale }
+            else if i % 2 == 0 { 0.0 }
+            else if -a < x && x < a {
+                sinc(PI * x) * sinc((PI * x) / a) * scale
             }
+            else { 0.0 }
         })
         .collect()
 }
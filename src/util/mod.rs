@@ -8,6 +8,7 @@ use std::sync::atomic::Ordering::Relaxed;
 pub mod atomic_ops;
 pub mod general;
 pub mod interp;
+pub mod rng;
 pub mod smoothing;
 pub mod thread_pool;
 pub mod window;
@@ -18,6 +19,9 @@ pub use interp::InterpolationType as InterpType;
 pub use atomic_ops::AtomicOps;
 pub use general::*;
 pub use interp::{ilerp, lerp};
+pub use rng::{
+    session_seed, set_session_seed, stream_seed, SeededRng, SessionRng,
+};
 pub use smoothing::*;
 pub use thread_pool::ThreadPool;
 pub use xfer::SmoothingType;
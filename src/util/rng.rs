@@ -0,0 +1,223 @@
+//! Seeded random number generation.
+//!
+//! Every random generator in the app draws from a *stream* of the global
+//! session seed, so that a session can be reproduced by starting it with the
+//! same seed (see [`set_session_seed()`]).
+
+use rand::{Error, RngCore};
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+/// The session seed used until another is set.
+pub const DEFAULT_SESSION_SEED: u64 = 0x5EED;
+
+/// The golden ratio increment of the SplitMix64 algorithm.
+const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+static SESSION_SEED: AtomicU64 = AtomicU64::new(DEFAULT_SESSION_SEED);
+/// Incremented each time the session seed is set, so that a [`SessionRng`]
+/// can cheaply tell whether its stream is out of date.
+static SEED_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the global session seed.
+pub fn session_seed() -> u64 {
+    SESSION_SEED.load(Relaxed)
+}
+
+/// Sets the global session seed. Each [`SessionRng`] restarts its stream
+/// from the new seed the next time it is used.
+pub fn set_session_seed(seed: u64) {
+    SESSION_SEED.store(seed, Relaxed);
+    SEED_GENERATION.fetch_add(1, Relaxed);
+}
+
+/// Returns the seed of the named `stream` of the session seed. Each stream
+/// produces unrelated values for the same session seed.
+pub fn stream_seed(stream: &str) -> u64 {
+    stream_seed_of(session_seed(), stream)
+}
+
+/// Returns the seed of the named `stream` of `session_seed`.
+fn stream_seed_of(session_seed: u64, stream: &str) -> u64 {
+    // FNV-1a hash of the stream name
+    let hash = stream.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+    });
+
+    mix(session_seed ^ hash)
+}
+
+/// The SplitMix64 output function, which thoroughly scrambles the bits of
+/// `x`.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// A small, fast pseudo-random number generator (SplitMix64) which always
+/// produces the same values from the same seed.
+///
+/// This implements [`RngCore`], so may be used with [`rand::Rng`]'s methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Creates a new generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { state: mix(seed) }
+    }
+
+    /// Creates a new generator from the named `stream` of the session seed.
+    pub fn from_stream(stream: &str) -> Self {
+        Self::new(stream_seed(stream))
+    }
+
+    /// Returns a value between `0.0` and `1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        // the upper 53 bits fill the mantissa of an f64
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GAMMA);
+        mix(self.state)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// A [`SeededRng`] which draws from a named stream of the session seed, and
+/// restarts that stream whenever the session seed changes.
+#[derive(Clone, Debug)]
+pub struct SessionRng {
+    stream: &'static str,
+    /// The seed generation the stream was started from.
+    generation: u64,
+    rng: SeededRng,
+}
+
+impl SessionRng {
+    /// Creates a new generator for the named `stream` of the session seed.
+    pub fn new(stream: &'static str) -> Self {
+        // the generation is loaded first so that a concurrent change to the
+        // seed is picked up on the next draw
+        let generation = SEED_GENERATION.load(Relaxed);
+
+        Self { stream, generation, rng: SeededRng::from_stream(stream) }
+    }
+
+    /// Restarts the stream from `session_seed` instead of the global session
+    /// seed, without changing the global seed. The stream follows the global
+    /// seed again the next time it is set.
+    pub fn restore_seed(&mut self, session_seed: u64) {
+        self.generation = SEED_GENERATION.load(Relaxed);
+        self.rng = SeededRng::new(stream_seed_of(session_seed, self.stream));
+    }
+
+    /// Returns a value between `0.0` and `1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        self.sync();
+        self.rng.next_f64()
+    }
+
+    /// Restarts the stream if the session seed has changed.
+    fn sync(&mut self) {
+        if self.generation != SEED_GENERATION.load(Relaxed) {
+            *self = Self::new(self.stream);
+        }
+    }
+}
+
+impl Default for SessionRng {
+    fn default() -> Self {
+        Self::new("default")
+    }
+}
+
+impl RngCore for SessionRng {
+    fn next_u32(&mut self) -> u32 {
+        self.sync();
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.sync();
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.sync();
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.sync();
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn values(mut rng: impl RngCore) -> Vec<u64> {
+        (0..64).map(|_| rng.next_u64()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_values() {
+        assert_eq!(values(SeededRng::new(7)), values(SeededRng::new(7)));
+        assert_ne!(values(SeededRng::new(7)), values(SeededRng::new(8)));
+    }
+
+    #[test]
+    fn streams_are_independent() {
+        assert_eq!(stream_seed("a"), stream_seed("a"));
+        assert_ne!(stream_seed("a"), stream_seed("b"));
+
+        assert_eq!(
+            values(SeededRng::from_stream("a")),
+            values(SessionRng::new("a"))
+        );
+    }
+
+    #[test]
+    fn restored_seed_overrides_session_seed() {
+        let mut restored = SessionRng::new("restore");
+        restored.restore_seed(1234);
+
+        assert_eq!(
+            values(restored),
+            values(SeededRng::new(stream_seed_of(1234, "restore")))
+        );
+    }
+
+    #[test]
+    fn values_are_in_range() {
+        let mut rng = SeededRng::new(3);
+
+        for _ in 0..1000 {
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+            assert!((-2.0..2.0).contains(&rng.gen_range(-2.0..2.0)));
+        }
+    }
+}